@@ -0,0 +1,178 @@
+use std::{
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::Path,
+    time::SystemTime,
+};
+
+use crate::{
+    deletion_report::DeletionReport, program_errors::ProgramError,
+    save_information::SaveInformation, timestamp,
+};
+
+/// Appends a timestamped line for every deletion attempt to the file given with `--log-file`.
+/// A log that cannot be written only produces warnings, it never stops a cleanup.
+pub struct AuditLog {
+    file: Option<File>,
+}
+impl AuditLog {
+    pub fn open(path: Option<&Path>) -> Self {
+        let file = path.and_then(|path| {
+            open_for_append(path)
+                .map_err(|e| {
+                    eprintln!(
+                        "Warning: unable to open log file {}, continuing without it: {}",
+                        path.display(),
+                        e
+                    )
+                })
+                .ok()
+        });
+
+        AuditLog { file }
+    }
+
+    pub fn disabled() -> Self {
+        AuditLog { file: None }
+    }
+
+    pub fn header(&mut self, options: &impl Display) {
+        self.write_line(&format!("run started with options: {}", options));
+    }
+
+    pub fn record(
+        &mut self,
+        path: &Path,
+        save_information: &SaveInformation,
+        result: &Result<(), ProgramError>,
+    ) {
+        let outcome = match result {
+            Ok(_) => "deleted".to_string(),
+            Err(e) => format!("failed: {}", e),
+        };
+
+        self.write_line(&format!(
+            "{}\t{}\t{:?}\t{}\t{}",
+            path.display(),
+            save_information.character_name,
+            save_information.save_type,
+            save_information
+                .save_number
+                .map(|save_number| save_number.to_string())
+                .unwrap_or_default(),
+            outcome
+        ));
+    }
+
+    pub fn footer(&mut self, result: &Result<DeletionReport, ProgramError>) {
+        match result {
+            Ok(report) => self.write_line(&format!(
+                "run finished: deleted {} folder(s), failed {}",
+                report.deleted.len(),
+                report.failed.len()
+            )),
+            Err(e) => self.write_line(&format!("run aborted: {}", e)),
+        }
+    }
+
+    fn write_line(&mut self, line: &str) {
+        let Some(file) = &mut self.file else {
+            return;
+        };
+
+        if let Err(e) = writeln!(
+            file,
+            "{}\t{}",
+            timestamp::format_utc(SystemTime::now()),
+            line
+        ) {
+            eprintln!("Warning: unable to write to log file, disabling it: {}", e);
+            self.file = None;
+        }
+    }
+}
+
+fn open_for_append(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod audit_log_should {
+    use std::fs;
+
+    use crate::{
+        audit_log::AuditLog, deletion_report::DeletionReport, program_errors::ProgramError,
+        save_information::SaveInformation, save_type::SaveType, test_utils::TempDir,
+    };
+
+    fn logged_save() -> SaveInformation {
+        SaveInformation::new(
+            "Some'me-123456789__QuickSave_3".to_string(),
+            "Some'me".to_string(),
+            SaveType::Quick,
+            Some(3),
+        )
+    }
+
+    #[test]
+    fn create_parent_directories_and_append() {
+        let temp_dir = TempDir::new();
+        let log_path = temp_dir.path().join("logs").join("bg3sc.log");
+        let save_path = temp_dir.path().join("Some'me-123456789__QuickSave_3");
+
+        AuditLog::open(Some(&log_path)).record(&save_path, &logged_save(), &Ok(()));
+        AuditLog::open(Some(&log_path)).record(
+            &save_path,
+            &logged_save(),
+            &Err(ProgramError::FailedToDelete(
+                "Permission denied".to_string(),
+            )),
+        );
+
+        let content = fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(&format!(
+            "\t{}\tSome'me\tQuick\t3\tdeleted",
+            save_path.display()
+        )));
+        assert!(lines[1].ends_with("\tfailed: \"Permission denied\""));
+    }
+
+    #[test]
+    fn write_header_and_footer() {
+        let temp_dir = TempDir::new();
+        let log_path = temp_dir.path().join("bg3sc.log");
+
+        let mut audit_log = AuditLog::open(Some(&log_path));
+        audit_log.header(&"--yes");
+        audit_log.footer(&Ok(DeletionReport {
+            deleted: vec![logged_save()],
+            failed: Vec::new(),
+        }));
+
+        let content = fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].ends_with("\trun started with options: --yes"));
+        assert!(lines[1].ends_with("\trun finished: deleted 1 folder(s), failed 0"));
+    }
+
+    #[test]
+    fn continue_without_log_when_it_cannot_be_opened() {
+        let temp_dir = TempDir::new();
+
+        // A directory can't be opened for appending.
+        let mut audit_log = AuditLog::open(Some(temp_dir.path()));
+        audit_log.record(temp_dir.path(), &logged_save(), &Ok(()));
+
+        assert!(audit_log.file.is_none());
+    }
+}
@@ -0,0 +1,72 @@
+/// The word typed to confirm a big delete, the exact number of folders works as well.
+const CONFIRMATION_WORD: &str = "DELETE";
+/// The share of the scanned saves a run may delete without `--allow-mass-delete`.
+pub const DEFAULT_MAX_DELETE_PERCENT: usize = 60;
+
+/// Whether deleting `count` of `scanned` saves is more than `max_percent` of them, which more
+/// likely comes from a wrong option or a misparsed name than from the user's intent.
+pub fn is_mass_delete(count: usize, scanned: usize, max_percent: usize) -> bool {
+    count * 100 > scanned * max_percent
+}
+
+/// Whether deleting `count` folders needs more than a `y`.
+pub fn needs_typed_confirmation(count: usize, threshold: usize) -> bool {
+    count > threshold
+}
+
+pub fn typed_confirmation_question(count: usize) -> String {
+    format!(
+        "This deletes {} folders. Type {} or {} to go ahead, anything else cancels:",
+        count, CONFIRMATION_WORD, count
+    )
+}
+
+pub fn is_typed_confirmation(input: &str, count: usize) -> bool {
+    let input = input.trim();
+
+    input == CONFIRMATION_WORD || input.parse::<usize>() == Ok(count)
+}
+
+#[cfg(test)]
+mod big_delete_should {
+    use crate::big_delete::{
+        is_mass_delete, is_typed_confirmation, needs_typed_confirmation,
+        typed_confirmation_question,
+    };
+
+    #[test]
+    fn only_ask_above_threshold() {
+        assert!(!needs_typed_confirmation(50, 50));
+        assert!(needs_typed_confirmation(51, 50));
+        assert!(!needs_typed_confirmation(0, 0));
+    }
+
+    #[test]
+    fn only_refuse_deleting_more_than_the_share() {
+        assert!(!is_mass_delete(60, 100, 60));
+        assert!(is_mass_delete(61, 100, 60));
+        assert!(!is_mass_delete(3, 5, 60));
+        assert!(is_mass_delete(4, 5, 60));
+        assert!(!is_mass_delete(5, 5, 100));
+        assert!(is_mass_delete(1, 5, 0));
+        assert!(!is_mass_delete(0, 0, 60));
+    }
+
+    #[test]
+    fn name_count_in_question() {
+        assert_eq!(
+            typed_confirmation_question(300),
+            "This deletes 300 folders. Type DELETE or 300 to go ahead, anything else cancels:"
+        );
+    }
+
+    #[test]
+    fn accept_word_or_exact_count() {
+        assert!(is_typed_confirmation("DELETE", 300));
+        assert!(is_typed_confirmation(" 300 ", 300));
+        assert!(!is_typed_confirmation("y", 300));
+        assert!(!is_typed_confirmation("delete", 300));
+        assert!(!is_typed_confirmation("299", 300));
+        assert!(!is_typed_confirmation("", 300));
+    }
+}
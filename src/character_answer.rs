@@ -0,0 +1,37 @@
+/// The answer given for one character with `--per-character`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CharacterAnswer {
+    Delete,
+    Skip,
+    Quit,
+}
+
+/// Parses `y`, `n`, `skip` and `quit`, `n` leaves the character untouched just like `skip`.
+pub fn parse_character_answer(input: &str) -> Result<CharacterAnswer, String> {
+    match input.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Ok(CharacterAnswer::Delete),
+        "" | "n" | "no" | "s" | "skip" => Ok(CharacterAnswer::Skip),
+        "q" | "quit" => Ok(CharacterAnswer::Quit),
+        other => Err(format!("{} is not one of y, n, skip or quit", other)),
+    }
+}
+
+#[cfg(test)]
+mod parse_character_answer_should {
+    use crate::character_answer::{parse_character_answer, CharacterAnswer};
+
+    #[test]
+    fn accept_short_and_long_answers() {
+        assert_eq!(parse_character_answer("Y"), Ok(CharacterAnswer::Delete));
+        assert_eq!(parse_character_answer("yes"), Ok(CharacterAnswer::Delete));
+        assert_eq!(parse_character_answer("n"), Ok(CharacterAnswer::Skip));
+        assert_eq!(parse_character_answer(" skip "), Ok(CharacterAnswer::Skip));
+        assert_eq!(parse_character_answer(""), Ok(CharacterAnswer::Skip));
+        assert_eq!(parse_character_answer("QUIT"), Ok(CharacterAnswer::Quit));
+    }
+
+    #[test]
+    fn reject_anything_else() {
+        assert!(parse_character_answer("1-3").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::save_information::SaveInformation;
+
+/// Digits of the campaign id shown next to the character name.
+const SHORT_ID_LENGTH: usize = 6;
+
+/// Saves are grouped per character and campaign, two playthroughs may use the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CharacterKey {
+    pub name: String,
+    pub campaign_id: Option<String>,
+}
+impl CharacterKey {
+    pub fn of(save_information: &SaveInformation) -> Self {
+        CharacterKey {
+            name: save_information.character_name.clone(),
+            campaign_id: save_information.campaign_id.clone(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn named(name: &str) -> Self {
+        CharacterKey {
+            name: name.to_string(),
+            campaign_id: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn in_campaign(name: &str, campaign_id: &str) -> Self {
+        CharacterKey {
+            name: name.to_string(),
+            campaign_id: Some(campaign_id.to_string()),
+        }
+    }
+}
+impl Display for CharacterKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.campaign_id {
+            Some(campaign_id) => write!(
+                f,
+                "{} #{}",
+                self.name,
+                campaign_id
+                    .chars()
+                    .take(SHORT_ID_LENGTH)
+                    .collect::<String>()
+            ),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod character_key_should {
+    use crate::character_key::CharacterKey;
+
+    #[test]
+    fn show_short_campaign_id() {
+        let key = CharacterKey::in_campaign("Tav", "1231415123");
+
+        assert_eq!(key.to_string(), "Tav #123141");
+        assert_eq!(CharacterKey::named("Tav").to_string(), "Tav");
+    }
+}
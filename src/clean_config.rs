@@ -0,0 +1,267 @@
+use std::{path::PathBuf, time::Duration};
+
+use clap::{builder::RangedU64ValueParser, ArgGroup, Args};
+
+use crate::{
+    duration_arg, output_format::OutputFormat, preserve_arg, regex::Regex,
+    save_type_filter::SaveTypeFilter, size_arg, sort_by::SortBy,
+};
+
+// Options of the clean command, also accepted without a subcommand. Kept as a plain comment
+// because clap would use a doc comment as the about text of the commands it is flattened into.
+#[derive(Args, Debug, PartialEq, Default)]
+#[command(group(ArgGroup::new("non_interactive").args(["yes", "dry_run", "watch"]).multiple(true)))]
+pub struct CleanConfig {
+    /// The latest n saves to ignore when selecting saves for deletion
+    #[arg(short, long)]
+    pub saves_to_preserve: Option<usize>,
+
+    /// List the saves that would be deleted without prompting or deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Delete the selected saves without asking for confirmation
+    #[arg(short = 'y', long, conflicts_with = "dry_run")]
+    pub yes: bool,
+
+    /// Confirm the deletions of each character separately, answering y/n/skip/quit
+    #[arg(long, conflicts_with_all = ["yes", "dry_run"])]
+    pub per_character: bool,
+
+    /// Ask to type DELETE instead of y when confirming more than this many folders, defaults to 50
+    #[arg(long)]
+    pub big_delete_threshold: Option<usize>,
+
+    /// Refuse to delete more than this percentage of the scanned saves, defaults to 60
+    #[arg(long, value_name = "PERCENT", value_parser = RangedU64ValueParser::<usize>::new().range(0..=100))]
+    pub max_delete_percent: Option<usize>,
+
+    /// Delete even if it is more than --max-delete-percent of the scanned saves
+    #[arg(long)]
+    pub allow_mass_delete: bool,
+
+    /// Don't explain what deleting does to saves that Steam Cloud syncs
+    #[arg(long)]
+    pub no_cloud_warning: bool,
+
+    /// Preserve a different number of saves for one character, e.g. "Tav=30". Can be given multiple times
+    #[arg(long, value_parser = preserve_arg::parse_preserve_for)]
+    pub preserve_for: Vec<(String, usize)>,
+
+    /// Allow deleting every save of a character, by default the newest one of each type is kept
+    #[arg(long)]
+    pub allow_delete_all: bool,
+
+    /// Only consider the given save type for deletion
+    #[arg(long, value_enum)]
+    pub only: Option<SaveTypeFilter>,
+
+    /// Also consider folders not identified as quick or auto saves, confirmed separately
+    #[arg(long)]
+    pub include_unrecognized: bool,
+
+    /// Also consider manual saves, keeping the latest n of them. They are never deleted otherwise
+    #[arg(long, value_name = "N")]
+    pub preserve_manual: Option<usize>,
+
+    /// Also consider Honour Mode saves, which are never deleted otherwise
+    #[arg(long)]
+    pub include_honour: bool,
+
+    /// Also delete save folders with a missing or empty .lsv file, confirmed separately
+    #[arg(long)]
+    pub clean_corrupt: bool,
+
+    /// Delete save folders without any file in them, as left behind by interrupted saves. They
+    /// don't count towards the preserved saves and are listed separately otherwise
+    #[arg(long)]
+    pub clean_empty: bool,
+
+    /// Also delete save folders copied by hand, like "Tav-123456789__QuickSave_50 (1)" or
+    /// "... - Copy", confirmed separately. They never count towards the preserved saves
+    #[arg(long)]
+    pub clean_copies: bool,
+
+    /// Delete the files lying next to the save folders, like stray thumbnails, after asking.
+    /// List files to keep, like desktop.ini, in --exclude-from
+    #[arg(long)]
+    pub clean_loose_files: bool,
+
+    /// Stop without deleting anything if a folder name can't be parsed. Folders of an
+    /// unrecognized save type parse fine, they are only considered with --include-unrecognized
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Only clean saves of the given character. Can be given multiple times
+    #[arg(short, long)]
+    pub character: Vec<String>,
+
+    /// Only clean saves of the campaign whose id starts with the given digits
+    #[arg(long, value_name = "ID")]
+    pub campaign: Option<String>,
+
+    /// Never clean saves of the given character. Can be given multiple times
+    #[arg(short, long)]
+    pub exclude_character: Vec<String>,
+
+    /// Read the character name, save time and game version from each save's meta.lsf instead
+    /// of relying on the folder name, falling back to it when the metadata can't be read
+    #[arg(long)]
+    pub read_metadata: bool,
+
+    /// How to tell which saves are the newest ones to preserve
+    #[arg(long, value_enum, default_value_t)]
+    pub sort_by: SortBy,
+
+    /// Sort a character's saves of a type by modified time when their numbers look like the
+    /// game restarted counting, instead of only warning about it
+    #[arg(long)]
+    pub prefer_mtime_on_conflict: bool,
+
+    /// Only consider save folders whose name matches this regex
+    #[arg(long = "match", value_name = "REGEX", value_parser = Regex::new)]
+    pub match_regex: Option<Regex>,
+
+    /// Leave save folders whose name matches this regex alone. Can be given multiple times
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+    pub exclude: Vec<Regex>,
+
+    /// Leave the save folders named in this file alone, one name or glob per line
+    #[arg(long, value_name = "FILE")]
+    pub exclude_from: Option<PathBuf>,
+
+    /// Leave folders and files matching this glob out of the scan, in addition to hidden
+    /// folders, *.bak, __MACOSX and System Volume Information. Can be given multiple times
+    #[arg(long, value_name = "GLOB")]
+    pub ignore: Vec<String>,
+
+    /// Only delete saves last modified longer ago than this, e.g. 30d, 2w or 12h
+    #[arg(long, value_parser = duration_arg::parse_duration)]
+    pub older_than: Option<Duration>,
+
+    /// Never delete saves modified within the last n hours
+    #[arg(long)]
+    pub keep_recent: Option<u64>,
+
+    /// Keep the newest saves of each character and save type up to this size, e.g. 800MB or 5GB
+    #[arg(long, value_parser = size_arg::parse_size)]
+    pub max_size_per_character: Option<u64>,
+
+    /// Keep every nth save beyond the preserved ones instead of deleting all of them
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub thin: Option<usize>,
+
+    /// Only print the outcome of the run and errors. Requires --yes or --dry-run
+    #[arg(short, long, requires = "non_interactive")]
+    pub quiet: bool,
+
+    /// Format of the output. Machine readable formats require --yes or --dry-run
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        requires_if("json", "non_interactive")
+    )]
+    pub output: OutputFormat,
+
+    /// Show sizes as exact byte counts instead of rounding them to KiB, MiB or GiB
+    #[arg(long)]
+    pub bytes: bool,
+
+    /// Write every discovered save to a CSV file at the given path
+    #[arg(long)]
+    pub export_csv: Option<PathBuf>,
+
+    /// Move the selected saves to the recycle bin instead of deleting them permanently
+    #[arg(long)]
+    pub trash: bool,
+
+    /// Move the selected saves into this directory instead of deleting them, see restore
+    #[arg(long, conflicts_with = "trash")]
+    pub quarantine: Option<PathBuf>,
+
+    /// Append the selected saves to this zstd compressed tar archive (.tar.zst) and delete them
+    /// once it is written, later runs add to the same archive. Get a save back with
+    /// tar -xf <archive> <folder>
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["trash", "quarantine"])]
+    pub archive: Option<PathBuf>,
+
+    /// Zip every save folder into this directory before deleting it
+    #[arg(long)]
+    pub backup_dir: Option<PathBuf>,
+
+    /// Skip the backup for this run even if a backup directory is given
+    #[arg(long)]
+    pub no_backup: bool,
+
+    /// Delete this many folders at the same time, defaults to 4
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub jobs: Option<usize>,
+
+    /// Try removing a file this many more times when it is locked, e.g. by a virus scanner,
+    /// defaults to 3
+    #[arg(long, value_name = "COUNT")]
+    pub retries: Option<usize>,
+
+    /// Append a line for every deleted folder to this file, creating it if needed
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
+
+    /// Delete folders even if they hold no .lsv file starting with the LSPK magic bytes, by
+    /// default such folders are skipped as they may not be saves
+    #[arg(long)]
+    pub no_verify: bool,
+
+    /// Also clean save folders that are symlinks, which are left alone with a warning by
+    /// default. Only the links are deleted, never the folders they point to
+    #[arg(long)]
+    pub follow_symlinks: bool,
+
+    /// Clean a directory that doesn't look like a save folder: it isn't named Story or below
+    /// Savegames, and too few of its folders are saves
+    #[arg(long)]
+    pub force_path: bool,
+
+    /// Percentage of the folders that have to be saves for a directory to count as a save
+    /// folder, defaults to 50
+    #[arg(long, value_name = "PERCENT", value_parser = RangedU64ValueParser::<usize>::new().range(0..=100))]
+    pub min_save_percent: Option<usize>,
+
+    /// Delete even though Baldur's Gate 3 is running, which may still have the saves open
+    #[arg(long)]
+    pub allow_while_running: bool,
+
+    /// Keep running after the first clean and clean again as new saves appear, implies --yes
+    /// and --allow-while-running. Saves modified within the last two minutes are never deleted
+    /// while watching. Each clean replaces the undo area of the one before, so bg3sc undo only
+    /// brings back the saves of the last clean
+    #[arg(long, conflicts_with_all = ["dry_run", "per_character", "write_plan", "apply_plan"])]
+    pub watch: bool,
+
+    /// With --watch, clean on this schedule, e.g. 10m, instead of when the save folder changes
+    #[arg(long, requires = "watch", value_parser = duration_arg::parse_duration)]
+    pub interval: Option<Duration>,
+
+    /// Write the saves selected for deletion to a plan file and exit without deleting
+    #[arg(long, conflicts_with_all = ["dry_run", "yes"])]
+    pub write_plan: Option<PathBuf>,
+
+    /// Delete exactly the saves listed in a plan file written by --write-plan, from the save
+    /// folder it was written for
+    #[arg(long, conflicts_with = "write_plan")]
+    pub apply_plan: Option<PathBuf>,
+
+    /// Delete exactly the save folders named on stdin, one per line, without preserving any.
+    /// Requires --yes or --dry-run as stdin holds the list
+    #[arg(
+        long,
+        requires = "non_interactive",
+        conflicts_with_all = ["watch", "per_character", "write_plan", "apply_plan"]
+    )]
+    pub from_stdin: bool,
+
+    /// With --from-stdin, delete the listed folders that exist instead of stopping when a name
+    /// isn't found
+    #[arg(long, requires = "from_stdin")]
+    pub skip_unknown: bool,
+}
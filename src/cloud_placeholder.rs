@@ -0,0 +1,86 @@
+use std::{fs::Metadata, path::Path};
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x40000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x400000;
+
+/// Whether a file below the save folder is an online-only placeholder, like the files OneDrive
+/// keeps in the cloud with Files On-Demand. Deleting such a folder deletes the cloud copy.
+/// Placeholders only exist on Windows, elsewhere this is always false.
+#[cfg(windows)]
+pub fn has_placeholders(folder: &Path) -> bool {
+    std::fs::read_dir(folder)
+        .map(|children| {
+            children.flatten().any(|child| match child.file_type() {
+                Ok(file_type) if file_type.is_dir() => has_placeholders(&child.path()),
+                Ok(_) => child
+                    .metadata()
+                    .is_ok_and(|metadata| is_placeholder(&metadata)),
+                Err(_) => false,
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+pub fn has_placeholders(_folder: &Path) -> bool {
+    false
+}
+
+/// The bytes a file takes up on disk, which is nothing for a placeholder whose data is only in
+/// the cloud. Elsewhere this is the file length.
+pub fn size_on_disk(metadata: &Metadata) -> u64 {
+    if is_placeholder(metadata) {
+        0
+    } else {
+        metadata.len()
+    }
+}
+
+#[cfg(windows)]
+fn is_placeholder(metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    is_placeholder_attributes(metadata.file_attributes())
+}
+
+#[cfg(not(windows))]
+fn is_placeholder(_metadata: &Metadata) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn is_placeholder_attributes(attributes: u32) -> bool {
+    attributes
+        & (FILE_ATTRIBUTE_OFFLINE
+            | FILE_ATTRIBUTE_RECALL_ON_OPEN
+            | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(test)]
+mod has_placeholders_should {
+    use crate::{cloud_placeholder::has_placeholders, test_utils::TempDir};
+
+    #[test]
+    fn find_no_placeholders_in_local_folders() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+
+        assert!(!has_placeholders(&save));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn detect_online_only_attributes() {
+        use crate::cloud_placeholder::is_placeholder_attributes;
+
+        // FILE_ATTRIBUTE_ARCHIVE, with and without FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS.
+        assert!(is_placeholder_attributes(0x20 | 0x400000));
+        assert!(is_placeholder_attributes(0x1000));
+        assert!(!is_placeholder_attributes(0x20));
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+/// The empty folders and loose files next to the saves that `--clean-empty` and
+/// `--clean-loose-files` remove along with the saves.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Clutter {
+    pub empty_folders: Vec<PathBuf>,
+    pub loose_files: Vec<PathBuf>,
+}
+impl Clutter {
+    pub fn is_empty(&self) -> bool {
+        self.empty_folders.is_empty() && self.loose_files.is_empty()
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.empty_folders.iter().chain(&self.loose_files)
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    ffi::OsString,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{save_information::SaveInformation, save_type::SaveType};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    Bold,
+}
+impl Color {
+    fn code(&self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Cyan => "36",
+            Color::Bold => "1",
+        }
+    }
+}
+
+/// Color is used unless `--no-color` is given, `NO_COLOR` is set to a non empty value
+/// or the output isn't a terminal. See <https://no-color.org>.
+pub fn should_enable(
+    no_color_flag: bool,
+    no_color_env: Option<OsString>,
+    is_terminal: bool,
+) -> bool {
+    !no_color_flag && no_color_env.is_none_or(|value| value.is_empty()) && is_terminal
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn paint(text: &str, color: Color) -> String {
+    if ENABLED.load(Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// The folder name with the character name and the save type highlighted.
+pub fn paint_save(save_information: &SaveInformation) -> String {
+    let paint_save_type = |text: &str| match save_information.save_type {
+        SaveType::Quick => paint(text, Color::Green),
+        SaveType::Auto => paint(text, Color::Yellow),
+        SaveType::Honour => paint(text, Color::Red),
+        SaveType::Manual | SaveType::Unrecognized => text.to_string(),
+    };
+
+    match save_information
+        .file_name
+        .strip_prefix(&save_information.character_name)
+    {
+        Some(rest) => format!(
+            "{}{}",
+            paint(&save_information.character_name, Color::Cyan),
+            paint_save_type(rest)
+        ),
+        None => paint_save_type(&save_information.file_name),
+    }
+}
+
+#[cfg(test)]
+mod should_enable_should {
+    use std::ffi::OsString;
+
+    use crate::color::should_enable;
+
+    #[test]
+    fn enable_on_terminal() {
+        assert!(should_enable(false, None, true));
+        assert!(should_enable(false, Some(OsString::new()), true));
+    }
+
+    #[test]
+    fn disable_with_flag_env_or_without_terminal() {
+        assert!(!should_enable(true, None, true));
+        assert!(!should_enable(false, Some(OsString::from("1")), true));
+        assert!(!should_enable(false, None, false));
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use clap::Subcommand;
+
+use crate::{clean_config::CleanConfig, completions::Shell};
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Command {
+    /// Delete old quick and auto saves, the default when no command is given
+    Clean(Box<CleanConfig>),
+    /// List the saves of each character without deleting anything
+    List {
+        /// Only list saves of the campaign whose id starts with the given digits
+        #[arg(long, value_name = "ID")]
+        campaign: Option<String>,
+    },
+    /// Show the disk usage of each character's saves
+    Stats {
+        /// Print sizes as exact byte counts
+        #[arg(long)]
+        bytes: bool,
+    },
+    /// Move saves from a quarantine directory back into the save folder
+    Restore {
+        /// The directory given to --quarantine
+        quarantine_dir: PathBuf,
+        /// Restore every quarantined folder without asking
+        #[arg(long)]
+        all: bool,
+    },
+    /// Move back the saves removed by the last clean
+    Undo,
+    /// Permanently remove the saves kept for undo
+    Purge,
+    /// Manage the config file holding default options
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Print a completion script for the given shell
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+impl Command {
+    /// Whether running the command deletes or moves save folders.
+    pub fn changes_saves(&self) -> bool {
+        match self {
+            Command::Clean(clean_config) => {
+                !clean_config.dry_run && clean_config.write_plan.is_none()
+            }
+            Command::Restore { .. } | Command::Undo | Command::Purge => true,
+            Command::List { .. }
+            | Command::Stats { .. }
+            | Command::Config { .. }
+            | Command::Completions { .. } => false,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum ConfigAction {
+    /// Write a commented config file template to --config or the default location
+    Init {
+        /// Replace an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+}
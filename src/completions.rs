@@ -0,0 +1,298 @@
+use clap::ValueEnum;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// The words offered for one (sub)command: its flags and the names of its subcommands.
+struct CompletionWords {
+    subcommand: Option<String>,
+    options: Vec<String>,
+    subcommands: Vec<String>,
+}
+
+/// Writes a completion script for `command`, which needs to be built so it lists the
+/// generated help and version flags as well as global arguments in each subcommand.
+pub fn generate(shell: Shell, command: &clap::Command) -> String {
+    let name = command.get_bin_name().unwrap_or(command.get_name());
+    let words = completion_words(command);
+
+    match shell {
+        Shell::Bash => bash(name, &words),
+        Shell::Zsh => zsh(name, &words),
+        Shell::Fish => fish(name, command),
+        Shell::Powershell => powershell(name, &words),
+    }
+}
+
+fn completion_words(command: &clap::Command) -> Vec<CompletionWords> {
+    let options = |command: &clap::Command| {
+        command
+            .get_arguments()
+            .filter(|argument| !argument.is_positional() && !argument.is_hide_set())
+            .flat_map(|argument| {
+                argument
+                    .get_long()
+                    .map(|long| format!("--{}", long))
+                    .into_iter()
+                    .chain(argument.get_short().map(|short| format!("-{}", short)))
+            })
+            .collect()
+    };
+    let subcommand_names = |command: &clap::Command| {
+        command
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect()
+    };
+
+    std::iter::once(CompletionWords {
+        subcommand: None,
+        options: options(command),
+        subcommands: subcommand_names(command),
+    })
+    .chain(command.get_subcommands().map(|subcommand| CompletionWords {
+        subcommand: Some(subcommand.get_name().to_string()),
+        options: options(subcommand),
+        subcommands: subcommand_names(subcommand),
+    }))
+    .collect()
+}
+
+fn all_words(words: &CompletionWords) -> String {
+    words
+        .options
+        .iter()
+        .chain(words.subcommands.iter())
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn subcommand_pattern(words: &[CompletionWords]) -> String {
+    words
+        .iter()
+        .filter_map(|words| words.subcommand.clone())
+        .collect::<Vec<String>>()
+        .join("|")
+}
+
+fn bash(name: &str, words: &[CompletionWords]) -> String {
+    let cases: String = words
+        .iter()
+        .map(|command_words| {
+            format!(
+                "        {})\n            opts=\"{}\"\n            ;;\n",
+                command_words.subcommand.as_deref().unwrap_or("*"),
+                all_words(command_words)
+            )
+        })
+        .rev()
+        .collect();
+
+    format!(
+        "_{name}() {{
+    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"
+    local subcommand=\"\"
+    local word
+    for word in \"${{COMP_WORDS[@]:1:COMP_CWORD-1}}\"; do
+        case \"$word\" in
+            {pattern})
+                subcommand=\"$word\"
+                break
+                ;;
+        esac
+    done
+
+    local opts
+    case \"$subcommand\" in
+{cases}    esac
+    COMPREPLY=( $(compgen -W \"$opts\" -- \"$cur\") )
+}}
+
+complete -F _{name} -o default {name}
+",
+        name = name,
+        pattern = subcommand_pattern(words),
+        cases = cases
+    )
+}
+
+fn zsh(name: &str, words: &[CompletionWords]) -> String {
+    let cases: String = words
+        .iter()
+        .map(|command_words| {
+            format!(
+                "        ({})\n            compadd -- {}\n            ;;\n",
+                command_words.subcommand.as_deref().unwrap_or("*"),
+                all_words(command_words)
+            )
+        })
+        .rev()
+        .collect();
+
+    format!(
+        "#compdef {name}
+
+_{name}() {{
+    local subcommand=\"\"
+    local word
+    for word in ${{words[2,CURRENT-1]}}; do
+        case $word in
+            ({pattern})
+                subcommand=$word
+                break
+                ;;
+        esac
+    done
+
+    case $subcommand in
+{cases}    esac
+    _files
+}}
+
+if [ \"$funcstack[1]\" = \"_{name}\" ]; then
+    _{name} \"$@\"
+else
+    compdef _{name} {name}
+fi
+",
+        name = name,
+        pattern = subcommand_pattern(words),
+        cases = cases
+    )
+}
+
+fn fish(name: &str, command: &clap::Command) -> String {
+    let subcommands: Vec<&str> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name())
+        .collect();
+    let escape = |text: &str| text.replace('\\', "\\\\").replace('\'', "\\'");
+
+    let argument_lines = |condition: &str, command: &clap::Command| -> String {
+        command
+            .get_arguments()
+            .filter(|argument| !argument.is_positional() && !argument.is_hide_set())
+            .map(|argument| {
+                let mut line = format!("complete -c {} -n '{}'", name, condition);
+                if let Some(short) = argument.get_short() {
+                    line.push_str(&format!(" -s {}", short));
+                }
+                if let Some(long) = argument.get_long() {
+                    line.push_str(&format!(" -l {}", long));
+                }
+                let possible_values: Vec<String> = argument
+                    .get_possible_values()
+                    .iter()
+                    .filter(|value| !value.is_hide_set())
+                    .map(|value| value.get_name().to_string())
+                    .collect();
+                if !possible_values.is_empty() {
+                    line.push_str(&format!(" -x -a '{}'", possible_values.join(" ")));
+                } else if argument.get_action().takes_values() {
+                    line.push_str(" -r");
+                }
+                if let Some(help) = argument.get_help() {
+                    line.push_str(&format!(" -d '{}'", escape(&help.to_string())));
+                }
+                line.push('\n');
+                line
+            })
+            .collect()
+    };
+
+    let subcommand_lines: String = command
+        .get_subcommands()
+        .map(|subcommand| {
+            format!(
+                "complete -c {} -n '__fish_use_subcommand' -f -a {}{}\n",
+                name,
+                subcommand.get_name(),
+                subcommand
+                    .get_about()
+                    .map(|about| format!(" -d '{}'", escape(&about.to_string())))
+                    .unwrap_or_default()
+            )
+        })
+        .collect();
+    let nested_lines: String = command
+        .get_subcommands()
+        .map(|subcommand| {
+            argument_lines(
+                &format!("__fish_seen_subcommand_from {}", subcommand.get_name()),
+                subcommand,
+            )
+        })
+        .collect();
+
+    format!(
+        "{}{}{}",
+        argument_lines(
+            &format!("not __fish_seen_subcommand_from {}", subcommands.join(" ")),
+            command
+        ),
+        subcommand_lines,
+        nested_lines
+    )
+}
+
+fn powershell(name: &str, words: &[CompletionWords]) -> String {
+    let quoted = |words: &CompletionWords| {
+        words
+            .options
+            .iter()
+            .chain(words.subcommands.iter())
+            .map(|word| format!("'{}'", word))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let subcommands = words
+        .iter()
+        .filter_map(|words| words.subcommand.as_ref())
+        .map(|subcommand| format!("'{}'", subcommand))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let cases: String = words
+        .iter()
+        .map(|command_words| {
+            format!(
+                "        {} {{ @({}) }}\n",
+                command_words.subcommand.as_ref().map_or(
+                    "default".to_string(),
+                    |subcommand| format!("'{}'", subcommand)
+                ),
+                quoted(command_words)
+            )
+        })
+        .rev()
+        .collect();
+
+    format!(
+        "Register-ArgumentCompleter -Native -CommandName '{name}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $subcommand = ''
+    foreach ($element in ($commandAst.CommandElements | Select-Object -Skip 1)) {{
+        if (@({subcommands}) -contains $element.ToString()) {{
+            $subcommand = $element.ToString()
+            break
+        }}
+    }}
+
+    $completions = switch ($subcommand) {{
+{cases}    }}
+    $completions | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterName', $_)
+    }}
+}}
+",
+        name = name,
+        subcommands = subcommands,
+        cases = cases
+    )
+}
@@ -0,0 +1,664 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    clean_config::CleanConfig, duration_arg, folder_pattern::FolderPattern, preserve_arg,
+    program_errors::ProgramError, size_arg,
+};
+
+const CONFIG_DIR_NAME: &str = "bg3_save_cleaner";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Written by `config init`, every key is commented out so it starts with the built-in defaults.
+pub const TEMPLATE: &str = r#"# Defaults for bg3sc, options given on the command line take precedence.
+
+# The save folder(s) to clean, a single path or a list of paths. BG3_SAVE_DIR takes precedence.
+# path_to_save_folder = "C:/Users/me/AppData/Local/Larian Studios/Baldur's Gate 3/PlayerProfiles/Public/Savegames/Story"
+# path_to_save_folder = ["D:/saves", "E:/saves-copy"]
+
+# Player profile to use when the path is a PlayerProfiles directory, or all of them.
+# profile = "Public"
+# all_profiles = false
+
+# Clean all paths together instead of keeping the newest saves per path.
+# combine_paths = false
+
+# The latest n saves of each character and save type to keep.
+# saves_to_preserve = 10
+
+# Keep a different number of saves for some characters.
+# preserve_for = ["Tav=30", "Karlach=5"]
+
+# Never clean saves of these characters.
+# exclude_character = ["Astarion"]
+
+# Leave folders and files matching these globs out of the scan, in addition to hidden folders,
+# *.bak, __MACOSX and System Volume Information.
+# ignore = ["*.old"]
+
+# Only delete saves older than this, e.g. 30d, 2w or 12h.
+# older_than = "30d"
+
+# Never delete saves modified within the last n hours.
+# keep_recent = 24
+
+# Refuse to delete more than this percentage of the scanned saves without --allow-mass-delete.
+# max_delete_percent = 60
+
+# Keep the newest saves of each character and save type up to this size, e.g. 800MB or 5GB.
+# max_size_per_character = "5GB"
+
+# Split folder names with this regex instead of the built-in parser, which is equivalent to
+# pattern = '^(?P<name>.+)-(?P<campaign>\d+)_+(?P<type>[^_]+)_(?P<number>\d+)$'
+
+# Words after an underscore that mark quick saves and autosaves, for a game running in another
+# language than English.
+# quick_keyword = ["Schnellspeichern"]
+# auto_keyword = ["Automatische-Speicherung"]
+
+# allow_delete_all = false
+# include_unrecognized = false
+# clean_corrupt = false
+# clean_empty = false
+# clean_copies = false
+# clean_loose_files = false
+
+# Where removed saves go instead of being deleted: the recycle bin or a quarantine directory.
+# trash = false
+# quarantine = "D:/bg3-quarantine"
+
+# Zip every save folder into this directory before deleting it.
+# backup_dir = "D:/bg3-backups"
+
+# Append a line for every deleted folder to this file.
+# log_file = "D:/bg3-backups/deleted.log"
+
+# verbose = false
+# no_color = false
+"#;
+
+/// Defaults read from the config file, unset keys leave the built-in defaults in place.
+#[derive(Debug, Default, PartialEq)]
+pub struct ConfigFile {
+    pub path_to_save_folder: Vec<PathBuf>,
+    pub profile: Option<String>,
+    pub all_profiles: bool,
+    pub combine_paths: bool,
+    pub verbose: bool,
+    pub no_color: bool,
+    pub saves_to_preserve: Option<usize>,
+    pub preserve_for: Vec<(String, usize)>,
+    pub exclude_character: Vec<String>,
+    pub ignore: Vec<String>,
+    pub older_than: Option<Duration>,
+    pub keep_recent: Option<u64>,
+    pub max_size_per_character: Option<u64>,
+    pub max_delete_percent: Option<usize>,
+    pub pattern: Option<FolderPattern>,
+    pub quick_keyword: Vec<String>,
+    pub auto_keyword: Vec<String>,
+    pub allow_delete_all: bool,
+    pub include_unrecognized: bool,
+    pub clean_corrupt: bool,
+    pub clean_empty: bool,
+    pub clean_copies: bool,
+    pub clean_loose_files: bool,
+    pub trash: bool,
+    pub quarantine: Option<PathBuf>,
+    pub backup_dir: Option<PathBuf>,
+    pub log_file: Option<PathBuf>,
+}
+
+/// The subset of TOML values the config file uses.
+#[derive(Debug, PartialEq)]
+enum TomlValue {
+    String(String),
+    Integer(u64),
+    Bool(bool),
+    Array(Vec<TomlValue>),
+}
+
+impl ConfigFile {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut seen_keys: Vec<String> = Vec::new();
+
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, strip_comment(line).trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .try_fold(
+                ConfigFile::default(),
+                |mut config_file, (line_number, line)| {
+                    if line.starts_with('[') {
+                        return Err(format!("line {}: tables are not supported", line_number));
+                    }
+                    let (key, value) = line
+                        .split_once('=')
+                        .map(|(key, value)| (key.trim(), value.trim()))
+                        .ok_or(format!("line {}: expected key = value", line_number))?;
+                    if seen_keys.iter().any(|seen_key| seen_key == key) {
+                        return Err(format!("line {}: {}: given twice", line_number, key));
+                    }
+                    seen_keys.push(key.to_string());
+
+                    parse_value(value)
+                        .and_then(|value| config_file.set(key, value))
+                        .map_err(|e| format!("line {}: {}: {}", line_number, key, e))?;
+
+                    Ok(config_file)
+                },
+            )
+    }
+
+    fn set(&mut self, key: &str, value: TomlValue) -> Result<(), String> {
+        match key {
+            "path_to_save_folder" => {
+                self.path_to_save_folder = match value {
+                    TomlValue::Array(_) => strings(value)?,
+                    value => vec![string(value)?],
+                }
+                .into_iter()
+                .map(PathBuf::from)
+                .collect()
+            }
+            "profile" => self.profile = Some(string(value)?),
+            "all_profiles" => self.all_profiles = boolean(value)?,
+            "combine_paths" => self.combine_paths = boolean(value)?,
+            "verbose" => self.verbose = boolean(value)?,
+            "no_color" => self.no_color = boolean(value)?,
+            "saves_to_preserve" => {
+                self.saves_to_preserve =
+                    Some(integer(value)?.try_into().map_err(|e| format!("{}", e))?)
+            }
+            "preserve_for" => {
+                self.preserve_for = strings(value)?
+                    .iter()
+                    .map(|preserve_for| preserve_arg::parse_preserve_for(preserve_for))
+                    .collect::<Result<Vec<(String, usize)>, String>>()?
+            }
+            "exclude_character" => self.exclude_character = strings(value)?,
+            "ignore" => self.ignore = strings(value)?,
+            "older_than" => self.older_than = Some(duration_arg::parse_duration(&string(value)?)?),
+            "keep_recent" => self.keep_recent = Some(integer(value)?),
+            "max_size_per_character" => {
+                self.max_size_per_character = Some(size_arg::parse_size(&string(value)?)?)
+            }
+            "max_delete_percent" => {
+                self.max_delete_percent = match integer(value)? {
+                    percent @ 0..=100 => Some(percent as usize),
+                    _ => return Err("expected a percentage from 0 to 100".to_string()),
+                }
+            }
+            "pattern" => self.pattern = Some(FolderPattern::new(&string(value)?)?),
+            "quick_keyword" => self.quick_keyword = strings(value)?,
+            "auto_keyword" => self.auto_keyword = strings(value)?,
+            "allow_delete_all" => self.allow_delete_all = boolean(value)?,
+            "include_unrecognized" => self.include_unrecognized = boolean(value)?,
+            "clean_corrupt" => self.clean_corrupt = boolean(value)?,
+            "clean_empty" => self.clean_empty = boolean(value)?,
+            "clean_copies" => self.clean_copies = boolean(value)?,
+            "clean_loose_files" => self.clean_loose_files = boolean(value)?,
+            "trash" => self.trash = boolean(value)?,
+            "quarantine" => self.quarantine = Some(PathBuf::from(string(value)?)),
+            "backup_dir" => self.backup_dir = Some(PathBuf::from(string(value)?)),
+            "log_file" => self.log_file = Some(PathBuf::from(string(value)?)),
+            _ => return Err("unknown key".to_string()),
+        }
+
+        Ok(())
+    }
+
+    /// Fills the options not given on the command line with the values from the file.
+    pub fn apply_to(&self, clean_config: &mut CleanConfig) {
+        clean_config.saves_to_preserve = clean_config.saves_to_preserve.or(self.saves_to_preserve);
+        if clean_config.preserve_for.is_empty() {
+            clean_config.preserve_for = self.preserve_for.clone();
+        }
+        if clean_config.exclude_character.is_empty() {
+            clean_config.exclude_character = self.exclude_character.clone();
+        }
+        if clean_config.ignore.is_empty() {
+            clean_config.ignore = self.ignore.clone();
+        }
+        clean_config.older_than = clean_config.older_than.or(self.older_than);
+        clean_config.keep_recent = clean_config.keep_recent.or(self.keep_recent);
+        clean_config.max_size_per_character = clean_config
+            .max_size_per_character
+            .or(self.max_size_per_character);
+        clean_config.max_delete_percent =
+            clean_config.max_delete_percent.or(self.max_delete_percent);
+        clean_config.allow_delete_all |= self.allow_delete_all;
+        clean_config.include_unrecognized |= self.include_unrecognized;
+        clean_config.clean_corrupt |= self.clean_corrupt;
+        clean_config.clean_empty |= self.clean_empty;
+        clean_config.clean_copies |= self.clean_copies;
+        clean_config.clean_loose_files |= self.clean_loose_files;
+        // A quarantine directory on the command line wins over the recycle bin from the file.
+        clean_config.trash |= self.trash && clean_config.quarantine.is_none();
+        if !clean_config.trash {
+            clean_config.quarantine = clean_config.quarantine.take().or(self.quarantine.clone());
+        }
+        clean_config.backup_dir = clean_config.backup_dir.take().or(self.backup_dir.clone());
+        clean_config.log_file = clean_config.log_file.take().or(self.log_file.clone());
+    }
+}
+
+/// Next to the executable on Windows, in the user's config directory elsewhere.
+pub fn default_path() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env::current_exe()
+            .ok()
+            .and_then(|executable| executable.parent().map(|dir| dir.join(CONFIG_FILE_NAME)));
+    }
+
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|config_home| !config_home.is_empty())
+        .map(PathBuf::from)
+        .or(env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config_dir| config_dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+/// Reads the given config file, or the default one if it exists.
+pub fn load(path: Option<&Path>) -> Result<ConfigFile, ProgramError> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => match default_path().filter(|path| path.is_file()) {
+            Some(path) => path,
+            None => return Ok(ConfigFile::default()),
+        },
+    };
+
+    fs::read_to_string(&path)
+        .map_err(|e| ProgramError::FailedToReadFile(format!("{}: {}", path.display(), e)))
+        .and_then(|content| {
+            ConfigFile::parse(&content)
+                .map_err(|e| ProgramError::InvalidConfig(format!("{} {}", path.display(), e)))
+        })
+}
+
+/// Writes the commented template, refusing to replace an existing file unless forced.
+pub fn write_template(path: &Path, force: bool) -> Result<(), ProgramError> {
+    if path.exists() && !force {
+        return Err(ProgramError::FailedToWriteFile(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        )));
+    }
+
+    path.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(path, TEMPLATE))
+        .map_err(|e| ProgramError::FailedToWriteFile(format!("{}: {}", path.display(), e)))
+}
+
+/// Removes a `#` comment, ignoring `#` inside strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(open), c) if c == open && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => (),
+        }
+        escaped = false;
+    }
+
+    line
+}
+
+fn parse_value(value: &str) -> Result<TomlValue, String> {
+    match value {
+        "true" => Ok(TomlValue::Bool(true)),
+        "false" => Ok(TomlValue::Bool(false)),
+        _ if value.starts_with('[') => value
+            .strip_suffix(']')
+            .ok_or("array is missing its closing ]".to_string())
+            .map(|items| split_items(&items[1..]))
+            .and_then(|items| {
+                items
+                    .iter()
+                    .map(|item| parse_value(item))
+                    .collect::<Result<Vec<TomlValue>, String>>()
+            })
+            .map(TomlValue::Array),
+        _ if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') => {
+            Ok(TomlValue::String(value[1..value.len() - 1].to_string()))
+        }
+        _ if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') => {
+            unescape(&value[1..value.len() - 1]).map(TomlValue::String)
+        }
+        _ => value
+            .replace('_', "")
+            .parse::<u64>()
+            .map(TomlValue::Integer)
+            .map_err(|_| format!("unsupported value {}", value)),
+    }
+}
+
+/// Splits the inside of an array on the commas that are not part of a string.
+fn split_items(items: &str) -> Vec<&str> {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    let mut split = Vec::new();
+
+    for (i, c) in items.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(open), c) if c == open && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                split.push(&items[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+        escaped = false;
+    }
+    split.push(&items[start..]);
+
+    // Allows a trailing comma and empty arrays.
+    split
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn unescape(value: &str) -> Result<String, String> {
+    let mut chars = value.chars();
+    let mut unescaped = String::new();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('"') => unescaped.push('"'),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            other => {
+                return Err(format!(
+                    "unsupported escape \\{}",
+                    other.map(String::from).unwrap_or_default()
+                ))
+            }
+        }
+    }
+
+    Ok(unescaped)
+}
+
+fn string(value: TomlValue) -> Result<String, String> {
+    match value {
+        TomlValue::String(value) => Ok(value),
+        _ => Err("expected a string".to_string()),
+    }
+}
+
+fn strings(value: TomlValue) -> Result<Vec<String>, String> {
+    match value {
+        TomlValue::Array(values) => values.into_iter().map(string).collect(),
+        _ => Err("expected a list of strings".to_string()),
+    }
+}
+
+fn integer(value: TomlValue) -> Result<u64, String> {
+    match value {
+        TomlValue::Integer(value) => Ok(value),
+        _ => Err("expected a whole number".to_string()),
+    }
+}
+
+fn boolean(value: TomlValue) -> Result<bool, String> {
+    match value {
+        TomlValue::Bool(value) => Ok(value),
+        _ => Err("expected true or false".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod parse_should {
+    use std::{path::PathBuf, time::Duration};
+
+    use crate::{
+        config_file::{ConfigFile, TEMPLATE},
+        folder_pattern::FolderPattern,
+    };
+
+    #[test]
+    fn read_supported_keys() {
+        let content = r#"
+            # Saves on two drives
+            path_to_save_folder = ["D:/saves", 'E:/saves # copy',]
+            saves_to_preserve = 5 # per character
+            preserve_for = ["Tav=30"]
+            older_than = "2w"
+            max_delete_percent = 80
+            trash = true
+            log_file = "C:\\logs\\bg3 \"deleted\".log"
+        "#;
+
+        assert_eq!(
+            ConfigFile::parse(content).unwrap(),
+            ConfigFile {
+                path_to_save_folder: vec![
+                    PathBuf::from("D:/saves"),
+                    PathBuf::from("E:/saves # copy")
+                ],
+                saves_to_preserve: Some(5),
+                preserve_for: vec![("Tav".to_string(), 30)],
+                older_than: Some(Duration::from_secs(14 * 24 * 60 * 60)),
+                max_delete_percent: Some(80),
+                trash: true,
+                log_file: Some(PathBuf::from("C:\\logs\\bg3 \"deleted\".log")),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn accept_single_path() {
+        assert_eq!(
+            ConfigFile::parse("path_to_save_folder = \"saves\"")
+                .unwrap()
+                .path_to_save_folder,
+            vec![PathBuf::from("saves")]
+        );
+    }
+
+    #[test]
+    fn read_folder_pattern() {
+        let content =
+            r"pattern = '^(?P<name>\w+) (?P<campaign>\d+) (?P<type>\w+) #(?P<number>\d+)$'";
+
+        assert_eq!(
+            ConfigFile::parse(content).unwrap().pattern,
+            Some(
+                FolderPattern::new(
+                    r"^(?P<name>\w+) (?P<campaign>\d+) (?P<type>\w+) #(?P<number>\d+)$"
+                )
+                .unwrap()
+            )
+        );
+        assert!(ConfigFile::parse("pattern = '^(?P<name>.+)$'")
+            .unwrap_err()
+            .starts_with("line 1: pattern: Pattern ^(?P<name>.+)$ has no group named campaign"));
+    }
+
+    #[test]
+    fn read_save_type_keywords() {
+        let config_file =
+            ConfigFile::parse("quick_keyword = [\"Rapide\"]\nauto_keyword = [\"Auto\"]").unwrap();
+
+        assert_eq!(
+            (config_file.quick_keyword, config_file.auto_keyword),
+            (vec!["Rapide".to_string()], vec!["Auto".to_string()])
+        );
+    }
+
+    #[test]
+    fn read_ignore_globs() {
+        assert_eq!(
+            ConfigFile::parse(r#"ignore = ["*.old", "Backup*"]"#)
+                .unwrap()
+                .ignore,
+            vec!["*.old".to_string(), "Backup*".to_string()]
+        );
+    }
+
+    #[test]
+    fn start_from_defaults_with_template() {
+        assert_eq!(ConfigFile::parse(TEMPLATE).unwrap(), ConfigFile::default());
+    }
+
+    #[test]
+    fn name_key_and_line_of_errors() {
+        let cases = [
+            (
+                "\nsaves_to_preserve = \"ten\"",
+                "line 2: saves_to_preserve: expected a whole number",
+            ),
+            ("trash = yes", "line 1: trash: unsupported value yes"),
+            (
+                "max_delete_percent = 101",
+                "line 1: max_delete_percent: expected a percentage from 0 to 100",
+            ),
+            (
+                "\n\nsave_path = \"saves\"",
+                "line 3: save_path: unknown key",
+            ),
+            (
+                "verbose = true\nverbose = false",
+                "line 2: verbose: given twice",
+            ),
+            (
+                "older_than = \"30\"",
+                "line 1: older_than: Missing unit in 30, expected one of m, h, d, w",
+            ),
+            ("[clean]", "line 1: tables are not supported"),
+            ("quiet", "line 1: expected key = value"),
+        ];
+
+        cases.iter().for_each(|(content, expected)| {
+            assert_eq!(ConfigFile::parse(content), Err(expected.to_string()))
+        });
+    }
+}
+
+#[cfg(test)]
+mod apply_to_should {
+    use std::path::PathBuf;
+
+    use crate::{clean_config::CleanConfig, config_file::ConfigFile};
+
+    #[test]
+    fn prefer_command_line_over_file() {
+        let config_file = ConfigFile {
+            saves_to_preserve: Some(5),
+            exclude_character: vec!["Astarion".to_string()],
+            backup_dir: Some(PathBuf::from("file-backups")),
+            ..Default::default()
+        };
+        let mut clean_config = CleanConfig {
+            saves_to_preserve: Some(3),
+            backup_dir: Some(PathBuf::from("cli-backups")),
+            ..Default::default()
+        };
+
+        config_file.apply_to(&mut clean_config);
+
+        assert_eq!(clean_config.saves_to_preserve, Some(3));
+        assert_eq!(clean_config.backup_dir, Some(PathBuf::from("cli-backups")));
+        assert_eq!(clean_config.exclude_character, vec!["Astarion".to_string()]);
+    }
+
+    #[test]
+    fn prefer_file_over_defaults() {
+        let config_file = ConfigFile {
+            saves_to_preserve: Some(5),
+            keep_recent: Some(24),
+            clean_corrupt: true,
+            ..Default::default()
+        };
+        let mut clean_config = CleanConfig::default();
+
+        config_file.apply_to(&mut clean_config);
+
+        assert_eq!(clean_config.saves_to_preserve, Some(5));
+        assert_eq!(clean_config.keep_recent, Some(24));
+        assert!(clean_config.clean_corrupt);
+    }
+
+    #[test]
+    fn keep_defaults_without_file() {
+        let mut clean_config = CleanConfig::default();
+
+        ConfigFile::default().apply_to(&mut clean_config);
+
+        assert_eq!(clean_config, CleanConfig::default());
+    }
+
+    #[test]
+    fn prefer_quarantine_on_command_line_over_trash_in_file() {
+        let config_file = ConfigFile {
+            trash: true,
+            ..Default::default()
+        };
+        let mut clean_config = CleanConfig {
+            quarantine: Some(PathBuf::from("quarantine")),
+            ..Default::default()
+        };
+
+        config_file.apply_to(&mut clean_config);
+
+        assert!(!clean_config.trash);
+        assert_eq!(clean_config.quarantine, Some(PathBuf::from("quarantine")));
+    }
+}
+
+#[cfg(test)]
+mod write_template_should {
+    use std::fs;
+
+    use crate::{
+        config_file::{load, write_template, TEMPLATE},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn write_template_once() {
+        let temp_dir = TempDir::new();
+        let path = temp_dir.path().join("bg3_save_cleaner").join("config.toml");
+
+        write_template(&path, false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), TEMPLATE);
+        assert!(write_template(&path, false).is_err());
+        assert!(write_template(&path, true).is_ok());
+        assert!(load(Some(&path)).is_ok());
+    }
+
+    #[test]
+    fn error_on_missing_explicit_file() {
+        let temp_dir = TempDir::new();
+
+        assert!(load(Some(&temp_dir.path().join("config.toml"))).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{command::Command, program_errors::ProgramError};
+
+/// How often a question is asked before an answer that can't be parsed counts as a no.
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// Parses `y`, `yes`, `n` and `no` in any case, `None` for anything else.
+pub fn parse_confirmation(input: &str) -> Option<bool> {
+    match input.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// The flags that run the command without asking, `None` if it never asks.
+fn confirmation_flags(command: &Command) -> Option<&'static str> {
+    match command {
+        Command::Clean(clean_config)
+            if !clean_config.yes
+                && !clean_config.dry_run
+                && !clean_config.watch
+                && clean_config.write_plan.is_none() =>
+        {
+            Some("--yes or --dry-run")
+        }
+        Command::Restore { all: false, .. } => Some("--all"),
+        _ => None,
+    }
+}
+
+/// Refuses to run a command that asks for confirmation without a terminal on stdin: from a
+/// scheduled task stdin is empty, and input piped in could confirm a deletion by accident.
+pub fn check_interactive(command: &Command, stdin_is_terminal: bool) -> Result<(), ProgramError> {
+    match confirmation_flags(command) {
+        Some(flags) if !stdin_is_terminal => Err(ProgramError::NotInteractive(format!(
+            "Stdin is not a terminal, so nothing can be confirmed. Pass {} to run without asking",
+            flags
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod parse_confirmation_should {
+    use crate::confirmation::parse_confirmation;
+
+    #[test]
+    fn accept_letters_and_words_in_any_case() {
+        assert_eq!(parse_confirmation("y"), Some(true));
+        assert_eq!(parse_confirmation(" YES "), Some(true));
+        assert_eq!(parse_confirmation("N"), Some(false));
+        assert_eq!(parse_confirmation("no"), Some(false));
+    }
+
+    #[test]
+    fn reject_anything_else() {
+        assert_eq!(parse_confirmation("u"), None);
+        assert_eq!(parse_confirmation("yess"), None);
+        assert_eq!(parse_confirmation(""), None);
+    }
+}
+
+#[cfg(test)]
+mod check_interactive_should {
+    use std::path::PathBuf;
+
+    use crate::{
+        clean_config::CleanConfig, command::Command, confirmation::check_interactive,
+        program_errors::ProgramError,
+    };
+
+    fn clean(clean_config: CleanConfig) -> Command {
+        Command::Clean(Box::new(clean_config))
+    }
+
+    #[test]
+    fn refuse_to_ask_without_a_terminal() {
+        assert!(matches!(
+            check_interactive(&clean(CleanConfig::default()), false),
+            Err(ProgramError::NotInteractive(_))
+        ));
+        assert!(matches!(
+            check_interactive(
+                &Command::Restore {
+                    quarantine_dir: PathBuf::from("quarantine"),
+                    all: false
+                },
+                false
+            ),
+            Err(ProgramError::NotInteractive(_))
+        ));
+    }
+
+    #[test]
+    fn allow_runs_that_never_ask() {
+        let yes = clean(CleanConfig {
+            yes: true,
+            ..Default::default()
+        });
+        let dry_run = clean(CleanConfig {
+            dry_run: true,
+            ..Default::default()
+        });
+
+        assert_eq!(check_interactive(&yes, false), Ok(()));
+        assert_eq!(check_interactive(&dry_run, false), Ok(()));
+        assert_eq!(check_interactive(&Command::Undo, false), Ok(()));
+    }
+
+    #[test]
+    fn ask_on_a_terminal() {
+        assert_eq!(
+            check_interactive(&clean(CleanConfig::default()), true),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/// The name of the folder a hand made copy was made from, `None` if the folder name doesn't end
+/// in a suffix Windows Explorer adds to copies: ` (2)` or ` - Copy`, also as ` - Copy (2)`.
+pub fn original_name(folder_name: &str) -> Option<&str> {
+    let without_number = folder_name
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .filter(|(_, number)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        .map(|(original, _)| original);
+    let without_copy = without_number
+        .unwrap_or(folder_name)
+        .strip_suffix(" - Copy");
+
+    without_copy
+        .or(without_number)
+        .filter(|original| !original.is_empty())
+}
+
+#[cfg(test)]
+mod original_name_should {
+    use crate::copy_suffix::original_name;
+
+    #[test]
+    fn strip_numbered_copies() {
+        assert_eq!(
+            original_name("Tav-123456789__QuickSave_50 (1)"),
+            Some("Tav-123456789__QuickSave_50")
+        );
+        assert_eq!(
+            original_name("Tav-123456789__QuickSave_50 (2)"),
+            Some("Tav-123456789__QuickSave_50")
+        );
+    }
+
+    #[test]
+    fn strip_copy_suffix() {
+        assert_eq!(
+            original_name("Tav-123456789__QuickSave_50 - Copy"),
+            Some("Tav-123456789__QuickSave_50")
+        );
+        assert_eq!(
+            original_name("Tav-123456789__QuickSave_50 - Copy (3)"),
+            Some("Tav-123456789__QuickSave_50")
+        );
+    }
+
+    #[test]
+    fn keep_other_names() {
+        assert_eq!(original_name("Tav-123456789__QuickSave_50"), None);
+        assert_eq!(original_name("Tav-123456789__Camp (before boss)"), None);
+        assert_eq!(original_name("Tav-123456789__Camp ()"), None);
+        assert_eq!(original_name(" (1)"), None);
+    }
+}
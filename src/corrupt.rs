@@ -0,0 +1,61 @@
+use std::{fs, path::Path};
+
+/// A save folder is corrupt when it has no `.lsv` file or an empty one, as left behind by a
+/// crash while saving. Any other files in the folder, like the `.webp` thumbnail, are ignored.
+pub fn is_corrupt(save_folder: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(save_folder) else {
+        return false;
+    };
+
+    let lsv_sizes: Vec<u64> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("lsv"))
+        })
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .collect();
+
+    lsv_sizes.is_empty() || lsv_sizes.contains(&0)
+}
+
+#[cfg(test)]
+mod is_corrupt_should {
+    use std::fs;
+
+    use crate::{corrupt::is_corrupt, test_utils::TempDir};
+
+    #[test]
+    fn accept_healthy_save_with_extra_files() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::write(save_path.join("Tav-123456789__QuickSave_1.webp"), b"RIFF").unwrap();
+        fs::write(save_path.join("notes.txt"), b"").unwrap();
+
+        assert!(!is_corrupt(&save_path));
+    }
+
+    #[test]
+    fn flag_save_without_lsv() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("Tav-123456789__QuickSave_1.webp"), b"RIFF").unwrap();
+
+        assert!(is_corrupt(&save_path));
+    }
+
+    #[test]
+    fn flag_save_with_empty_lsv() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("Tav-123456789__QuickSave_1.lsv"), b"").unwrap();
+
+        assert!(is_corrupt(&save_path));
+    }
+}
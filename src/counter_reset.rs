@@ -0,0 +1,77 @@
+use crate::save_information::SaveInformation;
+
+/// Whether the save numbers of one character's saves of a type look like the game restarted
+/// counting, which it does after every slot was deleted in game: the most recently modified
+/// save has a lower number than more than half of the other saves, all of them older.
+pub fn detect(saves: &[SaveInformation]) -> bool {
+    let dated: Vec<(u64, _)> = saves
+        .iter()
+        .filter_map(|save| save.save_number.zip(save.modified))
+        .collect();
+    let Some((newest_number, newest_modified)) = dated
+        .iter()
+        .max_by_key(|(number, modified)| (*modified, *number))
+        .copied()
+    else {
+        return false;
+    };
+
+    let higher_and_older = dated
+        .iter()
+        .filter(|(number, modified)| *number > newest_number && *modified < newest_modified)
+        .count();
+
+    higher_and_older * 2 > dated.len() - 1
+}
+
+#[cfg(test)]
+mod detect_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{counter_reset::detect, save_information::SaveInformation, save_type::SaveType};
+
+    fn saves(numbers_oldest_first: &[u64]) -> Vec<SaveInformation> {
+        let now = SystemTime::now();
+        numbers_oldest_first
+            .iter()
+            .enumerate()
+            .map(|(i, number)| {
+                SaveInformation::new(
+                    format!("Tav-123456789__QuickSave_{}", number),
+                    "Tav".to_string(),
+                    SaveType::Quick,
+                    Some(*number),
+                )
+                .with_modified(Some(
+                    now - Duration::from_secs(3600 * (numbers_oldest_first.len() - i) as u64),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detect_numbers_restarting_after_deleting_every_slot() {
+        assert!(detect(&saves(&[238, 239, 240, 1, 2])));
+        assert!(detect(&saves(&[240, 1])));
+    }
+
+    #[test]
+    fn accept_numbers_agreeing_with_modified_times() {
+        assert!(!detect(&saves(&[1, 2, 3, 4, 5])));
+        assert!(!detect(&saves(&[])));
+        assert!(!detect(&saves(&[7])));
+    }
+
+    #[test]
+    fn ignore_a_single_out_of_order_save() {
+        assert!(!detect(&saves(&[1, 2, 3, 5, 6, 7, 4])));
+    }
+
+    #[test]
+    fn ignore_saves_without_modified_time() {
+        let mut saves = saves(&[240, 1]);
+        saves[1].modified = None;
+
+        assert!(!detect(&saves));
+    }
+}
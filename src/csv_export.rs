@@ -0,0 +1,145 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+    character_key::CharacterKey, program_errors::ProgramError, save_information::SaveInformation,
+    saves::Saves, timestamp::format_local,
+};
+
+const HEADER: &str =
+    "character,save_type,save_number,folder_name,modified,size_bytes,game_version,marked_for_deletion";
+
+pub fn write_csv(
+    path: &Path,
+    saves: &HashMap<CharacterKey, Saves>,
+    deletable_saves: &[SaveInformation],
+) -> Result<(), ProgramError> {
+    fs::write(path, saves_to_csv(saves, deletable_saves))
+        .map_err(|e| ProgramError::FailedToWriteFile(e.to_string()))
+}
+
+/// Renders one row per discovered save, ordered by character and newest save first.
+fn saves_to_csv(
+    saves: &HashMap<CharacterKey, Saves>,
+    deletable_saves: &[SaveInformation],
+) -> String {
+    let mut characters: Vec<(&CharacterKey, &Saves)> = saves.iter().collect();
+    characters.sort_by_key(|(name, _)| *name);
+
+    characters
+        .into_iter()
+        .flat_map(|(_, saves)| saves.quick_saves.iter().chain(saves.auto_saves.iter()))
+        .map(|save_information| {
+            [
+                csv_field(&save_information.character_name),
+                format!("{:?}", save_information.save_type),
+                save_information
+                    .save_number
+                    .map(|save_number| save_number.to_string())
+                    .unwrap_or_default(),
+                csv_field(&save_information.file_name),
+                save_information
+                    .modified
+                    .map(format_local)
+                    .unwrap_or_default(),
+                save_information.size_bytes.to_string(),
+                csv_field(save_information.game_version.as_deref().unwrap_or_default()),
+                deletable_saves.contains(save_information).to_string(),
+            ]
+            .join(",")
+        })
+        .fold(format!("{}\n", HEADER), |csv, row| csv + &row + "\n")
+}
+
+/// Quotes a field when it contains a separator, quote or line break, doubling inner quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod saves_to_csv_should {
+    use std::{
+        collections::HashMap,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::{
+        character_key::CharacterKey, csv_export::saves_to_csv, save_information::SaveInformation,
+        save_type::SaveType, saves::Saves, timestamp::format_local,
+    };
+
+    #[test]
+    fn include_kept_and_deletable_saves() {
+        let mut kept = SaveInformation::new(
+            "Some'me-123456789__QuickSave_4".to_string(),
+            "Some'me".to_string(),
+            SaveType::Quick,
+            Some(4),
+        )
+        .with_modified(Some(UNIX_EPOCH + Duration::from_secs(1_709_414_045)))
+        .with_size_bytes(2048);
+        kept.game_version = Some("4.1.1.5022896".to_string());
+        let deletable = SaveInformation::new(
+            "Some'me-123456789__AutoSave_1".to_string(),
+            "Some'me".to_string(),
+            SaveType::Auto,
+            Some(1),
+        );
+        let mut saves = HashMap::new();
+        saves.insert(
+            CharacterKey::named("Some'me"),
+            Saves {
+                quick_saves: vec![kept],
+                auto_saves: vec![deletable.clone()],
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
+            },
+        );
+
+        let csv = saves_to_csv(&saves, &[deletable]);
+        assert_eq!(
+            csv,
+            format!(
+                "character,save_type,save_number,folder_name,modified,size_bytes,game_version,marked_for_deletion\n\
+                 Some'me,Quick,4,Some'me-123456789__QuickSave_4,{},2048,4.1.1.5022896,false\n\
+                 Some'me,Auto,1,Some'me-123456789__AutoSave_1,,0,,true\n",
+                format_local(UNIX_EPOCH + Duration::from_secs(1_709_414_045))
+            )
+        );
+    }
+
+    #[test]
+    fn quote_names_containing_commas() {
+        let save = SaveInformation::new(
+            "Tav, \"the\" Brave-123456789__QuickSave_2".to_string(),
+            "Tav, \"the\" Brave".to_string(),
+            SaveType::Quick,
+            Some(2),
+        );
+        let mut saves = HashMap::new();
+        saves.insert(
+            CharacterKey::of(&save),
+            Saves {
+                quick_saves: vec![save],
+                auto_saves: Vec::new(),
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
+            },
+        );
+
+        let csv = saves_to_csv(&saves, &[]);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "\"Tav, \"\"the\"\" Brave\",Quick,2,\"Tav, \"\"the\"\" Brave-123456789__QuickSave_2\",,0,,false"
+        );
+    }
+}
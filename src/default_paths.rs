@@ -0,0 +1,664 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{profiles, program_errors::ProgramError};
+
+/// Baldur's Gate 3 on Steam, naming its Proton prefix and its Steam Cloud files.
+pub const STEAM_APP_ID: &str = "1086940";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Platform {
+    Windows,
+    Linux,
+    MacOs,
+    #[default]
+    Other,
+}
+
+/// What the default save locations are derived from, read from the process environment and
+/// replaced with a fake directory layout in tests.
+#[derive(Debug, PartialEq, Default)]
+pub struct Environment {
+    pub platform: Platform,
+    /// `%LOCALAPPDATA%` on Windows.
+    pub local_app_data: Option<PathBuf>,
+    /// `%USERPROFILE%` on Windows, for when LOCALAPPDATA is not set.
+    pub user_profile: Option<PathBuf>,
+    /// `%ProgramFiles(x86)%`, where Steam installs on Windows.
+    pub program_files: Option<PathBuf>,
+    /// `$HOME` elsewhere.
+    pub home: Option<PathBuf>,
+    /// `$XDG_DATA_HOME`, where a native Steam install lives on Linux.
+    pub xdg_data_home: Option<PathBuf>,
+    /// Where Linux mounts removable media, like the SD card of a Steam Deck.
+    pub media_root: Option<PathBuf>,
+}
+impl Environment {
+    pub fn current() -> Self {
+        let var = |name| {
+            env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        Environment {
+            platform: if cfg!(windows) {
+                Platform::Windows
+            } else if cfg!(target_os = "linux") {
+                Platform::Linux
+            } else if cfg!(target_os = "macos") {
+                Platform::MacOs
+            } else {
+                Platform::Other
+            },
+            local_app_data: var("LOCALAPPDATA"),
+            user_profile: var("USERPROFILE"),
+            program_files: var("ProgramFiles(x86)"),
+            home: var("HOME"),
+            xdg_data_home: var("XDG_DATA_HOME"),
+            media_root: Some(PathBuf::from("/run/media")),
+        }
+    }
+}
+
+/// A `PlayerProfiles` directory the game may keep its saves in, with how it was found.
+#[derive(Debug, PartialEq)]
+pub struct Candidate {
+    pub path: PathBuf,
+    pub found_by: String,
+}
+impl Candidate {
+    fn has_profiles(&self) -> bool {
+        !profiles::find_profiles(&self.path).is_empty()
+    }
+}
+
+/// The locations the game keeps its saves in on the platform, most likely first. A location
+/// reached in several ways, like through a link, is only listed the first time.
+pub fn candidates(environment: &Environment) -> Vec<Candidate> {
+    let candidates = match environment.platform {
+        Platform::Windows => windows_candidates(environment),
+        Platform::Linux => linux_candidates(environment),
+        Platform::MacOs => macos_candidates(environment),
+        Platform::Other => Vec::new(),
+    };
+
+    let mut seen: Vec<PathBuf> = Vec::new();
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            let resolved = fs::canonicalize(&candidate.path).unwrap_or(candidate.path.clone());
+            !seen.contains(&resolved) && {
+                seen.push(resolved);
+                true
+            }
+        })
+        .collect()
+}
+
+/// One line per candidate for `--list-detected-paths`, marking the one that is used. None is
+/// when several have saves.
+pub fn list(environment: &Environment) -> Vec<String> {
+    let candidates = candidates(environment);
+    let with_profiles: Vec<usize> = (0..candidates.len())
+        .filter(|index| candidates[*index].has_profiles())
+        .collect();
+    let used = match with_profiles[..] {
+        [index] => Some(index),
+        _ => None,
+    };
+
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| {
+            let state = match (Some(index) == used, candidate.has_profiles()) {
+                (true, _) => "using",
+                (false, true) => "found",
+                (false, false) => "no saves",
+            };
+            format!(
+                "{:<8}  {} ({})",
+                state,
+                candidate.path.display(),
+                candidate.found_by
+            )
+        })
+        .collect()
+}
+
+/// The candidate with a player profile that has a `Savegames/Story` folder. When several have
+/// one, guessing could clean the wrong install, so the path has to be given.
+pub fn detect(environment: &Environment) -> Result<Candidate, ProgramError> {
+    let candidates = candidates(environment);
+    let searched = candidates
+        .iter()
+        .map(|candidate| candidate.path.display().to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut found: Vec<Candidate> = candidates
+        .into_iter()
+        .filter(Candidate::has_profiles)
+        .collect();
+    match found.len() {
+        1 => Ok(found.remove(0)),
+        0 if searched.is_empty() => Err(ProgramError::NoPath(
+            "No save folder given. Pass one with --path-to-save-folder, set BG3_SAVE_DIR or set path_to_save_folder in the config file".to_string(),
+        )),
+        0 => Err(ProgramError::NoPath(format!(
+            "No save folder given and none found in {}. Pass one with --path-to-save-folder, set BG3_SAVE_DIR or set path_to_save_folder in the config file",
+            searched
+        ))),
+        _ => Err(ProgramError::NoPath(format!(
+            "No save folder given and saves found in several places, pick one with --path-to-save-folder: {}",
+            found
+                .iter()
+                .map(|candidate| format!("{} ({})", candidate.path.display(), candidate.found_by))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))),
+    }
+}
+
+fn windows_candidates(environment: &Environment) -> Vec<Candidate> {
+    environment
+        .local_app_data
+        .iter()
+        .map(|local_app_data| (local_app_data.clone(), "LOCALAPPDATA"))
+        .chain(
+            environment
+                .user_profile
+                .iter()
+                .map(|user_profile| (user_profile.join("AppData").join("Local"), "USERPROFILE")),
+        )
+        .map(|(local_app_data, found_by)| Candidate {
+            path: player_profiles(&local_app_data),
+            found_by: found_by.to_string(),
+        })
+        .collect()
+}
+
+/// The native macOS build saves into the documents folder.
+fn macos_candidates(environment: &Environment) -> Vec<Candidate> {
+    environment
+        .home
+        .iter()
+        .map(|home| Candidate {
+            path: player_profiles(&home.join("Documents")),
+            found_by: "Documents".to_string(),
+        })
+        .collect()
+}
+
+/// The game runs through Proton, which keeps a Windows user folder in a prefix per game. The
+/// prefix is looked for in this order, each Steam install followed by the libraries it lists in
+/// `libraryfolders.vdf`:
+/// 1. `~/.steam/steam`, which usually links to the native install
+/// 2. the native install in `$XDG_DATA_HOME/Steam` or `~/.local/share/Steam`
+/// 3. `~/.steam/root`
+/// 4. the Flatpak install in `~/.var/app/com.valvesoftware.Steam`, the Steam Deck desktop mode
+///    default
+/// 5. libraries on removable media, like a Steam Deck SD card in `/run/media/mmcblk0p1`
+fn linux_candidates(environment: &Environment) -> Vec<Candidate> {
+    linux_steam_roots(environment)
+        .iter()
+        .flat_map(|(root, steam)| {
+            steam_libraries(root)
+                .into_iter()
+                .map(move |library| (library, *steam))
+        })
+        .chain(
+            environment
+                .media_root
+                .iter()
+                .flat_map(|media_root| removable_libraries(media_root))
+                .map(|library| (library, "Removable media Steam")),
+        )
+        .map(|(library, steam)| Candidate {
+            path: player_profiles(&proton_local_app_data(&library)),
+            found_by: format!("{} library {}", steam, library.display()),
+        })
+        .collect()
+}
+
+/// Where Steam itself may be installed, to find the files it keeps next to the games like the
+/// Steam Cloud lists. The libraries are not included.
+pub fn steam_roots(environment: &Environment) -> Vec<PathBuf> {
+    match environment.platform {
+        Platform::Windows => environment
+            .program_files
+            .iter()
+            .map(|program_files| program_files.join("Steam"))
+            .collect(),
+        Platform::Linux => linux_steam_roots(environment)
+            .into_iter()
+            .map(|(root, _)| root)
+            .collect(),
+        Platform::MacOs => environment
+            .home
+            .iter()
+            .map(|home| home.join("Library/Application Support/Steam"))
+            .collect(),
+        Platform::Other => Vec::new(),
+    }
+}
+
+/// The Steam installs in the order of `linux_candidates`, with the name of the package.
+fn linux_steam_roots(environment: &Environment) -> Vec<(PathBuf, &'static str)> {
+    let Some(home) = &environment.home else {
+        return Vec::new();
+    };
+    let flatpak = home.join(".var/app/com.valvesoftware.Steam");
+
+    vec![
+        (home.join(".steam/steam"), "Steam"),
+        (
+            environment
+                .xdg_data_home
+                .clone()
+                .unwrap_or(home.join(".local/share"))
+                .join("Steam"),
+            "Steam",
+        ),
+        (home.join(".steam/root"), "Steam"),
+        (flatpak.join(".local/share/Steam"), "Flatpak Steam"),
+        (flatpak.join("data/Steam"), "Flatpak Steam"),
+    ]
+}
+
+/// Steam libraries on media mounted directly, like `/run/media/mmcblk0p1`, or per user, like
+/// `/run/media/deck/<label>`, either at the top or in a `SteamLibrary` folder.
+fn removable_libraries(media_root: &Path) -> Vec<PathBuf> {
+    child_dirs(media_root)
+        .into_iter()
+        .flat_map(|mount| {
+            let nested = child_dirs(&mount);
+            std::iter::once(mount).chain(nested)
+        })
+        .flat_map(|volume| [volume.clone(), volume.join("SteamLibrary")])
+        .filter(|library| library.join("steamapps").is_dir())
+        .collect()
+}
+
+fn child_dirs(directory: &Path) -> Vec<PathBuf> {
+    let mut children: Vec<PathBuf> = fs::read_dir(directory)
+        .map(|children| {
+            children
+                .flatten()
+                .map(|child| child.path())
+                .filter(|child| child.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    children.sort();
+
+    children
+}
+
+/// The Steam install itself, then the libraries it lists.
+fn steam_libraries(steam_root: &Path) -> Vec<PathBuf> {
+    let listed = ["steamapps", "config"]
+        .iter()
+        .find_map(|dir| fs::read_to_string(steam_root.join(dir).join("libraryfolders.vdf")).ok())
+        .map(|vdf| library_paths(&vdf))
+        .unwrap_or_default();
+
+    std::iter::once(steam_root.to_path_buf())
+        .chain(listed)
+        .collect()
+}
+
+/// The `"path"` values of `libraryfolders.vdf`, one for each library.
+fn library_paths(vdf: &str) -> Vec<PathBuf> {
+    vdf.lines()
+        .filter_map(|line| {
+            let mut fields = line
+                .trim()
+                .split('"')
+                .filter(|field| !field.trim().is_empty());
+            match (fields.next(), fields.next()) {
+                (Some("path"), Some(path)) => Some(PathBuf::from(path.replace("\\\\", "\\"))),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn proton_local_app_data(library: &Path) -> PathBuf {
+    library
+        .join("steamapps/compatdata")
+        .join(STEAM_APP_ID)
+        .join("pfx/drive_c/users/steamuser/AppData/Local")
+}
+
+/// Laid out the same below the folder the game writes to on every platform.
+fn player_profiles(game_data_dir: &Path) -> PathBuf {
+    game_data_dir
+        .join("Larian Studios")
+        .join("Baldur's Gate 3")
+        .join("PlayerProfiles")
+}
+
+#[cfg(test)]
+mod detect_should {
+    use std::{fs, path::Path};
+
+    use crate::{
+        default_paths::{candidates, detect, player_profiles, Candidate, Environment, Platform},
+        profiles::story_dir,
+        program_errors::ProgramError,
+        test_utils::TempDir,
+    };
+
+    fn windows(local_app_data: Option<&Path>, user_profile: Option<&Path>) -> Environment {
+        Environment {
+            platform: Platform::Windows,
+            local_app_data: local_app_data.map(Path::to_path_buf),
+            user_profile: user_profile.map(Path::to_path_buf),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_the_player_profiles_in_local_app_data() {
+        let temp_dir = TempDir::new();
+        let local_app_data = temp_dir.path().join("AppData/Local");
+        fs::create_dir_all(story_dir(&player_profiles(&local_app_data).join("Public"))).unwrap();
+
+        assert_eq!(
+            detect(&windows(Some(&local_app_data), Some(temp_dir.path()))),
+            Ok(Candidate {
+                path: player_profiles(&local_app_data),
+                found_by: "LOCALAPPDATA".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn fall_back_to_the_user_profile() {
+        let temp_dir = TempDir::new();
+        let elsewhere = TempDir::new();
+        let local_app_data = temp_dir.path().join("AppData").join("Local");
+        fs::create_dir_all(story_dir(&player_profiles(&local_app_data).join("Public"))).unwrap();
+
+        let detected = detect(&windows(Some(elsewhere.path()), Some(temp_dir.path()))).unwrap();
+
+        assert_eq!(detected.path, player_profiles(&local_app_data));
+        assert_eq!(detected.found_by, "USERPROFILE");
+    }
+
+    #[test]
+    fn list_the_same_folder_once() {
+        let temp_dir = TempDir::new();
+        let local_app_data = temp_dir.path().join("AppData").join("Local");
+        fs::create_dir_all(story_dir(&player_profiles(&local_app_data).join("Public"))).unwrap();
+
+        let environment = windows(Some(&local_app_data), Some(temp_dir.path()));
+
+        assert_eq!(candidates(&environment).len(), 1);
+        assert_eq!(detect(&environment).unwrap().found_by, "LOCALAPPDATA");
+    }
+
+    #[test]
+    fn skip_locations_without_a_profile() {
+        let temp_dir = TempDir::new();
+        // The game was installed, but never saved.
+        fs::create_dir_all(player_profiles(temp_dir.path()).join("Public")).unwrap();
+
+        assert!(matches!(
+            detect(&windows(Some(temp_dir.path()), None)),
+            Err(ProgramError::NoPath(e)) if e.contains("none found in")
+        ));
+    }
+
+    #[test]
+    fn ask_for_a_path_where_nothing_is_probed() {
+        assert!(matches!(
+            detect(&Environment::default()),
+            Err(ProgramError::NoPath(e)) if e.contains("--path-to-save-folder")
+        ));
+    }
+}
+
+#[cfg(test)]
+mod linux_candidates_should {
+    use std::{fs, path::Path};
+
+    use crate::{
+        default_paths::{
+            candidates, detect, library_paths, linux_candidates, list, player_profiles,
+            proton_local_app_data, Environment, Platform,
+        },
+        profiles::story_dir,
+        program_errors::ProgramError,
+        test_utils::TempDir,
+    };
+
+    fn linux(home: &Path) -> Environment {
+        Environment {
+            platform: Platform::Linux,
+            home: Some(home.to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    fn with_media(home: &Path, media_root: &Path) -> Environment {
+        Environment {
+            media_root: Some(media_root.to_path_buf()),
+            ..linux(home)
+        }
+    }
+
+    fn create_profile(library: &Path) {
+        let player_profiles = player_profiles(&proton_local_app_data(library));
+        fs::create_dir_all(story_dir(&player_profiles.join("Public"))).unwrap();
+    }
+
+    #[test]
+    fn find_the_proton_prefix_of_native_steam() {
+        let home = TempDir::new();
+        let steam = home.path().join(".local/share/Steam");
+        create_profile(&steam);
+
+        let detected = detect(&linux(home.path())).unwrap();
+
+        assert_eq!(
+            detected.path,
+            steam.join("steamapps/compatdata/1086940/pfx/drive_c/users/steamuser/AppData/Local/Larian Studios/Baldur's Gate 3/PlayerProfiles")
+        );
+        assert_eq!(
+            detected.found_by,
+            format!("Steam library {}", steam.display())
+        );
+    }
+
+    #[test]
+    fn find_the_proton_prefix_of_flatpak_steam() {
+        let home = TempDir::new();
+        let steam = home
+            .path()
+            .join(".var/app/com.valvesoftware.Steam/.local/share/Steam");
+        create_profile(&steam);
+
+        let detected = detect(&linux(home.path())).unwrap();
+
+        assert_eq!(
+            detected.path,
+            player_profiles(&proton_local_app_data(&steam))
+        );
+        assert!(detected.found_by.starts_with("Flatpak Steam"));
+    }
+
+    #[test]
+    fn find_the_game_in_another_library() {
+        let home = TempDir::new();
+        let library = TempDir::new();
+        let steam = home.path().join(".local/share/Steam");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::write(
+            steam.join("steamapps/libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t\t\"label\"\t\t\"\"\n\t}}\n}}\n",
+                steam.display(),
+                library.path().display()
+            ),
+        )
+        .unwrap();
+        create_profile(library.path());
+
+        let detected = detect(&linux(home.path())).unwrap();
+
+        assert_eq!(
+            detected.path,
+            player_profiles(&proton_local_app_data(library.path()))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_a_linked_steam_folder_once() {
+        let home = TempDir::new();
+        let steam = home.path().join(".local/share/Steam");
+        create_profile(&steam);
+        fs::create_dir(home.path().join(".steam")).unwrap();
+        std::os::unix::fs::symlink(&steam, home.path().join(".steam/steam")).unwrap();
+
+        let listed = list(&linux(home.path()));
+
+        assert_eq!(candidates(&linux(home.path())).len(), 4);
+        assert!(listed[0].starts_with("using "));
+        assert!(listed[1..].iter().all(|line| line.starts_with("no saves")));
+    }
+
+    #[test]
+    fn read_the_library_paths() {
+        let vdf = "\"libraryfolders\"\n{\n\t\"1\"\n\t{\n\t\t\"path\"\t\t\"D:\\\\SteamLibrary\"\n\t\t\"contentid\"\t\t\"42\"\n\t}\n}";
+
+        assert_eq!(
+            library_paths(vdf),
+            vec![Path::new("D:\\SteamLibrary").to_path_buf()]
+        );
+    }
+
+    #[test]
+    fn find_the_game_on_an_sd_card() {
+        let home = TempDir::new();
+        let media = TempDir::new();
+        let sd_card = media.path().join("mmcblk0p1");
+        create_profile(&sd_card);
+
+        let detected = detect(&with_media(home.path(), media.path())).unwrap();
+
+        assert_eq!(
+            detected.path,
+            player_profiles(&proton_local_app_data(&sd_card))
+        );
+        assert!(detected
+            .found_by
+            .starts_with("Removable media Steam library"));
+    }
+
+    #[test]
+    fn find_libraries_mounted_per_user() {
+        let home = TempDir::new();
+        let media = TempDir::new();
+        let library = media.path().join("deck/Games/SteamLibrary");
+        fs::create_dir_all(library.join("steamapps")).unwrap();
+        fs::create_dir_all(media.path().join("deck/Photos")).unwrap();
+
+        let found: Vec<_> = candidates(&with_media(home.path(), media.path()))
+            .into_iter()
+            .filter(|candidate| candidate.path.starts_with(media.path()))
+            .map(|candidate| candidate.path)
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![player_profiles(&proton_local_app_data(&library))]
+        );
+    }
+
+    #[test]
+    fn ask_for_a_path_when_several_installs_have_saves() {
+        let home = TempDir::new();
+        let media = TempDir::new();
+        create_profile(&home.path().join(".local/share/Steam"));
+        create_profile(&media.path().join("mmcblk0p1"));
+        let environment = with_media(home.path(), media.path());
+
+        assert!(matches!(
+            detect(&environment),
+            Err(ProgramError::NoPath(e)) if e.contains("saves found in several places")
+        ));
+        assert_eq!(
+            list(&environment)
+                .iter()
+                .filter(|line| line.starts_with("found "))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn probe_nothing_without_a_home() {
+        let environment = Environment {
+            platform: Platform::Linux,
+            ..Default::default()
+        };
+
+        assert!(linux_candidates(&environment).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod macos_candidates_should {
+    use std::{fs, path::Path};
+
+    use crate::{
+        default_paths::{candidates, detect, Candidate, Environment, Platform},
+        profiles::{resolve, story_dir},
+        test_utils::TempDir,
+    };
+
+    fn macos(home: &Path) -> Environment {
+        Environment {
+            platform: Platform::MacOs,
+            home: Some(home.to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn look_in_the_documents_folder() {
+        let home = TempDir::new();
+
+        assert_eq!(
+            candidates(&macos(home.path())),
+            vec![Candidate {
+                path: home
+                    .path()
+                    .join("Documents/Larian Studios/Baldur's Gate 3/PlayerProfiles"),
+                found_by: "Documents".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn detect_a_folder_with_several_profiles() {
+        let home = TempDir::new();
+        let player_profiles = home
+            .path()
+            .join("Documents/Larian Studios/Baldur's Gate 3/PlayerProfiles");
+        ["Public", "Tav"].iter().for_each(|profile| {
+            fs::create_dir_all(story_dir(&player_profiles.join(profile))).unwrap()
+        });
+
+        let detected = detect(&macos(home.path())).unwrap();
+
+        assert_eq!(detected.path, player_profiles);
+        assert_eq!(resolve(&detected.path, None, false).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    json::{JsonValue, ToJson},
+    save_information::SaveInformation,
+};
+
+/// What happened to each folder of a deletion. Every folder is tried, so one that fails
+/// doesn't keep the others.
+#[derive(Debug, PartialEq, Default)]
+pub struct DeletionReport {
+    pub deleted: Vec<SaveInformation>,
+    pub failed: Vec<FailedDeletion>,
+}
+
+/// A save that is still there, with the reason.
+#[derive(Debug, PartialEq)]
+pub struct FailedDeletion {
+    pub save: SaveInformation,
+    pub error: String,
+}
+
+impl DeletionReport {
+    /// One line for each folder that couldn't be deleted, printed at the end of the run.
+    pub fn format_failures(&self) -> Vec<String> {
+        self.failed
+            .iter()
+            .map(|failed| format!("{}: {}", failed.save.file_name, failed.error))
+            .collect()
+    }
+}
+impl ToJson for FailedDeletion {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            ("save", self.save.to_json()),
+            ("error", JsonValue::String(self.error.clone())),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod deletion_report_should {
+    use crate::{
+        deletion_report::{DeletionReport, FailedDeletion},
+        json::ToJson,
+        save_information::SaveInformation,
+        save_type::SaveType,
+    };
+
+    fn failed_deletion() -> FailedDeletion {
+        FailedDeletion {
+            save: SaveInformation::new(
+                "Tav-123456789__QuickSave_3".to_string(),
+                "Tav".to_string(),
+                SaveType::Quick,
+                Some(3),
+            ),
+            error: "Permission denied".to_string(),
+        }
+    }
+
+    #[test]
+    fn list_every_failed_folder() {
+        let report = DeletionReport {
+            deleted: vec![SaveInformation::new_random(
+                SaveType::Quick,
+                "Tav".to_string(),
+            )],
+            failed: vec![failed_deletion()],
+        };
+
+        assert_eq!(
+            report.format_failures(),
+            vec!["Tav-123456789__QuickSave_3: Permission denied"]
+        );
+        assert!(DeletionReport::default().format_failures().is_empty());
+    }
+
+    #[test]
+    fn serialize_the_save_with_its_error() {
+        let json = failed_deletion().to_json().to_string();
+
+        assert!(json.starts_with("{\"save\":{\"character_name\":\"Tav\""));
+        assert!(json.ends_with(",\"error\":\"Permission denied\"}"));
+    }
+}
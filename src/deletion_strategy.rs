@@ -0,0 +1,50 @@
+use std::{fs, path::PathBuf};
+
+use crate::{run_summary::FreedSpace, size_format::format_size};
+
+/// How `delete` gets rid of a save folder.
+#[derive(Debug, PartialEq, Clone)]
+pub enum DeletionStrategy {
+    Remove,
+    Trash,
+    Quarantine(PathBuf),
+    /// Appended to a zstd compressed tar archive, then removed.
+    Archive(PathBuf),
+}
+impl DeletionStrategy {
+    /// Archived saves are removed once they are in the archive, the others still take up space.
+    pub fn freed_space(&self) -> FreedSpace {
+        match self {
+            DeletionStrategy::Remove => FreedSpace::Staged,
+            DeletionStrategy::Trash => FreedSpace::Trashed,
+            DeletionStrategy::Quarantine(_) => FreedSpace::Quarantined,
+            DeletionStrategy::Archive(_) => FreedSpace::Reclaimed,
+        }
+    }
+
+    /// The summary line of a run.
+    pub fn summary(&self, succeeded: usize, failed: usize) -> String {
+        match self {
+            DeletionStrategy::Remove => {
+                format!("Deleted {} folder(s), failed {}", succeeded, failed)
+            }
+            DeletionStrategy::Trash => format!(
+                "Moved {} folder(s) to the recycle bin, failed {}",
+                succeeded, failed
+            ),
+            DeletionStrategy::Quarantine(quarantine_dir) => format!(
+                "Moved {} folder(s) to the quarantine in {}, failed {}",
+                succeeded,
+                quarantine_dir.display(),
+                failed
+            ),
+            DeletionStrategy::Archive(archive_path) => format!(
+                "Archived {} folder(s) into {}, now {}, failed {}",
+                succeeded,
+                archive_path.display(),
+                format_size(fs::metadata(archive_path).map_or(0, |metadata| metadata.len())),
+                failed
+            ),
+        }
+    }
+}
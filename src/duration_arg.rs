@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+
+/// Parses durations such as `90m`, `12h`, `30d` or `2w` given on the command line.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let unit_index = value.find(|c: char| !c.is_ascii_digit()).ok_or(format!(
+        "Missing unit in {}, expected one of m, h, d, w",
+        value
+    ))?;
+    let (amount, unit) = value.split_at(unit_index);
+
+    let amount = amount
+        .parse::<u64>()
+        .map_err(|e| format!("Invalid amount in {}: {}", value, e))?;
+    let seconds_per_unit = match unit.to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" => SECONDS_PER_MINUTE,
+        "h" => SECONDS_PER_HOUR,
+        "d" => SECONDS_PER_DAY,
+        "w" => SECONDS_PER_WEEK,
+        _ => {
+            return Err(format!(
+                "Unknown unit {} in {}, expected one of s, m, h, d, w",
+                unit, value
+            ))
+        }
+    };
+
+    amount
+        .checked_mul(seconds_per_unit)
+        .map(Duration::from_secs)
+        .ok_or(format!("Duration {} is too large", value))
+}
+
+#[cfg(test)]
+mod parse_duration_should {
+    use std::time::Duration;
+
+    use crate::duration_arg::parse_duration;
+
+    #[test]
+    fn parse_each_unit() {
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(43_200));
+        assert_eq!(
+            parse_duration("30d").unwrap(),
+            Duration::from_secs(2_592_000)
+        );
+        assert_eq!(
+            parse_duration("2w").unwrap(),
+            Duration::from_secs(1_209_600)
+        );
+    }
+
+    #[test]
+    fn accept_uppercase_units() {
+        assert_eq!(parse_duration("1D").unwrap(), Duration::from_secs(86_400));
+    }
+
+    #[test]
+    fn error_without_unit() {
+        assert!(parse_duration("30").is_err());
+    }
+
+    #[test]
+    fn error_without_amount() {
+        assert!(parse_duration("d").is_err());
+    }
+
+    #[test]
+    fn error_on_unknown_unit() {
+        assert!(parse_duration("3y").is_err());
+    }
+}
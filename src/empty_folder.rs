@@ -0,0 +1,50 @@
+use std::{fs, path::Path};
+
+/// A folder is empty when neither it nor any folder below it holds a file, as left behind by
+/// an interrupted save. Folders that can't be read are not considered empty.
+pub fn is_empty(folder: &Path) -> bool {
+    fs::read_dir(folder)
+        .map(|children| {
+            children.into_iter().all(|child| {
+                child.is_ok_and(|child| {
+                    child
+                        .file_type()
+                        .is_ok_and(|file_type| file_type.is_dir() && is_empty(&child.path()))
+                })
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod is_empty_should {
+    use std::fs;
+
+    use crate::{empty_folder::is_empty, test_utils::TempDir};
+
+    #[test]
+    fn detect_folders_without_files() {
+        let temp_dir = TempDir::new();
+        let empty = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        let nested = temp_dir.path().join("Tav-123456789__QuickSave_2");
+        fs::create_dir(&empty).unwrap();
+        fs::create_dir_all(nested.join("a/b")).unwrap();
+        fs::create_dir(nested.join("c")).unwrap();
+
+        assert!(is_empty(&empty));
+        assert!(is_empty(&nested));
+    }
+
+    #[test]
+    fn keep_folders_with_a_file() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let nested = temp_dir.path().join("Tav-123456789__QuickSave_2");
+        fs::create_dir_all(nested.join("a/b")).unwrap();
+        fs::write(nested.join("a/b/thumbnail.webp"), b"").unwrap();
+
+        assert!(!is_empty(&save));
+        assert!(!is_empty(&nested));
+        assert!(!is_empty(&temp_dir.path().join("missing")));
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{program_errors::ProgramError, regex::Regex};
+
+/// Folder names or globs read from a `--exclude-from` file, one per line.
+#[derive(Debug)]
+pub struct ExcludeList {
+    pub path: PathBuf,
+    entries: Vec<(String, Regex)>,
+}
+impl ExcludeList {
+    pub fn load(path: &Path) -> Result<Self, ProgramError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| ProgramError::FailedToReadFile(format!("{}: {}", path.display(), e)))?;
+
+        ExcludeList::parse(path, &content)
+            .map_err(|e| ProgramError::FailedToReadFile(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Skips blank lines and lines starting with `#`.
+    pub fn parse(path: &Path, content: &str) -> Result<Self, String> {
+        let entries = content
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                Regex::from_glob(line)
+                    .map(|regex| (line.to_string(), regex))
+                    .map_err(|e| format!("line {}: {}", index + 1, e))
+            })
+            .collect::<Result<Vec<(String, Regex)>, String>>()?;
+
+        Ok(ExcludeList {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// The first entry matching the folder name.
+    pub fn matching_entry(&self, folder_name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(_, regex)| regex.is_match(folder_name))
+            .map(|(entry, _)| entry.as_str())
+    }
+
+    /// Entries that match none of the given folder names, likely left over from deleted saves.
+    pub fn stale_entries<'a>(&'a self, folder_names: &[&str]) -> Vec<&'a str> {
+        self.entries
+            .iter()
+            .filter(|(_, regex)| !folder_names.iter().any(|name| regex.is_match(name)))
+            .map(|(entry, _)| entry.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod exclude_list_should {
+    use std::path::Path;
+
+    use crate::{exclude_list::ExcludeList, program_errors::ProgramError, test_utils::TempDir};
+
+    const CONTENT: &str = "# Before the final fight\n\
+        Tav-123456789__QuickSave_42\n\
+        \n\
+        Durge-*__AutoSave_?\n";
+
+    #[test]
+    fn match_exact_names_and_globs() {
+        let exclude_list = ExcludeList::parse(Path::new("keep.txt"), CONTENT).unwrap();
+
+        assert_eq!(
+            exclude_list.matching_entry("Tav-123456789__QuickSave_42"),
+            Some("Tav-123456789__QuickSave_42")
+        );
+        assert_eq!(
+            exclude_list.matching_entry("Tav-123456789__QuickSave_421"),
+            None
+        );
+        assert_eq!(
+            exclude_list.matching_entry("Durge-987__AutoSave_3"),
+            Some("Durge-*__AutoSave_?")
+        );
+        assert_eq!(
+            exclude_list.matching_entry("# Before the final fight"),
+            None
+        );
+    }
+
+    #[test]
+    fn report_entries_matching_nothing() {
+        let exclude_list = ExcludeList::parse(Path::new("keep.txt"), CONTENT).unwrap();
+
+        assert_eq!(
+            exclude_list.stale_entries(&["Durge-987__AutoSave_3", "Tav-123456789__QuickSave_1"]),
+            vec!["Tav-123456789__QuickSave_42"]
+        );
+    }
+
+    #[test]
+    fn fail_on_missing_file() {
+        let temp_dir = TempDir::new();
+
+        let result = ExcludeList::load(&temp_dir.path().join("missing.txt"));
+        assert!(matches!(result, Err(ProgramError::FailedToReadFile(_))));
+    }
+
+    #[test]
+    fn name_the_line_of_an_invalid_glob() {
+        let result = ExcludeList::parse(Path::new("keep.txt"), "ok\nSave_[1-");
+        assert_eq!(
+            result.map(|_| ()),
+            Err("line 2: Invalid glob Save_[1-".to_string())
+        );
+    }
+}
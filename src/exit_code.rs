@@ -0,0 +1,110 @@
+use crate::program_errors::ProgramError;
+
+pub const SUCCESS: i32 = 0;
+/// Nothing was done: the save folder is missing, a filter matched nothing or the run was
+/// refused, e.g. by --strict or because the game is running.
+pub const NOTHING_DONE: i32 = 1;
+/// The run finished but some folders could not be deleted or restored.
+pub const PARTIAL_FAILURE: i32 = 2;
+/// Reading or writing files failed.
+pub const IO_ERROR: i32 = 3;
+/// Ctrl+C stopped the deletion, like shells report a process stopped by SIGINT.
+pub const INTERRUPTED: i32 = 130;
+
+/// Listed in `--help`.
+pub const DESCRIPTION: &str = "Exit codes:
+    0  Success, also when the deletion was not confirmed
+    1  Nothing done: the save folder is missing, a filter matched nothing or the run was refused
+    2  Some folders could not be deleted or restored
+    3  Reading or writing files failed
+  130  Ctrl+C stopped the deletion, the folders not yet deleted were kept";
+
+pub fn for_error(e: &ProgramError) -> i32 {
+    match e {
+        ProgramError::FailedToReadDir(_)
+        | ProgramError::FailedToReadFile(_)
+        | ProgramError::FailedToWriteFile(_)
+        | ProgramError::FailedToDelete(_)
+        | ProgramError::FailedToTrash(_)
+        | ProgramError::FailedToMove(_)
+        | ProgramError::FailedToRestore(_)
+        | ProgramError::InvalidJournal(_) => IO_ERROR,
+        ProgramError::Interrupted(_) => INTERRUPTED,
+        _ => NOTHING_DONE,
+    }
+}
+
+/// The code for the runs against each save folder, each either the number of folders that
+/// failed or its error. The worst one wins, except that a character or campaign filter only
+/// has to match in one of the save folders.
+pub fn for_runs(results: &[Result<usize, ProgramError>]) -> i32 {
+    let is_unmatched_filter = |result: &Result<usize, ProgramError>| {
+        matches!(
+            result,
+            Err(ProgramError::CharacterNotFound(_) | ProgramError::CampaignNotFound(_))
+        )
+    };
+    if !results.is_empty() && results.iter().all(is_unmatched_filter) {
+        return NOTHING_DONE;
+    }
+
+    results
+        .iter()
+        .filter(|result| !is_unmatched_filter(result))
+        .map(|result| match result {
+            Ok(0) => SUCCESS,
+            Ok(_) => PARTIAL_FAILURE,
+            Err(e) => for_error(e),
+        })
+        .max()
+        .unwrap_or(SUCCESS)
+}
+
+#[cfg(test)]
+mod for_runs_should {
+    use crate::{
+        exit_code::{for_runs, INTERRUPTED, IO_ERROR, NOTHING_DONE, PARTIAL_FAILURE, SUCCESS},
+        program_errors::ProgramError,
+    };
+
+    #[test]
+    fn succeed_when_every_folder_was_handled() {
+        assert_eq!(for_runs(&[Ok(0), Ok(0)]), SUCCESS);
+    }
+
+    #[test]
+    fn report_the_worst_outcome() {
+        assert_eq!(for_runs(&[Ok(0), Ok(2)]), PARTIAL_FAILURE);
+        assert_eq!(
+            for_runs(&[
+                Ok(2),
+                Err(ProgramError::FailedToReadDir("denied".to_string()))
+            ]),
+            IO_ERROR
+        );
+        assert_eq!(
+            for_runs(&[Err(ProgramError::NoPath("missing".to_string()))]),
+            NOTHING_DONE
+        );
+    }
+
+    #[test]
+    fn report_an_interrupted_deletion_over_failures() {
+        assert_eq!(
+            for_runs(&[
+                Ok(1),
+                Err(ProgramError::Interrupted("stopped".to_string())),
+                Err(ProgramError::FailedToReadDir("denied".to_string()))
+            ]),
+            INTERRUPTED
+        );
+    }
+
+    #[test]
+    fn only_fail_unmatched_characters_when_no_folder_matched() {
+        let not_found = || Err(ProgramError::CharacterNotFound("Tav".to_string()));
+
+        assert_eq!(for_runs(&[Ok(0), not_found()]), SUCCESS);
+        assert_eq!(for_runs(&[not_found(), not_found()]), NOTHING_DONE);
+    }
+}
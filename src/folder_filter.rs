@@ -0,0 +1,118 @@
+use crate::{exclude_list::ExcludeList, ignore_list::IgnoreList, regex::Regex};
+
+/// `--match`, `--exclude` and `--exclude-from`, applied to folder names before they are parsed.
+#[derive(Debug, Default)]
+pub struct FolderFilter<'a> {
+    pub include: Option<&'a Regex>,
+    pub exclude: &'a [Regex],
+    pub exclude_list: Option<&'a ExcludeList>,
+    /// Entries that are never saves, left out before anything else and not part of `describe`.
+    pub ignore_list: Option<&'a IgnoreList>,
+    /// Scan symlinked folders like any other instead of only listing them, see
+    /// `--follow-symlinks`. Not part of `describe` either.
+    pub follow_symlinks: bool,
+}
+impl FolderFilter<'_> {
+    pub fn is_active(&self) -> bool {
+        self.include.is_some() || !self.exclude.is_empty() || self.exclude_list.is_some()
+    }
+
+    /// Why the folder is left out of the scan, `None` if it is scanned.
+    pub fn reason_to_skip(&self, folder_name: &str) -> Option<String> {
+        if let Some(regex) = self
+            .exclude
+            .iter()
+            .find(|regex| regex.is_match(folder_name))
+        {
+            return Some(format!("matches --exclude {}", regex));
+        }
+        if let Some(entry) = self
+            .exclude_list
+            .and_then(|exclude_list| exclude_list.matching_entry(folder_name))
+        {
+            return Some(format!("listed as {} in --exclude-from", entry));
+        }
+
+        self.include
+            .filter(|regex| !regex.is_match(folder_name))
+            .map(|regex| format!("does not match --match {}", regex))
+    }
+
+    pub fn describe(&self) -> String {
+        self.include
+            .map(|regex| format!("--match {}", regex))
+            .into_iter()
+            .chain(
+                self.exclude
+                    .iter()
+                    .map(|regex| format!("--exclude {}", regex)),
+            )
+            .chain(
+                self.exclude_list
+                    .map(|exclude_list| format!("--exclude-from {}", exclude_list.path.display())),
+            )
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod folder_filter_should {
+    use std::path::Path;
+
+    use crate::{exclude_list::ExcludeList, folder_filter::FolderFilter, regex::Regex};
+
+    #[test]
+    fn apply_match_and_exclude_together() {
+        let include = Regex::new("QuickSave").unwrap();
+        let exclude = vec![Regex::new("_KEEP$").unwrap()];
+        let folder_filter = FolderFilter {
+            include: Some(&include),
+            exclude: &exclude,
+            exclude_list: None,
+            ignore_list: None,
+            follow_symlinks: false,
+        };
+
+        assert_eq!(
+            folder_filter.reason_to_skip("Tav-123456789__QuickSave_3"),
+            None
+        );
+        assert_eq!(
+            folder_filter.reason_to_skip("Tav-123456789__QuickSave_3_KEEP"),
+            Some("matches --exclude _KEEP$".to_string())
+        );
+        assert_eq!(
+            folder_filter.reason_to_skip("Tav-123456789__AutoSave_3"),
+            Some("does not match --match QuickSave".to_string())
+        );
+        assert_eq!(
+            folder_filter.describe(),
+            "--match QuickSave, --exclude _KEEP$"
+        );
+    }
+
+    #[test]
+    fn leave_out_folders_in_exclude_list() {
+        let exclude_list =
+            ExcludeList::parse(Path::new("keep.txt"), "Tav-*__QuickSave_42").unwrap();
+        let folder_filter = FolderFilter {
+            exclude_list: Some(&exclude_list),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            folder_filter.reason_to_skip("Tav-123456789__QuickSave_42"),
+            Some("listed as Tav-*__QuickSave_42 in --exclude-from".to_string())
+        );
+        assert_eq!(folder_filter.describe(), "--exclude-from keep.txt");
+    }
+
+    #[test]
+    fn let_everything_through_by_default() {
+        let folder_filter = FolderFilter::default();
+
+        assert!(!folder_filter.is_active());
+        assert_eq!(folder_filter.reason_to_skip("anything"), None);
+    }
+}
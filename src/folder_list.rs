@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use crate::plan;
+
+/// Folder names given one per line, as piped in with `--from-stdin`. Line endings of either
+/// kind and blank lines are dropped, a name listed twice is kept once.
+pub fn parse(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+        .fold(Vec::new(), |mut names, name| {
+            if !names.iter().any(|listed| listed == name) {
+                names.push(name.to_string());
+            }
+            names
+        })
+}
+
+/// Splits the names into those of folders under `root` and the rest, which includes anything
+/// that is not a plain folder name like `../other`.
+pub fn split_unknown(names: Vec<String>, root: &Path) -> (Vec<String>, Vec<String>) {
+    names
+        .into_iter()
+        .partition(|name| plan::is_plain_folder_name(name) && root.join(name).is_dir())
+}
+
+#[cfg(test)]
+mod parse_should {
+    use crate::folder_list::parse;
+
+    #[test]
+    fn read_one_name_per_line() {
+        assert_eq!(
+            parse("Tav-123456789__QuickSave_1\nTav-123456789__QuickSave_2\n"),
+            vec!["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_2"]
+        );
+    }
+
+    #[test]
+    fn drop_windows_line_endings_and_blank_lines() {
+        assert_eq!(
+            parse("Tav-123456789__QuickSave_1\r\n\r\n  \nTav-123456789__QuickSave_2\r"),
+            vec!["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_2"]
+        );
+    }
+
+    #[test]
+    fn keep_names_listed_twice_once() {
+        assert_eq!(
+            parse("Tav-123456789__QuickSave_1\nTav-123456789__QuickSave_1\r\n"),
+            vec!["Tav-123456789__QuickSave_1"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod split_unknown_should {
+    use std::fs;
+
+    use crate::{folder_list::split_unknown, test_utils::TempDir};
+
+    #[test]
+    fn only_accept_folders_under_the_root() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        let names = [
+            "Tav-123456789__QuickSave_1",
+            "Tav-123456789__QuickSave_2",
+            "notes.txt",
+            "../Tav-123456789__QuickSave_1",
+        ]
+        .map(str::to_string)
+        .to_vec();
+
+        let (known, unknown) = split_unknown(names, temp_dir.path());
+
+        assert_eq!(known, vec!["Tav-123456789__QuickSave_1"]);
+        assert_eq!(
+            unknown,
+            vec![
+                "Tav-123456789__QuickSave_2",
+                "notes.txt",
+                "../Tav-123456789__QuickSave_1"
+            ]
+        );
+    }
+}
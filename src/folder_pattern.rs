@@ -0,0 +1,90 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    sync::RwLock,
+};
+
+use crate::regex::Regex;
+
+/// What the built-in parser reads from folder names, written as a `--pattern`. Names may contain
+/// dashes, the campaign id starts after the last dash followed by digits.
+pub const DEFAULT_PATTERN: &str =
+    r"^(?P<name>.+)-(?P<campaign>\d+)_+(?P<type>[^_]+)_(?P<number>\d+)$";
+const REQUIRED_GROUPS: [&str; 4] = ["name", "campaign", "type", "number"];
+
+/// The pattern from `--pattern` for the current run, `None` to use the built-in parser.
+static ACTIVE: RwLock<Option<FolderPattern>> = RwLock::new(None);
+
+/// A regex with the named groups `name`, `campaign`, `type` and `number` that splits save folder
+/// names, for naming schemes the built-in parser doesn't know.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderPattern(Regex);
+impl FolderPattern {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let regex = Regex::new(pattern)?;
+        let missing: Vec<&str> = REQUIRED_GROUPS
+            .into_iter()
+            .filter(|group| !regex.group_names().iter().any(|name| name == group))
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(format!(
+                "Pattern {} has no group named {}. It needs the named groups name, campaign, type and number, the built-in parser is equivalent to {}",
+                pattern,
+                missing.join(", "),
+                DEFAULT_PATTERN
+            ));
+        }
+        Ok(FolderPattern(regex))
+    }
+
+    /// The text of each group for the folder name, `None` if the pattern doesn't match it.
+    /// Optional groups that took no part in the match are left out.
+    pub fn parts(&self, folder_name: &str) -> Option<HashMap<&str, String>> {
+        self.0.captures(folder_name)
+    }
+}
+impl Display for FolderPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub fn set_active(folder_pattern: Option<FolderPattern>) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = folder_pattern;
+    }
+}
+
+pub fn active() -> Option<FolderPattern> {
+    ACTIVE.read().ok().and_then(|active| active.clone())
+}
+
+#[cfg(test)]
+mod new_should {
+    use crate::folder_pattern::{FolderPattern, DEFAULT_PATTERN};
+
+    #[test]
+    fn accept_pattern_with_every_group() {
+        assert!(FolderPattern::new(DEFAULT_PATTERN).is_ok());
+        assert!(FolderPattern::new(
+            r"^(?<type>\w+) - (?<name>\w+) \((?<campaign>\d*)\) #(?<number>\d+)$"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn name_missing_groups() {
+        let error = FolderPattern::new(r"^(?P<name>.+)-(?P<campaign>\d+)__.*$").unwrap_err();
+
+        assert!(error.contains("has no group named type, number"));
+        assert!(error.contains(DEFAULT_PATTERN));
+    }
+
+    #[test]
+    fn reject_invalid_regex() {
+        assert!(FolderPattern::new("(?P<name>.+")
+            .unwrap_err()
+            .contains("missing closing )"));
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fs, path::Path};
+
+use crate::cloud_placeholder;
+
+/// Sums the sizes of all files below `path`, online-only cloud files count as nothing as
+/// deleting them frees no space. Entries that cannot be read are skipped so a single unreadable
+/// file only makes the result smaller instead of failing the scan.
+pub fn folder_size(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .map(|children| {
+            children
+                .flatten()
+                .map(|child| match child.file_type() {
+                    Ok(file_type) if file_type.is_dir() => folder_size(&child.path()),
+                    Ok(_) => child
+                        .metadata()
+                        .map(|metadata| cloud_placeholder::size_on_disk(&metadata))
+                        .unwrap_or(0),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod folder_size_should {
+    use std::fs;
+
+    use crate::{folder_size::folder_size, test_utils::TempDir};
+
+    #[test]
+    fn sum_file_sizes() {
+        let temp_dir = TempDir::new();
+        fs::write(temp_dir.path().join("a.lsv"), vec![0u8; 1000]).unwrap();
+        fs::write(temp_dir.path().join("b.webp"), vec![0u8; 24]).unwrap();
+
+        assert_eq!(folder_size(temp_dir.path()), 1024);
+    }
+
+    #[test]
+    fn include_nested_folders() {
+        let temp_dir = TempDir::new();
+        fs::create_dir_all(temp_dir.path().join("nested/deeper")).unwrap();
+        fs::write(temp_dir.path().join("a.lsv"), vec![0u8; 10]).unwrap();
+        fs::write(temp_dir.path().join("nested/deeper/b.lsv"), vec![0u8; 5]).unwrap();
+
+        assert_eq!(folder_size(temp_dir.path()), 15);
+    }
+
+    #[test]
+    fn return_zero_for_missing_folder() {
+        let temp_dir = TempDir::new();
+
+        assert_eq!(folder_size(&temp_dir.path().join("missing")), 0);
+    }
+}
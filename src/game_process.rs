@@ -0,0 +1,109 @@
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+#[cfg(target_os = "linux")]
+use std::{fs, path::Path};
+
+/// Process names of the game, the Windows ones also show up on Linux when run through Proton.
+const GAME_PROCESS_NAMES: [&str; 4] = ["bg3.exe", "bg3_dx11.exe", "bg3", "Baldur's Gate 3"];
+
+/// The name of a running game process, if any. Failing to list processes counts as not running.
+pub fn running_game_process() -> Option<String> {
+    find_game_process(process_names().iter().map(String::as_str))
+}
+
+fn find_game_process<'a>(mut process_names: impl Iterator<Item = &'a str>) -> Option<String> {
+    process_names
+        .find(|process_name| {
+            GAME_PROCESS_NAMES
+                .iter()
+                .any(|game_process_name| process_name.eq_ignore_ascii_case(game_process_name))
+        })
+        .map(str::to_string)
+}
+
+/// Reads the name of every process from /proc, which needs no extra permissions.
+#[cfg(target_os = "linux")]
+fn process_names() -> Vec<String> {
+    fs::read_dir("/proc")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|dir_entry| {
+            dir_entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|dir_entry| process_name(&dir_entry.path()))
+        .collect()
+}
+
+/// The executable name from the command line, `comm` is cut off after 15 characters.
+#[cfg(target_os = "linux")]
+fn process_name(process_dir: &Path) -> Option<String> {
+    let cmdline = fs::read(process_dir.join("cmdline")).ok()?;
+    let executable = cmdline.split(|byte| *byte == 0).next()?;
+    let executable = String::from_utf8_lossy(executable);
+
+    // Proton passes Windows paths, split on both separators.
+    executable
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(windows)]
+fn process_names() -> Vec<String> {
+    Command::new("tasklist")
+        .args(["/FO", "CSV", "/NH"])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split("\",\"").next())
+                .map(|name| name.trim_start_matches('"').to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn process_names() -> Vec<String> {
+    Command::new("ps")
+        .args(["-A", "-o", "comm="])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().rsplit('/').next())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod find_game_process_should {
+    use crate::game_process::find_game_process;
+
+    #[test]
+    fn find_game_among_other_processes() {
+        assert_eq!(
+            find_game_process(["steam", "bash", "bg3_dx11.exe"].into_iter()),
+            Some("bg3_dx11.exe".to_string())
+        );
+        assert_eq!(
+            find_game_process(["BG3.EXE"].into_iter()),
+            Some("BG3.EXE".to_string())
+        );
+    }
+
+    #[test]
+    fn ignore_similar_names() {
+        assert_eq!(
+            find_game_process(["bg3sc", "bg3.exe.bak", "steam"].into_iter()),
+            None
+        );
+    }
+}
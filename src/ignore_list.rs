@@ -0,0 +1,76 @@
+use crate::regex::Regex;
+
+/// Entries of the save folder that are never saves: hidden folders like Syncthing's
+/// `.stversions`, backups made by hand and folders the operating system creates.
+pub const DEFAULT_IGNORED: [&str; 4] = [".*", "*.bak", "__MACOSX", "System Volume Information"];
+
+/// Globs of folder and file names that are left out of the scan before parsing, the defaults
+/// followed by those from `--ignore`.
+#[derive(Debug)]
+pub struct IgnoreList {
+    entries: Vec<(String, Regex)>,
+}
+impl IgnoreList {
+    pub fn new(extra: &[String]) -> Result<Self, String> {
+        let entries = DEFAULT_IGNORED
+            .iter()
+            .map(|glob| glob.to_string())
+            .chain(extra.iter().cloned())
+            .map(|glob| Regex::from_glob(&glob).map(|regex| (glob, regex)))
+            .collect::<Result<Vec<(String, Regex)>, String>>()?;
+
+        Ok(IgnoreList { entries })
+    }
+
+    /// The first glob matching the name.
+    pub fn matching_entry(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(_, regex)| regex.is_match(name))
+            .map(|(glob, _)| glob.as_str())
+    }
+}
+impl Default for IgnoreList {
+    /// Only the built-in globs, which are all valid.
+    fn default() -> Self {
+        IgnoreList::new(&[]).unwrap_or(IgnoreList {
+            entries: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod matching_entry_should {
+    use crate::ignore_list::IgnoreList;
+
+    #[test]
+    fn ignore_hidden_backup_and_system_folders_by_default() {
+        let ignore_list = IgnoreList::new(&[]).unwrap();
+
+        assert_eq!(ignore_list.matching_entry(".stversions"), Some(".*"));
+        assert_eq!(ignore_list.matching_entry(".DS_Store"), Some(".*"));
+        assert_eq!(
+            ignore_list.matching_entry("Tav-123456789__QuickSave_3.bak"),
+            Some("*.bak")
+        );
+        assert_eq!(ignore_list.matching_entry("__MACOSX"), Some("__MACOSX"));
+        assert_eq!(
+            ignore_list.matching_entry("System Volume Information"),
+            Some("System Volume Information")
+        );
+        assert_eq!(
+            ignore_list.matching_entry("Tav-123456789__QuickSave_3"),
+            None
+        );
+    }
+
+    #[test]
+    fn add_globs_to_the_defaults() {
+        let ignore_list = IgnoreList::new(&["*.old".to_string(), "Backup_??".to_string()]).unwrap();
+
+        assert_eq!(ignore_list.matching_entry("foo.old"), Some("*.old"));
+        assert_eq!(ignore_list.matching_entry("Backup_01"), Some("Backup_??"));
+        assert_eq!(ignore_list.matching_entry("Backup_001"), None);
+        assert_eq!(ignore_list.matching_entry(".stversions"), Some(".*"));
+    }
+}
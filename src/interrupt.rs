@@ -0,0 +1,121 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+/// How often a sleep checks whether Ctrl+C was pressed.
+const CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Records Ctrl+C instead of terminating, long running modes check `is_interrupted` and stop
+/// between steps.
+pub fn install() {
+    if !INSTALLED.swap(true, Ordering::SeqCst) {
+        platform::install();
+    }
+}
+
+/// Records Ctrl+C like `install` until dropped, after which Ctrl+C terminates again. Kept when
+/// it was already installed, e.g. by watch mode.
+pub fn catch() -> Catch {
+    let installed_here = !INSTALLED.load(Ordering::SeqCst);
+    install();
+
+    Catch { installed_here }
+}
+
+pub struct Catch {
+    installed_here: bool,
+}
+impl Drop for Catch {
+    fn drop(&mut self) {
+        if self.installed_here {
+            platform::uninstall();
+            INSTALLED.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+fn on_interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Sleeps for `duration` or until Ctrl+C is pressed, returns whether it was pressed.
+pub fn sleep(duration: Duration) -> bool {
+    let until = Instant::now() + duration;
+
+    while !is_interrupted() {
+        let remaining = until.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        thread::sleep(remaining.min(CHECK_INTERVAL));
+    }
+
+    true
+}
+
+#[cfg(unix)]
+mod platform {
+    const SIGINT: i32 = 2;
+    const SIG_DFL: usize = 0;
+
+    extern "C" {
+        fn signal(signum: i32, handler: usize) -> usize;
+    }
+
+    extern "C" fn handle_signal(_: i32) {
+        super::on_interrupt();
+    }
+
+    pub fn install() {
+        // Only stores to an atomic, which is safe inside a signal handler.
+        unsafe {
+            signal(SIGINT, handle_signal as extern "C" fn(i32) as usize);
+        }
+    }
+
+    pub fn uninstall() {
+        unsafe {
+            signal(SIGINT, SIG_DFL);
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetConsoleCtrlHandler(handler: extern "system" fn(u32) -> i32, add: i32) -> i32;
+    }
+
+    extern "system" fn handle_ctrl(_: u32) -> i32 {
+        super::on_interrupt();
+        1
+    }
+
+    pub fn install() {
+        unsafe {
+            SetConsoleCtrlHandler(handle_ctrl, 1);
+        }
+    }
+
+    pub fn uninstall() {
+        unsafe {
+            SetConsoleCtrlHandler(handle_ctrl, 0);
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    pub fn install() {}
+
+    pub fn uninstall() {}
+}
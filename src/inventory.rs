@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use crate::{
+    character_key::CharacterKey,
+    save_information::SaveInformation,
+    saves::Saves,
+    timestamp::{format_local_minutes, format_modified},
+};
+
+/// Renders a per campaign breakdown of the quick and auto saves found, and of Honour Mode
+/// and manual saves if there are any.
+pub fn format_inventory(map: &HashMap<CharacterKey, Saves>) -> String {
+    let mut characters: Vec<(&CharacterKey, &Saves)> = map.iter().collect();
+    characters.sort_by_key(|(name, _)| *name);
+
+    characters
+        .into_iter()
+        .map(|(name, saves)| {
+            let optional_lines: String = [
+                ("honour saves", &saves.honour_saves),
+                ("manual saves", &saves.manual_saves),
+            ]
+            .iter()
+            .filter(|(_, saves)| !saves.is_empty())
+            .map(|(label, saves)| format!("\t{}: {}\n", label, save_counts(saves)))
+            .collect();
+            let newest_save = saves
+                .all()
+                .filter_map(|save_information| save_information.modified)
+                .max()
+                .map(|modified| format!(", newest save {}", format_local_minutes(modified)))
+                .unwrap_or_default();
+            format!(
+                "{}{}\n\tquick saves: {}\n\tauto saves: {}\n{}",
+                name,
+                newest_save,
+                save_counts(&saves.quick_saves),
+                save_counts(&saves.auto_saves),
+                optional_lines
+            )
+        })
+        .collect()
+}
+
+/// Expects saves sorted newest first, as done by `sort_map_saves`.
+fn save_counts(saves: &[SaveInformation]) -> String {
+    match (saves.first(), saves.last()) {
+        (Some(newest), Some(oldest)) => format!(
+            "{} (newest {} from {}, oldest {} from {})",
+            saves.len(),
+            save_number(newest),
+            format_modified(newest.modified).trim_end(),
+            save_number(oldest),
+            format_modified(oldest.modified).trim_end()
+        ),
+        _ => "0".to_string(),
+    }
+}
+
+fn save_number(save_information: &SaveInformation) -> String {
+    save_information
+        .save_number
+        .map_or("unnumbered".to_string(), |save_number| {
+            save_number.to_string()
+        })
+}
+
+#[cfg(test)]
+mod format_inventory_should {
+    use std::{
+        collections::HashMap,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::{
+        character_key::CharacterKey, inventory::format_inventory,
+        save_information::SaveInformation, save_type::SaveType, saves::Saves,
+        timestamp::format_local_minutes,
+    };
+
+    fn save(character_name: &str, save_type: SaveType, save_number: u64) -> SaveInformation {
+        SaveInformation::new(
+            format!(
+                "{}-123456789__{:?}Save_{}",
+                character_name, save_type, save_number
+            ),
+            character_name.to_string(),
+            save_type,
+            Some(save_number),
+        )
+    }
+
+    #[test]
+    fn list_counts_with_newest_and_oldest() {
+        let mut map = HashMap::new();
+        map.insert(
+            CharacterKey::named("Tav"),
+            Saves {
+                quick_saves: vec![
+                    save("Tav", SaveType::Quick, 30),
+                    save("Tav", SaveType::Quick, 12),
+                    save("Tav", SaveType::Quick, 4),
+                ],
+                auto_saves: vec![save("Tav", SaveType::Auto, 7)],
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
+            },
+        );
+
+        assert_eq!(
+            format_inventory(&map),
+            "Tav\n\tquick saves: 3 (newest 30 from unknown, oldest 4 from unknown)\n\tauto saves: 1 (newest 7 from unknown, oldest 7 from unknown)\n"
+        );
+    }
+
+    #[test]
+    fn list_honour_and_manual_saves_when_found() {
+        let mut map = HashMap::new();
+        map.insert(
+            CharacterKey::named("Tav"),
+            Saves {
+                honour_saves: vec![save("Tav", SaveType::Honour, 1)],
+                manual_saves: vec![
+                    save("Tav", SaveType::Manual, 9),
+                    save("Tav", SaveType::Manual, 2),
+                ],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            format_inventory(&map),
+            "Tav\n\tquick saves: 0\n\tauto saves: 0\n\thonour saves: 1 (newest 1 from unknown, oldest 1 from unknown)\n\tmanual saves: 2 (newest 9 from unknown, oldest 2 from unknown)\n"
+        );
+    }
+
+    #[test]
+    fn show_campaign_and_newest_save_date() {
+        let mut map = HashMap::new();
+        map.insert(
+            CharacterKey::in_campaign("Tav", "1231415123"),
+            Saves {
+                quick_saves: vec![save("Tav", SaveType::Quick, 2)
+                    .with_modified(Some(UNIX_EPOCH + Duration::from_secs(86_400)))],
+                auto_saves: vec![save("Tav", SaveType::Auto, 1).with_modified(Some(UNIX_EPOCH))],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            format_inventory(&map),
+            format!(
+                "Tav #123141, newest save {}\n\tquick saves: 1 (newest 2 from {}, oldest 2 from {})\n\tauto saves: 1 (newest 1 from {}, oldest 1 from {})\n",
+                format_local_minutes(UNIX_EPOCH + Duration::from_secs(86_400)),
+                format_local_minutes(UNIX_EPOCH + Duration::from_secs(86_400)),
+                format_local_minutes(UNIX_EPOCH + Duration::from_secs(86_400)),
+                format_local_minutes(UNIX_EPOCH),
+                format_local_minutes(UNIX_EPOCH)
+            )
+        );
+    }
+
+    #[test]
+    fn sort_characters_by_name() {
+        let mut map = HashMap::new();
+        map.insert(CharacterKey::named("Tav"), Saves::default());
+        map.insert(CharacterKey::named("Durge"), Saves::default());
+
+        assert_eq!(
+            format_inventory(&map),
+            "Durge\n\tquick saves: 0\n\tauto saves: 0\nTav\n\tquick saves: 0\n\tauto saves: 0\n"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    fmt::{Display, Write},
+    iter::Peekable,
+    str::Chars,
+};
+
+/// A minimal JSON document model used for machine readable output and plan files.
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, String>;
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_ref().map_or(JsonValue::Null, ToJson::to_json)
+    }
+}
+
+impl JsonValue {
+    pub fn object(fields: Vec<(&str, JsonValue)>) -> Self {
+        JsonValue::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Parses a complete JSON document. Only unsigned integers are supported as numbers.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut chars = input.chars().peekable();
+        let value = parse_value(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            None => Ok(value),
+            Some(c) => Err(format!("Unexpected trailing character {:?}", c)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, indent: usize) -> std::fmt::Result {
+        // The alternate flag, `{:#}`, pretty prints with two space indentation.
+        let newline = |f: &mut std::fmt::Formatter<'_>, indent: usize| {
+            if f.alternate() {
+                write!(f, "\n{:width$}", "", width = indent * 2)
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{}", value),
+            JsonValue::Number(value) => write!(f, "{}", value),
+            JsonValue::String(value) => write_escaped(f, value),
+            JsonValue::Array(values) if values.is_empty() => write!(f, "[]"),
+            JsonValue::Array(values) => {
+                f.write_char('[')?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    newline(f, indent + 1)?;
+                    value.write(f, indent + 1)?;
+                }
+                newline(f, indent)?;
+                f.write_char(']')
+            }
+            JsonValue::Object(fields) if fields.is_empty() => write!(f, "{{}}"),
+            JsonValue::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    newline(f, indent + 1)?;
+                    write_escaped(f, key)?;
+                    f.write_str(if f.alternate() { ": " } else { ":" })?;
+                    value.write(f, indent + 1)?;
+                }
+                newline(f, indent)?;
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+fn write_escaped(f: &mut impl Write, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars>, expected: &str) -> Result<(), String> {
+    expected
+        .chars()
+        .try_for_each(|expected_char| match chars.next() {
+            Some(c) if c == expected_char => Ok(()),
+            Some(c) => Err(format!("Expected {:?} but found {:?}", expected_char, c)),
+            None => Err(format!("Expected {:?} but input ended", expected_char)),
+        })
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<JsonValue, String> {
+    skip_whitespace(chars);
+
+    match chars.peek() {
+        Some('n') => expect(chars, "null").map(|_| JsonValue::Null),
+        Some('t') => expect(chars, "true").map(|_| JsonValue::Bool(true)),
+        Some('f') => expect(chars, "false").map(|_| JsonValue::Bool(false)),
+        Some('"') => parse_string(chars).map(JsonValue::String),
+        Some('[') => parse_array(chars),
+        Some('{') => parse_object(chars),
+        Some(c) if c.is_ascii_digit() => parse_number(chars),
+        Some(c) => Err(format!("Unexpected character {:?}", c)),
+        None => Err("Unexpected end of input".to_string()),
+    }
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Result<JsonValue, String> {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+
+    if let Some(c) = chars.next_if(|c| matches!(c, '.' | 'e' | 'E')) {
+        return Err(format!("Unsupported number format {}{}", digits, c));
+    }
+
+    digits
+        .parse::<u64>()
+        .map(JsonValue::Number)
+        .map_err(|e| format!("Invalid number {}: {}", digits, e))
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    expect(chars, "\"")?;
+
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(value),
+            Some('\\') => match chars.next() {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('/') => value.push('/'),
+                Some('b') => value.push('\u{8}'),
+                Some('f') => value.push('\u{c}'),
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('u') => value.push(parse_unicode_escape(chars)?),
+                Some(c) => return Err(format!("Invalid escape sequence \\{}", c)),
+                None => return Err("Unterminated string".to_string()),
+            },
+            Some(c) => value.push(c),
+            None => return Err("Unterminated string".to_string()),
+        }
+    }
+}
+
+fn parse_unicode_escape(chars: &mut Peekable<Chars>) -> Result<char, String> {
+    let high = parse_code_unit(chars)?;
+    let code_point = if (0xD800..0xDC00).contains(&high) {
+        // A surrogate pair encodes characters outside of the basic multilingual plane.
+        expect(chars, "\\u")?;
+        let low = parse_code_unit(chars)?;
+        0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+    } else {
+        high
+    };
+
+    char::from_u32(code_point).ok_or(format!("Invalid unicode code point {:x}", code_point))
+}
+
+fn parse_code_unit(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+    let hex: String = chars.by_ref().take(4).collect();
+
+    u32::from_str_radix(&hex, 16).map_err(|_| format!("Invalid unicode escape \\u{}", hex))
+}
+
+fn parse_array(chars: &mut Peekable<Chars>) -> Result<JsonValue, String> {
+    expect(chars, "[")?;
+    skip_whitespace(chars);
+
+    let mut values = Vec::new();
+    if chars.next_if_eq(&']').is_some() {
+        return Ok(JsonValue::Array(values));
+    }
+
+    loop {
+        values.push(parse_value(chars)?);
+        skip_whitespace(chars);
+
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(JsonValue::Array(values)),
+            Some(c) => return Err(format!("Expected ',' or ']' but found {:?}", c)),
+            None => return Err("Unterminated array".to_string()),
+        }
+    }
+}
+
+fn parse_object(chars: &mut Peekable<Chars>) -> Result<JsonValue, String> {
+    expect(chars, "{")?;
+    skip_whitespace(chars);
+
+    let mut fields = Vec::new();
+    if chars.next_if_eq(&'}').is_some() {
+        return Ok(JsonValue::Object(fields));
+    }
+
+    loop {
+        skip_whitespace(chars);
+        let key = parse_string(chars)?;
+        skip_whitespace(chars);
+        expect(chars, ":")?;
+        fields.push((key, parse_value(chars)?));
+        skip_whitespace(chars);
+
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(JsonValue::Object(fields)),
+            Some(c) => return Err(format!("Expected ',' or '}}' but found {:?}", c)),
+            None => return Err("Unterminated object".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod json_value_should {
+    use crate::json::JsonValue;
+
+    #[test]
+    fn serialize_scalars() {
+        assert_eq!(JsonValue::Null.to_string(), "null");
+        assert_eq!(JsonValue::Bool(true).to_string(), "true");
+        assert_eq!(JsonValue::Number(42).to_string(), "42");
+        assert_eq!(JsonValue::String("Tav".to_string()).to_string(), "\"Tav\"");
+    }
+
+    #[test]
+    fn escape_strings() {
+        let value = JsonValue::String("Some \"quoted\"\\name\n\u{1}".to_string());
+
+        assert_eq!(
+            value.to_string(),
+            "\"Some \\\"quoted\\\"\\\\name\\n\\u0001\""
+        );
+    }
+
+    #[test]
+    fn serialize_nested_values() {
+        let value = JsonValue::object(vec![
+            (
+                "saves",
+                JsonValue::Array(vec![JsonValue::Number(1), JsonValue::Number(2)]),
+            ),
+            ("empty", JsonValue::Object(Vec::new())),
+        ]);
+
+        assert_eq!(value.to_string(), "{\"saves\":[1,2],\"empty\":{}}");
+    }
+
+    #[test]
+    fn pretty_print_with_alternate_flag() {
+        let value = JsonValue::object(vec![
+            ("saves", JsonValue::Array(vec![JsonValue::Number(1)])),
+            ("empty", JsonValue::Array(Vec::new())),
+        ]);
+
+        assert_eq!(
+            format!("{:#}", value),
+            "{\n  \"saves\": [\n    1\n  ],\n  \"empty\": []\n}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod parse_should {
+    use crate::json::JsonValue;
+
+    #[test]
+    fn parse_scalars() {
+        assert_eq!(JsonValue::parse("null").unwrap(), JsonValue::Null);
+        assert_eq!(JsonValue::parse(" true ").unwrap(), JsonValue::Bool(true));
+        assert_eq!(JsonValue::parse("false").unwrap(), JsonValue::Bool(false));
+        assert_eq!(JsonValue::parse("1234").unwrap(), JsonValue::Number(1234));
+    }
+
+    #[test]
+    fn parse_escaped_strings() {
+        let value = JsonValue::parse("\"a\\\"b\\\\c\\n\\u00e9\\ud83d\\ude00\"").unwrap();
+
+        assert_eq!(value, JsonValue::String("a\"b\\c\né😀".to_string()));
+    }
+
+    #[test]
+    fn round_trip_nested_values() {
+        let value = JsonValue::object(vec![
+            (
+                "saves",
+                JsonValue::Array(vec![
+                    JsonValue::object(vec![("name", JsonValue::String("Tav".to_string()))]),
+                    JsonValue::Null,
+                ]),
+            ),
+            ("empty", JsonValue::Object(Vec::new())),
+        ]);
+
+        assert_eq!(JsonValue::parse(&value.to_string()).unwrap(), value);
+        assert_eq!(JsonValue::parse(&format!("{:#}", value)).unwrap(), value);
+    }
+
+    #[test]
+    fn error_on_trailing_characters() {
+        assert!(JsonValue::parse("{} x").is_err());
+    }
+
+    #[test]
+    fn error_on_unterminated_input() {
+        assert!(JsonValue::parse("[1, 2").is_err());
+        assert!(JsonValue::parse("{\"a\": \"b").is_err());
+    }
+
+    #[test]
+    fn error_on_unsupported_numbers() {
+        assert!(JsonValue::parse("-1").is_err());
+        assert!(JsonValue::parse("1.5").is_err());
+    }
+}
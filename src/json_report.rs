@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::{
+    character_key::CharacterKey,
+    deletion_report::DeletionReport,
+    json::{JsonValue, ToJson},
+    run_summary::RunSummary,
+    save_information::SaveInformation,
+    saves::Saves,
+};
+
+/// The document printed with `--output json`: every grouped save, the saves planned for
+/// deletion, the saves that were actually deleted, those that failed with their error and the
+/// summary of the run. The groups are a list holding the full campaign id, the shortened one
+/// shown to people could make two of them collide.
+#[derive(Debug, PartialEq, Default)]
+pub struct JsonReport {
+    pub saves: HashMap<CharacterKey, Saves>,
+    pub planned: Vec<SaveInformation>,
+    pub deletion: DeletionReport,
+    pub summary: RunSummary,
+}
+impl ToJson for JsonReport {
+    fn to_json(&self) -> JsonValue {
+        let mut characters: Vec<(&CharacterKey, &Saves)> = self.saves.iter().collect();
+        characters.sort_by_key(|(key, _)| *key);
+
+        JsonValue::object(vec![
+            (
+                "saves",
+                JsonValue::Array(
+                    characters
+                        .into_iter()
+                        .map(|(key, saves)| {
+                            JsonValue::object(vec![
+                                ("character", JsonValue::String(key.name.clone())),
+                                (
+                                    "campaign_id",
+                                    key.campaign_id
+                                        .clone()
+                                        .map_or(JsonValue::Null, JsonValue::String),
+                                ),
+                                ("saves", saves.to_json()),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+            ("planned", self.planned.to_json()),
+            ("deleted", self.deletion.deleted.to_json()),
+            ("failed", self.deletion.failed.to_json()),
+            ("summary", self.summary.to_json()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod json_report_should {
+    use std::collections::HashMap;
+
+    use crate::{
+        character_key::CharacterKey,
+        deletion_report::DeletionReport,
+        json::{JsonValue, ToJson},
+        json_report::JsonReport,
+        run_summary::RunSummary,
+        save_information::SaveInformation,
+        save_type::SaveType,
+        saves::Saves,
+    };
+
+    #[test]
+    fn serialize_grouped_planned_and_deleted_saves() {
+        let save = SaveInformation::new(
+            "Some'me-123456789__QuickSave_3".to_string(),
+            "Some'me".to_string(),
+            SaveType::Quick,
+            Some(3),
+        );
+        let mut saves = HashMap::new();
+        saves.insert(
+            CharacterKey::named("Some'me"),
+            Saves {
+                quick_saves: vec![save.clone()],
+                auto_saves: Vec::new(),
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
+            },
+        );
+
+        let report = JsonReport {
+            saves,
+            planned: vec![save.clone()],
+            deletion: DeletionReport::default(),
+            summary: RunSummary::new(1, std::slice::from_ref(&save), &[], 0, true),
+        };
+
+        let save_json = "{\"character_name\":\"Some'me\",\"file_name\":\"Some'me-123456789__QuickSave_3\",\"campaign_id\":null,\"save_type\":\"Quick\",\"save_number\":3,\"modified\":null,\"modified_local\":null,\"size_bytes\":0,\"corrupt\":false,\"root\":null,\"game_version\":null,\"duplicate_of\":null,\"cloud_only\":false}";
+        assert_eq!(
+            report.to_json().to_string(),
+            format!(
+                "{{\"saves\":[{{\"character\":\"Some'me\",\"campaign_id\":null,\"saves\":{{\"quick_saves\":[{}],\"auto_saves\":[],\"honour_saves\":[],\"manual_saves\":[],\"unrecognized_saves\":[],\"corrupt_saves\":[],\"copies\":[]}}}}],\"planned\":[{}],\"deleted\":[],\"failed\":[],\"summary\":{{\"scanned\":1,\"kept\":0,\"deleted\":1,\"failed\":0,\"bytes_removed\":0,\"bytes_reclaimed\":0,\"freed_space\":\"reclaimed\",\"dry_run\":true}}}}",
+                save_json, save_json
+            )
+        );
+    }
+
+    #[test]
+    fn keep_characters_apart_whose_campaign_ids_start_alike() {
+        let mut saves = HashMap::new();
+        ["1234567890", "1234567891"].iter().for_each(|campaign_id| {
+            saves.insert(
+                CharacterKey {
+                    name: "Tav".to_string(),
+                    campaign_id: Some(campaign_id.to_string()),
+                },
+                Saves::default(),
+            );
+        });
+        let report = JsonReport {
+            saves,
+            ..Default::default()
+        };
+
+        let json = report.to_json();
+        let campaign_ids: Vec<Option<&str>> = json
+            .get("saves")
+            .and_then(JsonValue::as_array)
+            .unwrap()
+            .iter()
+            .map(|group| group.get("campaign_id").and_then(JsonValue::as_str))
+            .collect();
+        assert_eq!(campaign_ids, vec![Some("1234567890"), Some("1234567891")]);
+    }
+}
@@ -0,0 +1,343 @@
+use crate::lz4;
+
+/// Magic bytes at the start of a binary Larian resource file.
+const MAGIC: &[u8; 4] = b"LSOF";
+const CHUNKED_COMPRESSION: u32 = 2;
+const EXTENDED_HEADER: u32 = 5;
+const ADDITIONAL_BLOB: u32 = 6;
+const MAX_VERSION: u32 = 7;
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_LZ4: u8 = 2;
+
+/// The sections of the file, each stored with its uncompressed size and its size on disk.
+struct Sections {
+    strings: (usize, usize),
+    nodes: (usize, usize),
+    attributes: (usize, usize),
+    values: (usize, usize),
+    compression: u8,
+    /// Newer files link attributes by offset, older ones store their values back to back.
+    linked_attributes: bool,
+}
+
+/// Reads every attribute of the file as (name, value) pairs, in file order. Only values of
+/// text, integer and boolean types are decoded, which is all save metadata needs, the nodes
+/// holding the attributes are not kept.
+pub fn read_attributes(bytes: &[u8]) -> Result<Vec<(String, String)>, String> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.take(4)? != MAGIC {
+        return Err("Not an LSF file".to_string());
+    }
+    let version = reader.u32()?;
+    if !(1..=MAX_VERSION).contains(&version) {
+        return Err(format!("Unsupported LSF version {}", version));
+    }
+    // The engine version, 64 bits wide since the extended header.
+    reader.take(if version >= EXTENDED_HEADER { 8 } else { 4 })?;
+
+    let sections = read_sections(&mut reader, version)?;
+    let chunked = version >= CHUNKED_COMPRESSION;
+    let strings = read_section(&mut reader, sections.strings, sections.compression, false)?;
+    read_section(&mut reader, sections.nodes, sections.compression, chunked)?;
+    let attributes = read_section(
+        &mut reader,
+        sections.attributes,
+        sections.compression,
+        chunked,
+    )?;
+    let values = read_section(&mut reader, sections.values, sections.compression, chunked)?;
+
+    let names = read_names(&strings)?;
+    read_attribute_values(&attributes, &values, &names, sections.linked_attributes)
+}
+
+fn read_sections(reader: &mut Reader, version: u32) -> Result<Sections, String> {
+    let mut section = || Ok::<_, String>((reader.u32()? as usize, reader.u32()? as usize));
+
+    let strings = section()?;
+    if version >= ADDITIONAL_BLOB {
+        // The node keys, stored after the values and not needed here.
+        section()?;
+    }
+    let nodes = section()?;
+    let attributes = section()?;
+    let values = section()?;
+    let compression = reader.u8()?;
+    reader.take(3)?;
+    let linked_attributes = reader.u32()? != 0;
+
+    Ok(Sections {
+        strings,
+        nodes,
+        attributes,
+        values,
+        compression,
+        linked_attributes,
+    })
+}
+
+fn read_section(
+    reader: &mut Reader,
+    (uncompressed_size, size_on_disk): (usize, usize),
+    compression: u8,
+    chunked: bool,
+) -> Result<Vec<u8>, String> {
+    // Sections that were not compressed only record their uncompressed size.
+    if size_on_disk == 0 {
+        return reader.take(uncompressed_size).map(<[u8]>::to_vec);
+    }
+
+    let data = reader.take(size_on_disk)?;
+    match compression & 0x0F {
+        COMPRESSION_NONE => Ok(data.to_vec()),
+        COMPRESSION_LZ4 if chunked => lz4::decompress_frame(data),
+        COMPRESSION_LZ4 => lz4::decompress_block(data, uncompressed_size),
+        method => Err(format!("Unsupported LSF compression method {}", method)),
+    }
+}
+
+/// The string table is a list of hash buckets, a name is referenced by its bucket in the upper
+/// 16 bits and its position in the bucket in the lower ones.
+fn read_names(strings: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let mut reader = Reader::new(strings);
+    let bucket_count = reader.u32()?;
+
+    (0..bucket_count)
+        .map(|_| {
+            let string_count = reader.u16()?;
+            (0..string_count)
+                .map(|_| {
+                    let length = usize::from(reader.u16()?);
+                    Ok(String::from_utf8_lossy(reader.take(length)?).to_string())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn read_attribute_values(
+    attributes: &[u8],
+    values: &[u8],
+    names: &[Vec<String>],
+    linked_attributes: bool,
+) -> Result<Vec<(String, String)>, String> {
+    let entry_size = if linked_attributes { 16 } else { 12 };
+    let mut reader = Reader::new(attributes);
+    let mut next_offset = 0;
+
+    (0..attributes.len() / entry_size)
+        .map(|_| {
+            let name_index = reader.u32()?;
+            let type_and_length = reader.u32()?;
+            let offset = if linked_attributes {
+                reader.u32()?;
+                reader.u32()? as usize
+            } else {
+                reader.u32()?;
+                next_offset
+            };
+            let length = (type_and_length >> 6) as usize;
+            next_offset = offset + length;
+
+            let name = names
+                .get((name_index >> 16) as usize)
+                .and_then(|bucket| bucket.get((name_index & 0xFFFF) as usize))
+                .ok_or(format!(
+                    "Attribute name {:#x} is not in the string table",
+                    name_index
+                ))?;
+            let value = values
+                .get(offset..offset.saturating_add(length))
+                .ok_or(format!("Value of attribute {} is out of range", name))?;
+
+            Ok(decode_value(type_and_length & 0x3F, value).map(|value| (name.clone(), value)))
+        })
+        .filter_map(Result::transpose)
+        .collect()
+}
+
+/// Renders a value as text, `None` for types save metadata never uses, like vectors.
+fn decode_value(value_type: u32, value: &[u8]) -> Option<String> {
+    let number = |size: usize| value.get(..size).map(|bytes| bytes.to_vec());
+    let unsigned = |size: usize| {
+        number(size).map(|bytes| {
+            bytes
+                .iter()
+                .rev()
+                .fold(0u64, |number, byte| number << 8 | u64::from(*byte))
+        })
+    };
+
+    match value_type {
+        1 | 19 => unsigned(1).map(|number| number.to_string()),
+        2 => number(2).map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]).to_string()),
+        3 => unsigned(2).map(|number| number.to_string()),
+        4 => number(4)
+            .map(|bytes| i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).to_string()),
+        5 => unsigned(4).map(|number| number.to_string()),
+        24 => unsigned(8).map(|number| number.to_string()),
+        26 | 32 => unsigned(8).map(|number| (number as i64).to_string()),
+        27 => number(1).map(|bytes| (bytes[0] as i8).to_string()),
+        // Strings, paths and their wide variants, all stored as UTF-8 with a terminating null.
+        20..=23 | 29 | 30 => Some(
+            String::from_utf8_lossy(value)
+                .trim_end_matches('\0')
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let taken = self
+            .bytes
+            .get(self.position..self.position.saturating_add(length))
+            .ok_or("LSF file is truncated".to_string())?;
+        self.position += length;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        self.take(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        self.take(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+/// Builds an uncompressed version 6 file holding one node with the given string attributes,
+/// for tests of code reading save metadata.
+#[cfg(test)]
+pub fn write_attributes(attributes: &[(&str, &str)]) -> Vec<u8> {
+    const LS_STRING: u32 = 23;
+
+    // One bucket, the node name first and then every attribute name.
+    let mut strings = 1u32.to_le_bytes().to_vec();
+    strings.extend_from_slice(&(attributes.len() as u16 + 1).to_le_bytes());
+    std::iter::once("MetaData")
+        .chain(attributes.iter().map(|(name, _)| *name))
+        .for_each(|name| {
+            strings.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            strings.extend_from_slice(name.as_bytes());
+        });
+
+    let mut nodes = Vec::new();
+    [0u32, u32::MAX, u32::MAX, 0]
+        .iter()
+        .for_each(|field| nodes.extend_from_slice(&field.to_le_bytes()));
+
+    let mut attribute_entries = Vec::new();
+    let mut values = Vec::new();
+    attributes.iter().enumerate().for_each(|(i, (_, value))| {
+        let length = value.len() as u32 + 1;
+        let next = if i + 1 < attributes.len() {
+            i as u32 + 1
+        } else {
+            u32::MAX
+        };
+        [
+            i as u32 + 1,
+            LS_STRING | length << 6,
+            next,
+            values.len() as u32,
+        ]
+        .iter()
+        .for_each(|field| attribute_entries.extend_from_slice(&field.to_le_bytes()));
+        values.extend_from_slice(value.as_bytes());
+        values.push(0);
+    });
+
+    let mut file = MAGIC.to_vec();
+    file.extend_from_slice(&ADDITIONAL_BLOB.to_le_bytes());
+    file.extend_from_slice(&0u64.to_le_bytes());
+    [&strings, &Vec::new(), &nodes, &attribute_entries, &values]
+        .iter()
+        .for_each(|section| {
+            file.extend_from_slice(&(section.len() as u32).to_le_bytes());
+            file.extend_from_slice(&0u32.to_le_bytes());
+        });
+    file.extend_from_slice(&[COMPRESSION_NONE, 0, 0, 0]);
+    file.extend_from_slice(&1u32.to_le_bytes());
+    [strings, nodes, attribute_entries, values]
+        .iter()
+        .for_each(|section| file.extend_from_slice(section));
+
+    file
+}
+
+#[cfg(test)]
+mod read_attributes_should {
+    use crate::lsf::{read_attributes, write_attributes};
+
+    #[test]
+    fn read_string_attributes() {
+        let file = write_attributes(&[("LeaderName", "Tav"), ("GameVersion", "4.1.1.5")]);
+
+        assert_eq!(
+            read_attributes(&file).unwrap(),
+            vec![
+                ("LeaderName".to_string(), "Tav".to_string()),
+                ("GameVersion".to_string(), "4.1.1.5".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn read_values_stored_back_to_back() {
+        let mut file = write_attributes(&[]);
+        // A version 2 file without node keys or linked attributes, the strings hold "Year".
+        let strings = [1, 0, 0, 0, 1, 0, 4, 0, b'Y', b'e', b'a', b'r'];
+        let attributes = [0u32, 3 | 2 << 6, 0];
+        file.truncate(4);
+        file.extend_from_slice(&2u32.to_le_bytes());
+        file.extend_from_slice(&0u32.to_le_bytes());
+        [strings.len(), 0, 12, 2].iter().for_each(|size| {
+            file.extend_from_slice(&(*size as u32).to_le_bytes());
+            file.extend_from_slice(&0u32.to_le_bytes());
+        });
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&strings);
+        attributes
+            .iter()
+            .for_each(|field| file.extend_from_slice(&field.to_le_bytes()));
+        file.extend_from_slice(&2024u16.to_le_bytes());
+
+        assert_eq!(
+            read_attributes(&file).unwrap(),
+            vec![("Year".to_string(), "2024".to_string())]
+        );
+    }
+
+    #[test]
+    fn reject_other_files() {
+        assert_eq!(
+            read_attributes(b"<?xml version=\"1.0\"?>").unwrap_err(),
+            "Not an LSF file"
+        );
+    }
+
+    #[test]
+    fn reject_truncated_files() {
+        let mut file = write_attributes(&[("LeaderName", "Tav")]);
+        file.truncate(file.len() - 2);
+
+        assert_eq!(read_attributes(&file).unwrap_err(), "LSF file is truncated");
+    }
+}
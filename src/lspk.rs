@@ -0,0 +1,261 @@
+use std::{fs, io::Read, path::Path};
+
+use crate::lz4;
+
+/// Magic bytes at the start of a Larian package, the `.lsv` file of every save.
+pub const MAGIC: &[u8; 4] = b"LSPK";
+/// The package version written by Baldur's Gate 3.
+const VERSION: u32 = 18;
+const FILE_ENTRY_SIZE: usize = 272;
+const NAME_SIZE: usize = 256;
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_LZ4: u8 = 2;
+
+/// Whether the folder holds an `.lsv` file starting with the package magic bytes, i.e. is a save.
+pub fn contains_package(folder: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return false;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("lsv"))
+        })
+        .any(|path| {
+            let mut magic = [0; 4];
+            fs::File::open(path)
+                .and_then(|mut file| file.read_exact(&mut magic))
+                .is_ok_and(|_| &magic == MAGIC)
+        })
+}
+
+/// Reads the file called `file_name` out of the package, `None` if the package has no such file.
+/// Only files stored uncompressed or with LZ4 can be read, the compression saves use.
+pub fn read_file(package: &Path, file_name: &str) -> Result<Option<Vec<u8>>, String> {
+    let bytes = fs::read(package).map_err(|e| format!("{}: {}", package.display(), e))?;
+    let truncated = || format!("{}: package is truncated", package.display());
+
+    if !bytes.starts_with(MAGIC) {
+        return Err(format!("{}: not a Larian package", package.display()));
+    }
+    let version = read_u32(&bytes, 4).ok_or_else(truncated)?;
+    if version != VERSION {
+        return Err(format!(
+            "{}: unsupported package version {}",
+            package.display(),
+            version
+        ));
+    }
+
+    let file_list_offset = read_u64(&bytes, 8)
+        .and_then(|offset| usize::try_from(offset).ok())
+        .ok_or_else(truncated)?;
+    let file_count = read_u32(&bytes, file_list_offset).ok_or_else(truncated)? as usize;
+    let compressed_size = read_u32(&bytes, file_list_offset + 4).ok_or_else(truncated)? as usize;
+    let compressed_list = bytes
+        .get(
+            file_list_offset.saturating_add(8)
+                ..file_list_offset.saturating_add(8 + compressed_size),
+        )
+        .ok_or_else(truncated)?;
+    let file_list = lz4::decompress_block(compressed_list, file_count * FILE_ENTRY_SIZE)
+        .map_err(|e| format!("{}: {}", package.display(), e))?;
+
+    file_list
+        .chunks_exact(FILE_ENTRY_SIZE)
+        .find(|entry| entry_name(entry).rsplit('/').next() == Some(file_name))
+        .map(|entry| {
+            read_entry(&bytes, entry).unwrap_or(Err("file entry is out of range".to_string()))
+        })
+        .transpose()
+        .map_err(|e| format!("{}: {}", package.display(), e))
+}
+
+fn entry_name(entry: &[u8]) -> String {
+    let name = &entry[..NAME_SIZE];
+    let length = name.iter().position(|byte| *byte == 0).unwrap_or(NAME_SIZE);
+
+    String::from_utf8_lossy(&name[..length]).replace('\\', "/")
+}
+
+fn read_entry(bytes: &[u8], entry: &[u8]) -> Option<Result<Vec<u8>, String>> {
+    let offset =
+        u64::from(read_u32(entry, NAME_SIZE)?) | (u64::from(read_u16(entry, NAME_SIZE + 4)?) << 32);
+    let flags = entry[NAME_SIZE + 7];
+    let size_on_disk = read_u32(entry, NAME_SIZE + 8)? as usize;
+    let uncompressed_size = read_u32(entry, NAME_SIZE + 12)? as usize;
+
+    let offset = usize::try_from(offset).ok()?;
+    let data = bytes.get(offset..offset.checked_add(size_on_disk)?)?;
+
+    Some(match flags & 0x0F {
+        COMPRESSION_NONE => Ok(data.to_vec()),
+        // Small files are stored as they are even with a compression method set.
+        COMPRESSION_LZ4 if uncompressed_size == 0 => Ok(data.to_vec()),
+        COMPRESSION_LZ4 => lz4::decompress_block(data, uncompressed_size),
+        method => Err(format!("unsupported compression method {}", method)),
+    })
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    bytes
+        .get(at..at.checked_add(2)?)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    bytes
+        .get(at..at.checked_add(4)?)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    bytes.get(at..at.checked_add(8)?).map(|bytes| {
+        u64::from_le_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ])
+    })
+}
+
+/// Builds packages in the layout read above, for tests of code that looks inside saves.
+#[cfg(test)]
+pub fn write_package(files: &[(&str, &[u8])]) -> Vec<u8> {
+    const HEADER_SIZE: usize = 40;
+
+    let mut data = Vec::new();
+    let mut file_list = Vec::new();
+    files.iter().for_each(|(name, content)| {
+        let offset = HEADER_SIZE + data.len();
+        let mut entry = vec![0u8; FILE_ENTRY_SIZE];
+        entry[..name.len()].copy_from_slice(name.as_bytes());
+        entry[NAME_SIZE..NAME_SIZE + 4].copy_from_slice(&(offset as u32).to_le_bytes());
+        entry[NAME_SIZE + 8..NAME_SIZE + 12].copy_from_slice(&(content.len() as u32).to_le_bytes());
+        file_list.extend_from_slice(&entry);
+        data.extend_from_slice(content);
+    });
+
+    let mut package = Vec::new();
+    package.extend_from_slice(MAGIC);
+    package.extend_from_slice(&VERSION.to_le_bytes());
+    package.extend_from_slice(&((HEADER_SIZE + data.len()) as u64).to_le_bytes());
+    package.resize(HEADER_SIZE, 0);
+    package.extend_from_slice(&data);
+    package.extend_from_slice(&(files.len() as u32).to_le_bytes());
+    // Stored as a single run of literals, which is a valid LZ4 block.
+    let mut block = vec![(file_list.len().min(15) as u8) << 4];
+    if let Some(mut remaining) = file_list.len().checked_sub(15) {
+        while remaining >= 255 {
+            block.push(255);
+            remaining -= 255;
+        }
+        block.push(remaining as u8);
+    }
+    block.extend_from_slice(&file_list);
+    package.extend_from_slice(&(block.len() as u32).to_le_bytes());
+    package.extend_from_slice(&block);
+
+    package
+}
+
+#[cfg(test)]
+mod contains_package_should {
+    use std::fs;
+
+    use crate::{
+        lspk::{contains_package, write_package},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn accept_folder_with_package() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("Tav-123456789__QuickSave_1.webp"), b"RIFF").unwrap();
+        fs::write(
+            save_path.join("Tav-123456789__QuickSave_1.LSV"),
+            write_package(&[]),
+        )
+        .unwrap();
+
+        assert!(contains_package(&save_path));
+    }
+
+    #[test]
+    fn reject_lsv_with_other_magic_bytes() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(
+            save_path.join("Tav-123456789__QuickSave_1.lsv"),
+            b"PK\x03\x04zip",
+        )
+        .unwrap();
+        fs::write(save_path.join("short.lsv"), b"LS").unwrap();
+
+        assert!(!contains_package(&save_path));
+    }
+
+    #[test]
+    fn reject_folder_without_lsv() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("LSPK.txt"), b"LSPK").unwrap();
+
+        assert!(!contains_package(&save_path));
+        assert!(!contains_package(&temp_dir.path().join("missing")));
+    }
+}
+
+#[cfg(test)]
+mod read_file_should {
+    use std::fs;
+
+    use crate::{
+        lspk::{read_file, write_package},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn read_stored_file_by_name() {
+        let temp_dir = TempDir::new();
+        let package = temp_dir.path().join("save.lsv");
+        fs::write(
+            &package,
+            write_package(&[("Globals.lsf", b"globals"), ("Save/meta.lsf", b"meta")]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_file(&package, "meta.lsf").unwrap(),
+            Some(b"meta".to_vec())
+        );
+        assert_eq!(read_file(&package, "missing.lsf").unwrap(), None);
+    }
+
+    #[test]
+    fn reject_files_that_are_not_packages() {
+        let temp_dir = TempDir::new();
+        let package = temp_dir.path().join("save.lsv");
+        fs::write(&package, b"RIFF0000WEBP").unwrap();
+
+        assert!(read_file(&package, "meta.lsf")
+            .unwrap_err()
+            .ends_with("not a Larian package"));
+    }
+
+    #[test]
+    fn reject_truncated_packages() {
+        let temp_dir = TempDir::new();
+        let package = temp_dir.path().join("save.lsv");
+        let mut bytes = write_package(&[("meta.lsf", b"meta")]);
+        bytes.truncate(bytes.len() - 10);
+        fs::write(&package, bytes).unwrap();
+
+        assert!(read_file(&package, "meta.lsf").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+/// Magic number at the start of an LZ4 frame.
+const FRAME_MAGIC: u32 = 0x184D_2204;
+/// Set on a frame block size when the block is stored without compression.
+const UNCOMPRESSED_BLOCK: u32 = 0x8000_0000;
+
+/// Decompresses a raw LZ4 block, as used by Larian packages for their file lists and files.
+pub fn decompress_block(input: &[u8], uncompressed_size: usize) -> Result<Vec<u8>, String> {
+    // A corrupt size must not reserve more than the block could ever decompress to.
+    let mut output = Vec::with_capacity(uncompressed_size.min(input.len().saturating_mul(255)));
+    decompress_block_into(input, &mut output)?;
+
+    if output.len() != uncompressed_size {
+        return Err(format!(
+            "LZ4 block decompressed to {} bytes instead of {}",
+            output.len(),
+            uncompressed_size
+        ));
+    }
+    Ok(output)
+}
+
+/// Decompresses an LZ4 frame, as used for the chunked sections of LSF files.
+pub fn decompress_frame(input: &[u8]) -> Result<Vec<u8>, String> {
+    let truncated = || "LZ4 frame is truncated".to_string();
+    let read_u32 = |at: usize| {
+        input
+            .get(at..at.saturating_add(4))
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .ok_or_else(truncated)
+    };
+
+    if read_u32(0)? != FRAME_MAGIC {
+        return Err("Not an LZ4 frame".to_string());
+    }
+    let flags = *input.get(4).ok_or_else(truncated)?;
+    let has_block_checksum = flags & 0x10 != 0;
+    let has_content_size = flags & 0x08 != 0;
+    // Magic, flags, block descriptor, optional content size and the header checksum.
+    let mut position = 4 + 2 + if has_content_size { 8 } else { 0 } + 1;
+
+    let mut output = Vec::new();
+    loop {
+        let block_size = read_u32(position)?;
+        position += 4;
+        if block_size == 0 {
+            return Ok(output);
+        }
+
+        let length = (block_size & !UNCOMPRESSED_BLOCK) as usize;
+        let block = input
+            .get(position..position.saturating_add(length))
+            .ok_or_else(truncated)?;
+        if block_size & UNCOMPRESSED_BLOCK != 0 {
+            output.extend_from_slice(block);
+        } else {
+            decompress_block_into(block, &mut output)?;
+        }
+        position += length + if has_block_checksum { 4 } else { 0 };
+    }
+}
+
+/// Appends the decompressed block to `output`, matches may reach back into earlier blocks.
+fn decompress_block_into(input: &[u8], output: &mut Vec<u8>) -> Result<(), String> {
+    let truncated = || "LZ4 block is truncated".to_string();
+    let mut position = 0;
+
+    while position < input.len() {
+        let token = input[position];
+        position += 1;
+
+        let literal_length = read_length(input, &mut position, usize::from(token >> 4))?;
+        let literals = input
+            .get(position..position.saturating_add(literal_length))
+            .ok_or_else(truncated)?;
+        output.extend_from_slice(literals);
+        position += literal_length;
+
+        // The last sequence of a block only has literals.
+        if position == input.len() {
+            break;
+        }
+
+        let offset = input
+            .get(position..position + 2)
+            .map(|bytes| usize::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+            .ok_or_else(truncated)?;
+        position += 2;
+        if offset == 0 || offset > output.len() {
+            return Err(format!("LZ4 match offset {} is out of range", offset));
+        }
+
+        let match_length = read_length(input, &mut position, usize::from(token & 0x0F))? + 4;
+        let start = output.len() - offset;
+        // Byte by byte as a match may overlap the bytes it produces.
+        (0..match_length).for_each(|i| output.push(output[start + i]));
+    }
+
+    Ok(())
+}
+
+/// A length of 15 continues in the following bytes, each 255 adding another byte.
+fn read_length(input: &[u8], position: &mut usize, length: usize) -> Result<usize, String> {
+    if length != 15 {
+        return Ok(length);
+    }
+
+    let mut length = length;
+    loop {
+        let byte = *input
+            .get(*position)
+            .ok_or("LZ4 block is truncated".to_string())?;
+        *position += 1;
+        length += usize::from(byte);
+        if byte != 255 {
+            return Ok(length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod decompress_block_should {
+    use crate::lz4::{decompress_block, decompress_frame};
+
+    #[test]
+    fn copy_literals() {
+        let block = [0x50, b'h', b'e', b'l', b'l', b'o'];
+
+        assert_eq!(decompress_block(&block, 5).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn repeat_overlapping_match() {
+        // Three literals, then a match of 9 bytes starting 3 bytes back.
+        let block = [0x35, b'a', b'b', b'c', 3, 0];
+
+        assert_eq!(decompress_block(&block, 12).unwrap(), b"abcabcabcabc");
+    }
+
+    #[test]
+    fn read_extended_lengths() {
+        let mut block = vec![0xF0, 5];
+        block.extend_from_slice(&[b'x'; 20]);
+
+        assert_eq!(decompress_block(&block, 20).unwrap(), vec![b'x'; 20]);
+    }
+
+    #[test]
+    fn reject_match_before_start() {
+        let block = [0x10, b'a', 2, 0];
+
+        assert!(decompress_block(&block, 5).is_err());
+    }
+
+    #[test]
+    fn reject_wrong_size() {
+        let block = [0x20, b'h', b'i'];
+
+        assert!(decompress_block(&block, 3).is_err());
+    }
+
+    #[test]
+    fn decompress_frame_of_stored_and_compressed_blocks() {
+        let mut frame = vec![0x04, 0x22, 0x4D, 0x18, 0x60, 0x40, 0x82];
+        frame.extend_from_slice(&(2u32 | 0x8000_0000).to_le_bytes());
+        frame.extend_from_slice(b"ab");
+        frame.extend_from_slice(&3u32.to_le_bytes());
+        frame.extend_from_slice(&[0x02, 2, 0]);
+        frame.extend_from_slice(&0u32.to_le_bytes());
+
+        assert_eq!(decompress_frame(&frame).unwrap(), b"abababab");
+    }
+}
@@ -1,645 +1,5777 @@
+mod audit_log;
+mod big_delete;
+mod character_answer;
+mod character_key;
+mod clean_config;
+mod cloud_placeholder;
+mod clutter;
+mod color;
+mod command;
+mod completions;
+mod config_file;
+mod confirmation;
+mod copy_suffix;
+mod corrupt;
+mod counter_reset;
+mod csv_export;
+mod default_paths;
+mod deletion_report;
+mod deletion_strategy;
+mod duration_arg;
+mod empty_folder;
+mod exclude_list;
+mod exit_code;
+mod folder_filter;
+mod folder_list;
+mod folder_pattern;
+mod folder_size;
+mod game_process;
+mod ignore_list;
+mod interrupt;
+mod inventory;
+mod json;
+mod json_report;
+mod lsf;
+mod lspk;
+mod lz4;
+mod output;
+mod output_format;
+mod plan;
+mod preserve_arg;
+mod profile_picker;
+mod profiles;
 mod program_errors;
+mod progress;
+mod quarantine;
+mod regex;
+mod retry;
+mod run_lock;
+mod run_summary;
 mod save_information;
+mod save_keywords;
+mod save_metadata;
+mod save_root;
 mod save_type;
+mod save_type_filter;
 mod saves;
+mod scan_result;
+mod selection;
+mod size_arg;
+mod size_format;
+mod sort_by;
+mod staged_removal;
+mod stats;
+mod steam_cloud;
+mod tar_archive;
+#[cfg(test)]
+mod test_utils;
+mod timestamp;
+mod trash;
+mod undo;
+mod verbatim_path;
+mod watch;
+mod zip_archive;
+mod zstd;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     env,
     ffi::OsString,
-    fs,
-    io::{stdin, stdout, Write},
+    fs::{self, DirEntry},
+    io::{stdin, stdout, IsTerminal, Read, Write},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, SystemTime},
 };
 
-use clap::Parser;
+use audit_log::AuditLog;
+use character_answer::CharacterAnswer;
+use character_key::CharacterKey;
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use clean_config::CleanConfig;
+use clutter::Clutter;
+use color::Color;
+use command::{Command, ConfigAction};
+use config_file::ConfigFile;
+use deletion_report::{DeletionReport, FailedDeletion};
+use deletion_strategy::DeletionStrategy;
+use exclude_list::ExcludeList;
+use folder_filter::FolderFilter;
+use folder_pattern::FolderPattern;
+use folder_size::folder_size;
+use ignore_list::IgnoreList;
+use json::ToJson;
+use json_report::JsonReport;
+use output::{info, outcome};
+use output_format::OutputFormat;
+use plan::Plan;
+use profiles::ProfileDirectory;
 use program_errors::ProgramError;
+use progress::{Progress, ProgressStyle, Spinner};
+use run_lock::RunLock;
+use run_summary::RunSummary;
 use save_information::SaveInformation;
+use save_metadata::SaveMetadata;
 use save_type::SaveType;
 use saves::Saves;
+use scan_result::ScanResult;
+use selection::Selection;
+use size_format::format_size;
+use sort_by::SortBy;
+use undo::Journal;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(after_help = exit_code::DESCRIPTION)]
 struct ProgramConfig {
-    /// The path the program should run against.
-    #[arg(short, long)]
-    path_to_save_folder: Option<OsString>,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// The latest n saves to ignore when selecting saves for deletion
-    #[arg(short, long)]
-    saves_to_preserve: Option<usize>,
-}
+    /// The path the program should run against, repeat it to run against several save folders.
+    /// Defaults to BG3_SAVE_DIR, then the config file, then the game's own save folder
+    #[arg(short, long, global = true)]
+    path_to_save_folder: Vec<OsString>,
 
-const DEFAULT_SAVES_TO_PRESERVE: usize = 10;
+    /// List every location the save folder is looked for when no path is given, and stop
+    #[arg(long, global = true)]
+    list_detected_paths: bool,
 
-fn main() -> Result<(), ProgramError> {
-    let program_config = ProgramConfig::parse();
-    let saves_to_preserve = program_config
-        .saves_to_preserve
-        .unwrap_or(DEFAULT_SAVES_TO_PRESERVE);
+    /// Clean all paths together, keeping the newest saves across them instead of per path
+    #[arg(long, global = true)]
+    combine_paths: bool,
 
-    let directory = path_to_use(program_config.path_to_save_folder)?;
+    /// Print every folder skipped during scanning and the reason it was skipped
+    #[arg(short, long, global = true)]
+    verbose: bool,
 
-    println!(
-        "Running program with saves_to_preserve: {} and path: {}",
-        &saves_to_preserve,
-        &directory.to_str().unwrap() // unwrap?
-    );
+    /// Run against this player profile when the path is a PlayerProfiles directory
+    #[arg(long, global = true)]
+    profile: Option<String>,
 
-    match fs::read_dir(directory.clone())
-        .map_err(|e| ProgramError::CannotReadDirectory(e.to_string()))
-        .map(|dir_entries| {
-            dir_entries
-                .flatten()
-                .filter(|dir_entry| {
-                    dir_entry
-                        .file_type()
-                        .map(|file_type| file_type.is_dir())
-                        .unwrap_or(false)
-                })
-                .filter(|dir_entry| {
-                    // Filter empty string folders and non ascii names.
-                    !dir_entry.file_name().is_empty() && dir_entry.file_name().is_ascii()
-                })
-                // Parse each directory
-                .flat_map(|dir_entry| {
-                    dir_entry
-                        .file_name()
-                        .to_str()
-                        .ok_or(ProgramError::AsciiErrorInFileName(
-                            "Unable to get ascii string from OsString".to_string(),
-                        ))
-                        .and_then(crate::package_details)
-                }) // Up to this point errors only affect individual folders, ignore errors as those folders will be dropped and continue.
-                .collect::<Vec<SaveInformation>>()
-        })
-        .map(crate::group_saves) // Here errors start to matter for the set, don't drop and output below.
-        .map(crate::sort_map_saves)
-        .map(|map| get_delete_vec(map, saves_to_preserve))
-        .map(crate::confirm_user_delete)
-        .and_then(|(deletable_saves, user_input)| delete((deletable_saves, user_input, directory)))
-    {
-        Ok(_) => (),
-        Err(e) => {
-            println!("Encountered error:");
-            println!("{}", e);
-        }
-    };
+    /// Run against every player profile when the path is a PlayerProfiles directory. Without this
+    /// or --profile, the profile is asked for when several have saves
+    #[arg(long, global = true, conflicts_with = "profile")]
+    all_profiles: bool,
 
-    Ok(())
-}
+    /// Read default options from this file instead of the default config file
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
 
-fn path_to_use(given_path: Option<OsString>) -> Result<PathBuf, ProgramError> {
-    match given_path {
-        Some(path) => Ok(PathBuf::from(path)),
-        None => env::current_dir().map_err(|e| ProgramError::NoPath(e.to_string())),
-    }
-}
+    /// Wait for another running instance to finish with the save folder instead of stopping
+    #[arg(long, global = true)]
+    wait: bool,
 
-fn package_details(file_name: &str) -> Result<SaveInformation, ProgramError> {
-    let parse_number = save_number(file_name)?;
-    let characters_name = character_name(file_name)?;
-    let s_type = save_type(file_name);
+    /// Split folder names with this regex instead of the built-in parser. It needs the named
+    /// groups name, campaign, type and number, the built-in parser is equivalent to
+    /// ^(?P<name>.+)-(?P<campaign>\d+)_+(?P<type>[^_]+)_(?P<number>\d+)$
+    #[arg(long, global = true, value_name = "REGEX", value_parser = FolderPattern::new)]
+    pattern: Option<FolderPattern>,
 
-    Ok(SaveInformation::new(
-        file_name.to_string(),
-        characters_name,
-        s_type,
-        parse_number,
-    ))
-}
+    /// Also treat folders with this word after an underscore as quick saves, like the name the
+    /// game uses in your language. Only QuickSave is built in. Can be given multiple times
+    #[arg(long, global = true, value_name = "WORD")]
+    quick_keyword: Vec<String>,
 
-fn save_type(folder_name: &str) -> SaveType {
-    if folder_name.to_ascii_lowercase().contains("quicksave") {
-        SaveType::Quick
-    } else if folder_name.to_ascii_lowercase().contains("autosave") {
-        SaveType::Auto
-    } else {
-        SaveType::Unrecognized
-    }
-}
+    /// Also treat folders with this word after an underscore as autosaves, only AutoSave is
+    /// built in. Can be given multiple times
+    #[arg(long, global = true, value_name = "WORD")]
+    auto_keyword: Vec<String>,
 
-fn character_name(folder_name: &str) -> Result<String, ProgramError> {
-    folder_name
-        .find('-')
-        .filter(|index| index > &0)
-        .map(|index| folder_name.chars().take(index).collect())
-        .ok_or(ProgramError::NameNotDetected(
-            "Could not detect character name".to_string(),
-        ))
+    /// Disable colored output. Also disabled by setting NO_COLOR or when not writing to a terminal
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Running without a subcommand cleans, keeping existing scripts working.
+    #[command(flatten)]
+    clean_config: CleanConfig,
 }
 
-fn save_number(folder_name: &str) -> Result<u16, ProgramError> {
-    let folder_name: Vec<&str> = folder_name.split('_').collect();
+const DEFAULT_SAVES_TO_PRESERVE: usize = 10;
+const SAVE_DIR_VARIABLE: &str = "BG3_SAVE_DIR";
+const DEFAULT_JOBS: usize = 4;
+const DEFAULT_BIG_DELETE_THRESHOLD: usize = 50;
 
-    if folder_name.len() <= 1 {
-        return Err(ProgramError::NotEnoughUnderscores(
-            "Did not find the correct number of underscores. Cannot continue with this save."
-                .to_string(),
-        ));
+/// Parses the command line. The shared options work before or after a subcommand, the clean
+/// options before one would be silently ignored and are refused instead.
+fn parse_program_config(
+    args: impl IntoIterator<Item = impl Into<OsString> + Clone>,
+) -> Result<ProgramConfig, clap::Error> {
+    let program_config = ProgramConfig::try_parse_from(args)?;
+    if program_config.command.is_some() && program_config.clean_config != CleanConfig::default() {
+        return Err(ProgramConfig::command()
+            .bin_name(env!("CARGO_BIN_NAME"))
+            .error(
+                ErrorKind::ArgumentConflict,
+                "clean options only work without a subcommand or after clean",
+            ));
     }
 
-    folder_name
-        .into_iter()
-        .last()
-        .ok_or(ProgramError::NotEnoughUnderscores(
-            "Could not find any elements".to_string(),
-        ))
-        .and_then(|save_number| {
-            save_number
-                .parse::<u16>()
-                .map_err(|e| ProgramError::StringNotNumber(e.to_string()))
-        })
-}
-
-fn group_saves(saves: Vec<SaveInformation>) -> HashMap<String, Saves> {
-    saves
-        .into_iter()
-        .fold(HashMap::new(), crate::group_by_character)
+    Ok(program_config)
 }
 
-fn group_by_character(
-    mut map: HashMap<String, Saves>,
-    save_information: SaveInformation,
-) -> HashMap<String, Saves> {
-    let saves = map
-        .entry(save_information.character_name.to_string())
-        .or_insert_with(Saves::new);
-
-    insert_save(saves, save_information);
+fn main() -> Result<(), ProgramError> {
+    let mut program_config = parse_program_config(std::env::args_os()).unwrap_or_else(|e| e.exit());
 
-    map
-}
+    // Writing the template must work even when the existing config file is broken.
+    if let Some(Command::Config {
+        action: ConfigAction::Init { force },
+    }) = &program_config.command
+    {
+        let path = program_config
+            .config
+            .clone()
+            .or_else(config_file::default_path)
+            .ok_or(ProgramError::NoPath(
+                "No default config location, pass one with --config".to_string(),
+            ));
+        return match path.and_then(|path| config_file::write_template(&path, *force).map(|_| path))
+        {
+            Ok(path) => {
+                outcome!("Wrote a config template to {}", path.display());
+                Ok(())
+            }
+            Err(e) => exit_with(&[Err(e)]),
+        };
+    }
 
-fn insert_save(save_by_type: &mut Saves, save_information: SaveInformation) {
-    match &save_information.save_type {
-        SaveType::Quick => save_by_type.quick_saves.push(save_information),
-        SaveType::Auto => save_by_type.auto_saves.push(save_information),
-        SaveType::Unrecognized => {}
+    let config_file = match config_file::load(program_config.config.as_deref()) {
+        Ok(config_file) => config_file,
+        Err(e) => return exit_with(&[Err(e)]),
     };
-}
+    apply_config_file(&mut program_config, &config_file);
+    // Set before any command runs, so list and stats read folder names like clean does.
+    folder_pattern::set_active(program_config.pattern.clone());
+    save_keywords::set_extra(&program_config.quick_keyword, &program_config.auto_keyword);
+    let mut command = program_config
+        .command
+        .unwrap_or(Command::Clean(Box::new(program_config.clean_config)));
+    if let Command::Clean(clean_config) = &mut command {
+        config_file.apply_to(clean_config);
+    }
 
-fn sort_map_saves(mut map: HashMap<String, Saves>) -> HashMap<String, Saves> {
-    map.values_mut().for_each(|value| {
-        value
-            .quick_saves
-            .sort_by(|save_a, save_b| save_b.save_number.partial_cmp(&save_a.save_number).unwrap());
+    color::set_enabled(color::should_enable(
+        program_config.no_color,
+        env::var_os("NO_COLOR"),
+        stdout().is_terminal(),
+    ));
 
-        value
-            .auto_saves
-            .sort_by(|save_a, save_b| save_b.save_number.partial_cmp(&save_a.save_number).unwrap())
-    });
+    // Completions describe the command line only, they never need a save folder.
+    if let Command::Completions { shell } = command {
+        let mut command = ProgramConfig::command().bin_name(env!("CARGO_BIN_NAME"));
+        command.build();
+        print!("{}", completions::generate(shell, &command));
+        return Ok(());
+    }
+    let environment = default_paths::Environment::current();
+    if program_config.list_detected_paths {
+        let detected = default_paths::list(&environment);
+        if detected.is_empty() {
+            println!("No save folder locations are known for this platform");
+        }
+        detected.iter().for_each(|line| println!("{}", line));
+        return Ok(());
+    }
+    if let Err(e) = confirmation::check_interactive(&command, stdin().is_terminal()) {
+        return exit_with(&[Err(e)]);
+    }
+    // A plan names the save folder it was written for, which is the only one it is applied to.
+    if let Command::Clean(clean_config) = &command {
+        if let Some(path) = &clean_config.apply_plan {
+            return exit_with(&[run_plan(
+                &command,
+                path,
+                clean_config,
+                program_config.wait,
+                program_config.verbose,
+            )]);
+        }
+    }
 
-    map
-}
+    let directories = match path_to_use(
+        program_config.path_to_save_folder,
+        env::var_os(SAVE_DIR_VARIABLE),
+        &config_file.path_to_save_folder,
+        &environment,
+    ) {
+        Ok(directories) => directories,
+        Err(e) => return exit_with(&[Err(e)]),
+    };
 
-fn get_delete_vec(map: HashMap<String, Saves>, number_to_preserve: usize) -> Vec<SaveInformation> {
-    map.into_iter()
-        .fold(Vec::new(), |deletion_saves, (_, character_saves)| {
-            deletion_saves
+    let results = match directories
+        .iter()
+        .map(|directory| {
+            let profile_directories = profiles::resolve(
+                directory,
+                program_config.profile.as_deref(),
+                program_config.all_profiles,
+            )?;
+            if program_config.profile.is_some()
+                || program_config.all_profiles
+                || profile_directories.len() < 2
+            {
+                return Ok(profile_directories);
+            }
+
+            let choices = profile_directories
                 .into_iter()
-                // Combine existing saves to be deleted with those detected deletable_saves.
-                // The grouping into a map is to apply number_to_preserve to each character as well as
-                // quick and auto saves for each character.
-                .chain(
-                    deletable_saves(character_saves.quick_saves, number_to_preserve)
-                        .into_iter()
-                        .chain(
-                            deletable_saves(character_saves.auto_saves, number_to_preserve)
-                                .into_iter(),
-                        ),
-                )
-                .collect()
+                .map(|profile_directory| profile_picker::Choice {
+                    saves: scan_saves(&profile_directory.path, &FolderFilter::default(), || ())
+                        .map(|scan_result| scan_result.saves.len())
+                        .unwrap_or_default(),
+                    profile: profile_directory,
+                })
+                .collect();
+            let can_ask = stdin().is_terminal()
+                && !matches!(&command, Command::Clean(clean_config) if clean_config.yes);
+            profile_picker::pick(choices, can_ask.then_some(read_answer))
         })
-}
+        .collect::<Result<Vec<Vec<ProfileDirectory>>, ProgramError>>()
+    {
+        Ok(profile_directories) => {
+            let profile_directories: Vec<ProfileDirectory> =
+                profile_directories.into_iter().flatten().collect();
+            let labelled = profile_directories.len() > 1;
+            let groups: Vec<Vec<ProfileDirectory>> =
+                if program_config.combine_paths && matches!(command, Command::Clean(_)) {
+                    vec![profile_directories]
+                } else {
+                    profile_directories
+                        .into_iter()
+                        .map(|profile_directory| vec![profile_directory])
+                        .collect()
+                };
 
-fn deletable_saves(saves: Vec<SaveInformation>, number_to_preserve: usize) -> Vec<SaveInformation> {
-    saves.into_iter().skip(number_to_preserve).collect()
-}
+            let single_folder_needed = match &command {
+                Command::Restore { .. } => {
+                    Some("Restore needs a single save folder, pick one with --profile")
+                }
+                Command::Clean(clean_config) if clean_config.watch => Some(
+                    "--watch needs a single save folder, pick one with --profile or add --combine-paths",
+                ),
+                _ => None,
+            };
 
-fn confirm_user_delete(deletable_saves: Vec<SaveInformation>) -> (Vec<SaveInformation>, String) {
-    println!("****");
-    deletable_saves
-        .iter()
-        .enumerate()
-        .for_each(|(i, save)| println!("\t{} | {}", i + 1, &save.file_name));
-    println!("****");
+            if let (Some(message), 2..) = (single_folder_needed, groups.len()) {
+                vec![Err(ProgramError::ProfileNotFound(message.to_string()))]
+            } else {
+                groups
+                    .into_iter()
+                    .map(|group| {
+                        if labelled && group.len() == 1 {
+                            info!("== {} ==", group[0].label());
+                        }
+                        let directories: Vec<PathBuf> = group
+                            .into_iter()
+                            .map(|profile_directory| profile_directory.path)
+                            .collect();
+                        if interrupt::is_interrupted() {
+                            return Err(ProgramError::Interrupted(format!(
+                                "Skipped {} after Ctrl+C",
+                                directories
+                                    .iter()
+                                    .map(|directory| directory.display().to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(", ")
+                            )));
+                        }
+                        // Held until the run is done, also when it panics.
+                        let _locks = lock_directories(&command, &directories, program_config.wait)?;
+                        finish_interrupted_removals(&command, &directories);
+                        run(&command, directories, program_config.verbose)
+                    })
+                    .collect()
+            }
+        }
+        Err(e) => vec![Err(e)],
+    };
 
-    print!("Delete the above files? y/n: ");
-    let _ = stdout().flush();
+    exit_with(&results)
+}
 
-    let mut user_input = String::new();
-    let _input = stdin().read_line(&mut user_input);
-    user_input = user_input.trim().to_string();
-    println!("User input read: {}", &user_input);
+/// Prints the errors of the runs and exits with the code they add up to.
+fn exit_with(results: &[Result<usize, ProgramError>]) -> Result<(), ProgramError> {
+    results.iter().for_each(|result| {
+        if let Err(e) = result {
+            eprintln!("{}", color::paint("Encountered error:", Color::Red));
+            eprintln!("{}", color::paint(&e.to_string(), Color::Red));
+        }
+    });
+    match exit_code::for_runs(results) {
+        exit_code::SUCCESS => Ok(()),
+        code => std::process::exit(code),
+    }
+}
 
-    (deletable_saves, user_input)
+/// Fills the global options not given on the command line with the values from the file,
+/// the save folders are picked by `path_to_use`.
+fn apply_config_file(program_config: &mut ProgramConfig, config_file: &ConfigFile) {
+    // Picking a profile on the command line overrides every profile option in the file.
+    if program_config.profile.is_none() && !program_config.all_profiles {
+        program_config.profile = config_file.profile.clone();
+        program_config.all_profiles = config_file.all_profiles && config_file.profile.is_none();
+    }
+    program_config.combine_paths |= config_file.combine_paths;
+    program_config.verbose |= config_file.verbose;
+    program_config.no_color |= config_file.no_color;
+    program_config.pattern = program_config
+        .pattern
+        .take()
+        .or(config_file.pattern.clone());
+    if program_config.quick_keyword.is_empty() {
+        program_config.quick_keyword = config_file.quick_keyword.clone();
+    }
+    if program_config.auto_keyword.is_empty() {
+        program_config.auto_keyword = config_file.auto_keyword.clone();
+    }
 }
 
-fn delete(
-    (deletable_saves, user_input, dir_to_use): (Vec<SaveInformation>, String, PathBuf),
-) -> Result<Vec<()>, ProgramError> {
-    if !user_input.eq_ignore_ascii_case("y") {
-        println!("User did not confirm delete");
+/// Locks the save folders against other instances when the command changes them.
+fn lock_directories(
+    command: &Command,
+    directories: &[PathBuf],
+    wait: bool,
+) -> Result<Vec<RunLock>, ProgramError> {
+    if !command.changes_saves() {
         return Ok(Vec::new());
     }
 
-    deletable_saves
-        .into_iter()
-        .map(move |save_information| {
-            let mut c = dir_to_use.clone().into_os_string();
-            c.push(format!("/{}", save_information.file_name));
-
-            c.into()
-        })
-        // Remove children in the directory and then remove the directory itself.
-        .map(|path: PathBuf| {
-            remove_children_of_dir(&path).and_then(|_| {
-                fs::remove_dir(path).map_err(|e| ProgramError::FailedToDelete(e.to_string()))
-            })
-        })
-        .collect::<Result<Vec<()>, ProgramError>>()
-}
-
-fn remove_children_of_dir(path: &impl AsRef<Path>) -> Result<Vec<()>, ProgramError> {
-    fs::read_dir(path)
-        .map_err(|e| ProgramError::FailedToReadDir(e.to_string()))
-        .and_then(|children| {
-            children
-                .flatten()
-                .map(|child| child.path())
-                .map(|child_path: PathBuf| {
-                    fs::remove_file(child_path)
-                        .map_err(|e| ProgramError::FailedToDelete(e.to_string()))
-                })
-                .collect::<Result<Vec<()>, ProgramError>>() // TODO: Review. This is going to drop some errors silently.
+    // A missing folder is reported by the run itself.
+    directories
+        .iter()
+        .filter(|directory| directory.is_dir())
+        .map(|directory| match wait {
+            true => RunLock::wait(directory),
+            false => RunLock::acquire(directory),
         })
+        .collect()
 }
 
-#[cfg(test)]
-mod package_details_should {
-    use rand::Rng;
+/// Removes the folders a run that was killed while deleting left staged, only while holding
+/// the lock so another instance's staged folders are left alone.
+fn finish_interrupted_removals(command: &Command, directories: &[PathBuf]) {
+    if !command.changes_saves() {
+        return;
+    }
 
-    use crate::{package_details, SaveInformation, SaveType};
+    directories.iter().for_each(|directory| {
+        let (removed, failed) = staged_removal::finish_leftovers(directory);
+        if !removed.is_empty() {
+            info!(
+                "Finished removing {} folder(s) left by an interrupted run",
+                removed.len()
+            );
+        }
+        failed.iter().for_each(|(_, e)| {
+            eprintln!(
+                "Warning: could not remove a folder left by an interrupted run: {}",
+                e
+            )
+        });
+    });
+}
 
-    #[test]
-    fn package_values_returned() {
-        let rand = rand::thread_rng().gen_range(u16::MIN..=u16::MAX);
-        let test_save = format!("Some'me-1231415123_QuickSave_{}", rand);
+/// Applies the plan at `path` once, holding the lock of the save folder it was written for.
+fn run_plan(
+    command: &Command,
+    path: &Path,
+    clean_config: &CleanConfig,
+    wait: bool,
+    verbose: bool,
+) -> Result<usize, ProgramError> {
+    let root = vec![plan::read_plan(path)?.root];
+    let _locks = lock_directories(command, &root, wait)?;
+    finish_interrupted_removals(command, &root);
 
-        let expected = SaveInformation::new(
-            test_save.clone(),
-            "Some'me".to_string(),
-            SaveType::Quick,
-            rand,
-        );
+    clean(root, clean_config, verbose).map(|summary| summary.failed)
+}
 
-        let result = package_details(test_save.as_str()).unwrap();
-        assert_eq!(result, expected);
+/// Runs the command, only cleaning treats several directories as one, the others get one.
+/// Returns the number of folders that failed, for the exit code.
+fn run(command: &Command, directories: Vec<PathBuf>, verbose: bool) -> Result<usize, ProgramError> {
+    if let Command::Clean(clean_config) = command {
+        if clean_config.watch {
+            return watch::watch(&directories, clean_config.interval, || {
+                clean(directories.clone(), clean_config, verbose)
+            })
+            .map(|session| {
+                outcome!("{}", session.format());
+                session.failed
+            });
+        }
+        return clean(directories, clean_config, verbose).map(|summary| summary.failed);
     }
+    let directory = directories[0].clone();
 
-    #[test]
-    fn errors_out_when_error_state_occurs() {
-        let test_save = "Some'me";
-
-        let result = package_details(test_save);
-        assert!(
-            result.is_err(),
-            "Package did not error when it was provided insufficient information"
-        );
+    match command {
+        Command::Clean(_) => Ok(0),
+        Command::List { campaign } => load_saves(&[directory], verbose)
+            .and_then(|map| filter_campaign(map, campaign.as_deref()))
+            .map(|map| print!("{}", inventory::format_inventory(&map)))
+            .map(|_| 0),
+        Command::Stats { bytes } => load_saves(&[directory], verbose)
+            .map(|map| print!("{}", stats::format_stats(&map, *bytes)))
+            .map(|_| 0),
+        Command::Restore {
+            quarantine_dir,
+            all,
+        } => restore(&directory, quarantine_dir, *all).map(|_| 0),
+        Command::Undo => undo(&directory),
+        Command::Completions { .. } | Command::Config { .. } => Ok(0),
+        Command::Purge => purge_undo_area(&directory).map(|(purged, reclaimed)| {
+            outcome!(
+                "Permanently removed {} folder(s) kept for undo, reclaimed {}",
+                purged,
+                format_size(reclaimed)
+            );
+            0
+        }),
     }
 }
 
-#[cfg(test)]
-mod save_type_should {
-    use crate::{save_type, SaveType};
-
-    #[test]
-    fn detect_quick_save() {
-        let test_save_type: &str = "QuickSave";
-        let save = format!("Some Name-1231415123_{}_277", test_save_type);
-
-        let save_type = save_type(save.as_str());
-        assert_eq!(save_type, SaveType::Quick);
+fn clean(
+    directories: Vec<PathBuf>,
+    clean_config: &CleanConfig,
+    verbose: bool,
+) -> Result<RunSummary, ProgramError> {
+    output::set_quiet(clean_config.quiet);
+    output::set_machine_readable(clean_config.output != OutputFormat::Text);
+    retry::set_retries(clean_config.retries.unwrap_or(retry::DEFAULT_RETRIES));
+    size_format::set_exact(clean_config.bytes);
+    if let Some(path) = &clean_config.apply_plan {
+        return apply_plan(path, clean_config, &directories[0]);
+    }
+    if clean_config.from_stdin {
+        let mut input = String::new();
+        stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| ProgramError::FailedToReadFile(format!("stdin: {}", e)))?;
+        return delete_listed(
+            folder_list::parse(&input),
+            directories[0].clone(),
+            clean_config,
+        );
     }
 
-    #[test]
-    fn detect_auto_save() {
-        let test_save_type: &str = "AutoSave";
-        let save = format!("Some Name-1231415123_{}_277", test_save_type);
+    let saves_to_preserve = clean_config
+        .saves_to_preserve
+        .unwrap_or(DEFAULT_SAVES_TO_PRESERVE);
 
-        let save_type = save_type(save.as_str());
-        assert_eq!(save_type, SaveType::Auto);
-    }
+    info!(
+        "Running program with saves_to_preserve: {} and path: {}",
+        &saves_to_preserve,
+        directories
+            .iter()
+            .map(|directory| directory.display().to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    // The first directory holds the undo area and is the root recorded in plans.
+    let directory = directories[0].clone();
 
-    #[test]
-    fn detect_unrecognized() {
-        let save = "Some Name-ManualSave";
+    let mut grouped_saves = HashMap::new();
+    let mut discovered = (0, 0);
+    let mut scanned = 0;
+    let mut clutter = Clutter::default();
 
-        let save_type = save_type(save);
-        assert_eq!(save_type, SaveType::Unrecognized);
+    let exclude_list = clean_config
+        .exclude_from
+        .as_deref()
+        .map(ExcludeList::load)
+        .transpose()?;
+    if !clean_config.force_path {
+        refuse_unlikely_save_folders(
+            &directories,
+            clean_config
+                .min_save_percent
+                .unwrap_or(save_root::DEFAULT_MIN_SAVE_PERCENT),
+        )?;
     }
-}
 
-#[cfg(test)]
-mod character_name_should {
-    use crate::{character_name, ProgramError};
+    let ignore_list = IgnoreList::new(&clean_config.ignore).map_err(ProgramError::InvalidConfig)?;
+    let folder_filter = FolderFilter {
+        include: clean_config.match_regex.as_ref(),
+        exclude: &clean_config.exclude,
+        exclude_list: exclude_list.as_ref(),
+        ignore_list: Some(&ignore_list),
+        follow_symlinks: clean_config.follow_symlinks,
+    };
+    scan_directories(&directories, &folder_filter)
+        .and_then(|scan_result| {
+            report_ignored(&scan_result.ignored, verbose);
+            report_filtered_folders(&folder_filter, &scan_result.filtered_folders, verbose);
+            if let Some(exclude_list) = &exclude_list {
+                report_stale_entries(exclude_list, &scan_result);
+            }
+            if clean_config.strict {
+                refuse_unparsed_folders(&scan_result.skipped_folders)?;
+            }
+            print_unparsed_folders(&scan_result.skipped_folders);
+            report_symlinks(&scan_result.symlinks);
+            clutter = Clutter {
+                empty_folders: select_empty_folders(
+                    &scan_result.empty_folders,
+                    clean_config,
+                    SystemTime::now(),
+                ),
+                loose_files: select_loose_files(
+                    &scan_result.loose_files,
+                    exclude_list.as_ref(),
+                    clean_config,
+                ),
+            };
+            Ok(scan_result.saves)
+        })
+        .map(|saves| match clean_config.read_metadata {
+            true => read_metadata(saves, &directory, verbose),
+            false => saves,
+        })
+        .map(crate::group_saves)
+        .map(|map| sort_map_saves(map, clean_config.sort_by))
+        .map(|map| {
+            resolve_counter_resets(
+                map,
+                clean_config.sort_by,
+                clean_config.prefer_mtime_on_conflict,
+            )
+        })
+        .inspect(|map| scanned = count_saves(map))
+        .and_then(|map| filter_characters(map, &clean_config.character))
+        .and_then(|map| filter_campaign(map, clean_config.campaign.as_deref()))
+        .map(|map| {
+            let (map, skipped_saves, unmatched_characters) =
+                exclude_characters(map, &clean_config.exclude_character);
 
-    #[test]
-    fn detect_with_space() {
-        let test_save = "Some Name-1231415123_{}_277";
-        let expected = "Some Name";
+            unmatched_characters.iter().for_each(|character| {
+                eprintln!(
+                    "Warning: excluded character {} did not match any discovered character",
+                    character
+                )
+            });
+            if !clean_config.exclude_character.is_empty() {
+                info!("Skipped {} save(s) of excluded characters", skipped_saves);
+            }
 
-        let name = character_name(test_save).unwrap();
-        assert_eq!(name, expected);
-    }
+            map
+        })
+        .map(|map| measure_sizes(map, &directory))
+        .map(|map| {
+            if clean_config.output == OutputFormat::Json || clean_config.export_csv.is_some() {
+                grouped_saves = map.clone();
+            }
 
-    #[test]
-    fn detect_with_underscore() {
-        let test_save = "Some_Name-1231415123_{}_277";
-        let expected = "Some_Name";
+            unmatched_preserve_for(&map, &clean_config.preserve_for)
+                .iter()
+                .for_each(|character| {
+                    eprintln!(
+                        "Warning: --preserve-for character {} did not match any discovered character, found: {}",
+                        character,
+                        discovered_characters(&map).join(", ")
+                    )
+                });
 
-        let name = character_name(test_save).unwrap();
-        assert_eq!(name, expected);
-    }
+            if !clean_config.clean_corrupt {
+                report_corrupt_saves(&map);
+            }
+            if !clean_config.clean_copies {
+                report_copies(&map);
+            }
+            if !clean_config.include_honour {
+                report_honour_saves(&map);
+            }
 
-    #[test]
-    fn detect_single_name() {
-        let test_save = "SomeName-1231415123_{}_277";
-        let expected = "SomeName";
+            discovered = (
+                map.values().map(Saves::count).sum(),
+                discovered_characters(&map).len(),
+            );
+            let (deletable_saves, guarded_saves) =
+                get_delete_vec(map, saves_to_preserve, clean_config);
+            if guarded_saves > 0 {
+                info!(
+                    "Kept the newest save of {} character and save type combination(s) that would have lost every save, use --allow-delete-all to delete them",
+                    guarded_saves
+                );
+            }
 
-        let name = character_name(test_save).unwrap();
-        assert_eq!(name, expected);
-    }
+            deletable_saves
+        })
+        .map(|deletable_saves| {
+            retain_older_than(deletable_saves, clean_config.older_than, SystemTime::now())
+        })
+        .map(|deletable_saves| {
+            let Some(hours) = clean_config.keep_recent else {
+                return deletable_saves;
+            };
 
-    #[test]
-    fn detect_with_apostrophe() {
-        let test_save = "Some'me-1231415123_{}_277";
-        let expected = "Some'me";
+            let (deletable_saves, rescued_saves) = rescue_recent_saves(
+                deletable_saves,
+                Duration::from_secs(hours.saturating_mul(60 * 60)),
+                SystemTime::now(),
+            );
+            info!(
+                "Kept {} save(s) modified within the last {} hour(s)",
+                rescued_saves, hours
+            );
 
-        let name = character_name(test_save).unwrap();
-        assert_eq!(name, expected);
-    }
+            deletable_saves
+        })
+        .map(|deletable_saves| {
+            if !clean_config.watch {
+                return deletable_saves;
+            }
 
-    #[test]
-    fn error_when_no_dashes() {
-        let test_save = "Some'me";
-        let expected = ProgramError::NameNotDetected("Could not detect character name".to_string());
+            let (deletable_saves, rescued_saves) =
+                rescue_recent_saves(deletable_saves, watch::SETTLE_TIME, SystemTime::now());
+            if rescued_saves > 0 {
+                info!(
+                    "Kept {} save(s) modified within the last {} minute(s), the game may still be writing them",
+                    rescued_saves,
+                    watch::SETTLE_TIME.as_secs() / 60
+                );
+            }
 
-        let error = character_name(test_save).unwrap_err();
-        assert_eq!(error, expected);
-    }
-}
+            deletable_saves
+        })
+        .and_then(|deletable_saves| {
+            if let Some(only) = &clean_config.only {
+                info!("Filter active: only {} are considered for deletion", only);
+            }
+            if let Some(thin) = clean_config.thin {
+                info!(
+                    "Thinning active: keeping every {} save beyond the newest {}",
+                    ordinal(thin),
+                    saves_to_preserve
+                );
+            }
 
-#[cfg(test)]
-mod save_number_should {
-    use rand::Rng;
+            if let Some(path) = &clean_config.export_csv {
+                csv_export::write_csv(path, &grouped_saves, &deletable_saves)?;
+                info!("Wrote save inventory to {}", path.display());
+            }
 
-    use crate::{save_number, ProgramError};
+            if let Some(path) = &clean_config.write_plan {
+                plan::write_plan(path, &Plan::new(directory.clone(), deletable_saves.clone()))?;
+                outcome!(
+                    "Wrote a plan for {} folder(s) to {}",
+                    deletable_saves.len(),
+                    path.display()
+                );
+                return Ok((deletable_saves, DeletionReport::default()));
+            }
+            if deletable_saves.is_empty() && clutter.is_empty() {
+                outcome!(
+                    "Nothing to delete, all characters are within the preserve limits (kept {} save(s) across {} character(s))",
+                    discovered.0,
+                    discovered.1
+                );
+                return Ok((Vec::new(), DeletionReport::default()));
+            }
+            let max_delete_percent = clean_config
+                .max_delete_percent
+                .unwrap_or(big_delete::DEFAULT_MAX_DELETE_PERCENT);
+            if !clean_config.allow_mass_delete
+                && big_delete::is_mass_delete(deletable_saves.len(), discovered.0, max_delete_percent)
+            {
+                let refusal = format!(
+                    "This would delete {} of {} save(s), more than {}%. Check the options and pass --allow-mass-delete or a higher --max-delete-percent to go ahead",
+                    deletable_saves.len(),
+                    discovered.0,
+                    max_delete_percent
+                );
+                // A dry run still shows the plan, but not as if a real run would carry it out.
+                if !clean_config.dry_run {
+                    return Err(ProgramError::MassDelete(refusal));
+                }
+                eprintln!("Warning: a real run would refuse: {}", refusal);
+            }
 
-    #[test]
-    fn convert_max_number() {
-        let test_save = format!("Some'me-1231415123_QuickSave_{}", u16::MAX);
+            execute_deletion(
+                deletable_saves,
+                std::mem::take(&mut clutter),
+                clean_config,
+                directory,
+            )
+        })
+        .map(|(planned, report)| {
+            report_run(
+                clean_config,
+                grouped_saves,
+                RunSummary::new(
+                    scanned,
+                    &planned,
+                    &report.deleted,
+                    report.failed.len(),
+                    clean_config.dry_run || clean_config.write_plan.is_some(),
+                )
+                .with_freed_space(deletion_strategy(clean_config).freed_space()),
+                planned,
+                report,
+            )
+        })
+}
 
-        let result = save_number(&test_save).unwrap();
-        assert_eq!(result, u16::MAX);
+/// Deletes the saves of the plan at `path`, which has to be written for the locked `directory`.
+fn apply_plan(
+    path: &Path,
+    clean_config: &CleanConfig,
+    directory: &Path,
+) -> Result<RunSummary, ProgramError> {
+    let (plan, vanished_saves) = plan::read_plan(path)?.split_vanished();
+    if plan.root != directory {
+        return Err(ProgramError::InvalidPlan(format!(
+            "the plan is for {}, but {} is the save folder locked for it",
+            plan.root.display(),
+            directory.display()
+        )));
     }
+    let scanned = plan.saves.len() + vanished_saves.len();
+
+    if plan.version != env!("CARGO_PKG_VERSION") {
+        eprintln!(
+            "Warning: plan was written by version {}, this is version {}",
+            plan.version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    vanished_saves.iter().for_each(|save_information| {
+        eprintln!(
+            "Warning: {} no longer exists and is skipped",
+            save_information.file_name
+        )
+    });
+    info!(
+        "Applying plan {} against path: {}",
+        path.display(),
+        plan.root.display()
+    );
+
+    execute_deletion(plan.saves, Clutter::default(), clean_config, plan.root).map(
+        |(planned, report)| {
+            report_run(
+                clean_config,
+                HashMap::new(),
+                RunSummary::new(
+                    scanned,
+                    &planned,
+                    &report.deleted,
+                    report.failed.len(),
+                    clean_config.dry_run,
+                )
+                .with_freed_space(deletion_strategy(clean_config).freed_space()),
+                planned,
+                report,
+            )
+        },
+    )
+}
+
+/// Printed before asking, as deleting synced saves also deletes them on every other computer
+/// and a computer that is out of sync can bring them back.
+fn warn_about_steam_cloud(deletable_saves: &[SaveInformation], directory: &Path) {
+    let steam_roots = default_paths::steam_roots(&default_paths::Environment::current());
+    let mut roots: Vec<&Path> = deletable_saves
+        .iter()
+        .map(|save_information| save_information.root_or(directory))
+        .collect();
+    roots.sort();
+    roots.dedup();
+
+    roots
+        .into_iter()
+        .filter_map(|root| {
+            steam_cloud::sync_reason(root, &steam_roots).map(|reason| (root, reason))
+        })
+        .for_each(|(root, reason)| {
+            eprintln!(
+                "{}",
+                color::paint(
+                    &format!(
+                        "Steam Cloud likely syncs {}, {}.\n  Deleted saves are also deleted from the cloud and from every computer that syncs with it.\n  A computer that is out of sync can upload them again. Pass --no-cloud-warning to hide this",
+                        root.display(),
+                        reason
+                    ),
+                    Color::Yellow
+                )
+            )
+        });
+}
+
+fn warn_about_cloud_saves(deletable_saves: &[SaveInformation]) {
+    let cloud_saves = deletable_saves
+        .iter()
+        .filter(|save_information| save_information.cloud_only)
+        .count();
+    if cloud_saves > 0 {
+        eprintln!(
+            "Warning: {} of the listed save(s) have online-only files, deleting them also deletes them from the cloud",
+            cloud_saves
+        );
+    }
+}
+
+/// Deletes exactly the folders named with --from-stdin, unknown names stop the run unless
+/// --skip-unknown is given.
+fn delete_listed(
+    names: Vec<String>,
+    directory: PathBuf,
+    clean_config: &CleanConfig,
+) -> Result<RunSummary, ProgramError> {
+    let scanned = names.len();
+    let (known, unknown) = folder_list::split_unknown(names, &directory);
+    if !unknown.is_empty() {
+        let message = format!(
+            "No save folder named {} in {}",
+            unknown.join(", "),
+            directory.display()
+        );
+        if !clean_config.skip_unknown {
+            return Err(ProgramError::UnknownFolders(format!(
+                "{}, nothing is deleted. Pass --skip-unknown to delete the others",
+                message
+            )));
+        }
+        eprintln!("Warning: {}", message);
+    }
+
+    // Listed folders are deleted even if their name can't be parsed, they are only shown as is.
+    let listed_saves: Vec<SaveInformation> = known
+        .into_iter()
+        .map(|name| {
+            let size_bytes = folder_size(&directory.join(&name));
+            package_details(&name)
+                .unwrap_or_else(|_| {
+                    SaveInformation::new(name.clone(), name, SaveType::Unrecognized, None)
+                })
+                .with_size_bytes(size_bytes)
+        })
+        .collect();
+    info!(
+        "Deleting the folders listed on stdin from path: {}",
+        directory.display()
+    );
+
+    execute_deletion(listed_saves, Clutter::default(), clean_config, directory).map(
+        |(planned, report)| {
+            report_run(
+                clean_config,
+                HashMap::new(),
+                RunSummary::new(
+                    scanned,
+                    &planned,
+                    &report.deleted,
+                    report.failed.len(),
+                    clean_config.dry_run,
+                )
+                .with_freed_space(deletion_strategy(clean_config).freed_space()),
+                planned,
+                report,
+            )
+        },
+    )
+}
+
+/// Deletes the given saves and clutter honouring --dry-run and --yes, returns the planned saves
+/// and what happened to them. Nothing is asked when there is nothing to delete.
+fn execute_deletion(
+    deletable_saves: Vec<SaveInformation>,
+    clutter: Clutter,
+    clean_config: &CleanConfig,
+    directory: PathBuf,
+) -> Result<(Vec<SaveInformation>, DeletionReport), ProgramError> {
+    if deletable_saves.is_empty() && clutter.is_empty() {
+        outcome!("Nothing to delete");
+        return Ok((Vec::new(), DeletionReport::default()));
+    }
+    warn_about_cloud_saves(&deletable_saves);
+    if clean_config.dry_run {
+        dry_run(&deletable_saves);
+        if !clutter.is_empty() {
+            info!(
+                "Would delete {} empty folder(s) and {} loose file(s)",
+                clutter.empty_folders.len(),
+                clutter.loose_files.len()
+            );
+        }
+        return Ok((deletable_saves, DeletionReport::default()));
+    }
+    // Watching is meant for while the game runs, it protects recent saves instead.
+    if !clean_config.allow_while_running && !clean_config.watch {
+        if let Some(process_name) = game_process::running_game_process() {
+            return Err(ProgramError::GameRunning(format!(
+                "Baldur's Gate 3 is running ({}), close it before deleting saves or pass --allow-while-running",
+                process_name
+            )));
+        }
+    }
+    if !clean_config.no_cloud_warning {
+        warn_about_steam_cloud(&deletable_saves, &directory);
+    }
+
+    let big_delete_threshold = clean_config
+        .big_delete_threshold
+        .unwrap_or(DEFAULT_BIG_DELETE_THRESHOLD);
+    let (deletable_saves, user_input) = if deletable_saves.is_empty() {
+        // Only clutter, which is asked about on its own.
+        (deletable_saves, "y".to_string())
+    } else if clean_config.yes || clean_config.watch {
+        skip_confirmation(deletable_saves)
+    } else if clean_config.per_character {
+        // The answers per character only pick the saves, the typed word still confirms the total.
+        match confirm_per_character(deletable_saves) {
+            (selected_saves, _)
+                if big_delete::needs_typed_confirmation(
+                    selected_saves.len(),
+                    big_delete_threshold,
+                ) =>
+            {
+                confirm_big_delete(selected_saves, read_answer)
+            }
+            confirmed => confirmed,
+        }
+    } else {
+        confirm_user_delete(deletable_saves, big_delete_threshold, read_answer)
+    };
+    // Clutter goes with the saves, never when they weren't confirmed.
+    let clutter = match user_input.eq_ignore_ascii_case("y") {
+        true => confirm_clutter(clutter, clean_config, read_answer),
+        false => Clutter::default(),
+    };
+    let mut audit_log = if user_input.eq_ignore_ascii_case("y") {
+        AuditLog::open(clean_config.log_file.as_deref())
+    } else {
+        AuditLog::disabled()
+    };
+    audit_log.header(&format!("{:?}", clean_config));
+
+    let backup_dir = clean_config
+        .backup_dir
+        .as_deref()
+        .filter(|_| !clean_config.no_backup);
+    let strategy = deletion_strategy(clean_config);
+
+    delete(
+        (deletable_saves.clone(), user_input, directory),
+        &clutter,
+        &strategy,
+        backup_dir,
+        !clean_config.no_verify,
+        clean_config.jobs.unwrap_or(DEFAULT_JOBS),
+        &mut audit_log,
+    )
+    .map(|report| {
+        if !deletable_saves.is_empty() {
+            outcome!(
+                "{}",
+                strategy.summary(report.deleted.len(), report.failed.len())
+            );
+        }
+        if !report.failed.is_empty() {
+            eprintln!("Could not delete {} folder(s):", report.failed.len());
+            report
+                .format_failures()
+                .iter()
+                .for_each(|failure| eprintln!("  {}", failure));
+        }
+        if strategy == DeletionStrategy::Remove && !report.deleted.is_empty() {
+            info!("Run bg3sc undo to bring them back, they are removed for good by the next run");
+        }
+
+        (deletable_saves, report)
+    })
+}
+
+fn deletion_strategy(clean_config: &CleanConfig) -> DeletionStrategy {
+    match (&clean_config.quarantine, &clean_config.archive) {
+        (Some(quarantine_dir), _) => DeletionStrategy::Quarantine(quarantine_dir.clone()),
+        (None, Some(archive_path)) => DeletionStrategy::Archive(archive_path.clone()),
+        (None, None) if clean_config.trash => DeletionStrategy::Trash,
+        (None, None) => DeletionStrategy::Remove,
+    }
+}
+
+/// Prints the summary of the run, as part of the JSON report with `--output json`.
+fn report_run(
+    clean_config: &CleanConfig,
+    saves: HashMap<CharacterKey, Saves>,
+    summary: RunSummary,
+    planned: Vec<SaveInformation>,
+    deletion: DeletionReport,
+) -> RunSummary {
+    // The deleted and failed counts repeat the outcome line, which is all `--quiet` keeps.
+    info!("{}", summary.format());
+    if clean_config.output == OutputFormat::Json {
+        let json_report = JsonReport {
+            saves,
+            planned,
+            deletion,
+            summary,
+        };
+        println!("{}", json_report.to_json());
+    }
+
+    summary
+}
+
+fn count_saves(map: &HashMap<CharacterKey, Saves>) -> usize {
+    map.values().map(Saves::count).sum()
+}
+
+fn restore(directory: &Path, quarantine_dir: &Path, all: bool) -> Result<(), ProgramError> {
+    let folders = quarantine::quarantined_folders(quarantine_dir)?;
+    if folders.is_empty() {
+        outcome!("Nothing to restore in {}", quarantine_dir.display());
+        return Ok(());
+    }
+
+    info!("****");
+    folders
+        .iter()
+        .enumerate()
+        .for_each(|(i, folder)| info!("\t{} | {}", i + 1, folder));
+    info!("****");
+
+    let selected_folders = if all {
+        folders
+    } else {
+        prompt_selection(
+            folders,
+            "Restore the above folders? y/n, or a selection like 1,4-7 or all except 2:",
+            read_answer,
+        )
+    };
+
+    let (restored, failed) =
+        selected_folders.iter().fold(
+            (0, 0),
+            |(restored, failed), folder| match quarantine::restore(
+                quarantine_dir,
+                folder,
+                directory,
+            ) {
+                Ok(_) => (restored + 1, failed),
+                Err(e) => {
+                    eprintln!("Failed to restore {}: {}", folder, e);
+                    (restored, failed + 1)
+                }
+            },
+        );
+    outcome!("Restored {} folder(s), failed {}", restored, failed);
+
+    Ok(())
+}
+
+/// Picks the save folders from the command line, then `BG3_SAVE_DIR`, then the config file,
+/// falling back to the folder the game saves to. Never the current directory, running from the
+/// wrong folder shouldn't delete whatever looks like a save there.
+fn path_to_use(
+    given_paths: Vec<OsString>,
+    env_dir: Option<OsString>,
+    config_paths: &[PathBuf],
+    environment: &default_paths::Environment,
+) -> Result<Vec<PathBuf>, ProgramError> {
+    if !given_paths.is_empty() {
+        return Ok(given_paths.into_iter().map(PathBuf::from).collect());
+    }
+
+    match env_dir.filter(|env_dir| !env_dir.is_empty()) {
+        Some(env_dir) if PathBuf::from(&env_dir).is_dir() => Ok(vec![PathBuf::from(env_dir)]),
+        Some(env_dir) => Err(ProgramError::NoPath(format!(
+            "{} is set to {}, which is not an existing directory",
+            SAVE_DIR_VARIABLE,
+            env_dir.to_string_lossy()
+        ))),
+        None if !config_paths.is_empty() => Ok(config_paths.to_vec()),
+        None => {
+            let detected = default_paths::detect(environment)?;
+            // On stderr, so it stays out of machine readable output.
+            eprintln!(
+                "Using the save folder found through {}: {}",
+                detected.found_by,
+                detected.path.display()
+            );
+            Ok(vec![detected.path])
+        }
+    }
+}
+
+/// Scans, groups and sorts the saves in `directories`, shared by every command.
+fn load_saves(
+    directories: &[PathBuf],
+    verbose: bool,
+) -> Result<HashMap<CharacterKey, Saves>, ProgramError> {
+    let ignore_list = IgnoreList::default();
+    let folder_filter = FolderFilter {
+        ignore_list: Some(&ignore_list),
+        ..Default::default()
+    };
+    scan_directories(directories, &folder_filter)
+        .map(|scan_result| {
+            report_skipped_folders(&scan_result.skipped_folders, verbose);
+            scan_result.saves
+        })
+        .map(crate::group_saves) // Here errors start to matter for the set, don't drop and output below.
+        .map(|map| measure_sizes(map, &directories[0]))
+        .map(|map| sort_map_saves(map, SortBy::Number))
+}
+
+/// Sums the files of every save folder. Done after filtering rather than while scanning so
+/// characters that are filtered out are never walked, which is slow on network drives.
+fn measure_sizes(
+    map: HashMap<CharacterKey, Saves>,
+    directory: &Path,
+) -> HashMap<CharacterKey, Saves> {
+    map.into_iter()
+        .map(|(key, saves)| {
+            let saves = saves.map_each(|save_information| {
+                let size_bytes = folder_size(
+                    &save_information
+                        .root_or(directory)
+                        .join(&save_information.folder_name),
+                );
+                save_information.with_size_bytes(size_bytes)
+            });
+            (key, saves)
+        })
+        .collect()
+}
+
+/// Replaces what was parsed from the folder names with the metadata of each save, keeping the
+/// parsed fields of saves whose metadata can't be read.
+fn read_metadata(
+    saves: Vec<SaveInformation>,
+    directory: &Path,
+    verbose: bool,
+) -> Vec<SaveInformation> {
+    let mut unreadable = Vec::new();
+    let saves: Vec<SaveInformation> = saves
+        .into_iter()
+        .map(|save_information| {
+            let save_folder = save_information
+                .root_or(directory)
+                .join(&save_information.folder_name);
+            match SaveMetadata::read(&save_folder) {
+                Ok(metadata) => metadata.apply_to(save_information),
+                Err(e) => {
+                    unreadable.push((save_information.file_name.clone(), e));
+                    save_information
+                }
+            }
+        })
+        .collect();
+
+    info!(
+        "Read the metadata of {} save(s), using the folder name for {}",
+        saves.len() - unreadable.len(),
+        unreadable.len()
+    );
+    if verbose {
+        unreadable
+            .iter()
+            .for_each(|(folder_name, e)| info!("\t{} | {}", folder_name, e));
+    }
+
+    saves
+}
+
+/// Scans every directory into one result, remembering the root of each save.
+fn scan_directories(
+    directories: &[PathBuf],
+    folder_filter: &FolderFilter,
+) -> Result<ScanResult, ProgramError> {
+    let mut spinner = Spinner::new(
+        "folders",
+        ProgressStyle::pick(stdout().is_terminal(), output::is_quiet()),
+        stdout(),
+    );
+
+    directories
+        .iter()
+        .try_fold(ScanResult::default(), |mut combined, directory| {
+            let scan_result = scan_saves(directory, folder_filter, || spinner.tick());
+            spinner.finish();
+
+            scan_result.map(|scan_result| {
+                combined.saves.extend(
+                    scan_result
+                        .saves
+                        .into_iter()
+                        .map(|save_information| save_information.with_root(directory)),
+                );
+                combined.skipped_folders.extend(scan_result.skipped_folders);
+                combined
+                    .filtered_folders
+                    .extend(scan_result.filtered_folders);
+                combined.empty_folders.extend(scan_result.empty_folders);
+                combined.loose_files.extend(scan_result.loose_files);
+                combined.ignored.extend(scan_result.ignored);
+                combined.symlinks.extend(scan_result.symlinks);
+                combined
+            })
+        })
+}
+
+/// Scans `directory`, calling `on_entry` for every entry examined so progress can be shown.
+fn scan_saves(
+    directory: &Path,
+    folder_filter: &FolderFilter,
+    mut on_entry: impl FnMut(),
+) -> Result<ScanResult, ProgramError> {
+    let dir_entries =
+        fs::read_dir(directory).map_err(|e| ProgramError::CannotReadDirectory(e.to_string()))?;
+    let mut scan_result = ScanResult::default();
+
+    for dir_entry in dir_entries.flatten() {
+        on_entry();
+
+        let Ok(file_type) = dir_entry.file_type() else {
+            continue;
+        };
+        // The undo area is hidden as well, but it's ours and not worth reporting.
+        if dir_entry.file_name() == undo::UNDO_DIR_NAME {
+            continue;
+        }
+        if let Some(glob) = folder_filter.ignore_list.and_then(|ignore_list| {
+            ignore_list.matching_entry(&dir_entry.file_name().to_string_lossy())
+        }) {
+            scan_result.ignored.push((
+                dir_entry.file_name().to_string_lossy().to_string(),
+                glob.to_string(),
+            ));
+            continue;
+        }
+        if file_type.is_file() {
+            scan_result.loose_files.push(dir_entry.path());
+            continue;
+        }
+        // Deleting through a symlink could reach another drive, they are only listed by default.
+        let is_symlinked_folder = file_type.is_symlink() && dir_entry.path().is_dir();
+        if is_symlinked_folder && !folder_filter.follow_symlinks {
+            scan_result.symlinks.push(dir_entry.path());
+            continue;
+        }
+        // Skip other entries and empty string folders.
+        if !(file_type.is_dir() || is_symlinked_folder) || dir_entry.file_name().is_empty() {
+            continue;
+        }
+        if let Some(reason) = folder_filter.reason_to_skip(&dir_entry.file_name().to_string_lossy())
+        {
+            scan_result
+                .filtered_folders
+                .push((dir_entry.file_name().to_string_lossy().to_string(), reason));
+            continue;
+        }
+
+        if empty_folder::is_empty(&dir_entry.path()) {
+            scan_result.empty_folders.push(dir_entry.path());
+            continue;
+        }
+
+        // Errors only affect individual folders, keep them for reporting and continue.
+        match scan_folder(&dir_entry) {
+            Ok(save_information) => scan_result.saves.push(save_information),
+            Err(skipped_folder) => scan_result.skipped_folders.push(skipped_folder),
+        };
+    }
+
+    Ok(scan_result)
+}
+
+fn scan_folder(dir_entry: &DirEntry) -> Result<SaveInformation, (String, ProgramError)> {
+    let folder_name = dir_entry.file_name();
+
+    // Names that aren't valid UTF-8 are parsed from a lossy copy, the exact name is kept to
+    // delete the folder.
+    crate::package_details(&folder_name.to_string_lossy())
+        .map(|save_information| {
+            save_information
+                // Follows symlinks, a linked save has the modified time of the folder it links to.
+                .with_modified(
+                    fs::metadata(dir_entry.path())
+                        .and_then(|metadata| metadata.modified())
+                        .ok(),
+                )
+                .with_corrupt(corrupt::is_corrupt(&dir_entry.path()))
+                .with_cloud_only(cloud_placeholder::has_placeholders(&dir_entry.path()))
+                .with_folder_name(folder_name.clone())
+        })
+        .map_err(|e| (folder_name.to_string_lossy().to_string(), e))
+}
+
+/// Lists the folders without any file and picks them for removal with `--clean-empty`. Folders
+/// modified within `watch::SETTLE_TIME` are left alone, the game may be about to write a save
+/// into them.
+fn select_empty_folders(
+    empty_folders: &[PathBuf],
+    clean_config: &CleanConfig,
+    now: SystemTime,
+) -> Vec<PathBuf> {
+    if empty_folders.is_empty() {
+        return Vec::new();
+    }
+
+    info!("Empty folders:");
+    empty_folders
+        .iter()
+        .for_each(|folder| info!("\t- | {}", folder.display()));
+    if !clean_config.clean_empty {
+        info!(
+            "Found {} empty folder(s), use --clean-empty to delete them",
+            empty_folders.len()
+        );
+        return Vec::new();
+    }
+
+    empty_folders
+        .iter()
+        .filter(|folder| {
+            fs::metadata(folder)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| {
+                    now.duration_since(modified)
+                        .is_ok_and(|age| age >= watch::SETTLE_TIME)
+                })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Lists the files next to the save folders, leaving out those in `--exclude-from` like
+/// `desktop.ini`, and picks them for removal with `--clean-loose-files`.
+fn select_loose_files(
+    loose_files: &[PathBuf],
+    exclude_list: Option<&ExcludeList>,
+    clean_config: &CleanConfig,
+) -> Vec<PathBuf> {
+    let loose_files: Vec<PathBuf> = loose_files
+        .iter()
+        .filter(|file| {
+            exclude_list.is_none_or(|exclude_list| {
+                file.file_name().is_none_or(|file_name| {
+                    exclude_list
+                        .matching_entry(&file_name.to_string_lossy())
+                        .is_none()
+                })
+            })
+        })
+        .cloned()
+        .collect();
+    if loose_files.is_empty() {
+        return Vec::new();
+    }
+
+    info!("Loose files:");
+    loose_files
+        .iter()
+        .for_each(|file| info!("\t- | {}", file.display()));
+    if !clean_config.clean_loose_files {
+        info!(
+            "Found {} loose file(s) next to the save folders, use --clean-loose-files to delete them",
+            loose_files.len()
+        );
+        return Vec::new();
+    }
+
+    loose_files
+}
+
+/// Asks before deleting loose files, which may be something the user put there, empty folders
+/// go without asking.
+fn confirm_clutter(
+    mut clutter: Clutter,
+    clean_config: &CleanConfig,
+    read_answer: impl FnMut(&str) -> String,
+) -> Clutter {
+    if clutter.loose_files.is_empty() || clean_config.yes || clean_config.watch {
+        return clutter;
+    }
+
+    let question = format!(
+        "Delete the {} loose file(s)? y/n:",
+        clutter.loose_files.len()
+    );
+    if !confirm(&question, read_answer) {
+        info!("Kept the loose files");
+        clutter.loose_files.clear();
+    }
+    clutter
+}
+
+fn report_skipped_folders(skipped_folders: &[(String, ProgramError)], verbose: bool) {
+    if skipped_folders.is_empty() {
+        return;
+    }
+
+    if !verbose {
+        info!(
+            "Skipped {} folder(s) (use --verbose for details)",
+            skipped_folders.len()
+        );
+        return;
+    }
+
+    info!("Skipped {} folder(s):", skipped_folders.len());
+    skipped_folders
+        .iter()
+        .for_each(|(folder_name, e)| info!("\t{} | {}", folder_name, e));
+}
+
+/// Only listed with `--verbose`, ignored entries are never saves.
+fn report_ignored(ignored: &[(String, String)], verbose: bool) {
+    if !verbose || ignored.is_empty() {
+        return;
+    }
+
+    info!("Ignored {} folder(s) and file(s)", ignored.len());
+    ignored
+        .iter()
+        .for_each(|(name, glob)| info!("\t{} | matches ignore entry {}", name, glob));
+}
+
+fn report_filtered_folders(
+    folder_filter: &FolderFilter,
+    filtered_folders: &[(String, String)],
+    verbose: bool,
+) {
+    if !folder_filter.is_active() {
+        return;
+    }
+
+    info!(
+        "Folder filters {} left out {} folder(s)",
+        folder_filter.describe(),
+        filtered_folders.len()
+    );
+    if verbose {
+        filtered_folders
+            .iter()
+            .for_each(|(folder_name, reason)| info!("\t{} | {}", folder_name, reason));
+    }
+}
+
+/// Warns about `--exclude-from` entries that match no folder in the scan.
+fn report_stale_entries(exclude_list: &ExcludeList, scan_result: &ScanResult) {
+    let loose_file_names: Vec<String> = scan_result
+        .loose_files
+        .iter()
+        .filter_map(|file| file.file_name())
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .collect();
+    let folder_names: Vec<&str> = scan_result
+        .saves
+        .iter()
+        .map(|save_information| save_information.file_name.as_str())
+        .chain(
+            scan_result
+                .skipped_folders
+                .iter()
+                .map(|(folder_name, _)| folder_name.as_str()),
+        )
+        .chain(
+            scan_result
+                .filtered_folders
+                .iter()
+                .map(|(folder_name, _)| folder_name.as_str()),
+        )
+        .chain(loose_file_names.iter().map(String::as_str))
+        .collect();
+
+    exclude_list
+        .stale_entries(&folder_names)
+        .iter()
+        .for_each(|entry| {
+            eprintln!(
+                "Warning: {} in {} matches no save folder, it may be stale",
+                entry,
+                exclude_list.path.display()
+            )
+        });
+}
+
+/// Lists every folder that could not be parsed as a save and why, they are never deleted.
+fn print_unparsed_folders(skipped_folders: &[(String, ProgramError)]) {
+    if skipped_folders.is_empty() {
+        return;
+    }
+
+    info!(
+        "Could not parse {} folder(s), they are left alone:",
+        skipped_folders.len()
+    );
+    skipped_folders
+        .iter()
+        .for_each(|(folder_name, e)| info!("\t- | {} | {}", folder_name, e));
+}
+
+fn report_symlinks(symlinks: &[PathBuf]) {
+    if symlinks.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "Warning: {} save folder(s) are symlinks and are left alone, pass --follow-symlinks to clean them:",
+        symlinks.len()
+    );
+    symlinks
+        .iter()
+        .for_each(|symlink| eprintln!("\t- | {}", symlink.display()));
+}
+
+/// Stops before scanning a directory that was likely given by mistake, where folders that
+/// happen to be named like saves would be deleted.
+fn refuse_unlikely_save_folders(
+    directories: &[PathBuf],
+    min_save_percent: usize,
+) -> Result<(), ProgramError> {
+    match directories
+        .iter()
+        .find(|directory| !save_root::looks_like_save_folder(directory, min_save_percent))
+    {
+        Some(directory) => Err(ProgramError::NotASaveFolder(format!(
+            "{} doesn't look like a Baldur's Gate 3 save folder: it isn't named Story or below Savegames, and fewer than {}% of its folders are saves. Pass --force-path to clean it anyway",
+            directory.display(),
+            min_save_percent
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// With `--strict` a folder that can't be parsed stops the run before anything is selected.
+fn refuse_unparsed_folders(skipped_folders: &[(String, ProgramError)]) -> Result<(), ProgramError> {
+    if skipped_folders.is_empty() {
+        return Ok(());
+    }
+
+    Err(ProgramError::UnparsedFolders(format!(
+        "Stopped by --strict, could not parse {} folder(s): {}",
+        skipped_folders.len(),
+        skipped_folders
+            .iter()
+            .map(|(folder_name, e)| format!("{} ({})", folder_name, e))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )))
+}
+
+fn package_details(file_name: &str) -> Result<SaveInformation, ProgramError> {
+    // Copies made in Explorer are parsed like the folder they were copied from.
+    if let Some(original_name) = copy_suffix::original_name(file_name) {
+        return package_details(original_name).map(|save_information| {
+            SaveInformation {
+                file_name: file_name.to_string(),
+                folder_name: OsString::from(file_name),
+                ..save_information
+            }
+            .with_duplicate_of(Some(original_name.to_string()))
+        });
+    }
+    if let Some(folder_pattern) = folder_pattern::active() {
+        return package_details_by_pattern(file_name, &folder_pattern);
+    }
+
+    // Folders copied by hand can end in spaces or dots, which are not part of the save number.
+    let parsed_name = file_name.trim_end_matches([' ', '.']);
+    let parse_number = save_number(parsed_name)?;
+    let characters_name = character_name(parsed_name)?;
+    let campaign_id = character_and_campaign(parsed_name)
+        .and_then(|(_, campaign_id)| campaign_id)
+        .map(str::to_string);
+    let s_type = save_type(parsed_name);
+
+    Ok(
+        SaveInformation::new(file_name.to_string(), characters_name, s_type, parse_number)
+            .with_campaign_id(campaign_id),
+    )
+}
+
+/// Splits the folder name with the groups of `--pattern` instead of the built-in parser.
+fn package_details_by_pattern(
+    file_name: &str,
+    folder_pattern: &FolderPattern,
+) -> Result<SaveInformation, ProgramError> {
+    let parts = folder_pattern
+        .parts(file_name)
+        .ok_or(ProgramError::PatternMismatch(format!(
+            "Does not match --pattern {}",
+            folder_pattern
+        )))?;
+    let part = |group: &str| parts.get(group).filter(|part| !part.is_empty());
+
+    let character_name = part("name").ok_or(ProgramError::NameNotDetected(
+        "Could not detect character name".to_string(),
+    ))?;
+    // Classified like the save type section of a built-in folder name.
+    let s_type = part("type").map_or(SaveType::Unrecognized, |save_type_text| {
+        save_type(&format!("_{}", save_type_text))
+    });
+    let parse_number = part("number").and_then(|number| number.parse::<u64>().ok());
+
+    Ok(SaveInformation::new(
+        file_name.to_string(),
+        character_name.clone(),
+        s_type,
+        parse_number,
+    )
+    .with_campaign_id(part("campaign").cloned()))
+}
+
+fn save_type(folder_name: &str) -> SaveType {
+    // Checked first, a misread Honour Mode save would be deleted like any other. Only after the
+    // character, so one named like Honoria keeps their quick saves.
+    let save_type_section = save_type_section(folder_name).to_ascii_lowercase();
+    if ["honour", "honor"]
+        .iter()
+        .any(|keyword| save_type_section.contains(keyword))
+    {
+        SaveType::Honour
+    } else if let Some((save_type, ..)) = save_keywords::find(folder_name) {
+        save_type
+    } else if is_manual_save_name(folder_name) {
+        SaveType::Manual
+    } else {
+        SaveType::Unrecognized
+    }
+}
+
+/// The folder name after the character's name and campaign id, all of it if they aren't found.
+fn save_type_section(folder_name: &str) -> &str {
+    character_and_campaign(folder_name)
+        .map(|(character_name, campaign_id)| {
+            &folder_name[character_name.len() + campaign_id.map_or(0, |id| id.len() + 1)..]
+        })
+        .unwrap_or(folder_name)
+}
+
+/// Manual saves follow the game's naming, `<character>-<id>__<label>_<number>`, without the
+/// keyword of another save type.
+fn is_manual_save_name(folder_name: &str) -> bool {
+    let all_digits = |text: &str| !text.is_empty() && text.chars().all(|c| c.is_ascii_digit());
+
+    folder_name
+        .split_once("__")
+        .and_then(|(character_and_id, label_and_number)| {
+            let (_, id) = character_and_id.rsplit_once('-')?;
+            let (label, number) = label_and_number.rsplit_once('_')?;
+            Some(all_digits(id) && !label.is_empty() && all_digits(number))
+        })
+        .unwrap_or(false)
+}
+
+fn character_name(folder_name: &str) -> Result<String, ProgramError> {
+    character_and_campaign(folder_name)
+        .map(|(character_name, _)| character_name.to_string())
+        .ok_or(ProgramError::NameNotDetected(
+            "Could not detect character name".to_string(),
+        ))
+}
+
+/// Splits `<name>-<campaign id>_...` at the last dash followed by a numeric id, names may
+/// contain dashes themselves. Without an id the name ends at the first dash. Before a save type
+/// token everything up to it is the id, so ids containing underscores are kept whole.
+fn character_and_campaign(folder_name: &str) -> Option<(&str, Option<&str>)> {
+    let (name_section, anchored) = match save_type_token(folder_name) {
+        Some((start, _)) => (folder_name[..start].trim_end_matches('_'), true),
+        None => (folder_name, false),
+    };
+
+    name_section
+        .match_indices('-')
+        .rev()
+        .filter(|(index, _)| *index > 0)
+        .find_map(|(index, _)| {
+            let rest = &name_section[index + 1..];
+            let campaign_id = if anchored {
+                rest
+            } else {
+                rest.split('_').next()?
+            };
+            (campaign_id.starts_with(|c: char| c.is_ascii_digit())
+                && campaign_id.chars().all(|c| c.is_ascii_digit() || c == '_'))
+            .then_some((&name_section[..index], Some(campaign_id)))
+        })
+        .or_else(|| {
+            name_section
+                .find('-')
+                .filter(|index| *index > 0)
+                .map(|index| (&name_section[..index], None))
+        })
+}
+
+/// The start and end of the `QuickSave` or `AutoSave` token in the folder name, in any of the
+/// game's languages.
+fn save_type_token(folder_name: &str) -> Option<(usize, usize)> {
+    save_keywords::find(folder_name).map(|(_, start, end)| (start, end))
+}
+
+/// The number right after the save type token, ignoring any suffix patches add after it like
+/// in `Tav-123456789_QuickSave_277_1`. Without a token it is the number after the last
+/// underscore. `None` if there is no number so the save is still cleaned, ordered by its
+/// modified time.
+fn save_number(folder_name: &str) -> Result<Option<u64>, ProgramError> {
+    if let Some((_, end)) = save_type_token(folder_name) {
+        let after_token = folder_name[end..].trim_start_matches('_');
+        let digits = after_token
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(after_token, |length| &after_token[..length]);
+
+        return Ok(digits.parse::<u64>().ok());
+    }
+
+    let folder_name: Vec<&str> = folder_name.split('_').collect();
+
+    if folder_name.len() <= 1 {
+        return Err(ProgramError::NotEnoughUnderscores(
+            "Did not find the correct number of underscores. Cannot continue with this save."
+                .to_string(),
+        ));
+    }
+
+    folder_name
+        .into_iter()
+        .last()
+        .ok_or(ProgramError::NotEnoughUnderscores(
+            "Could not find any elements".to_string(),
+        ))
+        .map(|save_number| save_number.parse::<u64>().ok())
+}
+
+fn group_saves(saves: Vec<SaveInformation>) -> HashMap<CharacterKey, Saves> {
+    saves
+        .into_iter()
+        .fold(HashMap::new(), crate::group_by_character)
+}
+
+fn group_by_character(
+    mut map: HashMap<CharacterKey, Saves>,
+    save_information: SaveInformation,
+) -> HashMap<CharacterKey, Saves> {
+    let saves = map.entry(CharacterKey::of(&save_information)).or_default();
+
+    insert_save(saves, save_information);
+
+    map
+}
+
+fn insert_save(save_by_type: &mut Saves, save_information: SaveInformation) {
+    if save_information.corrupt {
+        save_by_type.corrupt_saves.push(save_information);
+        return;
+    }
+    if save_information.duplicate_of.is_some() {
+        save_by_type.copies.push(save_information);
+        return;
+    }
+
+    match &save_information.save_type {
+        SaveType::Quick => save_by_type.quick_saves.push(save_information),
+        SaveType::Auto => save_by_type.auto_saves.push(save_information),
+        SaveType::Honour => save_by_type.honour_saves.push(save_information),
+        SaveType::Manual => save_by_type.manual_saves.push(save_information),
+        SaveType::Unrecognized => save_by_type.unrecognized_saves.push(save_information),
+    };
+}
+
+fn filter_characters(
+    mut map: HashMap<CharacterKey, Saves>,
+    characters: &[String],
+) -> Result<HashMap<CharacterKey, Saves>, ProgramError> {
+    if characters.is_empty() {
+        return Ok(map);
+    }
+
+    let missing_characters: Vec<&str> = characters
+        .iter()
+        .filter(|character| !map.keys().any(|key| same_character(&key.name, character)))
+        .map(|character| character.as_str())
+        .collect();
+
+    if !missing_characters.is_empty() {
+        return Err(ProgramError::CharacterNotFound(format!(
+            "No save folders found for character(s): {}",
+            missing_characters.join(", ")
+        )));
+    }
+
+    map.retain(|key, _| {
+        characters
+            .iter()
+            .any(|character| same_character(&key.name, character))
+    });
+
+    Ok(map)
+}
+
+/// Keeps the saves of the one campaign whose id starts with `prefix`, ids are long enough that
+/// typing a few digits is usually enough.
+fn filter_campaign(
+    mut map: HashMap<CharacterKey, Saves>,
+    prefix: Option<&str>,
+) -> Result<HashMap<CharacterKey, Saves>, ProgramError> {
+    let Some(prefix) = prefix else {
+        return Ok(map);
+    };
+
+    let mut campaigns: Vec<(&str, &str)> = map
+        .keys()
+        .filter_map(|key| Some((key.campaign_id.as_deref()?, key.name.as_str())))
+        .filter(|(campaign_id, _)| campaign_id.starts_with(prefix))
+        .collect();
+    campaigns.sort();
+    campaigns.dedup_by_key(|(campaign_id, _)| *campaign_id);
+
+    let campaign_id = match campaigns.as_slice() {
+        [] => {
+            return Err(ProgramError::CampaignNotFound(format!(
+                "No save folders found for campaign {}",
+                prefix
+            )))
+        }
+        [(campaign_id, _)] => campaign_id.to_string(),
+        _ => {
+            return Err(ProgramError::AmbiguousCampaign(format!(
+                "Campaign {} matches more than one campaign: {}",
+                prefix,
+                campaigns
+                    .iter()
+                    .map(|(campaign_id, name)| format!("{} ({})", campaign_id, name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )))
+        }
+    };
+
+    map.retain(|key, _| key.campaign_id.as_deref() == Some(campaign_id.as_str()));
+
+    Ok(map)
+}
+
+fn exclude_characters(
+    mut map: HashMap<CharacterKey, Saves>,
+    excluded_characters: &[String],
+) -> (HashMap<CharacterKey, Saves>, usize, Vec<String>) {
+    let unmatched_characters = excluded_characters
+        .iter()
+        .filter(|character| !map.keys().any(|key| same_character(&key.name, character)))
+        .cloned()
+        .collect();
+
+    let mut skipped_saves = 0;
+    map.retain(|key, saves| {
+        let excluded = excluded_characters
+            .iter()
+            .any(|character| same_character(&key.name, character));
+
+        if excluded {
+            skipped_saves += saves.count();
+        }
+
+        !excluded
+    });
+
+    (map, skipped_saves, unmatched_characters)
+}
+
+fn same_character(name: &str, other: &str) -> bool {
+    name.to_lowercase() == other.to_lowercase()
+}
+
+fn sort_map_saves(
+    mut map: HashMap<CharacterKey, Saves>,
+    sort_by: SortBy,
+) -> HashMap<CharacterKey, Saves> {
+    map.values_mut().for_each(|value| {
+        value
+            .quick_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .auto_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .honour_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .manual_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .unrecognized_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .corrupt_saves
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b));
+
+        value
+            .copies
+            .sort_by(|save_a, save_b| sort_by.newest_first(save_a, save_b))
+    });
+
+    map
+}
+
+/// Warns about the quick and autosaves whose numbers look like the game restarted counting,
+/// see `counter_reset::detect`, and sorts them by modified time when `prefer_mtime` is set.
+fn resolve_counter_resets(
+    mut map: HashMap<CharacterKey, Saves>,
+    sort_by: SortBy,
+    prefer_mtime: bool,
+) -> HashMap<CharacterKey, Saves> {
+    if sort_by == SortBy::Mtime {
+        return map;
+    }
+
+    let mut characters: Vec<(&CharacterKey, &mut Saves)> = map.iter_mut().collect();
+    characters.sort_by_key(|(key, _)| *key);
+    characters.into_iter().for_each(|(key, saves)| {
+        [
+            ("quick saves", &mut saves.quick_saves),
+            ("autosaves", &mut saves.auto_saves),
+        ]
+        .into_iter()
+        .filter(|(_, saves_of_type)| counter_reset::detect(saves_of_type))
+        .for_each(|(description, saves_of_type)| {
+            let consequence = match prefer_mtime {
+                true => "Keeping the most recently modified ones instead",
+                false => "The highest numbers are kept even though they are older, use --prefer-mtime-on-conflict to keep the most recently modified ones",
+            };
+            eprintln!(
+                "{}",
+                color::paint(
+                    &format!(
+                        "Warning: the newest {} of {} have lower numbers than older ones, the game may have restarted counting. {}",
+                        description, key, consequence
+                    ),
+                    Color::Yellow
+                )
+            );
+            if prefer_mtime {
+                saves_of_type.sort_by(|save_a, save_b| SortBy::Mtime.newest_first(save_a, save_b));
+            }
+        });
+    });
+
+    map
+}
+
+fn unmatched_preserve_for<'a>(
+    map: &HashMap<CharacterKey, Saves>,
+    preserve_for: &'a [(String, usize)],
+) -> Vec<&'a str> {
+    preserve_for
+        .iter()
+        .map(|(character, _)| character.as_str())
+        .filter(|character| !map.keys().any(|key| same_character(&key.name, character)))
+        .collect()
+}
+
+fn discovered_characters(map: &HashMap<CharacterKey, Saves>) -> Vec<&str> {
+    let mut characters: Vec<&str> = map.keys().map(|key| key.name.as_str()).collect();
+    characters.sort();
+    characters.dedup();
+
+    characters
+}
+
+/// Returns the saves to delete and how many character and save type combinations had their
+/// newest save kept back because every save of it would have been deleted.
+fn get_delete_vec(
+    map: HashMap<CharacterKey, Saves>,
+    number_to_preserve: usize,
+    clean_config: &CleanConfig,
+) -> (Vec<SaveInformation>, usize) {
+    map.into_iter().fold(
+        (Vec::new(), 0),
+        |(mut deletion_saves, mut guarded_saves), (character, character_saves)| {
+            // The last override given for a character wins, like repeated options usually do.
+            let number_to_preserve = clean_config
+                .preserve_for
+                .iter()
+                .rev()
+                .find(|(name, _)| same_character(name, &character.name))
+                .map(|(_, count)| *count)
+                .unwrap_or(number_to_preserve);
+
+            // The grouping into a map is to apply number_to_preserve to each character as well as
+            // quick and auto saves for each character.
+            [
+                (SaveType::Quick, character_saves.quick_saves),
+                (SaveType::Auto, character_saves.auto_saves),
+                (SaveType::Honour, character_saves.honour_saves),
+                (SaveType::Manual, character_saves.manual_saves),
+                (SaveType::Unrecognized, character_saves.unrecognized_saves),
+            ]
+            .into_iter()
+            // Honour Mode, manual and unrecognized saves are only ever deleted on request.
+            .filter(|(save_type, _)| is_included(save_type, clean_config))
+            .filter(|(save_type, _)| {
+                clean_config
+                    .only
+                    .is_none_or(|filter| filter.allows(save_type))
+            })
+            .for_each(|(save_type, saves)| {
+                let number_to_preserve = match (save_type, clean_config.preserve_manual) {
+                    (SaveType::Manual, Some(preserve_manual)) => preserve_manual,
+                    _ => number_to_preserve,
+                };
+                let deletable_saves = select_deletable_saves(
+                    saves.clone(),
+                    number_to_preserve,
+                    clean_config.max_size_per_character,
+                    clean_config.thin,
+                );
+
+                // Selection keeps the newest first order, skipping one keeps the newest save.
+                if !saves.is_empty()
+                    && deletable_saves.len() == saves.len()
+                    && !clean_config.allow_delete_all
+                {
+                    guarded_saves += 1;
+                    deletion_saves.extend(deletable_saves.into_iter().skip(1));
+                } else {
+                    deletion_saves.extend(deletable_saves);
+                }
+            });
+
+            let requested = |save_information: &SaveInformation| {
+                (save_information.save_type != SaveType::Honour || clean_config.include_honour)
+                    && clean_config
+                        .only
+                        .is_none_or(|filter| filter.allows(&save_information.save_type))
+            };
+            if clean_config.clean_corrupt {
+                deletion_saves.extend(character_saves.corrupt_saves.into_iter().filter(requested));
+            }
+            if clean_config.clean_copies {
+                deletion_saves.extend(character_saves.copies.into_iter().filter(requested));
+            }
+
+            (deletion_saves, guarded_saves)
+        },
+    )
+}
+
+/// Whether saves of this type may be selected for deletion at all.
+fn is_included(save_type: &SaveType, clean_config: &CleanConfig) -> bool {
+    match save_type {
+        SaveType::Honour => clean_config.include_honour,
+        SaveType::Manual => clean_config.preserve_manual.is_some(),
+        SaveType::Unrecognized => clean_config.include_unrecognized,
+        SaveType::Quick | SaveType::Auto => true,
+    }
+}
+
+fn retain_older_than(
+    deletable_saves: Vec<SaveInformation>,
+    older_than: Option<Duration>,
+    now: SystemTime,
+) -> Vec<SaveInformation> {
+    let Some(cutoff) = older_than.and_then(|older_than| now.checked_sub(older_than)) else {
+        return deletable_saves;
+    };
+
+    deletable_saves
+        .into_iter()
+        // Saves without a readable modified time are kept, their age is unknown.
+        .filter(|save_information| {
+            save_information
+                .modified
+                .is_some_and(|modified| modified < cutoff)
+        })
+        .collect()
+}
+
+fn rescue_recent_saves(
+    deletable_saves: Vec<SaveInformation>,
+    keep_recent: Duration,
+    now: SystemTime,
+) -> (Vec<SaveInformation>, usize) {
+    let Some(cutoff) = now.checked_sub(keep_recent) else {
+        let rescued_saves = deletable_saves.len();
+        return (Vec::new(), rescued_saves);
+    };
+
+    let (recent_saves, deletable_saves): (Vec<SaveInformation>, Vec<SaveInformation>) =
+        deletable_saves.into_iter().partition(|save_information| {
+            save_information
+                .modified
+                .is_some_and(|modified| modified >= cutoff)
+        });
+
+    (deletable_saves, recent_saves.len())
+}
+
+fn select_deletable_saves(
+    saves: Vec<SaveInformation>,
+    number_to_preserve: usize,
+    max_size: Option<u64>,
+    thin: Option<usize>,
+) -> Vec<SaveInformation> {
+    let by_count = match thin {
+        Some(thin) => thinned_saves(saves.clone(), number_to_preserve, thin),
+        None => deletable_saves(saves.clone(), number_to_preserve),
+    };
+    let by_size = max_size
+        .map(|max_size| over_size_budget(saves.clone(), max_size))
+        .unwrap_or_default();
+
+    // A save is deleted if either rule selects it, keeping the newest first order.
+    saves
+        .into_iter()
+        .filter(|save_information| {
+            by_count.contains(save_information) || by_size.contains(save_information)
+        })
+        .collect()
+}
+
+fn over_size_budget(saves: Vec<SaveInformation>, max_size: u64) -> Vec<SaveInformation> {
+    let mut total_size: u64 = 0;
+
+    saves
+        .into_iter()
+        .skip_while(|save_information| {
+            total_size = total_size.saturating_add(save_information.size_bytes);
+            total_size <= max_size
+        })
+        .collect()
+}
+
+fn thinned_saves(
+    saves: Vec<SaveInformation>,
+    number_to_preserve: usize,
+    thin: usize,
+) -> Vec<SaveInformation> {
+    saves
+        .into_iter()
+        .skip(number_to_preserve)
+        .enumerate()
+        // Every thin-th save beyond the preserved window survives.
+        .filter(|(i, _)| (i + 1) % thin != 0)
+        .map(|(_, save_information)| save_information)
+        .collect()
+}
+
+fn ordinal(number: usize) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", number, suffix)
+}
+
+/// Relies on `saves` being sorted newest first, which the ordering of `SaveInformation`
+/// guarantees to be the same on every run even for saves with equal numbers and times.
+fn deletable_saves(saves: Vec<SaveInformation>, number_to_preserve: usize) -> Vec<SaveInformation> {
+    saves.into_iter().skip(number_to_preserve).collect()
+}
+
+fn print_deletable_saves(deletable_saves: &[SaveInformation]) {
+    let (recognized_saves, unrecognized_saves, corrupt_saves, copies) =
+        partition_sections(deletable_saves.to_vec());
+
+    info!("****");
+    recognized_saves
+        .iter()
+        .enumerate()
+        .for_each(|(i, save)| info!("\t{} | {}", i + 1, save_label(save, deletable_saves)));
+    print_section("Not identified as quick/auto saves:", &unrecognized_saves);
+    print_section("Corrupt or incomplete:", &corrupt_saves);
+    print_section("Copies:", &copies);
+    character_subtotals(deletable_saves)
+        .iter()
+        .for_each(|subtotal| info!("{}", subtotal));
+    info!(
+        "Total: {} save(s), {}",
+        deletable_saves.len(),
+        format_size(
+            deletable_saves
+                .iter()
+                .map(|save_information| save_information.size_bytes)
+                .sum()
+        )
+    );
+    info!("****");
+}
+
+/// One line per character with the number and size of its listed saves.
+fn character_subtotals(saves: &[SaveInformation]) -> Vec<String> {
+    saves
+        .iter()
+        .fold(BTreeMap::new(), |mut subtotals, save_information| {
+            let (count, size_bytes) = subtotals
+                .entry(CharacterKey::of(save_information))
+                .or_insert((0, 0u64));
+            *count += 1;
+            *size_bytes += save_information.size_bytes;
+            subtotals
+        })
+        .into_iter()
+        .map(|(key, (count, size_bytes))| {
+            format!("{}: {} save(s), {}", key, count, format_size(size_bytes))
+        })
+        .collect()
+}
+
+fn print_section(title: &str, saves: &[SaveInformation]) {
+    if !saves.is_empty() {
+        info!("{}", title);
+        saves
+            .iter()
+            .for_each(|save| info!("\t- | {}", save_label(save, saves)));
+    }
+}
+
+/// The size right aligned with the sizes of the other listed saves and the modified time, then
+/// the painted save, with its root when the listed saves come from several roots.
+fn save_label(save_information: &SaveInformation, listed_saves: &[SaveInformation]) -> String {
+    let size_width = listed_saves
+        .iter()
+        .map(|listed_save| format_size(listed_save.size_bytes).len())
+        .max()
+        .unwrap_or_default();
+    let mut save = color::paint_save(save_information);
+    if let Some(game_version) = &save_information.game_version {
+        save = format!("{} | {}", save, game_version);
+    }
+    if save_information.cloud_only {
+        save = format!("{} | online-only files", save);
+    }
+    if listed_saves
+        .iter()
+        .any(|listed_save| listed_save.root != save_information.root)
+    {
+        save = format!(
+            "{} | {}",
+            save_information
+                .root
+                .as_deref()
+                .map(Path::display)
+                .map(|root| root.to_string())
+                .unwrap_or_default(),
+            save
+        );
+    }
+
+    format!(
+        "{:>width$} | {} | {}",
+        format_size(save_information.size_bytes),
+        timestamp::format_modified(save_information.modified),
+        save,
+        width = size_width
+    )
+}
+
+fn report_honour_saves(map: &HashMap<CharacterKey, Saves>) {
+    let honour_saves: Vec<SaveInformation> = map
+        .values()
+        .flat_map(|saves| saves.honour_saves.iter().cloned())
+        .collect();
+
+    if !honour_saves.is_empty() {
+        info!(
+            "Found {} Honour Mode save(s), they are kept unless --include-honour is given",
+            honour_saves.len()
+        );
+        print_section("Honour Mode:", &honour_saves);
+    }
+}
+
+fn report_corrupt_saves(map: &HashMap<CharacterKey, Saves>) {
+    let corrupt_saves: Vec<SaveInformation> = map
+        .values()
+        .flat_map(|saves| saves.corrupt_saves.iter().cloned())
+        .collect();
+
+    if !corrupt_saves.is_empty() {
+        info!(
+            "Found {} corrupt or incomplete save folder(s), use --clean-corrupt to delete them",
+            corrupt_saves.len()
+        );
+        print_section("Corrupt or incomplete:", &corrupt_saves);
+    }
+}
+
+fn report_copies(map: &HashMap<CharacterKey, Saves>) {
+    let copies: Vec<SaveInformation> = map
+        .values()
+        .flat_map(|saves| saves.copies.iter().cloned())
+        .collect();
+
+    if !copies.is_empty() {
+        info!(
+            "Found {} save folder(s) copied by hand, use --clean-copies to delete them",
+            copies.len()
+        );
+        print_section("Copies:", &copies);
+    }
+}
+
+/// Splits saves into the regular ones, those not identified as quick or auto saves, corrupt
+/// ones and copies, the latter three are confirmed separately.
+fn partition_sections(
+    saves: Vec<SaveInformation>,
+) -> (
+    Vec<SaveInformation>,
+    Vec<SaveInformation>,
+    Vec<SaveInformation>,
+    Vec<SaveInformation>,
+) {
+    let (corrupt_saves, saves): (Vec<SaveInformation>, Vec<SaveInformation>) = saves
+        .into_iter()
+        .partition(|save_information| save_information.corrupt);
+    let (copies, saves): (Vec<SaveInformation>, Vec<SaveInformation>) = saves
+        .into_iter()
+        .partition(|save_information| save_information.duplicate_of.is_some());
+    let (unrecognized_saves, recognized_saves) = saves
+        .into_iter()
+        .partition(|save_information| save_information.save_type == SaveType::Unrecognized);
+
+    (recognized_saves, unrecognized_saves, corrupt_saves, copies)
+}
+
+fn dry_run(deletable_saves: &[SaveInformation]) {
+    print_deletable_saves(deletable_saves);
+    outcome!(
+        "Dry run, nothing deleted. {} folder(s) would have been removed.",
+        deletable_saves.len()
+    );
+}
+
+/// Lists the saves and asks which to delete. Above `big_delete_threshold` the sections are
+/// confirmed first and the typed word of `confirm_big_delete` takes the place of the `y` for the
+/// recognized saves.
+fn confirm_user_delete(
+    deletable_saves: Vec<SaveInformation>,
+    big_delete_threshold: usize,
+    mut read_answer: impl FnMut(&str) -> String,
+) -> (Vec<SaveInformation>, String) {
+    print_deletable_saves(&deletable_saves);
+    let big_delete =
+        big_delete::needs_typed_confirmation(deletable_saves.len(), big_delete_threshold);
+    let (recognized_saves, unrecognized_saves, corrupt_saves, copies) =
+        partition_sections(deletable_saves);
+
+    let selected_saves = if big_delete {
+        let section_saves =
+            confirm_sections(unrecognized_saves, corrupt_saves, copies, &mut read_answer);
+        let selected_count = recognized_saves.len() + section_saves.len();
+        if big_delete::needs_typed_confirmation(selected_count, big_delete_threshold) {
+            return confirm_big_delete(
+                recognized_saves.into_iter().chain(section_saves).collect(),
+                read_answer,
+            );
+        }
+        let mut selected_saves = select_recognized(recognized_saves, &mut read_answer);
+        selected_saves.extend(section_saves);
+        selected_saves
+    } else {
+        let mut selected_saves = select_recognized(recognized_saves, &mut read_answer);
+        selected_saves.extend(confirm_sections(
+            unrecognized_saves,
+            corrupt_saves,
+            copies,
+            &mut read_answer,
+        ));
+        selected_saves
+    };
+
+    let user_input = if selected_saves.is_empty() { "n" } else { "y" };
+    (selected_saves, user_input.to_string())
+}
+
+fn select_recognized(
+    recognized_saves: Vec<SaveInformation>,
+    read_answer: impl FnMut(&str) -> String,
+) -> Vec<SaveInformation> {
+    if recognized_saves.is_empty() {
+        return Vec::new();
+    }
+
+    prompt_selection(
+        recognized_saves,
+        "Delete the above files? y/n, or a selection like 1,4-7 or all except 2:",
+        read_answer,
+    )
+}
+
+/// Asks about the folders that are only deleted when confirmed on their own.
+fn confirm_sections(
+    unrecognized_saves: Vec<SaveInformation>,
+    corrupt_saves: Vec<SaveInformation>,
+    copies: Vec<SaveInformation>,
+    mut read_answer: impl FnMut(&str) -> String,
+) -> Vec<SaveInformation> {
+    let mut selected_saves = confirm_section(
+        unrecognized_saves,
+        "not identified as quick/auto saves",
+        &mut read_answer,
+    );
+    selected_saves.extend(confirm_section(
+        corrupt_saves,
+        "that are corrupt or incomplete",
+        &mut read_answer,
+    ));
+    selected_saves.extend(confirm_section(copies, "copied by hand", &mut read_answer));
+
+    selected_saves
+}
+
+fn confirm_per_character(deletable_saves: Vec<SaveInformation>) -> (Vec<SaveInformation>, String) {
+    let (recognized_saves, unrecognized_saves, corrupt_saves, copies) =
+        partition_sections(deletable_saves);
+
+    let Some(mut selected_saves) = approve_per_character(recognized_saves, read_answer) else {
+        info!("Quit, nothing is deleted");
+        return (Vec::new(), "n".to_string());
+    };
+    if !unrecognized_saves.is_empty() || !corrupt_saves.is_empty() || !copies.is_empty() {
+        print_section("Not identified as quick/auto saves:", &unrecognized_saves);
+        print_section("Corrupt or incomplete:", &corrupt_saves);
+        print_section("Copies:", &copies);
+    }
+    selected_saves.extend(confirm_sections(
+        unrecognized_saves,
+        corrupt_saves,
+        copies,
+        read_answer,
+    ));
+
+    let user_input = if selected_saves.is_empty() { "n" } else { "y" };
+    (selected_saves, user_input.to_string())
+}
+
+/// Asks about the saves of one character at a time, returns the saves of the approved
+/// characters or `None` if the user quit.
+fn approve_per_character(
+    saves: Vec<SaveInformation>,
+    mut read_answer: impl FnMut(&str) -> String,
+) -> Option<Vec<SaveInformation>> {
+    let mut characters: Vec<CharacterKey> = saves.iter().map(CharacterKey::of).collect();
+    characters.sort();
+    characters.dedup();
+
+    let mut approved_saves = Vec::new();
+    for character in characters {
+        let character_saves: Vec<SaveInformation> = saves
+            .iter()
+            .filter(|save_information| CharacterKey::of(save_information) == character)
+            .cloned()
+            .collect();
+
+        info!("**** {}", character);
+        character_saves
+            .iter()
+            .enumerate()
+            .for_each(|(i, save)| info!("\t{} | {}", i + 1, save_label(save, &character_saves)));
+
+        let question = format!(
+            "Delete these {} save(s) of {}? y/n/skip/quit:",
+            character_saves.len(),
+            character
+        );
+        // Running out of attempts leaves the character untouched, like skip.
+        for _ in 0..confirmation::MAX_ATTEMPTS {
+            match character_answer::parse_character_answer(&read_answer(&question)) {
+                Ok(CharacterAnswer::Delete) => {
+                    approved_saves.extend(character_saves);
+                    break;
+                }
+                Ok(CharacterAnswer::Skip) => break,
+                Ok(CharacterAnswer::Quit) => return None,
+                Err(e) => eprintln!("Invalid answer: {}", e),
+            }
+        }
+    }
+
+    Some(approved_saves)
+}
+
+/// Expects a typed word instead of a `y`, for deletes too big to go ahead on a single key.
+fn confirm_big_delete(
+    selected_saves: Vec<SaveInformation>,
+    read_answer: impl FnOnce(&str) -> String,
+) -> (Vec<SaveInformation>, String) {
+    let answer = read_answer(&big_delete::typed_confirmation_question(
+        selected_saves.len(),
+    ));
+    if big_delete::is_typed_confirmation(&answer, selected_saves.len()) {
+        (selected_saves, "y".to_string())
+    } else {
+        info!("Big delete not confirmed, nothing is deleted");
+        (Vec::new(), "n".to_string())
+    }
+}
+
+fn confirm_section(
+    saves: Vec<SaveInformation>,
+    description: &str,
+    read_answer: impl FnMut(&str) -> String,
+) -> Vec<SaveInformation> {
+    if saves.is_empty() {
+        return saves;
+    }
+
+    let question = format!(
+        "Also delete the {} folder(s) {}? y/n:",
+        saves.len(),
+        description
+    );
+    if confirm(&question, read_answer) {
+        saves
+    } else {
+        Vec::new()
+    }
+}
+
+/// Asks a yes or no question until the answer parses, an answer that still doesn't after
+/// `confirmation::MAX_ATTEMPTS` tries counts as a no.
+fn confirm(question: &str, mut read_answer: impl FnMut(&str) -> String) -> bool {
+    for _ in 0..confirmation::MAX_ATTEMPTS {
+        match confirmation::parse_confirmation(&read_answer(question)) {
+            Some(answer) => return answer,
+            None => eprintln!("Please answer y or n"),
+        }
+    }
+    info!("No valid answer, treated as no");
+
+    false
+}
+
+/// Asks which of the listed items to act on, like `confirm` an answer that still doesn't parse
+/// after `confirmation::MAX_ATTEMPTS` tries selects nothing.
+fn prompt_selection<T>(
+    items: Vec<T>,
+    question: &str,
+    mut read_answer: impl FnMut(&str) -> String,
+) -> Vec<T> {
+    for _ in 0..confirmation::MAX_ATTEMPTS {
+        match selection::parse_selection(&read_answer(question), items.len()) {
+            Ok(Selection::All) => return items,
+            Ok(Selection::Nothing) => return Vec::new(),
+            Ok(Selection::Indices(indices)) => return select_indices(items, &indices),
+            Err(e) => eprintln!("Invalid selection: {}, please answer y or n", e),
+        }
+    }
+    info!("No valid answer, treated as no");
+
+    Vec::new()
+}
+
+fn read_answer(question: &str) -> String {
+    print!("{} ", color::paint(question, Color::Bold));
+    let _ = stdout().flush();
+
+    let mut user_input = String::new();
+    // Nothing more to read, treat it as a refusal instead of prompting forever.
+    if let Ok(0) | Err(_) = stdin().read_line(&mut user_input) {
+        return "n".to_string();
+    }
+    user_input = user_input.trim().to_string();
+    info!("User input read: {}", &user_input);
+
+    user_input
+}
+
+fn select_indices<T>(items: Vec<T>, indices: &[usize]) -> Vec<T> {
+    items
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| indices.contains(index))
+        .map(|(_, item)| item)
+        .collect()
+}
+
+fn skip_confirmation(deletable_saves: Vec<SaveInformation>) -> (Vec<SaveInformation>, String) {
+    print_deletable_saves(&deletable_saves);
+    info!("Confirmation skipped with --yes, deleting the above files");
+
+    (deletable_saves, "y".to_string())
+}
+
+fn delete(
+    (deletable_saves, user_input, dir_to_use): (Vec<SaveInformation>, String, PathBuf),
+    clutter: &Clutter,
+    strategy: &DeletionStrategy,
+    backup_dir: Option<&Path>,
+    verify: bool,
+    jobs: usize,
+    audit_log: &mut AuditLog,
+) -> Result<DeletionReport, ProgramError> {
+    if !user_input.eq_ignore_ascii_case("y") {
+        info!("User did not confirm delete");
+        return Ok(DeletionReport::default());
+    }
+
+    if let Some(backup_dir) = backup_dir {
+        fs::create_dir_all(backup_dir).map_err(|e| {
+            ProgramError::FailedToWriteFile(format!("{}: {}", backup_dir.display(), e))
+        })?;
+    }
+
+    let saves_with_paths: Vec<(PathBuf, SaveInformation)> = deletable_saves
+        .into_iter()
+        .map(|save_information| {
+            (
+                deletion_path(&save_information, &dir_to_use),
+                save_information,
+            )
+        })
+        .collect();
+
+    // Removed saves are staged for undo, the previous run's are purged to make room.
+    let journal = match strategy {
+        DeletionStrategy::Remove => {
+            let (purged, reclaimed) = purge_undo_area(&dir_to_use)?;
+            if purged > 0 {
+                info!(
+                    "Purged {} folder(s) staged by the last run, reclaimed {}",
+                    purged,
+                    format_size(reclaimed)
+                );
+            }
+            let sources: Vec<PathBuf> = saves_with_paths
+                .iter()
+                .map(|(path, _)| path.clone())
+                .chain(clutter.paths().cloned())
+                .collect();
+            Some(Journal::begin(&dir_to_use, &sources, SystemTime::now())?)
+        }
+        _ => None,
+    };
+
+    let mut progress = Progress::new(
+        "Deleting",
+        saves_with_paths.len(),
+        ProgressStyle::pick(stdout().is_terminal(), output::is_quiet()),
+        stdout(),
+    );
+    let mut results: Vec<(usize, FolderResult)> = Vec::new();
+    let next_folder = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let ctrl_c = interrupt::catch();
+
+    // Workers take the next folder until none are left or Ctrl+C is pressed, results are
+    // reported from this thread so the progress bar and audit log are only ever written from
+    // one place.
+    thread::scope(|scope| {
+        (0..jobs.clamp(1, saves_with_paths.len().max(1))).for_each(|_| {
+            let sender = sender.clone();
+            let (next_folder, saves_with_paths, journal, dir_to_use) =
+                (&next_folder, &saves_with_paths, &journal, &dir_to_use);
+            scope.spawn(move || loop {
+                // The folders being deleted are finished, the others are left alone.
+                if interrupt::is_interrupted() {
+                    break;
+                }
+                let i = next_folder.fetch_add(1, Ordering::Relaxed);
+                let Some((path, save_information)) = saves_with_paths.get(i) else {
+                    break;
+                };
+                let mut folder_result = delete_folder(
+                    path,
+                    save_information.root_or(dir_to_use),
+                    save_information,
+                    strategy,
+                    backup_dir,
+                    verify,
+                    journal.as_ref(),
+                );
+                // Counted per thread, so only this folder's retries.
+                folder_result.retried = retry::take_retried();
+                if sender.send((i, folder_result)).is_err() {
+                    break;
+                }
+            });
+        });
+        drop(sender);
+
+        receiver.iter().for_each(|(i, folder_result)| {
+            let (path, save_information) = &saves_with_paths[i];
+            progress.tick(&save_information.file_name);
+
+            match &folder_result {
+                FolderResult {
+                    result: Err(ProgramError::NotASave(e)),
+                    ..
+                } => progress.suspend(|| {
+                    eprintln!(
+                        "{} {}",
+                        color::paint("Skipped a folder that is not a save:", Color::Red),
+                        e
+                    )
+                }),
+                FolderResult {
+                    result: Err(e),
+                    backup_failed: true,
+                    ..
+                } => progress.suspend(|| {
+                    eprintln!(
+                        "Failed to back up {}, it was not deleted: {}",
+                        save_information.file_name, e
+                    )
+                }),
+                FolderResult { result: Err(e), .. } => progress.suspend(|| {
+                    eprintln!("Failed to delete {}: {}", save_information.file_name, e)
+                }),
+                _ => (),
+            }
+            audit_log.record(
+                &verbatim_path::for_display(path),
+                save_information,
+                &folder_result.result,
+            );
+            results.push((i, folder_result));
+        });
+    });
+    progress.finish();
+    drop(ctrl_c);
+
+    let not_saves = results
+        .iter()
+        .filter(|(_, folder_result)| matches!(folder_result.result, Err(ProgramError::NotASave(_))))
+        .count();
+    if not_saves > 0 {
+        eprintln!(
+            "{}",
+            color::paint(
+                &format!(
+                    "Skipped {} folder(s) without a save package, check them and pass --no-verify to delete them anyway",
+                    not_saves
+                ),
+                Color::Red
+            )
+        );
+    }
+
+    // Reported in the order the saves were listed, not the order they finished in.
+    results.sort_by_key(|(i, _)| *i);
+    let retried: Vec<&str> = results
+        .iter()
+        .filter(|(_, folder_result)| folder_result.retried > 0)
+        .map(|(i, _)| saves_with_paths[*i].1.file_name.as_str())
+        .collect();
+    if !retried.is_empty() {
+        info!(
+            "Needed retries because files were locked: {}",
+            retried.join(", ")
+        );
+    }
+    let attempted = results.len();
+    let report = results.into_iter().fold(
+        DeletionReport::default(),
+        |mut report, (i, folder_result)| {
+            let save = saves_with_paths[i].1.clone();
+            match folder_result.result {
+                Ok(()) => report.deleted.push(save),
+                Err(e) => report.failed.push(FailedDeletion {
+                    save,
+                    error: e.to_string(),
+                }),
+            }
+            report
+        },
+    );
+
+    let result = match attempted < saves_with_paths.len() {
+        true => Err(interrupted(&saves_with_paths, &report.deleted)),
+        false => {
+            remove_clutter(clutter, strategy, journal.as_ref(), audit_log);
+            Ok(report)
+        }
+    };
+    audit_log.footer(&result);
+    result
+}
+
+/// Lists what was and wasn't deleted when Ctrl+C stopped the deletion.
+fn interrupted(
+    saves_with_paths: &[(PathBuf, SaveInformation)],
+    deleted: &[SaveInformation],
+) -> ProgramError {
+    let not_deleted: Vec<&SaveInformation> = saves_with_paths
+        .iter()
+        .map(|(_, save_information)| save_information)
+        .filter(|save_information| !deleted.contains(save_information))
+        .collect();
+
+    eprintln!("Interrupted, deleted {} folder(s):", deleted.len());
+    deleted
+        .iter()
+        .for_each(|save_information| eprintln!("  {}", save_information.file_name));
+    eprintln!("Not deleted, {} folder(s):", not_deleted.len());
+    not_deleted
+        .iter()
+        .for_each(|save_information| eprintln!("  {}", save_information.file_name));
+
+    ProgramError::Interrupted(format!(
+        "Stopped by Ctrl+C after deleting {} of {} folder(s)",
+        deleted.len(),
+        saves_with_paths.len()
+    ))
+}
+
+/// What happened to a single save folder in `delete`.
+struct FolderResult {
+    result: Result<(), ProgramError>,
+    /// A save that couldn't be backed up is kept, the others are still deleted.
+    backup_failed: bool,
+    /// How often removing a locked file was tried again.
+    retried: usize,
+}
+
+/// The folder of the save below its root, ending in the folder name exactly as on disk.
+fn deletion_path(save_information: &SaveInformation, dir_to_use: &Path) -> PathBuf {
+    verbatim_path::for_removal(
+        &save_information
+            .root_or(dir_to_use)
+            .join(&save_information.folder_name),
+    )
+}
+
+fn delete_folder(
+    path: &Path,
+    root: &Path,
+    save_information: &SaveInformation,
+    strategy: &DeletionStrategy,
+    backup_dir: Option<&Path>,
+    verify: bool,
+    journal: Option<&Journal>,
+) -> FolderResult {
+    // Names come from the scan, a plan or stdin, neither may lead out of the save folder.
+    if let Err(e) = save_root::check_within(root, path) {
+        return FolderResult {
+            result: Err(e),
+            backup_failed: false,
+            retried: 0,
+        };
+    }
+    // Corrupt saves lack a valid package by definition and were confirmed separately.
+    if verify && !save_information.corrupt && !lspk::contains_package(path) {
+        return FolderResult {
+            result: Err(ProgramError::NotASave(format!(
+                "{} has no .lsv file starting with {}",
+                verbatim_path::for_display(path).display(),
+                String::from_utf8_lossy(lspk::MAGIC)
+            ))),
+            backup_failed: false,
+            retried: 0,
+        };
+    }
+
+    if let Some(backup_dir) = backup_dir {
+        let archive_path = backup_dir.join(format!("{}.zip", save_information.file_name));
+        if let Err(e) = zip_archive::write_zip(path, &archive_path) {
+            return FolderResult {
+                result: Err(e),
+                backup_failed: true,
+                retried: 0,
+            };
+        }
+    }
+
+    FolderResult {
+        result: remove_with(path, strategy, journal),
+        backup_failed: false,
+        retried: 0,
+    }
+}
+
+/// Gets rid of a save folder, empty folder or loose file the way `strategy` asks.
+fn remove_with(
+    path: &Path,
+    strategy: &DeletionStrategy,
+    journal: Option<&Journal>,
+) -> Result<(), ProgramError> {
+    match strategy {
+        DeletionStrategy::Remove => journal.map_or(
+            Err(ProgramError::FailedToDelete(
+                "No undo journal for this run".to_string(),
+            )),
+            |journal| journal.stage(path),
+        ),
+        // The recycle bin would take the folder the link points to.
+        DeletionStrategy::Trash if path.is_symlink() => remove_link(path),
+        DeletionStrategy::Trash => trash::move_to_trash(path),
+        DeletionStrategy::Quarantine(quarantine_dir) => {
+            quarantine::quarantine(path, quarantine_dir).map(|_| ())
+        }
+        DeletionStrategy::Archive(archive_path) => {
+            tar_archive::append(path, archive_path).and_then(|_| staged_removal::remove(path))
+        }
+    }
+}
+
+/// Removes the confirmed empty folders and loose files after the saves, the same way as the
+/// saves and recorded in the audit log. A folder the game wrote into since the scan is kept.
+fn remove_clutter(
+    clutter: &Clutter,
+    strategy: &DeletionStrategy,
+    journal: Option<&Journal>,
+    audit_log: &mut AuditLog,
+) {
+    let empty_folders: Vec<&PathBuf> = clutter
+        .empty_folders
+        .iter()
+        .filter(|folder| empty_folder::is_empty(folder))
+        .collect();
+
+    for (kind, paths) in [
+        ("empty folder", empty_folders),
+        ("loose file", clutter.loose_files.iter().collect()),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+
+        let deleted = paths
+            .into_iter()
+            .filter(|path| {
+                let result = remove_with(path, strategy, journal);
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                audit_log.record(
+                    path,
+                    &SaveInformation::new(
+                        name.to_string(),
+                        name.to_string(),
+                        SaveType::Unrecognized,
+                        None,
+                    ),
+                    &result,
+                );
+                if let Err(e) = &result {
+                    eprintln!("Failed to delete {} {}: {}", kind, path.display(), e);
+                }
+                result.is_ok()
+            })
+            .count();
+        outcome!("Deleted {} {}(s)", deleted, kind);
+    }
+}
+
+/// Returns the number of folders that could not be restored.
+fn undo(directory: &Path) -> Result<usize, ProgramError> {
+    let journal = Journal::read(directory)?;
+    let (restored, failed) = journal.undo();
+
+    failed.iter().for_each(|(path, e)| {
+        eprintln!(
+            "Failed to restore {}: {}",
+            verbatim_path::for_display(path).display(),
+            e
+        )
+    });
+    if failed.is_empty() {
+        purge_undo_area(directory)?;
+    }
+    outcome!(
+        "Restored {} folder(s) of run {}, failed {}",
+        restored.len(),
+        journal.run_id,
+        failed.len()
+    );
+
+    Ok(failed.len())
+}
+
+/// Permanently removes the saves staged by the last run, returning how many were removed and
+/// the bytes that freed. Staged saves only count as reclaimed space once they are purged here.
+fn purge_undo_area(directory: &Path) -> Result<(usize, u64), ProgramError> {
+    let undo_dir = undo::undo_dir(directory);
+    if !undo_dir.exists() {
+        return Ok((0, 0));
+    }
+    let staged_bytes = folder_size(&undo_dir);
+
+    let staged_saves = fs::read_dir(&undo_dir)
+        .map_err(|e| ProgramError::FailedToReadDir(e.to_string()))?
+        .flatten()
+        .filter(|run_dir| run_dir.path().is_dir())
+        .map(|run_dir| {
+            fs::read_dir(run_dir.path()).map_err(|e| ProgramError::FailedToReadDir(e.to_string()))
+        })
+        .collect::<Result<Vec<fs::ReadDir>, ProgramError>>()?
+        .into_iter()
+        .flat_map(|staged_saves| staged_saves.flatten())
+        .map(|staged_save| staged_removal::remove(&staged_save.path()))
+        .collect::<Vec<Result<(), ProgramError>>>();
+
+    // A save that can't be removed stays in the undo area for the next purge, the others go.
+    let failed: Vec<&ProgramError> = staged_saves
+        .iter()
+        .filter_map(|result| result.as_ref().err())
+        .collect();
+    failed
+        .iter()
+        .for_each(|e| eprintln!("Warning: could not purge a save of the last run: {}", e));
+    if failed.is_empty() {
+        fs::remove_dir_all(&undo_dir).map_err(|e| ProgramError::FailedToDelete(e.to_string()))?;
+    }
+
+    Ok((
+        staged_saves.len() - failed.len(),
+        staged_bytes.saturating_sub(folder_size(&undo_dir)),
+    ))
+}
+
+/// Removes a symlinked save folder, never the folder it links to.
+fn remove_link(path: &Path) -> Result<(), ProgramError> {
+    // Windows removes links to folders like folders, elsewhere they are files.
+    let result = if cfg!(windows) {
+        fs::remove_dir(path)
+    } else {
+        fs::remove_file(path)
+    };
+
+    result.map_err(|e| {
+        ProgramError::FailedToDelete(format!(
+            "{}: {}",
+            verbatim_path::for_display(path).display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod program_config_should {
+    use std::{ffi::OsString, path::PathBuf};
+
+    use clap::{CommandFactory, Parser};
+
+    use crate::{
+        apply_config_file, completions, completions::Shell, config_file::ConfigFile,
+        folder_pattern::FolderPattern, parse_program_config, Command, ConfigAction, ProgramConfig,
+    };
+
+    #[test]
+    fn clean_without_subcommand() {
+        let program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "-p", "saves", "-s", "3"]).unwrap();
+
+        assert_eq!(program_config.command, None);
+        assert_eq!(program_config.clean_config.saves_to_preserve, Some(3));
+    }
+
+    #[test]
+    fn accept_several_paths() {
+        let program_config = ProgramConfig::try_parse_from([
+            "bg3sc",
+            "-p",
+            "saves",
+            "--path-to-save-folder",
+            "copy",
+            "--combine-paths",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            program_config.path_to_save_folder,
+            vec![OsString::from("saves"), OsString::from("copy")]
+        );
+        assert!(program_config.combine_paths);
+    }
+
+    #[test]
+    fn prefer_command_line_over_config_file() {
+        let config_file = ConfigFile {
+            profile: Some("Public".to_string()),
+            verbose: true,
+            ..Default::default()
+        };
+
+        let mut program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "--all-profiles"]).unwrap();
+        apply_config_file(&mut program_config, &config_file);
+        assert_eq!(program_config.profile, None);
+        assert!(program_config.verbose);
+
+        let mut program_config = ProgramConfig::try_parse_from(["bg3sc"]).unwrap();
+        apply_config_file(&mut program_config, &config_file);
+        assert_eq!(program_config.profile, Some("Public".to_string()));
+    }
+
+    #[test]
+    fn read_folder_names_with_the_same_pattern_for_every_command() {
+        let pattern = r"^(?P<type>\w+) (?P<name>\w+) (?P<campaign>\d+) #(?P<number>\d+)$";
+        let config_file = ConfigFile {
+            pattern: Some(FolderPattern::new(pattern).unwrap()),
+            ..Default::default()
+        };
+
+        let mut list = parse_program_config(["bg3sc", "list"]).unwrap();
+        apply_config_file(&mut list, &config_file);
+        let stats = parse_program_config(["bg3sc", "stats", "--pattern", pattern]).unwrap();
+
+        assert_eq!(list.pattern, config_file.pattern);
+        assert_eq!(stats.pattern, config_file.pattern);
+    }
+
+    #[test]
+    fn read_save_type_keywords_for_every_command() {
+        let config_file = ConfigFile {
+            auto_keyword: vec!["Autozapis".to_string()],
+            ..Default::default()
+        };
+
+        let mut list =
+            parse_program_config(["bg3sc", "--quick-keyword", "Szybki zapis", "list"]).unwrap();
+        apply_config_file(&mut list, &config_file);
+
+        assert_eq!(list.quick_keyword, vec!["Szybki zapis".to_string()]);
+        assert_eq!(list.auto_keyword, config_file.auto_keyword);
+    }
+
+    #[test]
+    fn parse_config_init_subcommand() {
+        let program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "config", "init", "--config", "c.toml"])
+                .unwrap();
+
+        assert_eq!(
+            program_config.command,
+            Some(Command::Config {
+                action: ConfigAction::Init { force: false }
+            })
+        );
+        assert_eq!(program_config.config, Some(PathBuf::from("c.toml")));
+    }
+
+    #[test]
+    fn accept_clean_subcommand() {
+        let program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "clean", "-p", "saves", "-s", "3", "-y"])
+                .unwrap();
+
+        let Some(Command::Clean(clean_config)) = program_config.command else {
+            panic!("Clean subcommand was not parsed");
+        };
+        assert_eq!(clean_config.saves_to_preserve, Some(3));
+        assert!(clean_config.yes);
+        assert_eq!(
+            program_config.path_to_save_folder,
+            vec![OsString::from("saves")]
+        );
+    }
+
+    #[test]
+    fn share_path_with_list_subcommand() {
+        let program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "list", "-p", "saves"]).unwrap();
+
+        assert_eq!(
+            program_config.command,
+            Some(Command::List { campaign: None })
+        );
+        assert_eq!(
+            program_config.path_to_save_folder,
+            vec![OsString::from("saves")]
+        );
+    }
+
+    #[test]
+    fn parse_restore_subcommand() {
+        let program_config =
+            ProgramConfig::try_parse_from(["bg3sc", "restore", "quarantine", "--all"]).unwrap();
+
+        assert_eq!(
+            program_config.command,
+            Some(Command::Restore {
+                quarantine_dir: "quarantine".into(),
+                all: true
+            })
+        );
+    }
+
+    #[test]
+    fn reject_quarantine_with_trash() {
+        let result = ProgramConfig::try_parse_from(["bg3sc", "--quarantine", "q", "--trash"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn generate_bash_completions_with_all_options() {
+        let mut command = ProgramConfig::command().bin_name(env!("CARGO_BIN_NAME"));
+        command.build();
+
+        let script = completions::generate(Shell::Bash, &command);
+        assert!(script.contains("--saves-to-preserve"));
+        assert!(script.contains("restore)"));
+        assert!(script.contains("complete -F _bg3sc -o default bg3sc"));
+    }
+
+    #[test]
+    fn reject_clean_options_before_subcommand() {
+        let result = parse_program_config(["bg3sc", "-s", "3", "list"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn accept_shared_options_before_subcommand() {
+        let list = parse_program_config(["bg3sc", "-p", "saves", "--verbose", "list"]).unwrap();
+        let undo = parse_program_config(["bg3sc", "-p", "saves", "undo"]).unwrap();
+
+        assert_eq!(list.command, Some(Command::List { campaign: None }));
+        assert!(list.verbose);
+        assert_eq!(undo.command, Some(Command::Undo));
+        assert_eq!(
+            (list.path_to_save_folder, undo.path_to_save_folder),
+            (vec![OsString::from("saves")], vec![OsString::from("saves")])
+        );
+    }
+}
+
+#[cfg(test)]
+mod scan_saves_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        character_key::CharacterKey,
+        folder_filter::FolderFilter,
+        group_saves,
+        ignore_list::IgnoreList,
+        scan_saves,
+        test_utils::{set_modified, TempDir},
+        ProgramError, SaveType,
+    };
+
+    #[test]
+    fn report_every_examined_entry() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("SomeName-123456789__QuickSave_17");
+        temp_dir.create_save("NotASave");
+        std::fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+
+        let mut examined = 0;
+        scan_saves(temp_dir.path(), &FolderFilter::default(), || examined += 1).unwrap();
+
+        assert_eq!(examined, 3);
+    }
+
+    #[test]
+    fn parse_save_folders_and_skip_invalid_ones() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("SomeName-123456789__QuickSave_17");
+        temp_dir.create_save("SomeName-123456789__AutoSave_3");
+        temp_dir.create_save("NotASave");
+
+        let mut scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        scan_result.saves.sort_by_key(|save| save.save_number);
+
+        assert_eq!(scan_result.saves.len(), 2);
+        assert_eq!(scan_result.saves.first().unwrap().save_type, SaveType::Auto);
+        assert_eq!(scan_result.saves.last().unwrap().save_type, SaveType::Quick);
+    }
+
+    #[test]
+    fn keep_reason_for_skipped_folders() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("SomeName-123456789__QuickSave_17");
+        temp_dir.create_save("NotASave");
+
+        let scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+
+        assert_eq!(scan_result.saves.len(), 1);
+        assert_eq!(
+            scan_result.skipped_folders,
+            vec![(
+                "NotASave".to_string(),
+                ProgramError::NotEnoughUnderscores(
+                    "Did not find the correct number of underscores. Cannot continue with this save."
+                        .to_string()
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn scan_saves_of_non_ascii_characters() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Sömé-123456789__QuickSave_3");
+        temp_dir.create_save("Sömé-123456789__QuickSave_4");
+        temp_dir.create_save("塔夫-123456789__AutoSave_1");
+
+        let map = group_saves(
+            scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+                .unwrap()
+                .saves,
+        );
+
+        assert_eq!(
+            map[&CharacterKey::in_campaign("Sömé", "123456789")]
+                .quick_saves
+                .len(),
+            2
+        );
+        assert_eq!(
+            map[&CharacterKey::in_campaign("塔夫", "123456789")]
+                .auto_saves
+                .len(),
+            1
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_symlinked_folders_without_following_them() {
+        let temp_dir = TempDir::new();
+        let other_drive = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let target = other_drive.create_save("Tav-123456789__QuickSave_2");
+        let link = temp_dir.path().join("Tav-123456789__QuickSave_2");
+        std::os::unix::fs::symlink(target, &link).unwrap();
+
+        let scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        assert_eq!(scan_result.saves.len(), 1);
+        assert_eq!(scan_result.symlinks, vec![link]);
+
+        let scan_result = scan_saves(
+            temp_dir.path(),
+            &FolderFilter {
+                follow_symlinks: true,
+                ..Default::default()
+            },
+            || (),
+        )
+        .unwrap();
+        assert_eq!(scan_result.saves.len(), 2);
+        assert!(scan_result.symlinks.is_empty());
+    }
+
+    #[test]
+    fn ignore_files_next_to_save_folders() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("SomeName-123456789__QuickSave_17");
+        std::fs::write(temp_dir.path().join("stray.webp"), b"").unwrap();
+
+        let scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        assert_eq!(scan_result.saves.len(), 1);
+        assert!(scan_result.skipped_folders.is_empty());
+    }
+
+    #[test]
+    fn record_modified_time_of_save_folders() {
+        let temp_dir = TempDir::new();
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        set_modified(
+            &temp_dir.create_save("SomeName-123456789__QuickSave_17"),
+            modified,
+        );
+
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        assert_eq!(saves.first().unwrap().modified, Some(modified));
+    }
+
+    #[test]
+    fn flag_corrupt_save_folders() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("SomeName-123456789__QuickSave_17");
+        let corrupt_save = temp_dir.create_save("SomeName-123456789__QuickSave_18");
+        std::fs::write(corrupt_save.join("test.lsv"), b"").unwrap();
+
+        let mut saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        saves.sort_by_key(|save| save.save_number);
+
+        assert!(!saves[0].corrupt);
+        assert!(saves[1].corrupt);
+    }
+
+    #[test]
+    fn error_when_directory_is_missing() {
+        let temp_dir = TempDir::new();
+
+        let result = scan_saves(
+            &temp_dir.path().join("missing"),
+            &FolderFilter::default(),
+            || (),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn ignore_hidden_backup_and_listed_entries_before_parsing() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save(".stversions");
+        temp_dir.create_save("Tav-123456789__QuickSave_2.bak");
+        temp_dir.create_save("__MACOSX");
+        temp_dir.create_save("Old saves");
+        std::fs::write(temp_dir.path().join(".DS_Store"), "").unwrap();
+        let ignore_list = IgnoreList::new(&["Old *".to_string()]).unwrap();
+        let folder_filter = FolderFilter {
+            ignore_list: Some(&ignore_list),
+            ..Default::default()
+        };
+
+        let mut scan_result = scan_saves(temp_dir.path(), &folder_filter, || ()).unwrap();
+        scan_result.ignored.sort();
+
+        assert_eq!(scan_result.saves.len(), 1);
+        assert!(scan_result.skipped_folders.is_empty());
+        assert!(scan_result.loose_files.is_empty());
+        assert_eq!(
+            scan_result.ignored,
+            [
+                (".DS_Store", ".*"),
+                (".stversions", ".*"),
+                ("Old saves", "Old *"),
+                ("Tav-123456789__QuickSave_2.bak", "*.bak"),
+                ("__MACOSX", "__MACOSX"),
+            ]
+            .map(|(name, glob)| (name.to_string(), glob.to_string()))
+        );
+    }
+
+    #[test]
+    fn list_empty_folders_separately() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        std::fs::create_dir(temp_dir.path().join("Tav-123456789__QuickSave_2")).unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("Tav-123456789__QuickSave_3/nested")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("NotASave")).unwrap();
+
+        let mut scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        scan_result.empty_folders.sort();
+
+        assert_eq!(scan_result.saves.len(), 1);
+        assert!(scan_result.skipped_folders.is_empty());
+        assert_eq!(
+            scan_result.empty_folders,
+            vec![
+                temp_dir.path().join("NotASave"),
+                temp_dir.path().join("Tav-123456789__QuickSave_2"),
+                temp_dir.path().join("Tav-123456789__QuickSave_3"),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod select_empty_folders_should {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        select_empty_folders,
+        test_utils::{set_modified, TempDir},
+        CleanConfig,
+    };
+
+    fn empty_folders(temp_dir: &TempDir, now: SystemTime) -> Vec<PathBuf> {
+        ["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_2"]
+            .iter()
+            .map(|folder_name| {
+                let folder = temp_dir.path().join(folder_name);
+                std::fs::create_dir(&folder).unwrap();
+                set_modified(&folder, now - Duration::from_secs(3600));
+                folder
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pick_empty_folders_only_with_clean_empty() {
+        let temp_dir = TempDir::new();
+        let now = SystemTime::now();
+        let folders = empty_folders(&temp_dir, now);
+        let clean_config = CleanConfig {
+            clean_empty: true,
+            ..Default::default()
+        };
+
+        assert!(select_empty_folders(&folders, &CleanConfig::default(), now).is_empty());
+        assert_eq!(select_empty_folders(&folders, &clean_config, now), folders);
+        assert!(folders.iter().all(|folder| folder.exists()));
+    }
+
+    #[test]
+    fn leave_out_folders_the_game_may_still_write() {
+        let temp_dir = TempDir::new();
+        let now = SystemTime::now();
+        let folders = empty_folders(&temp_dir, now);
+        set_modified(&folders[0], now);
+        let clean_config = CleanConfig {
+            clean_empty: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            select_empty_folders(&folders, &clean_config, now),
+            vec![folders[1].clone()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod select_loose_files_should {
+    use std::path::{Path, PathBuf};
+
+    use crate::{
+        exclude_list::ExcludeList, folder_filter::FolderFilter, scan_saves, select_loose_files,
+        test_utils::TempDir, CleanConfig,
+    };
+
+    fn loose_files(temp_dir: &TempDir) -> Vec<PathBuf> {
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        [
+            "Tav-123456789__QuickSave_7.webp",
+            "orphan.lsv",
+            "desktop.ini",
+        ]
+        .iter()
+        .for_each(|file_name| std::fs::write(temp_dir.path().join(file_name), b"").unwrap());
+
+        let mut scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        assert_eq!(scan_result.saves.len(), 1);
+        scan_result.loose_files.sort();
+        scan_result.loose_files
+    }
+
+    #[test]
+    fn list_files_next_to_save_folders_but_never_as_saves() {
+        let temp_dir = TempDir::new();
+
+        assert_eq!(
+            loose_files(&temp_dir),
+            vec![
+                temp_dir.path().join("Tav-123456789__QuickSave_7.webp"),
+                temp_dir.path().join("desktop.ini"),
+                temp_dir.path().join("orphan.lsv"),
+            ]
+        );
+    }
+
+    #[test]
+    fn pick_files_except_excluded_ones() {
+        let temp_dir = TempDir::new();
+        let files = loose_files(&temp_dir);
+        let exclude_list = ExcludeList::parse(Path::new("ignore.txt"), "desktop.ini\n").unwrap();
+        let clean_config = CleanConfig {
+            clean_loose_files: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            select_loose_files(&files, Some(&exclude_list), &clean_config),
+            vec![
+                temp_dir.path().join("Tav-123456789__QuickSave_7.webp"),
+                temp_dir.path().join("orphan.lsv"),
+            ]
+        );
+        assert!(select_loose_files(&files, None, &CleanConfig::default()).is_empty());
+        assert!(files.iter().all(|file| file.exists()));
+    }
+}
+
+#[cfg(test)]
+mod remove_clutter_should {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        audit_log::AuditLog,
+        clean,
+        clean_config::CleanConfig,
+        clutter::Clutter,
+        deletion_strategy::DeletionStrategy,
+        program_errors::ProgramError,
+        remove_clutter,
+        test_utils::{set_modified, TempDir},
+        undo,
+    };
+
+    /// Two saves next to an empty folder and a loose file, both old enough to be removed.
+    fn saves_with_clutter() -> TempDir {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("Tav-123456789__QuickSave_2");
+        let empty = temp_dir.path().join("Tav-123456789__QuickSave_3");
+        fs::create_dir(&empty).unwrap();
+        set_modified(&empty, SystemTime::now() - Duration::from_secs(3600));
+        fs::write(temp_dir.path().join("orphan.lsv"), b"").unwrap();
+
+        temp_dir
+    }
+
+    fn clean_config(saves_to_preserve: usize) -> CleanConfig {
+        CleanConfig {
+            saves_to_preserve: Some(saves_to_preserve),
+            clean_empty: true,
+            clean_loose_files: true,
+            yes: true,
+            allow_while_running: true,
+            quiet: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stage_clutter_for_undo_like_the_saves() {
+        let temp_dir = saves_with_clutter();
+
+        clean(vec![temp_dir.path().to_path_buf()], &clean_config(1), false).unwrap();
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+        assert!(!temp_dir.path().join("orphan.lsv").exists());
+
+        undo(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+        assert!(temp_dir.path().join("orphan.lsv").exists());
+    }
+
+    #[test]
+    fn remove_clutter_when_no_save_is_deleted() {
+        let temp_dir = saves_with_clutter();
+
+        clean(
+            vec![temp_dir.path().to_path_buf()],
+            &clean_config(10),
+            false,
+        )
+        .unwrap();
+
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+        assert!(!temp_dir.path().join("orphan.lsv").exists());
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+
+    #[test]
+    fn keep_clutter_when_the_run_is_refused() {
+        let temp_dir = saves_with_clutter();
+
+        let everything = CleanConfig {
+            allow_delete_all: true,
+            ..clean_config(0)
+        };
+
+        let result = clean(vec![temp_dir.path().to_path_buf()], &everything, false);
+
+        assert!(matches!(result, Err(ProgramError::MassDelete(_))));
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+        assert!(temp_dir.path().join("orphan.lsv").exists());
+    }
+
+    #[test]
+    fn keep_folders_the_game_wrote_into_since_the_scan() {
+        let temp_dir = saves_with_clutter();
+        let folder = temp_dir.path().join("Tav-123456789__QuickSave_3");
+        fs::write(folder.join("test.lsv"), b"LSPK").unwrap();
+        let clutter = Clutter {
+            empty_folders: vec![folder.clone()],
+            loose_files: Vec::new(),
+        };
+
+        remove_clutter(
+            &clutter,
+            &DeletionStrategy::Quarantine(temp_dir.path().join("quarantine")),
+            None,
+            &mut AuditLog::disabled(),
+        );
+
+        assert!(folder.join("test.lsv").exists());
+    }
+
+    #[test]
+    fn keep_clutter_on_dry_run() {
+        let temp_dir = saves_with_clutter();
+        let dry_run = CleanConfig {
+            dry_run: true,
+            yes: false,
+            ..clean_config(1)
+        };
+
+        clean(vec![temp_dir.path().to_path_buf()], &dry_run, false).unwrap();
+
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+        assert!(temp_dir.path().join("orphan.lsv").exists());
+    }
+}
+
+#[cfg(test)]
+mod path_to_use_should {
+    use std::{ffi::OsString, fs, path::PathBuf};
+
+    use crate::{
+        default_paths::{Environment, Platform},
+        path_to_use,
+        profiles::story_dir,
+        program_errors::ProgramError,
+        test_utils::TempDir,
+    };
+
+    fn windows(local_app_data: &TempDir) -> Environment {
+        Environment {
+            platform: Platform::Windows,
+            local_app_data: Some(local_app_data.path().to_path_buf()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prefer_flag_then_env_var_then_config_file_then_detected_folder() {
+        let temp_dir = TempDir::new();
+        let local_app_data = TempDir::new();
+        let player_profiles = local_app_data
+            .path()
+            .join("Larian Studios/Baldur's Gate 3/PlayerProfiles");
+        fs::create_dir_all(story_dir(&player_profiles.join("Public"))).unwrap();
+        let environment = windows(&local_app_data);
+        let config_paths = vec![PathBuf::from("file-saves")];
+        let env_dir = Some(OsString::from(temp_dir.path()));
+
+        assert_eq!(
+            path_to_use(
+                vec![OsString::from("saves")],
+                env_dir.clone(),
+                &config_paths,
+                &environment
+            )
+            .unwrap(),
+            vec![PathBuf::from("saves")]
+        );
+        assert_eq!(
+            path_to_use(Vec::new(), env_dir, &config_paths, &environment).unwrap(),
+            vec![temp_dir.path().to_path_buf()]
+        );
+
+        assert_eq!(
+            path_to_use(Vec::new(), None, &config_paths, &environment).unwrap(),
+            config_paths
+        );
+        assert_eq!(
+            path_to_use(Vec::new(), None, &[], &environment).unwrap(),
+            vec![player_profiles]
+        );
+    }
+
+    #[test]
+    fn fail_instead_of_using_the_current_directory() {
+        let local_app_data = TempDir::new();
+
+        assert!(matches!(
+            path_to_use(Vec::new(), None, &[], &windows(&local_app_data)),
+            Err(ProgramError::NoPath(e)) if e.contains("--path-to-save-folder")
+        ));
+    }
+
+    #[test]
+    fn reject_env_var_that_is_not_a_directory() {
+        let temp_dir = TempDir::new();
+        let missing_dir = temp_dir.path().join("missing");
+        let environment = windows(&temp_dir);
+
+        assert_eq!(
+            path_to_use(
+                Vec::new(),
+                Some(missing_dir.clone().into_os_string()),
+                &[],
+                &environment
+            ),
+            Err(ProgramError::NoPath(format!(
+                "BG3_SAVE_DIR is set to {}, which is not an existing directory",
+                missing_dir.display()
+            )))
+        );
+        assert!(path_to_use(Vec::new(), Some(OsString::new()), &[], &environment).is_err());
+    }
+}
+
+#[cfg(test)]
+mod scan_directories_should {
+    use crate::{
+        folder_filter::FolderFilter, program_errors::ProgramError, scan_directories,
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn collect_saves_and_unparsed_folders_of_every_directory() {
+        let temp_dir = TempDir::new();
+        let other_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("NotASave");
+        other_dir.create_save("Tav-123456789__AutoSave_2");
+        other_dir.create_save("Tav-123456789__QuickSave_x");
+
+        let mut scan_result = scan_directories(
+            &[
+                temp_dir.path().to_path_buf(),
+                other_dir.path().to_path_buf(),
+            ],
+            &FolderFilter::default(),
+        )
+        .unwrap();
+        scan_result.saves.sort_by_key(|save| save.save_number);
+
+        let saves: Vec<&str> = scan_result
+            .saves
+            .iter()
+            .map(|save| save.file_name.as_str())
+            .collect();
+        assert_eq!(
+            saves,
+            vec![
+                "Tav-123456789__QuickSave_x",
+                "Tav-123456789__QuickSave_1",
+                "Tav-123456789__AutoSave_2"
+            ]
+        );
+        let skipped: Vec<&str> = scan_result
+            .skipped_folders
+            .iter()
+            .map(|(folder_name, _)| folder_name.as_str())
+            .collect();
+        assert_eq!(skipped, vec!["NotASave"]);
+        assert!(matches!(
+            scan_result.skipped_folders[0].1,
+            ProgramError::NotEnoughUnderscores(_)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod scan_with_folder_filter_should {
+    use crate::{folder_filter::FolderFilter, regex::Regex, scan_saves, test_utils::TempDir};
+
+    #[test]
+    fn leave_out_folders_by_match_and_exclude() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("Tav-123456789__QuickSave_2_KEEP");
+        temp_dir.create_save("Tav-123456789__AutoSave_3");
+        let include = Regex::new("QuickSave").unwrap();
+        let exclude = vec![Regex::new("_KEEP$").unwrap()];
+
+        let mut scan_result = scan_saves(
+            temp_dir.path(),
+            &FolderFilter {
+                include: Some(&include),
+                exclude: &exclude,
+                exclude_list: None,
+                ignore_list: None,
+                follow_symlinks: false,
+            },
+            || (),
+        )
+        .unwrap();
+        scan_result.filtered_folders.sort();
+
+        let saves: Vec<&str> = scan_result
+            .saves
+            .iter()
+            .map(|save| save.file_name.as_str())
+            .collect();
+        assert_eq!(saves, vec!["Tav-123456789__QuickSave_1"]);
+        assert_eq!(
+            scan_result.filtered_folders,
+            vec![
+                (
+                    "Tav-123456789__AutoSave_3".to_string(),
+                    "does not match --match QuickSave".to_string()
+                ),
+                (
+                    "Tav-123456789__QuickSave_2_KEEP".to_string(),
+                    "matches --exclude _KEEP$".to_string()
+                ),
+            ]
+        );
+        assert!(scan_result.skipped_folders.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod load_saves_should {
+    use std::fs;
+
+    use crate::{character_key::CharacterKey, load_saves, test_utils::TempDir};
+
+    #[test]
+    fn remember_the_root_of_each_save() {
+        let temp_dir = TempDir::new();
+        let other_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        other_dir.create_save("Tav-123456789__QuickSave_2");
+
+        let map = load_saves(
+            &[
+                temp_dir.path().to_path_buf(),
+                other_dir.path().to_path_buf(),
+            ],
+            false,
+        )
+        .unwrap();
+        let roots: Vec<_> = map[&CharacterKey::in_campaign("Tav", "123456789")]
+            .quick_saves
+            .iter()
+            .map(|save_information| save_information.root.as_deref())
+            .collect();
+
+        assert_eq!(roots, vec![Some(other_dir.path()), Some(temp_dir.path())]);
+    }
+
+    #[test]
+    fn measure_size_of_each_save_in_its_own_root() {
+        let temp_dir = TempDir::new();
+        let other_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        other_dir.create_save("Tav-123456789__QuickSave_2");
+        fs::write(
+            temp_dir
+                .path()
+                .join("Tav-123456789__QuickSave_1")
+                .join("a.webp"),
+            vec![0u8; 1000],
+        )
+        .unwrap();
+        fs::create_dir(other_dir.path().join("Tav-123456789__QuickSave_2/nested")).unwrap();
+        fs::write(
+            other_dir
+                .path()
+                .join("Tav-123456789__QuickSave_2/nested/b.webp"),
+            vec![0u8; 20],
+        )
+        .unwrap();
+
+        let map = load_saves(
+            &[
+                temp_dir.path().to_path_buf(),
+                other_dir.path().to_path_buf(),
+            ],
+            false,
+        )
+        .unwrap();
+        let sizes: Vec<u64> = map[&CharacterKey::in_campaign("Tav", "123456789")]
+            .quick_saves
+            .iter()
+            .map(|save_information| save_information.size_bytes)
+            .collect();
+
+        // Both folders also hold the 4 byte test.lsv.
+        assert_eq!(sizes, vec![24, 1004]);
+    }
+}
+
+#[cfg(test)]
+mod read_metadata_should {
+    use std::fs;
+
+    use crate::{
+        character_key::CharacterKey, group_saves, lsf::write_attributes, lspk::write_package,
+        read_metadata, scan_directories, test_utils::TempDir, FolderFilter,
+    };
+
+    #[test]
+    fn group_by_metadata_and_fall_back_to_folder_name() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("Tav-123456789__QuickSave_2");
+        fs::write(
+            save_path.join("test.lsv"),
+            write_package(&[(
+                "meta.lsf",
+                &write_attributes(&[("LeaderName", "Durge"), ("GameVersion", "4.1.1.5022896")]),
+            )]),
+        )
+        .unwrap();
+
+        let saves = scan_directories(&[temp_dir.path().to_path_buf()], &FolderFilter::default())
+            .unwrap()
+            .saves;
+        let map = group_saves(read_metadata(saves, temp_dir.path(), false));
+
+        let durge = &map[&CharacterKey::in_campaign("Durge", "123456789")].quick_saves;
+        assert_eq!(durge.len(), 1);
+        assert_eq!(durge[0].file_name, "Tav-123456789__QuickSave_1");
+        assert_eq!(durge[0].game_version.as_deref(), Some("4.1.1.5022896"));
+        let tav = &map[&CharacterKey::in_campaign("Tav", "123456789")].quick_saves;
+        assert_eq!(tav.len(), 1);
+        assert_eq!(tav[0].game_version, None);
+    }
+}
+
+#[cfg(test)]
+mod character_subtotals_should {
+    use crate::{character_subtotals, SaveInformation, SaveType};
+
+    #[test]
+    fn sum_count_and_size_per_character() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()).with_size_bytes(1000),
+            SaveInformation::new_random(SaveType::Quick, "Durge".to_string()).with_size_bytes(24),
+            SaveInformation::new_random(SaveType::Auto, "Tav".to_string()).with_size_bytes(24),
+        ];
+
+        assert_eq!(
+            character_subtotals(&saves),
+            vec!["Durge: 1 save(s), 24 B", "Tav: 2 save(s), 1.0 KiB"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod save_label_should {
+    use std::time::UNIX_EPOCH;
+
+    use crate::{save_label, SaveInformation, SaveType};
+
+    #[test]
+    fn right_align_the_sizes() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()).with_size_bytes(4),
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()).with_size_bytes(2048),
+        ];
+
+        assert!(save_label(&saves[0], &saves).starts_with("    4 B | "));
+        assert!(save_label(&saves[1], &saves).starts_with("2.0 KiB | "));
+    }
+
+    #[test]
+    fn show_unknown_dates_as_wide_as_known_ones() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+                .with_modified(Some(UNIX_EPOCH)),
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()).with_modified(None),
+        ];
+
+        let labels: Vec<String> = saves.iter().map(|save| save_label(save, &saves)).collect();
+
+        assert!(labels[1].starts_with("0 B | unknown          | "));
+        assert_eq!(labels[0].find(" | Tav"), labels[1].find(" | Tav"));
+    }
+}
+
+#[cfg(test)]
+mod clean_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        clean,
+        clean_config::CleanConfig,
+        folder_size,
+        plan::{self, Plan},
+        program_errors::ProgramError,
+        run_summary::{FreedSpace, RunSummary},
+        sort_by::SortBy,
+        test_utils::{set_modified, TempDir},
+    };
+
+    fn save_folders() -> TempDir {
+        let temp_dir = TempDir::new();
+        (1..=4).for_each(|n| {
+            temp_dir.create_save(&format!("Tav-123456789__QuickSave_{}", n));
+        });
+        temp_dir.create_save("Tav-123456789__AutoSave_1");
+
+        temp_dir
+    }
+
+    #[test]
+    fn summarize_deleted_saves() {
+        let temp_dir = save_folders();
+        let save_size = folder_size(&temp_dir.path().join("Tav-123456789__QuickSave_1"));
+
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(1),
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            RunSummary {
+                scanned: 5,
+                kept: 2,
+                deleted: 3,
+                failed: 0,
+                bytes_removed: 3 * save_size,
+                dry_run: false,
+                freed_space: FreedSpace::Staged,
+            }
+        );
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_4").exists());
+    }
+
+    #[test]
+    fn summarize_dry_run_without_deleting() {
+        let temp_dir = save_folders();
+
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(2),
+                dry_run: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!((summary.kept, summary.deleted), (3, 2));
+        assert!(summary.dry_run);
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+
+    #[test]
+    fn finish_without_asking_when_nothing_is_to_delete() {
+        let temp_dir = save_folders();
+
+        // Not confirmed with --yes, asking would wait for stdin.
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(10),
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!((summary.scanned, summary.kept, summary.deleted), (5, 5, 0));
+    }
+
+    #[test]
+    fn refuse_directories_that_are_not_save_folders() {
+        let temp_dir = TempDir::new();
+        ["Photos", "Invoices", "Projects"]
+            .iter()
+            .for_each(|name| std::fs::create_dir(temp_dir.path().join(name)).unwrap());
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("Tav-123456789__QuickSave_2");
+        let clean_config = CleanConfig {
+            saves_to_preserve: Some(1),
+            yes: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        let result = clean(vec![temp_dir.path().to_path_buf()], &clean_config, false);
+        assert!(matches!(result, Err(ProgramError::NotASaveFolder(_))));
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                force_path: true,
+                ..clean_config
+            },
+            false,
+        )
+        .unwrap();
+        assert_eq!(summary.deleted, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_save_paths_that_are_not_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join(OsStr::from_bytes(b"Sav\xffgames"));
+        (1..=3).for_each(|n| {
+            let folder = save_path.join(format!("Tav-123456789__QuickSave_{}", n));
+            std::fs::create_dir_all(&folder).unwrap();
+            std::fs::write(folder.join("test.lsv"), b"LSPK").unwrap();
+        });
+
+        let summary = clean(
+            vec![save_path],
+            &CleanConfig {
+                saves_to_preserve: Some(1),
+                dry_run: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!((summary.scanned, summary.deleted), (3, 2));
+    }
+
+    #[test]
+    fn stop_before_deleting_anything_when_strict() {
+        let temp_dir = save_folders();
+        temp_dir.create_save("NotASave");
+        temp_dir.create_save("Tav-Camp_2");
+
+        let result = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(1),
+                strict: true,
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        );
+
+        match result {
+            Err(ProgramError::UnparsedFolders(e)) => {
+                assert!(e.contains("NotASave"));
+                assert!(!e.contains("Camp"));
+            }
+            other => panic!("Expected UnparsedFolders, got {:?}", other),
+        }
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+
+    #[test]
+    fn not_stop_for_unrecognized_save_types_when_strict() {
+        let temp_dir = save_folders();
+        temp_dir.create_save("Tav-Camp_2");
+
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(1),
+                strict: true,
+                dry_run: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(summary.scanned, 6);
+    }
+
+    #[test]
+    fn leave_saves_the_game_may_still_write_when_watching() {
+        let temp_dir = save_folders();
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        (2..=4).for_each(|number| {
+            set_modified(
+                &temp_dir
+                    .path()
+                    .join(format!("Tav-123456789__QuickSave_{}", number)),
+                an_hour_ago,
+            )
+        });
+
+        let summary = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(0),
+                allow_delete_all: true,
+                watch: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(summary.deleted, 3);
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+        assert!(temp_dir.path().join("Tav-123456789__AutoSave_1").exists());
+    }
+
+    #[test]
+    fn preserve_most_recently_modified_saves_when_sorting_by_mtime() {
+        let temp_dir = save_folders();
+        let now = SystemTime::now();
+        // The game reused slot 1 for the newest save.
+        [(1, 0), (2, 3), (3, 2), (4, 1)]
+            .iter()
+            .for_each(|(number, hours_ago)| {
+                set_modified(
+                    &temp_dir
+                        .path()
+                        .join(format!("Tav-123456789__QuickSave_{}", number)),
+                    now - Duration::from_secs(hours_ago * 3600),
+                )
+            });
+
+        clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                saves_to_preserve: Some(2),
+                sort_by: SortBy::Mtime,
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        let remaining = |number: u16| {
+            temp_dir
+                .path()
+                .join(format!("Tav-123456789__QuickSave_{}", number))
+                .exists()
+        };
+        assert_eq!(
+            (1..=4).map(remaining).collect::<Vec<bool>>(),
+            vec![true, false, false, true]
+        );
+    }
+
+    #[test]
+    fn refuse_a_plan_written_for_another_folder() {
+        let temp_dir = save_folders();
+        let other_dir = TempDir::new();
+        let plan_path = other_dir.path().join("plan.json");
+        plan::write_plan(
+            &plan_path,
+            &Plan::new(other_dir.path().to_path_buf(), Vec::new()),
+        )
+        .unwrap();
+
+        let result = clean(
+            vec![temp_dir.path().to_path_buf()],
+            &CleanConfig {
+                apply_plan: Some(plan_path),
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+            false,
+        );
+
+        assert!(matches!(result, Err(ProgramError::InvalidPlan(_))));
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+}
+
+#[cfg(test)]
+mod package_details_should {
+    use rand::Rng;
+
+    use crate::{
+        folder_pattern, package_details, package_details_by_pattern, FolderPattern, ProgramError,
+        SaveInformation, SaveType,
+    };
+
+    #[test]
+    fn parse_copies_like_the_folder_they_were_copied_from() {
+        ["(1)", "(2)", "- Copy"].into_iter().for_each(|suffix| {
+            let file_name = format!("Tav-123456789__QuickSave_50 {}", suffix);
+
+            let save_information = package_details(&file_name).unwrap();
+
+            assert_eq!(save_information.file_name, file_name);
+            assert_eq!(save_information.folder_name, file_name.as_str());
+            assert_eq!(save_information.save_type, SaveType::Quick);
+            assert_eq!(save_information.save_number, Some(50));
+            assert_eq!(
+                save_information.duplicate_of.as_deref(),
+                Some("Tav-123456789__QuickSave_50")
+            );
+        });
+    }
+
+    #[test]
+    fn package_values_returned() {
+        let rand = rand::thread_rng().gen_range(u16::MIN..=u16::MAX);
+        let test_save = format!("Some'me-1231415123_QuickSave_{}", rand);
+
+        let expected = SaveInformation::new(
+            test_save.clone(),
+            "Some'me".to_string(),
+            SaveType::Quick,
+            Some(rand.into()),
+        )
+        .with_campaign_id(Some("1231415123".to_string()));
+
+        let result = package_details(test_save.as_str()).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_folder_names_of_random_saves() {
+        [SaveType::Quick, SaveType::Auto]
+            .into_iter()
+            .for_each(|save_type| {
+                let save = SaveInformation::new_random(save_type, "Tav_1".to_string());
+
+                let result = package_details(&save.file_name).unwrap();
+                assert_eq!(result.save_number, save.save_number);
+                assert_eq!(result.character_name, "Tav_1");
+            });
+    }
+
+    #[test]
+    fn ignore_trailing_spaces_and_dots_but_keep_them_in_the_folder_name() {
+        let result = package_details("Tav-123456789__QuickSave_3 .").unwrap();
+
+        assert_eq!(result.save_number, Some(3));
+        assert_eq!(result.save_type, SaveType::Quick);
+        assert_eq!(result.file_name, "Tav-123456789__QuickSave_3 .");
+    }
+
+    #[test]
+    fn parse_folders_with_pattern_the_built_in_parser_cannot() {
+        let folder_pattern = FolderPattern::new(
+            r"^\[(?<type>[A-Za-z]+)\] (?<name>[^(]+) \((?<campaign>\d+)?\) #(?<number>\d+)$",
+        )
+        .unwrap();
+
+        let result = package_details_by_pattern("[Quick] Tav (42) #7", &folder_pattern).unwrap();
+        assert_eq!(
+            result,
+            SaveInformation::new(
+                "[Quick] Tav (42) #7".to_string(),
+                "Tav".to_string(),
+                SaveType::Unrecognized,
+                Some(7),
+            )
+            .with_campaign_id(Some("42".to_string()))
+        );
+        assert!(package_details("[Quick] Tav (42) #7").is_err());
+
+        let result =
+            package_details_by_pattern("[AutoSave] Karlach () #3", &folder_pattern).unwrap();
+        assert_eq!(result.save_type, SaveType::Auto);
+        assert_eq!(result.campaign_id, None);
+
+        assert!(matches!(
+            package_details_by_pattern("Tav-123456789__QuickSave_3", &folder_pattern),
+            Err(ProgramError::PatternMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn parse_like_the_built_in_parser_with_default_pattern() {
+        let folder_pattern = FolderPattern::new(folder_pattern::DEFAULT_PATTERN).unwrap();
+
+        [
+            "Jean-Luc-1231415123__QuickSave_3",
+            "Some Name-1231415123_AutoSave_277",
+            "Tav-1231415123__HonourMode_1",
+            "Tav-1231415123__Before the Elder Brain_12",
+        ]
+        .iter()
+        .for_each(|folder_name| {
+            let by_pattern = package_details_by_pattern(folder_name, &folder_pattern).unwrap();
+            let built_in = package_details(folder_name).unwrap();
+
+            assert_eq!(by_pattern.character_name, built_in.character_name);
+            assert_eq!(by_pattern.campaign_id, built_in.campaign_id);
+            assert_eq!(by_pattern.save_number, built_in.save_number);
+        });
+    }
+
+    #[test]
+    fn errors_out_when_error_state_occurs() {
+        let test_save = "Some'me";
+
+        let result = package_details(test_save);
+        assert!(
+            result.is_err(),
+            "Package did not error when it was provided insufficient information"
+        );
+    }
+}
+
+#[cfg(test)]
+mod save_type_should {
+    use crate::{save_type, SaveType};
+
+    #[test]
+    fn detect_quick_save() {
+        let test_save_type: &str = "QuickSave";
+        let save = format!("Some Name-1231415123_{}_277", test_save_type);
+
+        let save_type = save_type(save.as_str());
+        assert_eq!(save_type, SaveType::Quick);
+    }
+
+    #[test]
+    fn detect_auto_save() {
+        let test_save_type: &str = "AutoSave";
+        let save = format!("Some Name-1231415123_{}_277", test_save_type);
+
+        let save_type = save_type(save.as_str());
+        assert_eq!(save_type, SaveType::Auto);
+    }
+
+    #[test]
+    fn detect_honour_save_in_either_spelling() {
+        assert_eq!(save_type("Tav-1231415123__HonourMode_3"), SaveType::Honour);
+        assert_eq!(save_type("Tav-1231415123__HonorMode_3"), SaveType::Honour);
+        assert_eq!(
+            save_type("Tav-1231415123__HONOUR_QuickSave_3"),
+            SaveType::Honour
+        );
+    }
+
+    #[test]
+    fn not_take_a_character_named_like_honour_mode_for_it() {
+        assert_eq!(
+            save_type("Honoria-1231415123__QuickSave_3"),
+            SaveType::Quick
+        );
+        assert_eq!(save_type("Honorine-1231415123__AutoSave_3"), SaveType::Auto);
+        assert_eq!(
+            save_type("Honoria-1231415123__HonourMode_3"),
+            SaveType::Honour
+        );
+    }
+
+    #[test]
+    fn detect_manual_save() {
+        assert_eq!(
+            save_type("Tav-1231415123__Before the Elder Brain_12"),
+            SaveType::Manual
+        );
+        assert_eq!(save_type("Some Name-1231415123__Camp_3"), SaveType::Manual);
+    }
+
+    #[test]
+    fn not_mistake_quick_and_auto_saves_for_manual_ones() {
+        assert_eq!(save_type("Tav-1231415123__QuickSave_12"), SaveType::Quick);
+        assert_eq!(save_type("Tav-1231415123__AutoSave_12"), SaveType::Auto);
+        assert_eq!(save_type("Tav-Camp_3"), SaveType::Unrecognized);
+        assert_eq!(save_type("Tav-abc__Camp_3"), SaveType::Unrecognized);
+        assert_eq!(save_type("Tav-1231415123__Camp"), SaveType::Unrecognized);
+    }
+
+    #[test]
+    fn keep_other_languages_as_manual_saves_without_keywords() {
+        assert_eq!(
+            save_type("Tav-1231415123__SauvegardeRapide_12"),
+            SaveType::Manual
+        );
+        assert_eq!(
+            save_type("タヴ-1231415123__クイックセーブ_7"),
+            SaveType::Manual
+        );
+    }
+
+    #[test]
+    fn detect_unrecognized() {
+        let save = "Some Name-ManualSave";
+
+        let save_type = save_type(save);
+        assert_eq!(save_type, SaveType::Unrecognized);
+    }
+}
+
+#[cfg(test)]
+mod character_name_should {
+    use crate::{character_and_campaign, character_name, ProgramError};
+
+    #[test]
+    fn detect_with_space() {
+        let test_save = "Some Name-1231415123_{}_277";
+        let expected = "Some Name";
+
+        let name = character_name(test_save).unwrap();
+        assert_eq!(name, expected);
+    }
+
+    #[test]
+    fn detect_with_underscore() {
+        let test_save = "Some_Name-1231415123_{}_277";
+        let expected = "Some_Name";
+
+        let name = character_name(test_save).unwrap();
+        assert_eq!(name, expected);
+    }
+
+    #[test]
+    fn detect_single_name() {
+        let test_save = "SomeName-1231415123_{}_277";
+        let expected = "SomeName";
+
+        let name = character_name(test_save).unwrap();
+        assert_eq!(name, expected);
+    }
+
+    #[test]
+    fn detect_with_apostrophe() {
+        let test_save = "Some'me-1231415123_{}_277";
+        let expected = "Some'me";
+
+        let name = character_name(test_save).unwrap();
+        assert_eq!(name, expected);
+    }
+
+    #[test]
+    fn detect_non_ascii_names() {
+        assert_eq!(
+            character_name("Astarión-1231415123__QuickSave_3").unwrap(),
+            "Astarión"
+        );
+        assert_eq!(
+            character_name("塔夫-1231415123__QuickSave_3").unwrap(),
+            "塔夫"
+        );
+        assert_eq!(
+            character_name("Tav 🐉-1231415123__QuickSave_3").unwrap(),
+            "Tav 🐉"
+        );
+        assert_eq!(
+            character_name("Jéan-Lüc-1231415123__QuickSave_3").unwrap(),
+            "Jéan-Lüc"
+        );
+    }
+
+    #[test]
+    fn keep_dashes_in_name() {
+        assert_eq!(
+            character_name("Jean-Luc-1231415123__QuickSave_3").unwrap(),
+            "Jean-Luc"
+        );
+        assert_eq!(
+            character_name("Jean-Luc-Picard-1231415123__AutoSave_3").unwrap(),
+            "Jean-Luc-Picard"
+        );
+    }
+
+    #[test]
+    fn detect_numeric_looking_name() {
+        assert_eq!(
+            character_name("1234-1231415123__QuickSave_3").unwrap(),
+            "1234"
+        );
+        assert_eq!(
+            character_name("2024-05-1231415123__QuickSave_3").unwrap(),
+            "2024-05"
+        );
+    }
+
+    #[test]
+    fn capture_campaign_id() {
+        assert_eq!(
+            character_and_campaign("Jean-Luc-1231415123__QuickSave_3"),
+            Some(("Jean-Luc", Some("1231415123")))
+        );
+        assert_eq!(
+            character_and_campaign("Some Name-ManualSave"),
+            Some(("Some Name", None))
+        );
+    }
+
+    #[test]
+    fn end_name_and_campaign_at_save_type_token() {
+        assert_eq!(
+            character_and_campaign("Tav-123456789_QuickSave_277_1"),
+            Some(("Tav", Some("123456789")))
+        );
+        assert_eq!(
+            character_and_campaign("Tav-123_456__AutoSave_3"),
+            Some(("Tav", Some("123_456")))
+        );
+        assert_eq!(
+            character_and_campaign("Tav 2-99-123456789__QuickSave_4"),
+            Some(("Tav 2-99", Some("123456789")))
+        );
+    }
+
+    #[test]
+    fn error_when_no_dashes() {
+        let test_save = "Some'me";
+        let expected = ProgramError::NameNotDetected("Could not detect character name".to_string());
+
+        let error = character_name(test_save).unwrap_err();
+        assert_eq!(error, expected);
+    }
+}
+
+#[cfg(test)]
+mod save_number_should {
+    use rand::Rng;
+
+    use crate::{save_number, ProgramError};
+
+    #[test]
+    fn convert_max_number() {
+        let test_save = format!("Some'me-1231415123_QuickSave_{}", u64::MAX);
+
+        let result = save_number(&test_save).unwrap();
+        assert_eq!(result, Some(u64::MAX));
+    }
+
+    #[test]
+    fn convert_minimum_number() {
+        let test_save = format!("Some'me-1231415123_QuickSave_{}", u64::MIN);
+
+        let result = save_number(&test_save).unwrap();
+        assert_eq!(result, Some(u64::MIN));
+    }
+
+    #[test]
+    fn convert_number_beyond_u16() {
+        let test_save = "Some'me-1231415123_QuickSave_65536";
+
+        let result = save_number(test_save).unwrap();
+        assert_eq!(result, Some(65536));
+    }
+
+    #[test]
+    fn convert_random_number() {
+        let random_u16: u16 = rand::thread_rng().gen_range(1..u16::MAX); // Ignore min and max as that is explicitly tested.
+        let test_save = format!("Some'me-1231415123_QuickSave_{}", random_u16);
+
+        let result = save_number(&test_save).unwrap();
+        assert_eq!(result, Some(random_u16.into()));
+    }
+
+    #[test]
+    fn return_none_for_negative_number() {
+        let test_save = format!("Some'me-1231415123_QuickSave_{}", -22);
+
+        let result = save_number(&test_save).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn return_none_for_text_after_last_underscore() {
+        let test_save = "Some'me-1231415123_QuickSave_backup";
+
+        let result = save_number(test_save).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn take_number_after_save_type_token() {
+        assert_eq!(
+            save_number("Tav-123456789_QuickSave_277_1").unwrap(),
+            Some(277)
+        );
+        assert_eq!(save_number("Tav-123456789__AutoSave_12").unwrap(), Some(12));
+        assert_eq!(
+            save_number("Tav-123456789__quicksave_5_backup").unwrap(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn ignore_numbers_before_save_type_token() {
+        assert_eq!(save_number("Tav_2-123_456__QuickSave_9").unwrap(), Some(9));
+        assert_eq!(save_number("Tav-123456789__QuickSave").unwrap(), None);
+    }
+
+    #[test]
+    fn error_when_no_underscores() {
+        let test_save = "Some'me";
+        let expected = ProgramError::NotEnoughUnderscores(
+            "Did not find the correct number of underscores. Cannot continue with this save."
+                .to_string(),
+        );
+
+        let result = save_number(test_save).unwrap_err();
+        assert_eq!(result, expected);
+    }
+}
+
+#[cfg(test)]
+mod group_by_character_should {
+    use std::collections::HashMap;
+
+    use crate::{
+        character_key::CharacterKey, group_by_character, group_saves, SaveInformation, SaveType,
+    };
+
+    #[test]
+    fn create_and_assign_new_character_quicksave() {
+        let map = HashMap::default();
+        let character_name = "First Last".to_string();
+        let save_information =
+            SaveInformation::new_random(SaveType::Quick, character_name.to_string());
+        let expected = save_information.clone();
+
+        let map = group_by_character(map, save_information);
+
+        let save_by_type = map.get(&CharacterKey::named(&character_name)).unwrap();
+        assert_eq!(save_by_type.quick_saves.len(), 1);
+        assert_eq!(save_by_type.quick_saves.first().unwrap(), &expected);
+    }
+
+    #[test]
+    fn assign_manual_saves_to_their_own_bucket() {
+        let save_information = SaveInformation::new_random(SaveType::Manual, "Tav".to_string());
+
+        let map = group_by_character(HashMap::default(), save_information.clone());
+
+        let save_by_type = map.get(&CharacterKey::named("Tav")).unwrap();
+        assert_eq!(save_by_type.manual_saves, vec![save_information]);
+        assert!(save_by_type.unrecognized_saves.is_empty());
+    }
+
+    #[test]
+    fn create_and_assign_new_character_autosave() {
+        let map = HashMap::default();
+        let character_name = "First Last".to_string();
+        let save_information = SaveInformation::new_random(SaveType::Auto, character_name.clone());
+        let expected = save_information.clone();
+
+        let map = group_by_character(map, save_information);
+
+        let save_by_type = map.get(&CharacterKey::named(&character_name)).unwrap();
+        assert_eq!(save_by_type.auto_saves.len(), 1);
+        assert_eq!(save_by_type.auto_saves.first().unwrap(), &expected);
+    }
+
+    #[test]
+    fn multiple_saves_of_single_character() {
+        let map = HashMap::default();
+        let character_name = "First Last".to_string();
+
+        let save_informations = [
+            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
+            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
+            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
+        ];
+
+        let map = group_by_character(map, save_informations.first().unwrap().clone());
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .len(),
+            1
+        );
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .first()
+                .unwrap(),
+            save_informations.first().unwrap()
+        );
+
+        let map = group_by_character(map, save_informations.get(1).unwrap().clone());
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .len(),
+            2
+        );
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .last()
+                .unwrap(),
+            save_informations.get(1).unwrap()
+        );
+
+        let map = group_by_character(map, save_informations.last().unwrap().clone());
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .len(),
+            3
+        );
+        assert_eq!(
+            map.get(&CharacterKey::named(&character_name))
+                .unwrap()
+                .quick_saves
+                .last()
+                .unwrap(),
+            save_informations.last().unwrap()
+        );
+    }
+
+    #[test]
+    fn multiple_saves_of_multiple_characters() {
+        let (fl, some) = ("First Last".to_string(), "Some'me".to_string());
+
+        let fl_save_information = vec![
+            SaveInformation::new_random(SaveType::Quick, fl.clone()),
+            SaveInformation::new_random(SaveType::Auto, fl.clone()),
+            SaveInformation::new_random(SaveType::Quick, fl.clone()),
+            SaveInformation::new_random(SaveType::Auto, fl.clone()),
+        ];
+
+        let some_save_information = vec![
+            SaveInformation::new_random(SaveType::Quick, some.clone()),
+            SaveInformation::new_random(SaveType::Auto, some.clone()),
+            SaveInformation::new_random(SaveType::Quick, some.clone()),
+            SaveInformation::new_random(SaveType::Auto, some.clone()),
+        ];
+
+        let map = group_saves(
+            fl_save_information
+                .clone()
+                .into_iter()
+                .chain(some_save_information.clone())
+                .collect(),
+        );
+
+        assert_eq!(map.keys().len(), 2);
+        assert!(map.keys().any(|key| key.name == "First Last"));
+        assert!(map.keys().any(|key| key.name == "Some'me"));
+
+        let fl_saves = map.get(&CharacterKey::named(&fl)).unwrap();
+        let some_saves = map.get(&CharacterKey::named(&some)).unwrap();
+
+        for save in fl_save_information.into_iter() {
+            assert!(
+                match &save.save_type {
+                    SaveType::Quick => fl_saves
+                        .quick_saves
+                        .iter()
+                        .any(|quick_save| quick_save.eq(&save)),
+                    SaveType::Auto => fl_saves
+                        .auto_saves
+                        .iter()
+                        .any(|auto_save| auto_save.eq(&save)),
+                    SaveType::Honour | SaveType::Manual | SaveType::Unrecognized => {
+                        panic!("Unrecognized save type was not removed")
+                    }
+                },
+                "Failed to match save"
+            );
+        }
+
+        for save in some_save_information.into_iter() {
+            assert!(
+                match &save.save_type {
+                    SaveType::Quick => some_saves
+                        .quick_saves
+                        .iter()
+                        .any(|quick_save| quick_save.eq(&save)),
+                    SaveType::Auto => some_saves
+                        .auto_saves
+                        .iter()
+                        .any(|auto_save| auto_save.eq(&save)),
+                    SaveType::Honour | SaveType::Manual | SaveType::Unrecognized => {
+                        panic!("Unrecognized save type was not removed")
+                    }
+                },
+                "Failed to match save"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod sort_map_saves_should {
+
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        character_key::CharacterKey,
+        folder_filter::FolderFilter,
+        get_delete_vec, group_saves, scan_saves, sort_map_saves,
+        test_utils::{set_modified, TempDir},
+        CleanConfig, SaveInformation, SaveType, SortBy,
+    };
 
     #[test]
-    fn convert_minimum_number() {
-        let test_save = format!("Some'me-1231415123_QuickSave_{}", u16::MIN);
+    fn sorts_quick_saves() {
+        let mut saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+        ];
+        // Force ascending order
+        saves.sort_by_key(|save| save.save_number);
 
-        let result = save_number(&test_save).unwrap();
-        assert_eq!(result, u16::MIN);
+        let map = group_saves(saves.clone());
+        let map = sort_map_saves(map, SortBy::Number);
+        let fl_saves = map.get(&CharacterKey::named("First Last")).unwrap();
+
+        assert_eq!(fl_saves.quick_saves.first().unwrap(), saves.last().unwrap());
+        assert_eq!(fl_saves.quick_saves.last().unwrap(), saves.first().unwrap());
     }
 
     #[test]
-    fn convert_random_number() {
-        let random_u16: u16 = rand::thread_rng().gen_range(1..u16::MAX); // Ignore min and max as that is explicitly tested.
-        let test_save = format!("Some'me-1231415123_QuickSave_{}", random_u16);
+    fn sorts_auto_saves() {
+        let mut saves = vec![
+            SaveInformation::new_random(SaveType::Auto, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Auto, "First Last".to_string()),
+        ];
+        // Force ascending order
+        saves.sort_by_key(|save| save.save_number);
 
-        let result = save_number(&test_save).unwrap();
-        assert_eq!(result, random_u16);
+        let map = group_saves(saves.clone());
+        let map = sort_map_saves(map, SortBy::Number);
+        let fl_saves = map.get(&CharacterKey::named("First Last")).unwrap();
+
+        assert_eq!(fl_saves.auto_saves.first().unwrap(), saves.last().unwrap());
+        assert_eq!(fl_saves.auto_saves.last().unwrap(), saves.first().unwrap());
     }
 
     #[test]
-    fn error_on_negative_number() {
-        let test_save = format!("Some'me-1231415123_QuickSave_{}", -22);
-        let expected = ProgramError::StringNotNumber("invalid digit found in string".to_string());
+    fn sorts_manual_saves() {
+        let mut saves = vec![
+            SaveInformation::new_random(SaveType::Manual, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Manual, "First Last".to_string()),
+        ];
+        saves.sort_by_key(|save| save.save_number);
 
-        let result = save_number(&test_save).unwrap_err();
-        assert_eq!(result, expected);
+        let map = sort_map_saves(group_saves(saves.clone()), SortBy::Number);
+        let fl_saves = map.get(&CharacterKey::named("First Last")).unwrap();
+
+        assert_eq!(
+            fl_saves.manual_saves.first().unwrap(),
+            saves.last().unwrap()
+        );
+        assert_eq!(
+            fl_saves.manual_saves.last().unwrap(),
+            saves.first().unwrap()
+        );
     }
 
     #[test]
-    fn error_when_no_underscores() {
-        let test_save = "Some'me";
-        let expected = ProgramError::NotEnoughUnderscores(
-            "Did not find the correct number of underscores. Cannot continue with this save."
+    fn sorts_by_modified_time() {
+        let now = SystemTime::now();
+        let save = |number: u64, modified: Option<SystemTime>| {
+            SaveInformation::new(
+                format!("Tav-123__QuickSave_{}", number),
+                "Tav".to_string(),
+                SaveType::Quick,
+                Some(number),
+            )
+            .with_modified(modified)
+        };
+        let saves = vec![
+            save(1, Some(now)),
+            save(7, None),
+            save(9, Some(now - Duration::from_secs(3600))),
+        ];
+
+        let map = sort_map_saves(group_saves(saves), SortBy::Mtime);
+        let numbers: Vec<u64> = map[&CharacterKey::named("Tav")]
+            .quick_saves
+            .iter()
+            .filter_map(|save| save.save_number)
+            .collect();
+
+        assert_eq!(numbers, vec![1, 9, 7]);
+    }
+
+    #[test]
+    fn delete_older_of_saves_with_same_number() {
+        let now = SystemTime::now();
+        let save = |campaign_id: &str, modified: SystemTime| {
+            SaveInformation::new(
+                format!("Tav-{}__QuickSave_4", campaign_id),
+                "Tav".to_string(),
+                SaveType::Quick,
+                Some(4),
+            )
+            .with_modified(Some(modified))
+        };
+        let stale = save("123", now - Duration::from_secs(86_400));
+        let fresh = save("456", now);
+
+        [
+            vec![stale.clone(), fresh.clone()],
+            vec![fresh.clone(), stale.clone()],
+        ]
+        .into_iter()
+        .for_each(|saves| {
+            let map = sort_map_saves(group_saves(saves), SortBy::Number);
+            assert_eq!(
+                map[&CharacterKey::named("Tav")].quick_saves,
+                vec![fresh.clone(), stale.clone()]
+            );
+
+            let (deletable, _) = get_delete_vec(map, 1usize, &CleanConfig::default());
+            assert_eq!(deletable, vec![stale.clone()]);
+        });
+    }
+
+    #[test]
+    fn sort_unnumbered_saves_after_numbered_by_modified_time() {
+        let now = SystemTime::now();
+        let save = |suffix: &str, number: Option<u64>, modified: SystemTime| {
+            SaveInformation::new(
+                format!("Tav-123__QuickSave_{}", suffix),
+                "Tav".to_string(),
+                SaveType::Quick,
+                number,
+            )
+            .with_modified(Some(modified))
+        };
+        let saves = vec![
+            save("old", None, now - Duration::from_secs(7200)),
+            save("3", Some(3), now - Duration::from_secs(3600)),
+            save("new", None, now),
+            save("5", Some(5), now),
+        ];
+
+        let map = sort_map_saves(group_saves(saves), SortBy::Number);
+        let folders: Vec<&str> = map[&CharacterKey::named("Tav")]
+            .quick_saves
+            .iter()
+            .map(|save| save.file_name.as_str())
+            .collect();
+
+        assert_eq!(
+            folders,
+            vec![
+                "Tav-123__QuickSave_5",
+                "Tav-123__QuickSave_3",
+                "Tav-123__QuickSave_new",
+                "Tav-123__QuickSave_old"
+            ]
+        );
+
+        let (deletable, _) = get_delete_vec(map, 3usize, &CleanConfig::default());
+        assert_eq!(
+            deletable
+                .iter()
+                .map(|save| save.file_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Tav-123__QuickSave_old"]
+        );
+    }
+
+    #[test]
+    fn keep_hand_renamed_saves_in_scope() {
+        let temp_dir = TempDir::new();
+        let now = SystemTime::now();
+        [
+            ("Tav-123456789__QuickSave_8", 3600),
+            ("Tav-123456789__QuickSave_9", 0),
+            ("Tav-123456789_QuickSave_backup", 7200),
+            ("Tav-123456789__QuickSave_before boss", 60),
+        ]
+        .into_iter()
+        .for_each(|(folder_name, age)| {
+            let folder = temp_dir.create_save(folder_name);
+            set_modified(&folder, now - Duration::from_secs(age));
+        });
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+
+        let map = sort_map_saves(group_saves(saves), SortBy::Number);
+        let quick_saves = &map[&CharacterKey::in_campaign("Tav", "123456789")].quick_saves;
+        assert_eq!(
+            quick_saves
+                .iter()
+                .map(|save| save.file_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "Tav-123456789__QuickSave_9",
+                "Tav-123456789__QuickSave_8",
+                "Tav-123456789__QuickSave_before boss",
+                "Tav-123456789_QuickSave_backup"
+            ]
+        );
+
+        let (deletable, _) = get_delete_vec(map, 3usize, &CleanConfig::default());
+        assert_eq!(
+            deletable
+                .iter()
+                .map(|save| save.file_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Tav-123456789_QuickSave_backup"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod resolve_counter_resets_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        character_key::CharacterKey, group_saves, resolve_counter_resets, sort_map_saves,
+        SaveInformation, SaveType, SortBy,
+    };
+
+    fn numbers_after(prefer_mtime: bool) -> Vec<Option<u64>> {
+        let now = SystemTime::now();
+        let saves = [(239, 3), (240, 2), (1, 1), (2, 0)]
+            .into_iter()
+            .map(|(number, hours_ago)| {
+                SaveInformation::new(
+                    format!("Tav-123__QuickSave_{}", number),
+                    "Tav".to_string(),
+                    SaveType::Quick,
+                    Some(number),
+                )
+                .with_modified(Some(now - Duration::from_secs(3600 * hours_ago)))
+            })
+            .collect();
+        let map = sort_map_saves(group_saves(saves), SortBy::Number);
+
+        resolve_counter_resets(map, SortBy::Number, prefer_mtime)[&CharacterKey::named("Tav")]
+            .quick_saves
+            .iter()
+            .map(|save| save.save_number)
+            .collect()
+    }
+
+    #[test]
+    fn keep_number_order_unless_preferring_mtime() {
+        assert_eq!(
+            numbers_after(false),
+            vec![Some(240), Some(239), Some(2), Some(1)]
+        );
+        assert_eq!(
+            numbers_after(true),
+            vec![Some(2), Some(1), Some(240), Some(239)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod filter_characters_should {
+    use crate::{
+        character_key::CharacterKey, filter_characters, group_saves, ProgramError, SaveInformation,
+        SaveType,
+    };
+
+    fn saves_of_characters() -> Vec<SaveInformation> {
+        vec![
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Auto, "Some'me".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "Durge".to_string()),
+        ]
+    }
+
+    #[test]
+    fn keep_everything_without_filter() {
+        let map = group_saves(saves_of_characters());
+
+        let result = filter_characters(map.clone(), &[]).unwrap();
+        assert_eq!(result, map);
+    }
+
+    #[test]
+    fn match_case_insensitively() {
+        let map = group_saves(saves_of_characters());
+
+        let result = filter_characters(map, &["durge".to_string()]).unwrap();
+        assert_eq!(result.keys().len(), 1);
+        assert!(result.contains_key(&CharacterKey::named("Durge")));
+    }
+
+    #[test]
+    fn union_multiple_characters() {
+        let map = group_saves(saves_of_characters());
+
+        let result =
+            filter_characters(map, &["Durge".to_string(), "FIRST LAST".to_string()]).unwrap();
+        assert_eq!(result.keys().len(), 2);
+        assert!(result.contains_key(&CharacterKey::named("Durge")));
+        assert!(result.contains_key(&CharacterKey::named("First Last")));
+    }
+
+    #[test]
+    fn error_when_character_not_found() {
+        let map = group_saves(saves_of_characters());
+        let expected = ProgramError::CharacterNotFound(
+            "No save folders found for character(s): Karlach".to_string(),
+        );
+
+        let error =
+            filter_characters(map, &["Durge".to_string(), "Karlach".to_string()]).unwrap_err();
+        assert_eq!(error, expected);
+    }
+}
+
+#[cfg(test)]
+mod filter_campaign_should {
+    use crate::{filter_campaign, group_saves, ProgramError, SaveInformation, SaveType};
+
+    fn saves_of_campaigns() -> Vec<SaveInformation> {
+        [
+            ("Tav", "1231415123"),
+            ("Tav", "1239876543"),
+            ("Durge", "5550001111"),
+        ]
+        .iter()
+        .map(|(name, campaign_id)| {
+            SaveInformation::new_random(SaveType::Quick, name.to_string())
+                .with_campaign_id(Some(campaign_id.to_string()))
+        })
+        .collect()
+    }
+
+    #[test]
+    fn keep_everything_without_campaign() {
+        let map = group_saves(saves_of_campaigns());
+
+        let result = filter_campaign(map.clone(), None).unwrap();
+        assert_eq!(result, map);
+    }
+
+    #[test]
+    fn keep_only_campaign_matching_prefix() {
+        let map = group_saves(saves_of_campaigns());
+
+        let result = filter_campaign(map, Some("12314")).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result
+            .keys()
+            .all(|key| key.campaign_id.as_deref() == Some("1231415123")));
+    }
+
+    #[test]
+    fn error_on_ambiguous_prefix() {
+        let map = group_saves(saves_of_campaigns());
+
+        let expected = ProgramError::AmbiguousCampaign(
+            "Campaign 123 matches more than one campaign: 1231415123 (Tav), 1239876543 (Tav)"
                 .to_string(),
         );
+        assert_eq!(filter_campaign(map, Some("123")).unwrap_err(), expected);
+    }
 
-        let result = save_number(test_save).unwrap_err();
-        assert_eq!(result, expected);
+    #[test]
+    fn error_on_unknown_campaign() {
+        let map = group_saves(saves_of_campaigns());
+
+        let expected =
+            ProgramError::CampaignNotFound("No save folders found for campaign 999".to_string());
+        assert_eq!(filter_campaign(map, Some("999")).unwrap_err(), expected);
     }
 }
 
 #[cfg(test)]
-mod group_by_character_should {
+mod exclude_characters_should {
+    use crate::{
+        character_key::CharacterKey, exclude_characters, group_saves, SaveInformation, SaveType,
+    };
+
+    fn saves_of_characters() -> Vec<SaveInformation> {
+        vec![
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Auto, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "Durge".to_string()),
+        ]
+    }
+
+    #[test]
+    fn keep_everything_without_exclusions() {
+        let map = group_saves(saves_of_characters());
+
+        let (result, skipped_saves, unmatched_characters) = exclude_characters(map.clone(), &[]);
+        assert_eq!(result, map);
+        assert_eq!(skipped_saves, 0);
+        assert!(unmatched_characters.is_empty());
+    }
+
+    #[test]
+    fn remove_excluded_character_case_insensitively() {
+        let map = group_saves(saves_of_characters());
+
+        let (result, skipped_saves, unmatched_characters) =
+            exclude_characters(map, &["first last".to_string()]);
+        assert_eq!(result.keys().len(), 1);
+        assert!(result.contains_key(&CharacterKey::named("Durge")));
+        assert_eq!(skipped_saves, 2);
+        assert!(unmatched_characters.is_empty());
+    }
+
+    #[test]
+    fn report_unmatched_characters() {
+        let map = group_saves(saves_of_characters());
+
+        let (result, skipped_saves, unmatched_characters) =
+            exclude_characters(map, &["Durge".to_string(), "Karlach".to_string()]);
+        assert_eq!(result.keys().len(), 1);
+        assert_eq!(skipped_saves, 1);
+        assert_eq!(unmatched_characters, vec!["Karlach".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod unmatched_preserve_for_should {
     use std::collections::HashMap;
 
-    use crate::{group_by_character, group_saves, SaveInformation, SaveType};
+    use crate::{character_key::CharacterKey, unmatched_preserve_for, Saves};
 
     #[test]
-    fn create_and_assign_new_character_quicksave() {
-        let map = HashMap::default();
-        let character_name = "First Last".to_string();
-        let save_information =
-            SaveInformation::new_random(SaveType::Quick, character_name.to_string());
-        let expected = save_information.clone();
+    fn return_names_without_discovered_character() {
+        let mut map = HashMap::new();
+        map.insert(CharacterKey::named("Tav"), Saves::default());
+
+        let preserve_for = [("tav".to_string(), 30), ("Tva".to_string(), 2)];
+        assert_eq!(unmatched_preserve_for(&map, &preserve_for), vec!["Tva"]);
+    }
+}
+
+#[cfg(test)]
+mod get_delete_vec_should {
+    use std::collections::HashMap;
+
+    use crate::{
+        character_key::CharacterKey, get_delete_vec, group_saves, package_details,
+        save_type_filter::SaveTypeFilter, CleanConfig, SaveInformation, SaveType, Saves,
+    };
+
+    fn quick_and_auto_saves(name: &str) -> HashMap<CharacterKey, Saves> {
+        let mut map = HashMap::new();
+        map.insert(
+            CharacterKey::named(name),
+            Saves {
+                quick_saves: vec![
+                    SaveInformation::new_random(SaveType::Quick, name.to_string()),
+                    SaveInformation::new_random(SaveType::Quick, name.to_string()),
+                ],
+                auto_saves: vec![
+                    SaveInformation::new_random(SaveType::Auto, name.to_string()),
+                    SaveInformation::new_random(SaveType::Auto, name.to_string()),
+                ],
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
+            },
+        );
+
+        map
+    }
+
+    #[test]
+    fn preserve_saves_of_each_campaign_independently() {
+        let saves: Vec<SaveInformation> = ["111111111", "222222222"]
+            .iter()
+            .flat_map(|campaign_id| {
+                (1..=3).rev().map(move |save_number| {
+                    SaveInformation::new(
+                        format!("Tav-{}__QuickSave_{}", campaign_id, save_number),
+                        "Tav".to_string(),
+                        SaveType::Quick,
+                        Some(save_number),
+                    )
+                    .with_campaign_id(Some(campaign_id.to_string()))
+                })
+            })
+            .collect();
+        let map = group_saves(saves);
+        assert_eq!(map.len(), 2);
+
+        let clean_config = CleanConfig {
+            allow_delete_all: true,
+            ..Default::default()
+        };
+        let (result, _) = get_delete_vec(map, 1usize, &clean_config);
+        let mut deleted: Vec<&str> = result
+            .iter()
+            .map(|save_information| save_information.file_name.as_str())
+            .collect();
+        deleted.sort();
+        assert_eq!(
+            deleted,
+            vec![
+                "Tav-111111111__QuickSave_1",
+                "Tav-111111111__QuickSave_2",
+                "Tav-222222222__QuickSave_1",
+                "Tav-222222222__QuickSave_2",
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_preserve_override_per_character() {
+        let mut map = quick_and_auto_saves("Tav");
+        map.extend(quick_and_auto_saves("Some'me Else"));
 
-        let map = group_by_character(map, save_information);
+        let clean_config = CleanConfig {
+            preserve_for: vec![("some'me else".to_string(), 2)],
+            allow_delete_all: true,
+            ..Default::default()
+        };
 
-        let save_by_type = map.get(&character_name).unwrap();
-        assert_eq!(save_by_type.quick_saves.len(), 1);
-        assert_eq!(save_by_type.quick_saves.first().unwrap(), &expected);
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 4);
+        assert!(result
+            .iter()
+            .all(|save_information| save_information.character_name == "Tav"));
     }
 
     #[test]
-    fn create_and_assign_new_character_autosave() {
-        let map = HashMap::default();
-        let character_name = "First Last".to_string();
-        let save_information = SaveInformation::new_random(SaveType::Auto, character_name.clone());
-        let expected = save_information.clone();
+    fn never_return_honour_saves_unless_included() {
+        let mut map = quick_and_auto_saves("Tav");
+        let honour_save = SaveInformation::new_random(SaveType::Honour, "Tav".to_string());
+        let corrupt_honour_save =
+            SaveInformation::new_random(SaveType::Honour, "Tav".to_string()).with_corrupt(true);
+        let saves = map.get_mut(&CharacterKey::named("Tav")).unwrap();
+        saves.honour_saves.push(honour_save.clone());
+        saves.corrupt_saves.push(corrupt_honour_save.clone());
 
-        let map = group_by_character(map, save_information);
+        let clean_config = CleanConfig {
+            allow_delete_all: true,
+            clean_corrupt: true,
+            ..Default::default()
+        };
+        let (result, _) = get_delete_vec(map.clone(), 0usize, &clean_config);
+        assert_eq!(result.len(), 4);
+        assert!(!result.contains(&honour_save));
+        assert!(!result.contains(&corrupt_honour_save));
 
-        let save_by_type = map.get(&character_name).unwrap();
-        assert_eq!(save_by_type.auto_saves.len(), 1);
-        assert_eq!(save_by_type.auto_saves.first().unwrap(), &expected);
+        let clean_config = CleanConfig {
+            include_honour: true,
+            ..clean_config
+        };
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 6);
+        assert!(result.contains(&honour_save));
+        assert!(result.contains(&corrupt_honour_save));
     }
 
     #[test]
-    fn multiple_saves_of_single_character() {
-        let map = HashMap::default();
-        let character_name = "First Last".to_string();
+    fn only_return_manual_saves_with_their_own_preserve_count() {
+        let mut map = quick_and_auto_saves("Tav");
+        let mut manual_saves: Vec<SaveInformation> = (0..3)
+            .map(|_| SaveInformation::new_random(SaveType::Manual, "Tav".to_string()))
+            .collect();
+        manual_saves.sort_by_key(|save| std::cmp::Reverse(save.save_number));
+        map.get_mut(&CharacterKey::named("Tav"))
+            .unwrap()
+            .manual_saves = manual_saves.clone();
 
-        let save_informations = vec![
-            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
-            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
-            SaveInformation::new_random(SaveType::Quick, character_name.clone()),
-        ];
+        let clean_config = CleanConfig {
+            allow_delete_all: true,
+            ..Default::default()
+        };
+        let (result, _) = get_delete_vec(map.clone(), 0usize, &clean_config);
+        assert!(manual_saves.iter().all(|save| !result.contains(save)));
 
-        let map = group_by_character(map, save_informations.first().unwrap().clone());
-        assert_eq!(map.get(&character_name).unwrap().quick_saves.len(), 1);
-        assert_eq!(
-            map.get(&character_name)
-                .unwrap()
-                .quick_saves
-                .first()
-                .unwrap(),
-            save_informations.first().unwrap()
-        );
+        let clean_config = CleanConfig {
+            preserve_manual: Some(1),
+            ..clean_config
+        };
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 6);
+        assert!(!result.contains(&manual_saves[0]));
+        assert!(result.contains(&manual_saves[1]));
+        assert!(result.contains(&manual_saves[2]));
+    }
 
-        let map = group_by_character(map, save_informations.iter().nth(1).unwrap().clone());
-        assert_eq!(map.get(&character_name).unwrap().quick_saves.len(), 2);
-        assert_eq!(
-            map.get(&character_name)
-                .unwrap()
-                .quick_saves
-                .last()
-                .unwrap(),
-            save_informations.iter().nth(1).unwrap()
-        );
+    #[test]
+    fn keep_newest_save_of_each_type_when_preserving_none() {
+        let map = quick_and_auto_saves("Tav");
+        let saves = map.get(&CharacterKey::named("Tav")).unwrap().clone();
 
-        let map = group_by_character(map, save_informations.last().unwrap().clone());
-        assert_eq!(map.get(&character_name).unwrap().quick_saves.len(), 3);
+        let (result, guarded_saves) = get_delete_vec(map, 0usize, &CleanConfig::default());
+        assert_eq!(guarded_saves, 2);
         assert_eq!(
-            map.get(&character_name)
-                .unwrap()
-                .quick_saves
-                .last()
-                .unwrap(),
-            save_informations.last().unwrap()
+            result,
+            vec![saves.quick_saves[1].clone(), saves.auto_saves[1].clone()]
         );
     }
 
     #[test]
-    fn multiple_saves_of_multiple_characters() {
-        let (fl, some) = ("First Last".to_string(), "Some'me".to_string());
-
-        let fl_save_information = vec![
-            SaveInformation::new_random(SaveType::Quick, fl.clone()),
-            SaveInformation::new_random(SaveType::Auto, fl.clone()),
-            SaveInformation::new_random(SaveType::Quick, fl.clone()),
-            SaveInformation::new_random(SaveType::Auto, fl.clone()),
-        ];
+    fn delete_every_save_when_allowed() {
+        let map = quick_and_auto_saves("Tav");
+        let clean_config = CleanConfig {
+            allow_delete_all: true,
+            ..Default::default()
+        };
 
-        let some_save_information = vec![
-            SaveInformation::new_random(SaveType::Quick, some.clone()),
-            SaveInformation::new_random(SaveType::Auto, some.clone()),
-            SaveInformation::new_random(SaveType::Quick, some.clone()),
-            SaveInformation::new_random(SaveType::Auto, some.clone()),
-        ];
+        let (result, guarded_saves) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(guarded_saves, 0);
+        assert_eq!(result.len(), 4);
+    }
 
-        let map = group_saves(
-            fl_save_information
-                .clone()
-                .into_iter()
-                .chain(some_save_information.clone().into_iter())
-                .collect(),
+    #[test]
+    fn only_return_corrupt_saves_when_cleaning_them() {
+        let name = "Tav".to_string();
+        let corrupt_save =
+            SaveInformation::new_random(SaveType::Quick, name.clone()).with_corrupt(true);
+        let map = group_saves(vec![
+            SaveInformation::new_random(SaveType::Quick, name.clone()),
+            corrupt_save.clone(),
+        ]);
+        assert_eq!(
+            map.get(&CharacterKey::named(&name)).unwrap().corrupt_saves,
+            vec![corrupt_save.clone()]
         );
 
-        assert_eq!(map.keys().len(), 2);
-        assert!(map.keys().any(|key| key.eq("First Last")));
-        assert!(map.keys().any(|key| key.eq("Some'me")));
-
-        let fl_saves = map.get(&fl).unwrap();
-        let some_saves = map.get(&some).unwrap();
-
-        for save in fl_save_information.into_iter() {
-            assert!(
-                match &save.save_type {
-                    SaveType::Quick => fl_saves
-                        .quick_saves
-                        .iter()
-                        .any(|quick_save| quick_save.eq(&save)),
-                    SaveType::Auto => fl_saves
-                        .auto_saves
-                        .iter()
-                        .any(|auto_save| auto_save.eq(&save)),
-                    SaveType::Unrecognized => panic!("Unrecognized save type was not removed"),
-                },
-                "Failed to match save"
-            );
-        }
+        let (result, _) = get_delete_vec(map.clone(), 0usize, &CleanConfig::default());
+        assert!(result.is_empty());
 
-        for save in some_save_information.into_iter() {
-            assert!(
-                match &save.save_type {
-                    SaveType::Quick => some_saves
-                        .quick_saves
-                        .iter()
-                        .any(|quick_save| quick_save.eq(&save)),
-                    SaveType::Auto => some_saves
-                        .auto_saves
-                        .iter()
-                        .any(|auto_save| auto_save.eq(&save)),
-                    SaveType::Unrecognized => panic!("Unrecognized save type was not removed"),
-                },
-                "Failed to match save"
-            );
-        }
+        let clean_config = CleanConfig {
+            clean_corrupt: true,
+            ..Default::default()
+        };
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result, vec![corrupt_save]);
     }
-}
-
-#[cfg(test)]
-mod sort_map_saves_should {
-
-    use crate::{group_saves, sort_map_saves, SaveInformation, SaveType};
 
     #[test]
-    fn sorts_quick_saves() {
-        let mut saves = vec![
-            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
-            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
-        ];
-        // Force ascending order
-        saves
-            .sort_by(|save_a, save_b| save_a.save_number.partial_cmp(&save_b.save_number).unwrap());
+    fn only_return_unrecognized_saves_when_included() {
+        let name = "Tav".to_string();
+        let unrecognized_save = SaveInformation::new_random(SaveType::Unrecognized, name.clone());
+        let map = group_saves(vec![
+            SaveInformation::new_random(SaveType::Quick, name.clone()),
+            unrecognized_save.clone(),
+        ]);
+        assert_eq!(
+            map.get(&CharacterKey::named(&name))
+                .unwrap()
+                .unrecognized_saves,
+            vec![unrecognized_save.clone()]
+        );
 
-        let map = group_saves(saves.clone());
-        let map = sort_map_saves(map);
-        let fl_saves = map.get("First Last").unwrap();
+        let clean_config = CleanConfig {
+            allow_delete_all: true,
+            ..Default::default()
+        };
+        let (result, _) = get_delete_vec(map.clone(), 0usize, &clean_config);
+        assert!(!result.contains(&unrecognized_save));
 
-        assert_eq!(fl_saves.quick_saves.first().unwrap(), saves.last().unwrap());
-        assert_eq!(fl_saves.quick_saves.last().unwrap(), saves.first().unwrap());
+        let clean_config = CleanConfig {
+            include_unrecognized: true,
+            ..clean_config
+        };
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 2);
+        assert!(result.contains(&unrecognized_save));
     }
 
     #[test]
-    fn sorts_auto_saves() {
-        let mut saves = vec![
-            SaveInformation::new_random(SaveType::Auto, "First Last".to_string()),
-            SaveInformation::new_random(SaveType::Auto, "First Last".to_string()),
-        ];
-        // Force ascending order
-        saves
-            .sort_by(|save_a, save_b| save_a.save_number.partial_cmp(&save_b.save_number).unwrap());
+    fn only_return_quick_saves_when_filtered() {
+        let map = quick_and_auto_saves("First Last");
 
-        let map = group_saves(saves.clone());
-        let map = sort_map_saves(map);
-        let fl_saves = map.get("First Last").unwrap();
+        let clean_config = CleanConfig {
+            only: Some(SaveTypeFilter::Quick),
+            allow_delete_all: true,
+            ..Default::default()
+        };
 
-        assert_eq!(fl_saves.auto_saves.first().unwrap(), saves.last().unwrap());
-        assert_eq!(fl_saves.auto_saves.last().unwrap(), saves.first().unwrap());
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 2);
+        assert!(result
+            .iter()
+            .all(|save_information| save_information.save_type == SaveType::Quick));
     }
-}
 
-#[cfg(test)]
-mod get_delete_vec_should {
-    use std::collections::HashMap;
+    #[test]
+    fn only_return_auto_saves_when_filtered() {
+        let map = quick_and_auto_saves("First Last");
+
+        let clean_config = CleanConfig {
+            only: Some(SaveTypeFilter::Auto),
+            allow_delete_all: true,
+            ..Default::default()
+        };
 
-    use crate::{get_delete_vec, SaveInformation, SaveType, Saves};
+        let (result, _) = get_delete_vec(map, 0usize, &clean_config);
+        assert_eq!(result.len(), 2);
+        assert!(result
+            .iter()
+            .all(|save_information| save_information.save_type == SaveType::Auto));
+    }
 
     #[test]
     fn handle_quick_and_auto_saves() {
@@ -656,14 +5788,19 @@ mod get_delete_vec_should {
         ];
 
         map.insert(
-            name.to_string(),
+            CharacterKey::named(&name),
             Saves {
                 quick_saves: quick_saves.clone(),
                 auto_saves: auto_saves.clone(),
+                honour_saves: Vec::new(),
+                manual_saves: Vec::new(),
+                unrecognized_saves: Vec::new(),
+                corrupt_saves: Vec::new(),
+                copies: Vec::new(),
             },
         );
 
-        let result = get_delete_vec(map.clone(), 1usize);
+        let (result, _) = get_delete_vec(map.clone(), 1usize, &CleanConfig::default());
         assert_eq!(result.len(), 2);
         assert_eq!(
             result
@@ -686,9 +5823,332 @@ mod get_delete_vec_should {
                 .find(|save_information| save_information.save_type == SaveType::Quick)
                 .unwrap()
                 .clone(),
-            quick_saves.iter().nth(1).unwrap().clone()
+            quick_saves.get(1).unwrap().clone()
         )
     }
+
+    #[test]
+    fn never_keep_copies_instead_of_originals() {
+        let saves: Vec<SaveInformation> = [
+            "Tav-123456789__QuickSave_50",
+            "Tav-123456789__QuickSave_50 (1)",
+            "Tav-123456789__QuickSave_50 - Copy",
+            "Tav-123456789__QuickSave_49",
+        ]
+        .iter()
+        .map(|file_name| package_details(file_name).unwrap())
+        .collect();
+
+        let (deletable, _) = get_delete_vec(group_saves(saves.clone()), 1, &CleanConfig::default());
+        assert_eq!(
+            deletable
+                .iter()
+                .map(|save| save.file_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Tav-123456789__QuickSave_49"]
+        );
+
+        let clean_copies = CleanConfig {
+            clean_copies: true,
+            ..Default::default()
+        };
+        let (mut deletable, _) = get_delete_vec(group_saves(saves), 1, &clean_copies);
+        deletable.sort_by(|save_a, save_b| save_a.file_name.cmp(&save_b.file_name));
+        assert_eq!(
+            deletable
+                .iter()
+                .map(|save| save.file_name.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "Tav-123456789__QuickSave_49",
+                "Tav-123456789__QuickSave_50 (1)",
+                "Tav-123456789__QuickSave_50 - Copy"
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod select_deletable_saves_should {
+    use crate::{select_deletable_saves, SaveInformation, SaveType};
+
+    fn saves_of_size(sizes: &[u64]) -> Vec<SaveInformation> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, size)| {
+                SaveInformation::new(
+                    format!("test_file_name{}", i),
+                    "First Last".to_string(),
+                    SaveType::Quick,
+                    Some((sizes.len() - i) as u64),
+                )
+                .with_size_bytes(*size)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn use_count_rule_without_size_budget() {
+        let saves = saves_of_size(&[100, 100, 100]);
+
+        let result = select_deletable_saves(saves.clone(), 1usize, None, None);
+        assert_eq!(result, saves[1..].to_vec());
+    }
+
+    #[test]
+    fn delete_when_size_rule_is_stricter() {
+        let saves = saves_of_size(&[100, 100, 100, 100]);
+
+        let result = select_deletable_saves(saves.clone(), 3usize, Some(150), None);
+        assert_eq!(result, saves[1..].to_vec());
+    }
+
+    #[test]
+    fn combine_thinning_with_size_rule() {
+        let saves = saves_of_size(&[100, 100, 100, 100, 100]);
+
+        let result = select_deletable_saves(saves.clone(), 1usize, Some(250), Some(2));
+        assert_eq!(
+            result,
+            vec![
+                saves[1].clone(),
+                saves[2].clone(),
+                saves[3].clone(),
+                saves[4].clone()
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_when_count_rule_is_stricter() {
+        let saves = saves_of_size(&[100, 100, 100, 100]);
+
+        let result = select_deletable_saves(saves.clone(), 1usize, Some(1000), None);
+        assert_eq!(result, saves[1..].to_vec());
+    }
+}
+
+#[cfg(test)]
+mod thinned_saves_should {
+    use crate::{ordinal, thinned_saves, SaveInformation, SaveType};
+
+    fn numbered_saves(count: u64) -> Vec<SaveInformation> {
+        (1..=count)
+            .rev()
+            .map(|save_number| {
+                SaveInformation::new(
+                    format!("First Last-123456789__QuickSave_{}", save_number),
+                    "First Last".to_string(),
+                    SaveType::Quick,
+                    Some(save_number),
+                )
+            })
+            .collect()
+    }
+
+    fn save_numbers(saves: &[SaveInformation]) -> Vec<u64> {
+        saves.iter().filter_map(|save| save.save_number).collect()
+    }
+
+    #[test]
+    fn keep_every_nth_save_beyond_preserved() {
+        let saves = numbered_saves(30);
+
+        let result = thinned_saves(saves, 10usize, 5usize);
+        let kept: Vec<u64> = (1..=30)
+            .rev()
+            .filter(|save_number| !save_numbers(&result).contains(save_number))
+            .collect();
+        assert_eq!(
+            kept,
+            vec![30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 16, 11, 6, 1]
+        );
+    }
+
+    #[test]
+    fn delete_nothing_when_thinning_every_save() {
+        let saves = numbered_saves(10);
+
+        let result = thinned_saves(saves, 2usize, 1usize);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn delete_exact_fixed_pool() {
+        let saves = numbered_saves(8);
+
+        let result = thinned_saves(saves, 2usize, 3usize);
+        assert_eq!(save_numbers(&result), vec![6, 5, 3, 2]);
+    }
+
+    #[test]
+    fn format_ordinals() {
+        assert_eq!(ordinal(1), "1st");
+        assert_eq!(ordinal(2), "2nd");
+        assert_eq!(ordinal(3), "3rd");
+        assert_eq!(ordinal(5), "5th");
+        assert_eq!(ordinal(12), "12th");
+        assert_eq!(ordinal(22), "22nd");
+    }
+}
+
+#[cfg(test)]
+mod over_size_budget_should {
+    use crate::{over_size_budget, SaveInformation, SaveType};
+
+    fn saves_of_size(sizes: &[u64]) -> Vec<SaveInformation> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, size)| {
+                SaveInformation::new(
+                    format!("test_file_name{}", i),
+                    "First Last".to_string(),
+                    SaveType::Auto,
+                    Some((sizes.len() - i) as u64),
+                )
+                .with_size_bytes(*size)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keep_newest_saves_within_budget() {
+        let saves = saves_of_size(&[40, 40, 40, 40]);
+
+        let result = over_size_budget(saves.clone(), 100);
+        assert_eq!(result, saves[2..].to_vec());
+    }
+
+    #[test]
+    fn keep_save_exactly_filling_budget() {
+        let saves = saves_of_size(&[50, 50, 50]);
+
+        let result = over_size_budget(saves.clone(), 100);
+        assert_eq!(result, saves[2..].to_vec());
+    }
+
+    #[test]
+    fn delete_everything_after_first_save_exceeding_budget() {
+        let saves = saves_of_size(&[10, 500, 10, 10]);
+
+        let result = over_size_budget(saves.clone(), 100);
+        assert_eq!(result, saves[1..].to_vec());
+    }
+
+    #[test]
+    fn return_empty_vec_when_everything_fits() {
+        let saves = saves_of_size(&[10, 10, 10]);
+
+        let result = over_size_budget(saves, 100);
+        assert!(result.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod retain_older_than_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        folder_filter::FolderFilter,
+        retain_older_than, scan_saves,
+        test_utils::{set_modified, TempDir},
+        SaveInformation, SaveType,
+    };
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn keep_everything_without_cutoff() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "First Last".to_string()),
+        ];
+
+        let result = retain_older_than(saves.clone(), None, SystemTime::now());
+        assert_eq!(result, saves);
+    }
+
+    #[test]
+    fn only_return_saves_older_than_cutoff() {
+        let now = SystemTime::now();
+        let temp_dir = TempDir::new();
+        set_modified(
+            &temp_dir.create_save("SomeName-123456789__QuickSave_1"),
+            now - DAY * 40,
+        );
+        set_modified(
+            &temp_dir.create_save("SomeName-123456789__QuickSave_2"),
+            now - DAY * 2,
+        );
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+
+        let result = retain_older_than(saves, Some(DAY * 30), now);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.first().unwrap().save_number, Some(1));
+    }
+
+    #[test]
+    fn never_delete_saves_without_modified_time() {
+        let saves = vec![SaveInformation::new_random(
+            SaveType::Quick,
+            "First Last".to_string(),
+        )];
+
+        let result = retain_older_than(saves, Some(DAY), SystemTime::now());
+        assert!(result.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod rescue_recent_saves_should {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        folder_filter::FolderFilter,
+        rescue_recent_saves, scan_saves,
+        test_utils::{set_modified, TempDir},
+        SaveInformation, SaveType,
+    };
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    #[test]
+    fn remove_recently_modified_saves() {
+        let now = SystemTime::now();
+        let temp_dir = TempDir::new();
+        set_modified(
+            &temp_dir.create_save("SomeName-123456789__QuickSave_1"),
+            now - HOUR / 2,
+        );
+        set_modified(
+            &temp_dir.create_save("SomeName-123456789__QuickSave_2"),
+            now - HOUR * 3,
+        );
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+
+        let (result, rescued_saves) = rescue_recent_saves(saves, HOUR, now);
+        assert_eq!(rescued_saves, 1);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.first().unwrap().save_number, Some(2));
+    }
+
+    #[test]
+    fn leave_saves_without_modified_time_to_other_rules() {
+        let saves = vec![SaveInformation::new_random(
+            SaveType::Auto,
+            "First Last".to_string(),
+        )];
+
+        let (result, rescued_saves) = rescue_recent_saves(saves.clone(), HOUR, SystemTime::now());
+        assert_eq!(rescued_saves, 0);
+        assert_eq!(result, saves);
+    }
 }
 
 #[cfg(test)]
@@ -704,19 +6164,19 @@ mod deletable_saves_should {
                 "test_file_name1".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                33u16,
+                Some(33),
             ),
             SaveInformation::new(
                 "test_file_name2".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                32u16,
+                Some(32),
             ),
             SaveInformation::new(
                 "test_file_name3".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                31u16,
+                Some(31),
             ),
         ];
 
@@ -753,19 +6213,19 @@ mod deletable_saves_should {
                 "test_file_name1".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                33u16,
+                Some(33),
             ),
             SaveInformation::new(
                 "test_file_name2".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                32u16,
+                Some(32),
             ),
             SaveInformation::new(
                 "test_file_name3".to_string(),
                 "First Last".to_string(),
                 SaveType::Auto,
-                31u16,
+                Some(31),
             ),
         ];
 
@@ -776,3 +6236,778 @@ mod deletable_saves_should {
         );
     }
 }
+
+#[cfg(test)]
+mod select_indices_should {
+    use crate::{save_information::SaveInformation, save_type::SaveType, select_indices};
+
+    #[test]
+    fn keep_only_selected_saves_in_order() {
+        let saves: Vec<SaveInformation> = (0..5)
+            .map(|_| SaveInformation::new_random(SaveType::Quick, "Tav".to_string()))
+            .collect();
+
+        let result = select_indices(saves.clone(), &[0, 3, 4]);
+        assert_eq!(
+            result,
+            vec![saves[0].clone(), saves[3].clone(), saves[4].clone()]
+        );
+    }
+}
+
+#[cfg(test)]
+mod delete_listed_should {
+    use crate::{
+        clean_config::CleanConfig, delete_listed, program_errors::ProgramError, test_utils::TempDir,
+    };
+
+    fn save_folders() -> TempDir {
+        let temp_dir = TempDir::new();
+        (1..=3).for_each(|n| {
+            temp_dir.create_save(&format!("Tav-123456789__QuickSave_{}", n));
+        });
+
+        temp_dir
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn delete_exactly_the_listed_folders() {
+        let temp_dir = save_folders();
+
+        let summary = delete_listed(
+            names(&["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_3"]),
+            temp_dir.path().to_path_buf(),
+            &CleanConfig {
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.deleted, 2);
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_2").exists());
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_3").exists());
+    }
+
+    #[test]
+    fn finish_without_asking_when_nothing_is_listed() {
+        let temp_dir = save_folders();
+
+        let summary = delete_listed(
+            Vec::new(),
+            temp_dir.path().to_path_buf(),
+            &CleanConfig {
+                quiet: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(summary.deleted, 0);
+    }
+
+    #[test]
+    fn stop_on_unknown_names() {
+        let temp_dir = save_folders();
+
+        let result = delete_listed(
+            names(&["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_9"]),
+            temp_dir.path().to_path_buf(),
+            &CleanConfig {
+                yes: true,
+                quiet: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(result, Err(ProgramError::UnknownFolders(_))));
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+
+    #[test]
+    fn delete_the_others_when_skipping_unknown_names() {
+        let temp_dir = save_folders();
+
+        let summary = delete_listed(
+            names(&["Tav-123456789__QuickSave_1", "Tav-123456789__QuickSave_9"]),
+            temp_dir.path().to_path_buf(),
+            &CleanConfig {
+                yes: true,
+                quiet: true,
+                skip_unknown: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!((summary.scanned, summary.deleted), (2, 1));
+        assert!(!temp_dir.path().join("Tav-123456789__QuickSave_1").exists());
+    }
+}
+
+#[cfg(test)]
+mod confirm_user_delete_should {
+    use crate::{confirm_user_delete, save_information::SaveInformation, save_type::SaveType};
+
+    fn saves() -> Vec<SaveInformation> {
+        vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Unrecognized, "Tav".to_string()),
+        ]
+    }
+
+    fn answers(answers: &[&str]) -> impl FnMut(&str) -> String {
+        let mut answers: Vec<String> = answers.iter().rev().map(|a| a.to_string()).collect();
+        move |_| answers.pop().unwrap()
+    }
+
+    #[test]
+    fn accept_yes_and_no_words() {
+        let saves = saves();
+
+        let (selected, user_input) =
+            confirm_user_delete(saves.clone(), 10, answers(&["yes", "NO"]));
+        assert_eq!(
+            (selected, user_input.as_str()),
+            (vec![saves[0].clone()], "y")
+        );
+    }
+
+    #[test]
+    fn ask_again_after_invalid_answer() {
+        let saves = saves();
+
+        let (selected, _) =
+            confirm_user_delete(saves.clone(), 10, answers(&["u", "y", "maybe", "y"]));
+        assert_eq!(selected, saves);
+    }
+
+    #[test]
+    fn decline_after_too_many_invalid_answers() {
+        let (selected, user_input) =
+            confirm_user_delete(saves(), 10, answers(&["u", "u", "u", "u", "u", "u"]));
+        assert_eq!((selected.len(), user_input.as_str()), (0, "n"));
+    }
+
+    #[test]
+    fn ask_as_usual_up_to_the_big_delete_threshold() {
+        let saves = saves();
+
+        let (selected, _) = confirm_user_delete(saves.clone(), 2, answers(&["y", "y"]));
+        assert_eq!(selected, saves);
+    }
+
+    #[test]
+    fn confirm_unrecognized_saves_on_their_own_above_the_big_delete_threshold() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Unrecognized, "Tav".to_string()),
+        ];
+
+        let (declined, user_input) =
+            confirm_user_delete(saves.clone(), 1, answers(&["n", "DELETE"]));
+        assert_eq!((declined, user_input.as_str()), (saves[..2].to_vec(), "y"));
+
+        let (confirmed, _) = confirm_user_delete(saves.clone(), 1, answers(&["y", "DELETE"]));
+        assert_eq!(confirmed.len(), 3);
+    }
+
+    #[test]
+    fn ask_as_usual_when_declined_sections_bring_it_under_the_threshold() {
+        let saves = saves();
+
+        let (selected, _) = confirm_user_delete(saves.clone(), 1, answers(&["n", "y"]));
+        assert_eq!(selected, vec![saves[0].clone()]);
+    }
+
+    #[test]
+    fn ask_only_for_the_typed_word_above_the_big_delete_threshold() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Auto, "Tav".to_string()),
+        ];
+
+        let (selected, user_input) = confirm_user_delete(saves.clone(), 1, answers(&["DELETE"]));
+        assert_eq!((selected, user_input.as_str()), (saves, "y"));
+    }
+}
+
+#[cfg(test)]
+mod confirm_big_delete_should {
+    use crate::{confirm_big_delete, save_information::SaveInformation, save_type::SaveType};
+
+    fn confirmed_saves(count: usize) -> Vec<SaveInformation> {
+        (0..count)
+            .map(|_| SaveInformation::new_random(SaveType::Quick, "Tav".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn go_ahead_with_typed_word() {
+        let (saves, user_input) = confirm_big_delete(confirmed_saves(4), |question| {
+            assert!(question.contains("4 folders"));
+            "DELETE".to_string()
+        });
+        assert_eq!((saves.len(), user_input.as_str()), (4, "y"));
+    }
+
+    #[test]
+    fn cancel_on_plain_yes() {
+        let (saves, user_input) = confirm_big_delete(confirmed_saves(4), |_| "y".to_string());
+        assert_eq!((saves.len(), user_input.as_str()), (0, "n"));
+    }
+}
+
+#[cfg(test)]
+mod approve_per_character_should {
+    use crate::{approve_per_character, save_information::SaveInformation, save_type::SaveType};
+
+    fn saves() -> Vec<SaveInformation> {
+        vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Auto, "Durge".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string()),
+            SaveInformation::new_random(SaveType::Quick, "Karlach".to_string()),
+        ]
+    }
+
+    fn answers(answers: &[&str]) -> impl FnMut(&str) -> String {
+        let mut answers: Vec<String> = answers.iter().rev().map(|a| a.to_string()).collect();
+        move |_| answers.pop().unwrap()
+    }
+
+    #[test]
+    fn keep_only_saves_of_approved_characters() {
+        let saves = saves();
+
+        // Characters are asked about in alphabetical order: Durge, Karlach, Tav.
+        let approved = approve_per_character(saves.clone(), answers(&["y", "skip", "y"]));
+        assert_eq!(
+            approved,
+            Some(vec![saves[1].clone(), saves[0].clone(), saves[2].clone()])
+        );
+    }
+
+    #[test]
+    fn delete_nothing_after_quit() {
+        let approved = approve_per_character(saves(), answers(&["y", "quit"]));
+        assert_eq!(approved, None);
+    }
+
+    #[test]
+    fn ask_again_after_invalid_answer() {
+        let saves = saves();
+
+        let approved = approve_per_character(saves.clone(), answers(&["n", "1-2", "y", "n"]));
+        assert_eq!(approved, Some(vec![saves[3].clone()]));
+    }
+
+    #[test]
+    fn skip_a_character_after_too_many_invalid_answers() {
+        let saves = saves();
+
+        let approved = approve_per_character(
+            saves.clone(),
+            answers(&["1-2", "all", "everything", "n", "y"]),
+        );
+        assert_eq!(approved, Some(vec![saves[0].clone(), saves[2].clone()]));
+    }
+
+    #[test]
+    fn ask_about_each_campaign_of_a_character() {
+        let saves = vec![
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+                .with_campaign_id(Some("111111aaaa".to_string())),
+            SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+                .with_campaign_id(Some("222222bbbb".to_string())),
+        ];
+        let mut questions = Vec::new();
+
+        let approved = approve_per_character(saves.clone(), |question: &str| {
+            questions.push(question.to_string());
+            if questions.len() == 1 { "n" } else { "y" }.to_string()
+        });
+
+        assert_eq!(approved, Some(vec![saves[1].clone()]));
+        assert_eq!(
+            questions,
+            vec![
+                "Delete these 1 save(s) of Tav #111111? y/n/skip/quit:",
+                "Delete these 1 save(s) of Tav #222222? y/n/skip/quit:",
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod delete_should {
+    use std::{
+        ffi::OsString,
+        path::{Path, PathBuf},
+    };
+
+    use crate::{
+        audit_log::AuditLog, clutter::Clutter, delete, deletion_path,
+        deletion_strategy::DeletionStrategy, folder_filter::FolderFilter, purge_undo_area,
+        scan_saves, skip_confirmation, test_utils::TempDir, verbatim_path, SaveInformation,
+        SaveType,
+    };
+
+    #[test]
+    fn join_folder_name_to_root() {
+        let save_information = SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+            .with_folder_name(OsString::from("Tav-123456789__QuickSave_3"));
+
+        ["saves", "saves/", "./saves/Story"]
+            .iter()
+            .for_each(|directory| {
+                assert_eq!(
+                    deletion_path(&save_information, Path::new(directory)),
+                    Path::new(directory).join("Tav-123456789__QuickSave_3")
+                );
+            });
+        assert_eq!(
+            deletion_path(
+                &save_information.clone().with_root(Path::new("other")),
+                Path::new("saves")
+            ),
+            Path::new("other/Tav-123456789__QuickSave_3")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn join_with_backslashes_on_windows() {
+        let save_information = SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+            .with_folder_name(OsString::from("Tav-123456789__QuickSave_3"));
+
+        [
+            r"C:\Savegames\Story",
+            r"C:\Savegames\Story\",
+            "C:/Savegames/Story",
+        ]
+        .iter()
+        .for_each(|directory| {
+            let path = deletion_path(&save_information, Path::new(directory));
+
+            assert_eq!(
+                path,
+                Path::new(directory).join("Tav-123456789__QuickSave_3")
+            );
+            assert!(!path.to_string_lossy().contains("\\\\Tav"));
+        });
+    }
+
+    #[test]
+    fn delete_many_folders_in_parallel() {
+        let temp_dir = TempDir::new();
+        (1..=60).for_each(|n| {
+            temp_dir.create_save(&format!("Tav-123456789__QuickSave_{}", n));
+        });
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+
+        let deleted = delete(
+            (
+                saves.clone(),
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            8,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted, saves);
+        assert!(scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves
+            .is_empty());
+    }
+
+    #[test]
+    fn keep_going_when_a_folder_fails() {
+        let temp_dir = TempDir::new();
+        (1..=5).for_each(|n| {
+            temp_dir.create_save(&format!("Tav-123456789__QuickSave_{}", n));
+        });
+        let mut saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        saves.sort_by_key(|save| save.save_number);
+        let quarantine_dir = temp_dir.path().join("quarantine");
+        // A file where the quarantine directory should be makes every move fail.
+        std::fs::write(&quarantine_dir, "").unwrap();
+
+        let deleted = delete(
+            (saves, "y".to_string(), temp_dir.path().to_path_buf()),
+            &Clutter::default(),
+            &DeletionStrategy::Quarantine(quarantine_dir),
+            None,
+            true,
+            3,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert!(deleted.is_empty());
+        assert_eq!(
+            scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+                .unwrap()
+                .saves
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn report_the_folder_that_failed_and_delete_the_others() {
+        let temp_dir = TempDir::new();
+        (1..=4).for_each(|n| {
+            temp_dir.create_save(&format!("Tav-123456789__QuickSave_{}", n));
+        });
+        // Without a save package the folder is refused, the others are still deleted.
+        std::fs::write(
+            temp_dir
+                .path()
+                .join("Tav-123456789__QuickSave_2")
+                .join("test.lsv"),
+            b"XXXX",
+        )
+        .unwrap();
+        let mut saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        saves.sort_by_key(|save| save.save_number);
+
+        let report = delete(
+            (
+                saves.clone(),
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            2,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            report.deleted,
+            vec![saves[0].clone(), saves[2].clone(), saves[3].clone()]
+        );
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].save, saves[1]);
+        assert!(report.format_failures()[0].starts_with("Tav-123456789__QuickSave_2: \""));
+        assert!(temp_dir.path().join("Tav-123456789__QuickSave_2").exists());
+    }
+
+    #[test]
+    fn refuse_folders_outside_the_save_folder() {
+        let temp_dir = TempDir::new();
+        let root = temp_dir.path().join("Story");
+        std::fs::create_dir(&root).unwrap();
+        let victim = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let hostile = SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+            .with_folder_name(OsString::from("../Tav-123456789__QuickSave_1"));
+
+        let report = delete(
+            (vec![hostile], "y".to_string(), root),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            false,
+            1,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap();
+
+        assert!(report.deleted.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert!(report.failed[0].error.contains("resolves to outside of"));
+        assert!(victim.join("test.lsv").exists());
+    }
+
+    #[test]
+    fn delete_folders_ending_in_space_or_dot() {
+        let temp_dir = TempDir::new();
+        let folders: Vec<PathBuf> = ["Tav-123456789__QuickSave_1 ", "Tav-123456789__QuickSave_2."]
+            .iter()
+            .map(|folder_name| {
+                // Windows only creates such folders through a verbatim path as well.
+                let folder = verbatim_path::for_removal(&temp_dir.path().join(folder_name));
+                std::fs::create_dir(&folder).unwrap();
+                std::fs::write(folder.join("test.lsv"), b"LSPK").unwrap();
+                folder
+            })
+            .collect();
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        assert_eq!(saves.len(), 2);
+
+        let deleted = delete(
+            (
+                saves.clone(),
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            2,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted.len(), 2);
+        assert!(folders.iter().all(|folder| !folder.exists()));
+    }
+
+    #[test]
+    fn delete_folders_with_nested_folders() {
+        let temp_dir = TempDir::new();
+        let folder = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        std::fs::create_dir_all(folder.join("Mods/Cache")).unwrap();
+        std::fs::write(folder.join("Mods/Cache/settings.json"), "{}").unwrap();
+        let saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+
+        let deleted = delete(
+            (
+                saves.clone(),
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            1,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted, saves);
+        assert!(!folder.exists());
+        // Staged for undo until the next run purges it, nested folders included.
+        assert_eq!(
+            purge_undo_area(temp_dir.path()).map(|(purged, _)| purged),
+            Ok(1)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn delete_symlinks_but_not_the_folders_they_link_to() {
+        let temp_dir = TempDir::new();
+        let other_drive = TempDir::new();
+        let target = other_drive.create_save("Tav-123456789__QuickSave_1");
+        let link = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        let saves = scan_saves(
+            temp_dir.path(),
+            &FolderFilter {
+                follow_symlinks: true,
+                ..Default::default()
+            },
+            || (),
+        )
+        .unwrap()
+        .saves;
+
+        [DeletionStrategy::Remove, DeletionStrategy::Trash]
+            .iter()
+            .for_each(|strategy| {
+                std::os::unix::fs::symlink(&target, &link).ok();
+                let deleted = delete(
+                    (
+                        saves.clone(),
+                        "y".to_string(),
+                        temp_dir.path().to_path_buf(),
+                    ),
+                    &Clutter::default(),
+                    strategy,
+                    None,
+                    true,
+                    1,
+                    &mut AuditLog::disabled(),
+                )
+                .unwrap()
+                .deleted;
+                purge_undo_area(temp_dir.path()).unwrap();
+
+                assert_eq!(deleted, saves);
+                assert!(!link.is_symlink());
+                assert!(target.join("test.lsv").exists());
+            });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn delete_folders_with_names_that_are_not_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let temp_dir = TempDir::new();
+        let folder = temp_dir
+            .path()
+            .join(OsStr::from_bytes(b"T\xffv-123456789__QuickSave_3"));
+        std::fs::create_dir(&folder).unwrap();
+        std::fs::write(folder.join("test.lsv"), b"LSPK").unwrap();
+        let scan_result = scan_saves(temp_dir.path(), &FolderFilter::default(), || ()).unwrap();
+        assert!(scan_result.skipped_folders.is_empty());
+        assert_eq!(scan_result.saves[0].character_name, "T\u{FFFD}v");
+        assert_eq!(scan_result.saves[0].save_number, Some(3));
+
+        let deleted = delete(
+            (
+                skip_confirmation(scan_result.saves).0,
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            1,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted.len(), 1);
+        assert!(!folder.exists());
+    }
+
+    #[test]
+    fn skip_folders_without_save_package_unless_not_verifying() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let not_a_save = temp_dir.create_save("Tav-123456789__QuickSave_2");
+        std::fs::write(not_a_save.join("test.lsv"), b"MZ\x90\x00").unwrap();
+        let mut saves = scan_saves(temp_dir.path(), &FolderFilter::default(), || ())
+            .unwrap()
+            .saves;
+        saves.sort_by_key(|save| save.save_number);
+
+        let deleted = delete(
+            (
+                saves.clone(),
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            true,
+            2,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted, vec![saves[0].clone()]);
+        assert!(not_a_save.exists());
+
+        let deleted = delete(
+            (
+                vec![saves[1].clone()],
+                "y".to_string(),
+                temp_dir.path().to_path_buf(),
+            ),
+            &Clutter::default(),
+            &DeletionStrategy::Remove,
+            None,
+            false,
+            2,
+            &mut AuditLog::disabled(),
+        )
+        .unwrap()
+        .deleted;
+
+        assert_eq!(deleted, vec![saves[1].clone()]);
+        assert!(!not_a_save.exists());
+    }
+}
+
+#[cfg(test)]
+mod purge_undo_area_should {
+    use std::time::SystemTime;
+
+    use crate::{purge_undo_area, test_utils::TempDir, undo, Journal};
+
+    #[test]
+    fn remove_staged_saves_and_journal() {
+        let temp_dir = TempDir::new();
+        let sources = vec![temp_dir.create_save("Tav-123456789__QuickSave_3")];
+        let journal = Journal::begin(temp_dir.path(), &sources, SystemTime::now()).unwrap();
+        journal.stage(&sources[0]).unwrap();
+
+        assert_eq!(
+            purge_undo_area(temp_dir.path()).map(|(purged, _)| purged),
+            Ok(1)
+        );
+        assert!(!undo::undo_dir(temp_dir.path()).exists());
+        assert_eq!(purge_undo_area(temp_dir.path()), Ok((0, 0)));
+    }
+
+    #[test]
+    fn remove_staged_saves_with_nested_folders() {
+        let temp_dir = TempDir::new();
+        let source = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        std::fs::create_dir_all(source.join("Mods/Cache")).unwrap();
+        std::fs::write(source.join("Mods/Cache/settings.json"), "{}").unwrap();
+        std::fs::write(source.join("Mods/modsettings.lsx"), "").unwrap();
+        let journal = Journal::begin(
+            temp_dir.path(),
+            std::slice::from_ref(&source),
+            SystemTime::now(),
+        )
+        .unwrap();
+        journal.stage(&source).unwrap();
+
+        assert_eq!(
+            purge_undo_area(temp_dir.path()).map(|(purged, _)| purged),
+            Ok(1)
+        );
+        assert!(!undo::undo_dir(temp_dir.path()).exists());
+    }
+
+    #[test]
+    fn count_the_purged_bytes_as_reclaimed() {
+        let temp_dir = TempDir::new();
+        let source = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        let journal = Journal::begin(
+            temp_dir.path(),
+            std::slice::from_ref(&source),
+            SystemTime::now(),
+        )
+        .unwrap();
+        journal.stage(&source).unwrap();
+        let staged_bytes = crate::folder_size(&undo::undo_dir(temp_dir.path()));
+
+        assert!(staged_bytes > 0);
+        assert_eq!(purge_undo_area(temp_dir.path()), Ok((1, staged_bytes)));
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static MACHINE_READABLE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed) || is_machine_readable()
+}
+
+/// Reserves stdout for a machine readable document, suppressing all human readable output.
+pub fn set_machine_readable(machine_readable: bool) {
+    MACHINE_READABLE.store(machine_readable, Ordering::Relaxed);
+}
+
+pub fn is_machine_readable() -> bool {
+    MACHINE_READABLE.load(Ordering::Relaxed)
+}
+
+/// Prints informational output, suppressed when running with `--quiet`.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if !crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use info;
+
+/// Prints the outcome of a run, which is kept with `--quiet` but not for machine readable output.
+macro_rules! outcome {
+    ($($arg:tt)*) => {
+        if !crate::output::is_machine_readable() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use outcome;
@@ -0,0 +1,10 @@
+use clap::ValueEnum;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable listing
+    #[default]
+    Text,
+    /// A single JSON document on stdout
+    Json,
+}
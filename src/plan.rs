@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    json::{FromJson, JsonValue, ToJson},
+    program_errors::ProgramError,
+    save_information::SaveInformation,
+};
+
+/// A deletion plan written with `--write-plan` and executed later with `--apply-plan`.
+#[derive(Debug, PartialEq)]
+pub struct Plan {
+    pub version: String,
+    pub root: PathBuf,
+    pub saves: Vec<SaveInformation>,
+}
+impl Plan {
+    pub fn new(root: PathBuf, saves: Vec<SaveInformation>) -> Self {
+        Plan {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            root,
+            saves,
+        }
+    }
+
+    /// Splits off the saves whose folder no longer exists below their root.
+    pub fn split_vanished(self) -> (Plan, Vec<SaveInformation>) {
+        let root = self.root;
+        let (saves, vanished) = self.saves.into_iter().partition(|save_information| {
+            save_information
+                .root_or(&root)
+                .join(&save_information.folder_name)
+                .is_dir()
+        });
+
+        (
+            Plan {
+                version: self.version,
+                root,
+                saves,
+            },
+            vanished,
+        )
+    }
+}
+impl ToJson for Plan {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            ("version", JsonValue::String(self.version.clone())),
+            (
+                "root",
+                JsonValue::String(self.root.to_string_lossy().to_string()),
+            ),
+            ("saves", self.saves.to_json()),
+        ])
+    }
+}
+impl FromJson for Plan {
+    fn from_json(value: &JsonValue) -> Result<Self, String> {
+        let string_field = |key: &str| {
+            value
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .ok_or(format!("Missing or invalid field {}", key))
+        };
+
+        let saves = value
+            .get("saves")
+            .and_then(JsonValue::as_array)
+            .ok_or("Missing or invalid field saves".to_string())?
+            .iter()
+            .map(SaveInformation::from_json)
+            .collect::<Result<Vec<SaveInformation>, String>>()?;
+
+        // A plan only ever names folders directly inside its root, anything else was edited in.
+        if let Some(save_information) = saves
+            .iter()
+            .find(|save_information| !is_plain_folder_name(&save_information.file_name))
+        {
+            return Err(format!(
+                "Save {} is not a folder name directly inside the root",
+                save_information.file_name
+            ));
+        }
+
+        Ok(Plan {
+            version: string_field("version")?.to_string(),
+            root: PathBuf::from(string_field("root")?),
+            saves,
+        })
+    }
+}
+
+pub fn write_plan(path: &Path, plan: &Plan) -> Result<(), ProgramError> {
+    fs::write(path, format!("{:#}\n", plan.to_json()))
+        .map_err(|e| ProgramError::FailedToWriteFile(e.to_string()))
+}
+
+pub fn read_plan(path: &Path) -> Result<Plan, ProgramError> {
+    fs::read_to_string(path)
+        .map_err(|e| ProgramError::FailedToReadFile(e.to_string()))
+        .and_then(|content| {
+            JsonValue::parse(&content)
+                .and_then(|value| Plan::from_json(&value))
+                .map_err(ProgramError::InvalidPlan)
+        })
+}
+
+pub fn is_plain_folder_name(file_name: &str) -> bool {
+    let mut components = Path::new(file_name).components();
+
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    )
+}
+
+#[cfg(test)]
+mod plan_should {
+    use std::{
+        fs,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::{
+        plan::{read_plan, write_plan, Plan},
+        program_errors::ProgramError,
+        save_information::SaveInformation,
+        save_type::SaveType,
+        test_utils::TempDir,
+    };
+
+    fn planned_saves() -> Vec<SaveInformation> {
+        vec![
+            SaveInformation::new(
+                "Some'me-123456789__QuickSave_3".to_string(),
+                "Some'me".to_string(),
+                SaveType::Quick,
+                Some(3),
+            )
+            .with_modified(Some(UNIX_EPOCH + Duration::from_secs(1_709_414_045)))
+            .with_size_bytes(1024),
+            SaveInformation::new(
+                "Some'me-123456789__AutoSave_1".to_string(),
+                "Some'me".to_string(),
+                SaveType::Auto,
+                Some(1),
+            ),
+        ]
+    }
+
+    #[test]
+    fn round_trip_through_file() {
+        let temp_dir = TempDir::new();
+        let plan_path = temp_dir.path().join("plan.json");
+        let plan = Plan::new(temp_dir.path().join("Story"), planned_saves());
+
+        write_plan(&plan_path, &plan).unwrap();
+
+        assert_eq!(read_plan(&plan_path).unwrap(), plan);
+    }
+
+    #[test]
+    fn split_off_vanished_saves() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Some'me-123456789__QuickSave_3");
+        let plan = Plan::new(temp_dir.path().to_path_buf(), planned_saves());
+
+        let (plan, vanished) = plan.split_vanished();
+        assert_eq!(plan.saves, planned_saves()[..1].to_vec());
+        assert_eq!(vanished, planned_saves()[1..].to_vec());
+    }
+
+    #[test]
+    fn reject_folders_outside_root() {
+        let temp_dir = TempDir::new();
+        let plan_path = temp_dir.path().join("plan.json");
+        let mut saves = planned_saves();
+        saves[0].file_name = "../Documents".to_string();
+        write_plan(&plan_path, &Plan::new(temp_dir.path().join("Story"), saves)).unwrap();
+
+        let error = read_plan(&plan_path).unwrap_err();
+        assert_eq!(
+            error,
+            ProgramError::InvalidPlan(
+                "Save ../Documents is not a folder name directly inside the root".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn error_on_malformed_plan() {
+        let temp_dir = TempDir::new();
+        let plan_path = temp_dir.path().join("plan.json");
+        fs::write(&plan_path, "{\"version\": \"0.1.0\"}").unwrap();
+
+        let error = read_plan(&plan_path).unwrap_err();
+        assert_eq!(
+            error,
+            ProgramError::InvalidPlan("Missing or invalid field saves".to_string())
+        );
+    }
+
+    #[test]
+    fn error_on_missing_file() {
+        let temp_dir = TempDir::new();
+
+        let result = read_plan(&temp_dir.path().join("missing.json"));
+        assert!(matches!(result, Err(ProgramError::FailedToReadFile(_))));
+    }
+}
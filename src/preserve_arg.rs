@@ -0,0 +1,45 @@
+/// Parses a `Name=N` override given to `--preserve-for`. The value is split on the last `=`
+/// so character names containing spaces, apostrophes or `=` work.
+pub fn parse_preserve_for(value: &str) -> Result<(String, usize), String> {
+    let (name, count) = value
+        .rsplit_once('=')
+        .ok_or(format!("Missing = in {}, expected Name=N", value))?;
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(format!("Missing character name in {}", value));
+    }
+
+    count
+        .trim()
+        .parse::<usize>()
+        .map(|count| (name.to_string(), count))
+        .map_err(|e| format!("Invalid count in {}: {}", value, e))
+}
+
+#[cfg(test)]
+mod parse_preserve_for_should {
+    use crate::preserve_arg::parse_preserve_for;
+
+    #[test]
+    fn parse_name_and_count() {
+        assert_eq!(parse_preserve_for("Tav=30"), Ok(("Tav".to_string(), 30)));
+        assert_eq!(
+            parse_preserve_for("Some'me Else = 2"),
+            Ok(("Some'me Else".to_string(), 2))
+        );
+    }
+
+    #[test]
+    fn split_on_last_equals_sign() {
+        assert_eq!(parse_preserve_for("A=B=5"), Ok(("A=B".to_string(), 5)));
+    }
+
+    #[test]
+    fn reject_invalid_values() {
+        assert!(parse_preserve_for("Tav").is_err());
+        assert!(parse_preserve_for("=3").is_err());
+        assert!(parse_preserve_for("Tav=many").is_err());
+        assert!(parse_preserve_for("Tav=-1").is_err());
+    }
+}
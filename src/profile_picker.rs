@@ -0,0 +1,186 @@
+use crate::{confirmation, profiles::ProfileDirectory, program_errors::ProgramError};
+
+/// A profile offered in the menu, with the number of saves in it.
+#[derive(Debug, PartialEq)]
+pub struct Choice {
+    pub profile: ProfileDirectory,
+    pub saves: usize,
+}
+
+/// One numbered line per profile, like `1) Public — 212 saves`.
+pub fn render_menu(choices: &[Choice]) -> Vec<String> {
+    choices
+        .iter()
+        .enumerate()
+        .map(|(index, choice)| {
+            format!(
+                "{}) {} — {} saves",
+                index + 1,
+                choice.profile.label(),
+                choice.saves
+            )
+        })
+        .collect()
+}
+
+/// Parses the number of a menu line, or the name of a profile, into the index of the choice.
+pub fn parse_choice(input: &str, choices: &[Choice]) -> Result<usize, String> {
+    let input = input.trim();
+    match input.parse::<usize>() {
+        Ok(number) if (1..=choices.len()).contains(&number) => Ok(number - 1),
+        Ok(number) => Err(format!("{} is not between 1 and {}", number, choices.len())),
+        Err(_) => choices
+            .iter()
+            .position(|choice| choice.profile.profile.as_deref() == Some(input))
+            .ok_or(format!("{} is neither a number nor a profile", input)),
+    }
+}
+
+/// Keeps the profiles that have saves, asking which one to use when several do. Without
+/// `read_answer` nobody can be asked, so the profile has to be picked on the command line.
+pub fn pick(
+    choices: Vec<Choice>,
+    read_answer: Option<impl FnMut(&str) -> String>,
+) -> Result<Vec<ProfileDirectory>, ProgramError> {
+    let (mut with_saves, without_saves): (Vec<Choice>, Vec<Choice>) =
+        choices.into_iter().partition(|choice| choice.saves > 0);
+    if with_saves.len() < 2 {
+        // Without saves anywhere, every profile reports that it has none.
+        let kept = if with_saves.is_empty() {
+            without_saves
+        } else {
+            with_saves
+        };
+        return Ok(kept.into_iter().map(|choice| choice.profile).collect());
+    }
+
+    let Some(mut read_answer) = read_answer else {
+        return Err(ProgramError::ProfileNotFound(format!(
+            "Found saves in several player profiles, pick one with --profile or use --all-profiles: {}",
+            with_saves
+                .iter()
+                .map(|choice| choice.profile.label())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )));
+    };
+
+    println!("Found saves in several player profiles:");
+    render_menu(&with_saves)
+        .iter()
+        .for_each(|line| println!("  {}", line));
+    for _ in 0..confirmation::MAX_ATTEMPTS {
+        match parse_choice(&read_answer("Run against which profile?"), &with_saves) {
+            Ok(index) => return Ok(vec![with_saves.swap_remove(index).profile]),
+            Err(e) => eprintln!("Invalid profile: {}, please answer with its number", e),
+        }
+    }
+
+    Err(ProgramError::ProfileNotFound(
+        "No profile picked, nothing was done".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod profile_picker_should {
+    use std::path::PathBuf;
+
+    use crate::{
+        profile_picker::{parse_choice, pick, render_menu, Choice},
+        profiles::ProfileDirectory,
+        program_errors::ProgramError,
+    };
+
+    fn choice(profile: &str, saves: usize) -> Choice {
+        Choice {
+            profile: ProfileDirectory {
+                profile: Some(profile.to_string()),
+                path: PathBuf::from(profile),
+            },
+            saves,
+        }
+    }
+
+    fn answering(answers: &[&str]) -> Option<impl FnMut(&str) -> String> {
+        let mut answers: Vec<String> = answers.iter().rev().map(|a| a.to_string()).collect();
+        Some(move |_: &str| answers.pop().unwrap_or("n".to_string()))
+    }
+
+    fn picked(picked: Result<Vec<ProfileDirectory>, ProgramError>) -> Vec<Option<String>> {
+        picked
+            .unwrap()
+            .into_iter()
+            .map(|profile_directory| profile_directory.profile)
+            .collect()
+    }
+
+    #[test]
+    fn number_every_profile_with_its_saves() {
+        assert_eq!(
+            render_menu(&[choice("Public", 212), choice("Kids", 31)]),
+            vec!["1) Public — 212 saves", "2) Kids — 31 saves"]
+        );
+    }
+
+    #[test]
+    fn parse_numbers_and_names() {
+        let choices = [choice("Public", 212), choice("Kids", 31)];
+
+        assert_eq!(parse_choice(" 2 ", &choices), Ok(1));
+        assert_eq!(parse_choice("Public", &choices), Ok(0));
+        assert!(parse_choice("0", &choices).is_err());
+        assert!(parse_choice("3", &choices).is_err());
+        assert!(parse_choice("Guests", &choices).is_err());
+    }
+
+    #[test]
+    fn use_the_picked_profile() {
+        let choices = vec![
+            choice("Public", 212),
+            choice("Kids", 31),
+            choice("Empty", 0),
+        ];
+
+        assert_eq!(
+            picked(pick(choices, answering(&["Empty", "3", "2"]))),
+            vec![Some("Kids".to_string())]
+        );
+    }
+
+    #[test]
+    fn give_up_after_invalid_answers() {
+        let choices = vec![choice("Public", 212), choice("Kids", 31)];
+
+        assert!(matches!(
+            pick(choices, answering(&[])),
+            Err(ProgramError::ProfileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn ask_for_the_profile_on_the_command_line_without_a_terminal() {
+        let choices = vec![choice("Public", 212), choice("Kids", 31)];
+        let nobody: Option<fn(&str) -> String> = None;
+
+        assert_eq!(
+            pick(choices, nobody),
+            Err(ProgramError::ProfileNotFound(
+                "Found saves in several player profiles, pick one with --profile or use --all-profiles: Public, Kids".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn not_ask_when_only_one_profile_has_saves() {
+        let nobody: Option<fn(&str) -> String> = None;
+
+        assert_eq!(
+            picked(pick(vec![choice("Public", 0), choice("Kids", 31)], nobody)),
+            vec![Some("Kids".to_string())]
+        );
+        assert_eq!(
+            picked(pick(vec![choice("Public", 0), choice("Kids", 0)], nobody)),
+            vec![Some("Public".to_string()), Some("Kids".to_string())]
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::program_errors::ProgramError;
+
+/// A directory to run against, with the name of the player profile it belongs to if it was
+/// found below a `PlayerProfiles` directory.
+#[derive(Debug, PartialEq)]
+pub struct ProfileDirectory {
+    pub profile: Option<String>,
+    pub path: PathBuf,
+}
+
+impl ProfileDirectory {
+    /// The profile name, or the path when the directory is not part of a profile.
+    pub fn label(&self) -> String {
+        self.profile
+            .clone()
+            .unwrap_or(self.path.display().to_string())
+    }
+}
+
+pub fn story_dir(profile_dir: &Path) -> PathBuf {
+    profile_dir.join("Savegames").join("Story")
+}
+
+/// The profiles below a `PlayerProfiles` directory that have a `Savegames/Story` folder,
+/// sorted by name.
+pub fn find_profiles(player_profiles: &Path) -> Vec<ProfileDirectory> {
+    let mut profiles: Vec<ProfileDirectory> = fs::read_dir(player_profiles)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| story_dir(&entry.path()).is_dir())
+                .map(|entry| ProfileDirectory {
+                    profile: Some(entry.file_name().to_string_lossy().to_string()),
+                    path: story_dir(&entry.path()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    profiles.sort_by(|a, b| a.profile.cmp(&b.profile));
+
+    profiles
+}
+
+/// Picks the directories to run against. A directory without profiles is used as is, from a
+/// `PlayerProfiles` directory the named profile or every profile is picked, each run on its own
+/// so saves never mix between profiles. Which of several to use is up to `profile_picker`.
+pub fn resolve(
+    directory: &Path,
+    profile: Option<&str>,
+    all_profiles: bool,
+) -> Result<Vec<ProfileDirectory>, ProgramError> {
+    let profiles = find_profiles(directory);
+    let names = || {
+        profiles
+            .iter()
+            .filter_map(|profile_directory| profile_directory.profile.clone())
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    if profiles.is_empty() {
+        return match (profile, all_profiles) {
+            (None, false) => Ok(vec![ProfileDirectory {
+                profile: None,
+                path: directory.to_path_buf(),
+            }]),
+            _ => Err(ProgramError::ProfileNotFound(format!(
+                "No player profiles with a Savegames/Story folder found in {}",
+                directory.display()
+            ))),
+        };
+    }
+
+    match profile {
+        Some(profile) => {
+            let found_names = names();
+            profiles
+                .into_iter()
+                .find(|profile_directory| profile_directory.profile.as_deref() == Some(profile))
+                .map(|profile_directory| vec![profile_directory])
+                .ok_or(ProgramError::ProfileNotFound(format!(
+                    "No profile named {}, found: {}",
+                    profile, found_names
+                )))
+        }
+        None => Ok(profiles),
+    }
+}
+
+#[cfg(test)]
+mod resolve_should {
+    use std::fs;
+
+    use crate::{
+        profiles::{resolve, story_dir, ProfileDirectory},
+        program_errors::ProgramError,
+        test_utils::TempDir,
+    };
+
+    fn profile_tree(profiles: &[&str]) -> TempDir {
+        let temp_dir = TempDir::new();
+        profiles.iter().for_each(|profile| {
+            fs::create_dir_all(story_dir(&temp_dir.path().join(profile))).unwrap()
+        });
+        // Not a profile, it has no saves.
+        fs::create_dir_all(temp_dir.path().join("Empty")).unwrap();
+
+        temp_dir
+    }
+
+    #[test]
+    fn use_directory_without_profiles_as_is() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+
+        assert_eq!(
+            resolve(temp_dir.path(), None, false).unwrap(),
+            vec![ProfileDirectory {
+                profile: None,
+                path: temp_dir.path().to_path_buf()
+            }]
+        );
+        assert!(matches!(
+            resolve(temp_dir.path(), Some("Public"), false),
+            Err(ProgramError::ProfileNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn pick_named_profile() {
+        let temp_dir = profile_tree(&["Public", "Second"]);
+
+        assert_eq!(
+            resolve(temp_dir.path(), Some("Second"), false).unwrap(),
+            vec![ProfileDirectory {
+                profile: Some("Second".to_string()),
+                path: story_dir(&temp_dir.path().join("Second"))
+            }]
+        );
+        assert_eq!(
+            resolve(temp_dir.path(), Some("Third"), false),
+            Err(ProgramError::ProfileNotFound(
+                "No profile named Third, found: Public, Second".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn pick_every_profile_without_a_name() {
+        let temp_dir = profile_tree(&["Second", "Public"]);
+
+        let profiles: Vec<Option<String>> = resolve(temp_dir.path(), None, true)
+            .unwrap()
+            .into_iter()
+            .map(|profile_directory| profile_directory.profile)
+            .collect();
+        assert_eq!(
+            profiles,
+            vec![Some("Public".to_string()), Some("Second".to_string())]
+        );
+        assert_eq!(resolve(temp_dir.path(), None, false).unwrap().len(), 2);
+
+        let temp_dir = profile_tree(&["Public"]);
+        assert_eq!(resolve(temp_dir.path(), None, false).unwrap().len(), 1);
+    }
+}
@@ -5,11 +5,33 @@ pub enum ProgramError {
     NameNotDetected(String),
     CannotReadDirectory(String),
     NotEnoughUnderscores(String),
-    StringNotNumber(String),
-    AsciiErrorInFileName(String),
     NoPath(String),
     FailedToDelete(String),
     FailedToReadDir(String),
+    CharacterNotFound(String),
+    FailedToWriteFile(String),
+    FailedToReadFile(String),
+    InvalidPlan(String),
+    FailedToTrash(String),
+    FailedToMove(String),
+    FailedToRestore(String),
+    NothingToUndo(String),
+    InvalidJournal(String),
+    ProfileNotFound(String),
+    InvalidConfig(String),
+    UnparsedFolders(String),
+    GameRunning(String),
+    CampaignNotFound(String),
+    AmbiguousCampaign(String),
+    NotASave(String),
+    PatternMismatch(String),
+    UnknownFolders(String),
+    NotInteractive(String),
+    NotASaveFolder(String),
+    AlreadyRunning(String),
+    Interrupted(String),
+    OutsideSaveRoot(String),
+    MassDelete(String),
 }
 impl Error for ProgramError {}
 impl Display for ProgramError {
@@ -17,12 +39,34 @@ impl Display for ProgramError {
         match self {
             ProgramError::NameNotDetected(e) => write!(f, "{:#?}", e),
             ProgramError::NotEnoughUnderscores(e) => write!(f, "{:#?}", e),
-            ProgramError::StringNotNumber(e) => write!(f, "{:#?}", e),
-            ProgramError::AsciiErrorInFileName(e) => write!(f, "{:#?}", e),
             ProgramError::NoPath(e) => write!(f, "{:#?}", e),
             ProgramError::CannotReadDirectory(e) => write!(f, "{:#?}", e),
             ProgramError::FailedToDelete(e) => write!(f, "{:#?}", e),
             ProgramError::FailedToReadDir(e) => write!(f, "{:#?}", e),
+            ProgramError::CharacterNotFound(e) => write!(f, "{:#?}", e),
+            ProgramError::FailedToWriteFile(e) => write!(f, "{:#?}", e),
+            ProgramError::FailedToReadFile(e) => write!(f, "{:#?}", e),
+            ProgramError::InvalidPlan(e) => write!(f, "{:#?}", e),
+            ProgramError::FailedToTrash(e) => write!(f, "{:#?}", e),
+            ProgramError::FailedToMove(e) => write!(f, "{:#?}", e),
+            ProgramError::FailedToRestore(e) => write!(f, "{:#?}", e),
+            ProgramError::NothingToUndo(e) => write!(f, "{:#?}", e),
+            ProgramError::InvalidJournal(e) => write!(f, "{:#?}", e),
+            ProgramError::ProfileNotFound(e) => write!(f, "{:#?}", e),
+            ProgramError::InvalidConfig(e) => write!(f, "{:#?}", e),
+            ProgramError::UnparsedFolders(e) => write!(f, "{:#?}", e),
+            ProgramError::GameRunning(e) => write!(f, "{:#?}", e),
+            ProgramError::CampaignNotFound(e) => write!(f, "{:#?}", e),
+            ProgramError::AmbiguousCampaign(e) => write!(f, "{:#?}", e),
+            ProgramError::NotASave(e) => write!(f, "{:#?}", e),
+            ProgramError::PatternMismatch(e) => write!(f, "{:#?}", e),
+            ProgramError::UnknownFolders(e) => write!(f, "{:#?}", e),
+            ProgramError::NotInteractive(e) => write!(f, "{:#?}", e),
+            ProgramError::NotASaveFolder(e) => write!(f, "{:#?}", e),
+            ProgramError::AlreadyRunning(e) => write!(f, "{:#?}", e),
+            ProgramError::Interrupted(e) => write!(f, "{:#?}", e),
+            ProgramError::OutsideSaveRoot(e) => write!(f, "{:#?}", e),
+            ProgramError::MassDelete(e) => write!(f, "{:#?}", e),
         }
     }
 }
@@ -0,0 +1,226 @@
+use std::io::Write;
+
+const CLEAR_LINE: &str = "\r\x1b[2K";
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+/// Without a terminal a line is printed for every tenth of the work.
+const LINE_STEPS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressStyle {
+    /// A single line redrawn in place.
+    Bar,
+    /// Plain lines now and then, for logs and pipes.
+    Lines,
+    Hidden,
+}
+impl ProgressStyle {
+    pub fn pick(is_terminal: bool, quiet: bool) -> Self {
+        match (is_terminal, quiet) {
+            (_, true) => ProgressStyle::Hidden,
+            (true, false) => ProgressStyle::Bar,
+            (false, false) => ProgressStyle::Lines,
+        }
+    }
+}
+
+/// Reports "<verb> x of n" while working through a known number of items.
+pub struct Progress<W: Write> {
+    verb: &'static str,
+    total: usize,
+    current: usize,
+    style: ProgressStyle,
+    writer: W,
+    drawn: bool,
+}
+impl<W: Write> Progress<W> {
+    pub fn new(verb: &'static str, total: usize, style: ProgressStyle, writer: W) -> Self {
+        Progress {
+            verb,
+            total,
+            current: 0,
+            style,
+            writer,
+            drawn: false,
+        }
+    }
+
+    /// Counts one item as started, showing its name on the bar and the periodic lines.
+    pub fn tick(&mut self, name: &str) {
+        self.current += 1;
+
+        // Progress is best effort, failing to draw it never stops the work.
+        let _ = match self.style {
+            ProgressStyle::Bar => {
+                self.drawn = true;
+                write!(
+                    self.writer,
+                    "{}{} {} of {} | {}",
+                    CLEAR_LINE, self.verb, self.current, self.total, name
+                )
+            }
+            ProgressStyle::Lines
+                if self.current == self.total
+                    || self
+                        .current
+                        .is_multiple_of(self.total.div_ceil(LINE_STEPS).max(1)) =>
+            {
+                writeln!(
+                    self.writer,
+                    "{} {} of {} | {}",
+                    self.verb, self.current, self.total, name
+                )
+            }
+            _ => Ok(()),
+        };
+        let _ = self.writer.flush();
+    }
+
+    /// Clears the bar while `print` writes, e.g. an error, and draws it again below.
+    pub fn suspend(&mut self, print: impl FnOnce()) {
+        if !self.drawn {
+            print();
+            return;
+        }
+
+        let _ = write!(self.writer, "{}", CLEAR_LINE).and_then(|_| self.writer.flush());
+        print();
+        let _ = write!(
+            self.writer,
+            "{} {} of {}",
+            self.verb, self.current, self.total
+        )
+        .and_then(|_| self.writer.flush());
+    }
+
+    /// Removes the bar so the summary starts on a clean line.
+    pub fn finish(&mut self) {
+        if self.drawn {
+            let _ = write!(self.writer, "{}", CLEAR_LINE).and_then(|_| self.writer.flush());
+            self.drawn = false;
+        }
+    }
+}
+
+/// Counts items while their total is still unknown, only ever drawn on a terminal.
+pub struct Spinner<W: Write> {
+    noun: &'static str,
+    count: usize,
+    visible: bool,
+    writer: W,
+}
+impl<W: Write> Spinner<W> {
+    pub fn new(noun: &'static str, style: ProgressStyle, writer: W) -> Self {
+        Spinner {
+            noun,
+            count: 0,
+            visible: style == ProgressStyle::Bar,
+            writer,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.count += 1;
+
+        if self.visible {
+            let _ = write!(
+                self.writer,
+                "{}{} scanned {} {}...",
+                CLEAR_LINE,
+                SPINNER_FRAMES[self.count % SPINNER_FRAMES.len()],
+                self.count,
+                self.noun
+            )
+            .and_then(|_| self.writer.flush());
+        }
+    }
+
+    pub fn finish(&mut self) {
+        if self.visible && self.count > 0 {
+            let _ = write!(self.writer, "{}", CLEAR_LINE).and_then(|_| self.writer.flush());
+        }
+    }
+}
+
+#[cfg(test)]
+mod progress_should {
+    use crate::progress::{Progress, ProgressStyle};
+
+    fn run(style: ProgressStyle, total: usize) -> String {
+        let mut output = Vec::new();
+        let mut progress = Progress::new("Deleting", total, style, &mut output);
+        (1..=total).for_each(|i| progress.tick(&format!("Save_{}", i)));
+        progress.finish();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn redraw_bar_in_place() {
+        assert_eq!(
+            run(ProgressStyle::Bar, 2),
+            "\r\x1b[2KDeleting 1 of 2 | Save_1\r\x1b[2KDeleting 2 of 2 | Save_2\r\x1b[2K"
+        );
+    }
+
+    #[test]
+    fn print_every_tenth_without_terminal() {
+        let output = run(ProgressStyle::Lines, 25);
+
+        assert_eq!(output.lines().count(), 9);
+        assert_eq!(output.lines().next(), Some("Deleting 3 of 25 | Save_3"));
+        assert_eq!(output.lines().last(), Some("Deleting 25 of 25 | Save_25"));
+    }
+
+    #[test]
+    fn print_nothing_when_hidden() {
+        assert_eq!(run(ProgressStyle::Hidden, 5), "");
+    }
+
+    #[test]
+    fn clear_bar_around_suspended_output() {
+        let mut output = Vec::new();
+        let mut progress = Progress::new("Deleting", 2, ProgressStyle::Bar, &mut output);
+        progress.tick("Save_1");
+        progress.suspend(|| ());
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\r\x1b[2KDeleting 1 of 2 | Save_1\r\x1b[2KDeleting 1 of 2"
+        );
+    }
+
+    #[test]
+    fn pick_style() {
+        assert_eq!(ProgressStyle::pick(true, false), ProgressStyle::Bar);
+        assert_eq!(ProgressStyle::pick(false, false), ProgressStyle::Lines);
+        assert_eq!(ProgressStyle::pick(true, true), ProgressStyle::Hidden);
+    }
+}
+
+#[cfg(test)]
+mod spinner_should {
+    use crate::progress::{ProgressStyle, Spinner};
+
+    fn run(style: ProgressStyle, count: usize) -> String {
+        let mut output = Vec::new();
+        let mut spinner = Spinner::new("folders", style, &mut output);
+        (0..count).for_each(|_| spinner.tick());
+        spinner.finish();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn count_up_on_terminal() {
+        assert_eq!(
+            run(ProgressStyle::Bar, 2),
+            "\r\x1b[2K/ scanned 1 folders...\r\x1b[2K- scanned 2 folders...\r\x1b[2K"
+        );
+    }
+
+    #[test]
+    fn stay_silent_without_terminal() {
+        assert_eq!(run(ProgressStyle::Lines, 3), "");
+        assert_eq!(run(ProgressStyle::Hidden, 3), "");
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{program_errors::ProgramError, retry};
+
+/// Moves a save folder into `quarantine_dir`, keeping its name. A name already taken in the
+/// quarantine gets a numeric suffix like ` (2)`, which is dropped again on restore.
+pub fn quarantine(path: &Path, quarantine_dir: &Path) -> Result<PathBuf, ProgramError> {
+    let folder_name = path
+        .file_name()
+        .ok_or(ProgramError::FailedToMove(format!(
+            "{} has no folder name",
+            path.display()
+        )))?
+        .to_string_lossy()
+        .to_string();
+    fs::create_dir_all(quarantine_dir)
+        .map_err(|e| ProgramError::FailedToMove(format!("{}: {}", quarantine_dir.display(), e)))?;
+
+    let destination = (1..)
+        .map(|n| match n {
+            1 => quarantine_dir.join(&folder_name),
+            n => quarantine_dir.join(format!("{} ({})", folder_name, n)),
+        })
+        .find(|destination| !destination.exists())
+        .ok_or(ProgramError::FailedToMove(
+            "No free name in the quarantine".to_string(),
+        ))?;
+
+    move_dir(path, &destination).map(|_| destination)
+}
+
+/// The folder names in the quarantine, sorted.
+pub fn quarantined_folders(quarantine_dir: &Path) -> Result<Vec<String>, ProgramError> {
+    let mut folders: Vec<String> = fs::read_dir(quarantine_dir)
+        .map_err(|e| {
+            ProgramError::CannotReadDirectory(format!("{}: {}", quarantine_dir.display(), e))
+        })?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    folders.sort();
+
+    Ok(folders)
+}
+
+/// Moves a quarantined folder back into `save_dir`, never replacing an existing folder.
+pub fn restore(
+    quarantine_dir: &Path,
+    folder_name: &str,
+    save_dir: &Path,
+) -> Result<PathBuf, ProgramError> {
+    let destination = save_dir.join(original_name(folder_name));
+    if destination.exists() {
+        return Err(ProgramError::FailedToRestore(format!(
+            "{} already exists",
+            destination.display()
+        )));
+    }
+
+    move_dir(&quarantine_dir.join(folder_name), &destination).map(|_| destination)
+}
+
+fn original_name(folder_name: &str) -> &str {
+    folder_name
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .filter(|(_, n)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        .map_or(folder_name, |(name, _)| name)
+}
+
+/// Renames a folder, falling back to copying and deleting it when the rename fails, e.g.
+/// because the destination is on another filesystem.
+pub fn move_dir(from: &Path, to: &Path) -> Result<(), ProgramError> {
+    if retry::io(|| fs::rename(from, to)).is_ok() {
+        return Ok(());
+    }
+
+    // A loose file across volumes is copied as a whole.
+    if from.is_file() {
+        return fs::copy(from, to)
+            .and_then(|_| fs::remove_file(from))
+            .map_err(|e| {
+                if from.exists() {
+                    let _ = fs::remove_file(to);
+                }
+                ProgramError::FailedToMove(format!("{} to {}: {}", from.display(), to.display(), e))
+            });
+    }
+
+    copy_dir(from, to)
+        .and_then(|_| fs::remove_dir_all(from))
+        .map_err(|e| {
+            // Only the copy is cleaned up, the source stays untouched on failure.
+            if from.exists() {
+                let _ = fs::remove_dir_all(to);
+            }
+            ProgramError::FailedToMove(format!("{} to {}: {}", from.display(), to.display(), e))
+        })
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir(to)?;
+
+    fs::read_dir(from)?.try_for_each(|entry| {
+        let entry = entry?;
+        let destination = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &destination)
+        } else {
+            fs::copy(entry.path(), destination).map(|_| ())
+        }
+    })
+}
+
+#[cfg(test)]
+mod quarantine_should {
+    use std::fs;
+
+    use crate::{
+        program_errors::ProgramError,
+        quarantine::{copy_dir, original_name, quarantine, quarantined_folders, restore},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn move_folder_and_suffix_collisions() {
+        let temp_dir = TempDir::new();
+        let quarantine_dir = temp_dir.path().join("quarantine");
+
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        quarantine(&save_path, &quarantine_dir).unwrap();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        let destination = quarantine(&save_path, &quarantine_dir).unwrap();
+
+        assert!(!save_path.exists());
+        assert_eq!(
+            destination,
+            quarantine_dir.join("Tav-123456789__QuickSave_3 (2)")
+        );
+        assert_eq!(
+            quarantined_folders(&quarantine_dir).unwrap(),
+            vec![
+                "Tav-123456789__QuickSave_3".to_string(),
+                "Tav-123456789__QuickSave_3 (2)".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn restore_under_original_name() {
+        let temp_dir = TempDir::new();
+        let quarantine_dir = temp_dir.path().join("quarantine");
+        let save_dir = temp_dir.path().join("saves");
+        fs::create_dir(&save_dir).unwrap();
+        fs::create_dir(&quarantine_dir).unwrap();
+        copy_dir(
+            &temp_dir.create_save("Tav-123456789__QuickSave_3"),
+            &quarantine_dir.join("Tav-123456789__QuickSave_3 (2)"),
+        )
+        .unwrap();
+
+        let restored =
+            restore(&quarantine_dir, "Tav-123456789__QuickSave_3 (2)", &save_dir).unwrap();
+
+        assert_eq!(restored, save_dir.join("Tav-123456789__QuickSave_3"));
+        assert_eq!(fs::read(restored.join("test.lsv")).unwrap(), b"LSPK");
+        assert!(quarantined_folders(&quarantine_dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn refuse_to_overwrite_existing_save() {
+        let temp_dir = TempDir::new();
+        let quarantine_dir = temp_dir.path().join("quarantine");
+        quarantine(
+            &temp_dir.create_save("Tav-123456789__QuickSave_3"),
+            &quarantine_dir,
+        )
+        .unwrap();
+        temp_dir.create_save("Tav-123456789__QuickSave_3");
+
+        let result = restore(
+            &quarantine_dir,
+            "Tav-123456789__QuickSave_3",
+            temp_dir.path(),
+        );
+        assert!(matches!(result, Err(ProgramError::FailedToRestore(_))));
+        assert!(quarantine_dir.join("Tav-123456789__QuickSave_3").exists());
+    }
+
+    #[test]
+    fn strip_only_numeric_suffixes() {
+        assert_eq!(original_name("Tav__QuickSave_3 (12)"), "Tav__QuickSave_3");
+        assert_eq!(original_name("Tav__QuickSave_3"), "Tav__QuickSave_3");
+        assert_eq!(original_name("Tav (Dark Urge)"), "Tav (Dark Urge)");
+    }
+}
@@ -0,0 +1,556 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+/// A small backtracking regular expression, enough for matching folder names: literals, `.`,
+/// classes like `[a-z]`, `\d`, `\w` and `\s`, groups with `|`, named groups like
+/// `(?P<name>...)`, anchors and the quantifiers `*`, `+`, `?` and `{n,m}`.
+#[derive(Debug, Clone)]
+pub struct Regex {
+    source: String,
+    alternatives: Vec<Vec<Node>>,
+    /// The names of the named groups, a group's index in here is the index of its capture.
+    names: Vec<String>,
+}
+
+/// Where each named group matched during a match, by group index.
+type Captures = RefCell<Vec<Option<(usize, usize)>>>;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Capture {
+        index: usize,
+        alternatives: Vec<Vec<Node>>,
+    },
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            position: 0,
+            names: Vec::new(),
+        };
+        let alternatives = parser.alternatives()?;
+        if let Some(c) = parser.peek() {
+            return Err(format!(
+                "Invalid regex {}: unexpected {} at position {}",
+                pattern, c, parser.position
+            ));
+        }
+
+        Ok(Regex {
+            source: pattern.to_string(),
+            alternatives,
+            names: parser.names,
+        })
+    }
+
+    /// A regex matching whole names against a glob, where `*` is any run of characters, `?` a
+    /// single one and `[...]` a class.
+    pub fn from_glob(glob: &str) -> Result<Self, String> {
+        let mut pattern = String::from("^");
+        let mut in_class = false;
+        glob.chars().for_each(|c| match c {
+            '[' if !in_class => {
+                in_class = true;
+                pattern.push(c)
+            }
+            ']' if in_class => {
+                in_class = false;
+                pattern.push(c)
+            }
+            _ if in_class => pattern.push(c),
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c if c.is_alphanumeric() || c == ' ' => pattern.push(c),
+            c => {
+                pattern.push('\\');
+                pattern.push(c)
+            }
+        });
+        pattern.push('$');
+
+        Regex::new(&pattern).map_err(|_| format!("Invalid glob {}", glob))
+    }
+
+    /// Whether the pattern matches anywhere in `text`, anchor it with `^` and `$` to match
+    /// all of it.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.captures(text).is_some()
+    }
+
+    /// The text each named group matched at the first match in `text`, `None` without a match.
+    /// Groups that took no part in the match are left out.
+    pub fn captures(&self, text: &str) -> Option<HashMap<&str, String>> {
+        let text: Vec<char> = text.chars().collect();
+        let captures: Captures = RefCell::new(vec![None; self.names.len()]);
+
+        let matched = (0..=text.len()).any(|start| {
+            self.alternatives
+                .iter()
+                .any(|nodes| match_sequence(nodes, &text, start, &captures, &|_| true))
+        });
+
+        matched.then(|| {
+            self.names
+                .iter()
+                .zip(captures.into_inner())
+                .filter_map(|(name, capture)| {
+                    capture.map(|(start, end)| (name.as_str(), text[start..end].iter().collect()))
+                })
+                .collect()
+        })
+    }
+
+    pub fn group_names(&self) -> &[String] {
+        &self.names
+    }
+}
+impl PartialEq for Regex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+impl Display for Regex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    names: Vec<String>,
+}
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!(
+            "Invalid regex {}: {}",
+            self.chars.iter().collect::<String>(),
+            message
+        )
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.position += 1;
+            alternatives.push(self.sequence()?);
+        }
+
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, String> {
+        let mut nodes = Vec::new();
+
+        while let Some(c) = self.peek() {
+            let node = match c {
+                '|' | ')' => break,
+                '*' | '+' | '?' | '{' => return Err(self.error("nothing to repeat")),
+                _ => self.atom()?,
+            };
+            nodes.push(self.quantified(node)?);
+        }
+
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some('.') => Ok(Node::Any),
+            Some('^') => Ok(Node::Start),
+            Some('$') => Ok(Node::End),
+            Some('(') => {
+                let name = self.group_name()?;
+                let alternatives = self.alternatives()?;
+                if self.next() != Some(')') {
+                    return Err(self.error("missing closing )"));
+                }
+
+                Ok(match name {
+                    Some(name) => {
+                        self.names.push(name);
+                        Node::Capture {
+                            index: self.names.len() - 1,
+                            alternatives,
+                        }
+                    }
+                    None => Node::Group(alternatives),
+                })
+            }
+            Some('[') => self.class(),
+            Some('\\') => self.escape(),
+            Some(c) => Ok(Node::Char(c)),
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    /// The name of a `(?P<name>` or `(?<name>` group, `None` for other groups including `(?:`.
+    fn group_name(&mut self) -> Result<Option<String>, String> {
+        if self.peek() != Some('?') {
+            return Ok(None);
+        }
+        self.position += 1;
+
+        match self.next() {
+            Some(':') => return Ok(None),
+            Some('P') if self.peek() == Some('<') => self.position += 1,
+            Some('<') => (),
+            _ => return Err(self.error("unsupported group")),
+        }
+        let length = self.chars[self.position..]
+            .iter()
+            .position(|c| *c == '>')
+            .ok_or(self.error("missing > after group name"))?;
+        let name: String = self.chars[self.position..self.position + length]
+            .iter()
+            .collect();
+        self.position += length + 1;
+
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(self.error(&format!("invalid group name {}", name)));
+        }
+        if self.names.contains(&name) {
+            return Err(self.error(&format!("group name {} given twice", name)));
+        }
+        Ok(Some(name))
+    }
+
+    fn escape(&mut self) -> Result<Node, String> {
+        let class = |ranges: &[(char, char)], negated| Node::Class {
+            ranges: ranges.to_vec(),
+            negated,
+        };
+
+        match self.next() {
+            Some('d') => Ok(class(&DIGITS, false)),
+            Some('D') => Ok(class(&DIGITS, true)),
+            Some('w') => Ok(class(&WORD, false)),
+            Some('W') => Ok(class(&WORD, true)),
+            Some('s') => Ok(class(&SPACE, false)),
+            Some('S') => Ok(class(&SPACE, true)),
+            Some(c) if c.is_ascii_alphanumeric() => {
+                Err(self.error(&format!("unsupported escape \\{}", c)))
+            }
+            Some(c) => Ok(Node::Char(c)),
+            None => Err(self.error("trailing backslash")),
+        }
+    }
+
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.position += 1;
+        }
+        let mut ranges = Vec::new();
+
+        loop {
+            let start = match self.next() {
+                // A ] right after the opening bracket is a literal.
+                Some(']') if !ranges.is_empty() => break,
+                Some('\\') => match self.escape()? {
+                    Node::Class {
+                        ranges: escaped, ..
+                    } => {
+                        ranges.extend(escaped);
+                        continue;
+                    }
+                    Node::Char(c) => c,
+                    _ => return Err(self.error("invalid escape in class")),
+                },
+                Some(c) => c,
+                None => return Err(self.error("missing closing ]")),
+            };
+
+            let is_range = self.peek() == Some('-')
+                && self.chars.get(self.position + 1).is_some_and(|c| *c != ']');
+            if is_range {
+                self.position += 1;
+                let end = self.next().ok_or(self.error("missing closing ]"))?;
+                if end < start {
+                    return Err(self.error(&format!("invalid range {}-{}", start, end)));
+                }
+                ranges.push((start, end));
+            } else {
+                ranges.push((start, start));
+            }
+        }
+
+        Ok(Node::Class { ranges, negated })
+    }
+
+    fn quantified(&mut self, node: Node) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.counted(node),
+            _ => return Ok(node),
+        };
+        self.position += 1;
+        if matches!(node, Node::Start | Node::End) {
+            return Err(self.error("nothing to repeat"));
+        }
+
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    fn counted(&mut self, node: Node) -> Result<Node, String> {
+        self.position += 1;
+        let closing = self.chars[self.position..]
+            .iter()
+            .position(|c| *c == '}')
+            .ok_or(self.error("missing closing }"))?;
+        let counts: String = self.chars[self.position..self.position + closing]
+            .iter()
+            .collect();
+        self.position += closing + 1;
+
+        let parse = |count: &str| {
+            count
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| self.error(&format!("invalid repetition {{{}}}", counts)))
+        };
+        let (min, max) = match counts.split_once(',') {
+            None => (parse(&counts)?, Some(parse(&counts)?)),
+            Some((min, "")) => (parse(min)?, None),
+            Some((min, max)) => (parse(min)?, Some(parse(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            return Err(self.error(&format!("invalid repetition {{{}}}", counts)));
+        }
+
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+}
+
+const DIGITS: [(char, char); 1] = [('0', '9')];
+const WORD: [(char, char); 4] = [('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+const SPACE: [(char, char); 4] = [(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')];
+
+/// Matches `nodes` at `position`, calling `rest` with every position a match could end at
+/// until it accepts one.
+fn match_sequence(
+    nodes: &[Node],
+    text: &[char],
+    position: usize,
+    captures: &Captures,
+    rest: &dyn Fn(usize) -> bool,
+) -> bool {
+    match nodes.split_first() {
+        None => rest(position),
+        Some((node, remaining)) => match_node(node, text, position, captures, &|next| {
+            match_sequence(remaining, text, next, captures, rest)
+        }),
+    }
+}
+
+fn match_node(
+    node: &Node,
+    text: &[char],
+    position: usize,
+    captures: &Captures,
+    rest: &dyn Fn(usize) -> bool,
+) -> bool {
+    let current = text.get(position).copied();
+
+    match node {
+        Node::Char(c) => current == Some(*c) && rest(position + 1),
+        Node::Any => current.is_some() && rest(position + 1),
+        Node::Class { ranges, negated } => {
+            current.is_some_and(|c| {
+                ranges
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&c))
+                    != *negated
+            }) && rest(position + 1)
+        }
+        Node::Start => position == 0 && rest(position),
+        Node::End => position == text.len() && rest(position),
+        Node::Group(alternatives) => alternatives
+            .iter()
+            .any(|nodes| match_sequence(nodes, text, position, captures, rest)),
+        // Recorded before the rest is matched and put back if the rest fails.
+        Node::Capture {
+            index,
+            alternatives,
+        } => alternatives.iter().any(|nodes| {
+            match_sequence(nodes, text, position, captures, &|next| {
+                let previous = captures.borrow_mut()[*index].replace((position, next));
+                rest(next) || {
+                    captures.borrow_mut()[*index] = previous;
+                    false
+                }
+            })
+        }),
+        Node::Repeat { node, min, max } => {
+            match_repeat(node, (*min, *max), 0, text, position, captures, rest)
+        }
+    }
+}
+
+/// Greedy repetition, trying one more match of `node` before handing over to `rest`.
+fn match_repeat(
+    node: &Node,
+    (min, max): (usize, Option<usize>),
+    count: usize,
+    text: &[char],
+    position: usize,
+    captures: &Captures,
+    rest: &dyn Fn(usize) -> bool,
+) -> bool {
+    let may_repeat = max.is_none_or(|max| count < max);
+
+    (may_repeat
+        && match_node(node, text, position, captures, &|next| {
+            // An empty match can't make progress, it only counts towards the minimum.
+            (next != position || count < min)
+                && match_repeat(node, (min, max), count + 1, text, next, captures, rest)
+        }))
+        || (count >= min && rest(position))
+}
+
+#[cfg(test)]
+mod regex_should {
+    use crate::regex::Regex;
+
+    fn is_match(pattern: &str, text: &str) -> bool {
+        Regex::new(pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn match_literals_anywhere() {
+        assert!(is_match("_KEEP", "Tav-123__QuickSave_3_KEEP"));
+        assert!(!is_match("_KEEP", "Tav-123__QuickSave_3"));
+        assert!(is_match("", "anything"));
+    }
+
+    #[test]
+    fn honour_anchors() {
+        assert!(is_match("_KEEP$", "Save_KEEP"));
+        assert!(!is_match("_KEEP$", "Save_KEEP_old"));
+        assert!(is_match("^Tav-", "Tav-123"));
+        assert!(!is_match("^Tav-", "OldTav-123"));
+    }
+
+    #[test]
+    fn match_classes_and_quantifiers() {
+        assert!(is_match(
+            r"^\w+-\d+__QuickSave_\d+$",
+            "Tav-123456789__QuickSave_12"
+        ));
+        assert!(!is_match(
+            r"^\w+-\d+__QuickSave_\d+$",
+            "Tav-123456789__QuickSave_"
+        ));
+        assert!(is_match("^[A-Z][a-z]{2,3}$", "Tav"));
+        assert!(!is_match("^[A-Z][a-z]{2,3}$", "Karlach"));
+        assert!(is_match("^[^_]*$", "Tav-123"));
+        assert!(!is_match("^[^_]*$", "Tav_123"));
+        assert!(is_match("^colou?r$", "color"));
+        assert!(is_match(r"^a\.b$", "a.b"));
+        assert!(!is_match(r"^a\.b$", "axb"));
+    }
+
+    #[test]
+    fn match_groups_and_alternatives() {
+        assert!(is_match("(Quick|Auto)Save", "Tav__AutoSave_3"));
+        assert!(!is_match("(Quick|Auto)Save", "Tav__ManualSave_3"));
+        assert!(is_match("^(ab)+$", "ababab"));
+        assert!(!is_match("^(ab)+$", "ababa"));
+        assert!(is_match("^(a?){2}b$", "b"));
+        assert!(is_match("^x|y$", "xz"));
+    }
+
+    #[test]
+    fn capture_named_groups() {
+        let regex =
+            Regex::new(r"^(?P<name>.+)-(?<campaign>\d+)_+(?:Quick|Auto)Save_(?P<number>\d+)?$")
+                .unwrap();
+
+        let captures = regex.captures("Jean-Luc-123456789__QuickSave_12").unwrap();
+        assert_eq!(captures["name"], "Jean-Luc");
+        assert_eq!(captures["campaign"], "123456789");
+        assert_eq!(captures["number"], "12");
+        assert_eq!(regex.group_names(), ["name", "campaign", "number"]);
+
+        let captures = regex.captures("Tav-1__AutoSave_").unwrap();
+        assert_eq!(captures.get("number"), None);
+        assert!(regex.captures("Tav__AutoSave_3").is_none());
+    }
+
+    #[test]
+    fn keep_last_repetition_of_a_group() {
+        let regex = Regex::new("^((?<part>[a-z])-)+$").unwrap();
+
+        assert_eq!(regex.captures("a-b-c-").unwrap()["part"], "c");
+    }
+
+    #[test]
+    fn match_whole_names_against_globs() {
+        let glob = |glob: &str, text: &str| Regex::from_glob(glob).unwrap().is_match(text);
+
+        assert!(glob("Tav-*__QuickSave_?", "Tav-123__QuickSave_7"));
+        assert!(!glob("Tav-*__QuickSave_?", "Tav-123__QuickSave_17"));
+        assert!(glob("*(1).sav", "Tav (1).sav"));
+        assert!(!glob("a.b", "axb"));
+        assert!(glob("Save_[0-4]", "Save_3"));
+        assert!(!glob("Save", "Save_3"));
+        assert!(Regex::from_glob("Save_[0-4").is_err());
+    }
+
+    #[test]
+    fn reject_invalid_patterns() {
+        assert_eq!(
+            Regex::new("(Quick"),
+            Err("Invalid regex (Quick: missing closing )".to_string())
+        );
+        assert!(Regex::new("[a-").is_err());
+        assert!(Regex::new("*a").is_err());
+        assert!(Regex::new("a)").is_err());
+        assert!(Regex::new("a{3,1}").is_err());
+        assert!(Regex::new(r"\q").is_err());
+        assert!(Regex::new("z-a").is_ok());
+        assert!(Regex::new("[z-a]").is_err());
+        assert!(Regex::new("(?<>a)").is_err());
+        assert!(Regex::new("(?<a>x)(?<a>y)").is_err());
+        assert!(Regex::new("(?=a)").is_err());
+    }
+}
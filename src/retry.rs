@@ -0,0 +1,157 @@
+use std::{
+    cell::Cell,
+    io::{self, ErrorKind},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+/// How often a failed removal is tried again, unless given with `--retries`.
+pub const DEFAULT_RETRIES: usize = 3;
+/// Waited before each retry, the last one is repeated for further retries.
+const BACKOFF: [Duration; 3] = [
+    Duration::from_millis(200),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+];
+
+static RETRIES: AtomicUsize = AtomicUsize::new(DEFAULT_RETRIES);
+thread_local! {
+    static RETRIED: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn set_retries(retries: usize) {
+    RETRIES.store(retries, Ordering::Relaxed);
+}
+
+/// Runs `operation`, trying it again after a short wait when it fails in a way that passes by
+/// itself, like a virus scanner or Steam Cloud briefly holding a file open after playing.
+pub fn io<T>(operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    with_policy(RETRIES.load(Ordering::Relaxed), thread::sleep, operation)
+}
+
+/// The number of retries on this thread since the last call, so each folder of a deletion can
+/// report whether it needed any.
+pub fn take_retried() -> usize {
+    RETRIED.with(|retried| retried.replace(0))
+}
+
+fn with_policy<T>(
+    retries: usize,
+    sleep: impl Fn(Duration),
+    mut operation: impl FnMut() -> io::Result<T>,
+) -> io::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match operation() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                sleep(BACKOFF[attempt.min(BACKOFF.len() - 1)]);
+                attempt += 1;
+                RETRIED.with(|retried| retried.set(retried.get() + 1));
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Windows reports a file held open by another process as a sharing or lock violation.
+fn is_transient(e: &io::Error) -> bool {
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    matches!(
+        e.kind(),
+        ErrorKind::PermissionDenied | ErrorKind::WouldBlock | ErrorKind::Interrupted
+    ) || (cfg!(windows)
+        && matches!(
+            e.raw_os_error(),
+            Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+        ))
+}
+
+#[cfg(test)]
+mod with_policy_should {
+    use std::{
+        cell::RefCell,
+        io::{self, ErrorKind},
+        time::Duration,
+    };
+
+    use crate::retry::{take_retried, with_policy};
+
+    fn failing(
+        failures: Vec<ErrorKind>,
+        attempts: &RefCell<usize>,
+    ) -> impl FnMut() -> io::Result<&'static str> + '_ {
+        move || {
+            let attempt = attempts.replace_with(|attempts| *attempts + 1);
+            match failures.get(attempt) {
+                Some(kind) => Err(io::Error::new(*kind, "locked")),
+                None => Ok("removed"),
+            }
+        }
+    }
+
+    #[test]
+    fn retry_transient_failures_with_growing_waits() {
+        let attempts = RefCell::new(0);
+        let waits = RefCell::new(Vec::new());
+        take_retried();
+
+        let result = with_policy(
+            3,
+            |wait| waits.borrow_mut().push(wait),
+            failing(
+                vec![ErrorKind::PermissionDenied, ErrorKind::WouldBlock],
+                &attempts,
+            ),
+        );
+
+        assert_eq!(result.unwrap(), "removed");
+        assert_eq!(*attempts.borrow(), 3);
+        assert_eq!(
+            *waits.borrow(),
+            vec![Duration::from_millis(200), Duration::from_millis(500)]
+        );
+        assert_eq!(take_retried(), 2);
+        assert_eq!(take_retried(), 0);
+    }
+
+    #[test]
+    fn give_up_after_the_last_retry() {
+        let attempts = RefCell::new(0);
+
+        let result = with_policy(
+            2,
+            |_| (),
+            failing(vec![ErrorKind::PermissionDenied; 5], &attempts),
+        );
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::PermissionDenied);
+        assert_eq!(*attempts.borrow(), 3);
+    }
+
+    #[test]
+    fn not_retry_permanent_failures() {
+        let attempts = RefCell::new(0);
+
+        let result = with_policy(3, |_| (), failing(vec![ErrorKind::NotFound], &attempts));
+
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(*attempts.borrow(), 1);
+    }
+
+    #[test]
+    fn try_once_without_retries() {
+        let attempts = RefCell::new(0);
+
+        let result = with_policy(
+            0,
+            |_| (),
+            failing(vec![ErrorKind::PermissionDenied], &attempts),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(*attempts.borrow(), 1);
+    }
+}
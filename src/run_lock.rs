@@ -0,0 +1,223 @@
+#[cfg(not(target_os = "linux"))]
+use std::process::Command;
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+use crate::{output::info, program_errors::ProgramError};
+
+/// Hidden like the undo area, so scans leave it out.
+pub const LOCK_FILE_NAME: &str = ".bg3sc.lock";
+/// How often `--wait` checks whether the other instance is done.
+pub const WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Marks a save folder as being cleaned by this process, holding its process id. The lock file
+/// is removed again when dropped, also when the run panics.
+#[derive(Debug)]
+pub struct RunLock {
+    path: PathBuf,
+}
+impl RunLock {
+    /// Takes the lock of `directory`, taking over a lock left behind by a process that no
+    /// longer runs.
+    pub fn acquire(directory: &Path) -> Result<RunLock, ProgramError> {
+        Self::acquire_as(directory, process::id(), is_running).map_err(|e| match e {
+            ProgramError::AlreadyRunning(e) => {
+                ProgramError::AlreadyRunning(format!("{}, pass --wait to wait for it", e))
+            }
+            e => e,
+        })
+    }
+
+    /// Like `acquire`, but waits for another instance to release the lock instead of failing.
+    pub fn wait(directory: &Path) -> Result<RunLock, ProgramError> {
+        Self::wait_as(directory, process::id(), is_running, WAIT_INTERVAL)
+    }
+
+    fn wait_as(
+        directory: &Path,
+        pid: u32,
+        is_running: impl Fn(u32) -> bool,
+        interval: Duration,
+    ) -> Result<RunLock, ProgramError> {
+        let mut waiting = false;
+        loop {
+            match Self::acquire_as(directory, pid, &is_running) {
+                Err(ProgramError::AlreadyRunning(e)) => {
+                    if !waiting {
+                        info!("{}, waiting for it to finish", e);
+                        waiting = true;
+                    }
+                    thread::sleep(interval);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn acquire_as(
+        directory: &Path,
+        pid: u32,
+        is_running: impl Fn(u32) -> bool,
+    ) -> Result<RunLock, ProgramError> {
+        let path = directory.join(LOCK_FILE_NAME);
+        let failed = |e: std::io::Error| {
+            ProgramError::FailedToWriteFile(format!("{}: {}", path.display(), e))
+        };
+
+        // The second attempt follows removing a stale lock.
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let lock = RunLock { path: path.clone() };
+                    write!(file, "{}", pid).map_err(failed)?;
+                    return Ok(lock);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let owner = fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|content| content.trim().parse::<u32>().ok());
+                    match owner {
+                        Some(owner) if owner != pid && !is_running(owner) => {
+                            fs::remove_file(&path).or_else(|e| match e.kind() {
+                                ErrorKind::NotFound => Ok(()),
+                                _ => Err(failed(e)),
+                            })?;
+                        }
+                        Some(owner) if owner != pid => {
+                            return Err(ProgramError::AlreadyRunning(format!(
+                                "Another instance (process {}) is cleaning {}",
+                                owner,
+                                directory.display()
+                            )))
+                        }
+                        Some(_) => fs::remove_file(&path).map_err(failed)?,
+                        // Just created by another instance that hasn't written its id yet.
+                        None => {
+                            return Err(ProgramError::AlreadyRunning(format!(
+                                "Another instance is cleaning {}, remove {} if none is running",
+                                directory.display(),
+                                path.display()
+                            )))
+                        }
+                    }
+                }
+                Err(e) => return Err(failed(e)),
+            }
+        }
+
+        Err(ProgramError::AlreadyRunning(format!(
+            "Another instance keeps taking the lock of {}",
+            directory.display()
+        )))
+    }
+}
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_running(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// Failing to list processes counts as running, a lock is never taken over by mistake.
+#[cfg(windows)]
+fn is_running(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid)))
+        .unwrap_or(true)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn is_running(pid: u32) -> bool {
+    Command::new("ps")
+        .args(["-p", &pid.to_string()])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod run_lock_should {
+    use std::{fs, thread, time::Duration};
+
+    use crate::{
+        program_errors::ProgramError,
+        run_lock::{RunLock, LOCK_FILE_NAME},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn hold_the_lock_until_dropped() {
+        let temp_dir = TempDir::new();
+        let lock_path = temp_dir.path().join(LOCK_FILE_NAME);
+
+        let lock = RunLock::acquire_as(temp_dir.path(), 42, |_| true).unwrap();
+        assert_eq!(fs::read_to_string(&lock_path).unwrap(), "42");
+
+        drop(lock);
+        assert!(!lock_path.exists());
+    }
+
+    #[test]
+    fn refuse_a_second_instance() {
+        let temp_dir = TempDir::new();
+        let _lock = RunLock::acquire_as(temp_dir.path(), 42, |_| true).unwrap();
+
+        let result = RunLock::acquire_as(temp_dir.path(), 43, |pid| pid == 42);
+
+        assert!(matches!(result, Err(ProgramError::AlreadyRunning(_))));
+    }
+
+    #[test]
+    fn take_over_locks_of_processes_that_are_gone() {
+        let temp_dir = TempDir::new();
+        fs::write(temp_dir.path().join(LOCK_FILE_NAME), "42").unwrap();
+
+        let _lock = RunLock::acquire_as(temp_dir.path(), 43, |_| false).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(LOCK_FILE_NAME)).unwrap(),
+            "43"
+        );
+    }
+
+    #[test]
+    fn release_the_lock_on_panic() {
+        let temp_dir = TempDir::new();
+        let path = temp_dir.path().to_path_buf();
+
+        let result = thread::spawn(move || {
+            let _lock = RunLock::acquire_as(&path, 42, |_| true).unwrap();
+            panic!("run failed");
+        })
+        .join();
+
+        assert!(result.is_err());
+        assert!(!temp_dir.path().join(LOCK_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn wait_for_the_other_instance() {
+        let temp_dir = TempDir::new();
+        let lock = RunLock::acquire_as(temp_dir.path(), 42, |_| true).unwrap();
+        let releasing = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(lock);
+        });
+
+        let lock = RunLock::wait_as(temp_dir.path(), 43, |_| true, Duration::from_millis(10));
+        releasing.join().unwrap();
+
+        assert!(lock.is_ok());
+    }
+}
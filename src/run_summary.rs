@@ -0,0 +1,220 @@
+use crate::{
+    json::{JsonValue, ToJson},
+    save_information::SaveInformation,
+    size_format::format_size,
+};
+
+/// What happened to the space of the deleted saves, only `Reclaimed` frees it right away.
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub enum FreedSpace {
+    #[default]
+    Reclaimed,
+    /// Kept in the undo area until a later run or `bg3sc purge` removes it.
+    Staged,
+    Trashed,
+    Quarantined,
+}
+impl FreedSpace {
+    fn name(&self) -> &'static str {
+        match self {
+            FreedSpace::Reclaimed => "reclaimed",
+            FreedSpace::Staged => "staged",
+            FreedSpace::Trashed => "trashed",
+            FreedSpace::Quarantined => "quarantined",
+        }
+    }
+
+    pub fn format(&self, bytes: u64, dry_run: bool) -> String {
+        let size = format_size(bytes);
+        match (self, dry_run) {
+            (FreedSpace::Reclaimed, false) => format!("reclaimed {}", size),
+            (FreedSpace::Reclaimed, true) => format!("reclaiming {}", size),
+            (FreedSpace::Staged, false) => format!(
+                "staged {} for undo, freed once a later run or bg3sc purge removes it",
+                size
+            ),
+            (FreedSpace::Staged, true) => format!("staging {} for undo", size),
+            (FreedSpace::Trashed, false) => format!("moved {} to the recycle bin", size),
+            (FreedSpace::Trashed, true) => format!("moving {} to the recycle bin", size),
+            (FreedSpace::Quarantined, false) => format!("moved {} to the quarantine", size),
+            (FreedSpace::Quarantined, true) => format!("moving {} to the quarantine", size),
+        }
+    }
+}
+
+/// The counts printed at the end of a clean and included in the JSON report.
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct RunSummary {
+    pub scanned: usize,
+    pub kept: usize,
+    pub deleted: usize,
+    pub failed: usize,
+    /// Measured while scanning, before the folders are removed. Only freed right away when
+    /// `freed_space` is `Reclaimed`.
+    pub bytes_removed: u64,
+    /// The deleted counts are what would have been deleted.
+    pub dry_run: bool,
+    pub freed_space: FreedSpace,
+}
+impl RunSummary {
+    pub fn new(
+        scanned: usize,
+        planned: &[SaveInformation],
+        deleted: &[SaveInformation],
+        failed: usize,
+        dry_run: bool,
+    ) -> Self {
+        let removed = if dry_run { planned } else { deleted };
+
+        RunSummary {
+            scanned,
+            kept: scanned.saturating_sub(removed.len()),
+            deleted: removed.len(),
+            failed,
+            bytes_removed: removed
+                .iter()
+                .map(|save_information| save_information.size_bytes)
+                .sum(),
+            dry_run,
+            freed_space: FreedSpace::default(),
+        }
+    }
+
+    pub fn with_freed_space(mut self, freed_space: FreedSpace) -> Self {
+        self.freed_space = freed_space;
+        self
+    }
+
+    /// The part of `bytes_removed` that is free again once the run is done.
+    pub fn bytes_reclaimed(&self) -> u64 {
+        match self.freed_space {
+            FreedSpace::Reclaimed => self.bytes_removed,
+            _ => 0,
+        }
+    }
+
+    pub fn format(&self) -> String {
+        let freed = self.freed_space.format(self.bytes_removed, self.dry_run);
+        if self.dry_run {
+            return format!(
+                "Scanned {} save(s), would keep {} and delete {}, {}",
+                self.scanned, self.kept, self.deleted, freed
+            );
+        }
+
+        format!(
+            "Scanned {} save(s), kept {}, deleted {}, failed {}, {}",
+            self.scanned, self.kept, self.deleted, self.failed, freed
+        )
+    }
+}
+impl ToJson for RunSummary {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            ("scanned", JsonValue::Number(self.scanned as u64)),
+            ("kept", JsonValue::Number(self.kept as u64)),
+            ("deleted", JsonValue::Number(self.deleted as u64)),
+            ("failed", JsonValue::Number(self.failed as u64)),
+            ("bytes_removed", JsonValue::Number(self.bytes_removed)),
+            ("bytes_reclaimed", JsonValue::Number(self.bytes_reclaimed())),
+            (
+                "freed_space",
+                JsonValue::String(self.freed_space.name().to_string()),
+            ),
+            ("dry_run", JsonValue::Bool(self.dry_run)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod run_summary_should {
+    use crate::{
+        json::{JsonValue, ToJson},
+        run_summary::{FreedSpace, RunSummary},
+        save_information::SaveInformation,
+        save_type::SaveType,
+    };
+
+    fn saves(sizes: &[u64]) -> Vec<SaveInformation> {
+        sizes
+            .iter()
+            .map(|size| {
+                SaveInformation::new_random(SaveType::Quick, "Tav".to_string())
+                    .with_size_bytes(*size)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn count_deleted_saves() {
+        let planned = saves(&[1024, 2048, 512]);
+        let summary = RunSummary::new(10, &planned, &planned[..2], 1, false);
+
+        assert_eq!(
+            summary,
+            RunSummary {
+                scanned: 10,
+                kept: 8,
+                deleted: 2,
+                failed: 1,
+                bytes_removed: 3072,
+                dry_run: false,
+                freed_space: FreedSpace::Reclaimed,
+            }
+        );
+        assert_eq!(
+            summary.format(),
+            "Scanned 10 save(s), kept 8, deleted 2, failed 1, reclaimed 3.0 KiB"
+        );
+    }
+
+    #[test]
+    fn count_planned_saves_in_dry_run() {
+        let summary = RunSummary::new(4, &saves(&[100, 200]), &[], 0, true);
+
+        assert_eq!(summary.deleted, 2);
+        assert_eq!(
+            summary.format(),
+            "Scanned 4 save(s), would keep 2 and delete 2, reclaiming 300 B"
+        );
+        assert_eq!(
+            summary.to_json().to_string(),
+            "{\"scanned\":4,\"kept\":2,\"deleted\":2,\"failed\":0,\"bytes_removed\":300,\"bytes_reclaimed\":300,\"freed_space\":\"reclaimed\",\"dry_run\":true}"
+        );
+    }
+
+    #[test]
+    fn say_where_the_space_went() {
+        let planned = saves(&[1024]);
+        let summary = RunSummary::new(2, &planned, &planned, 0, false);
+
+        assert_eq!(
+            summary.with_freed_space(FreedSpace::Staged).format(),
+            "Scanned 2 save(s), kept 1, deleted 1, failed 0, staged 1.0 KiB for undo, freed once a later run or bg3sc purge removes it"
+        );
+        assert_eq!(
+            summary
+                .with_freed_space(FreedSpace::Staged)
+                .bytes_reclaimed(),
+            0
+        );
+        assert_eq!(
+            summary
+                .with_freed_space(FreedSpace::Staged)
+                .to_json()
+                .get("freed_space")
+                .and_then(JsonValue::as_str),
+            Some("staged")
+        );
+        assert_eq!(
+            summary.with_freed_space(FreedSpace::Trashed).format(),
+            "Scanned 2 save(s), kept 1, deleted 1, failed 0, moved 1.0 KiB to the recycle bin"
+        );
+        assert_eq!(
+            RunSummary::new(2, &planned, &[], 0, true)
+                .with_freed_space(FreedSpace::Quarantined)
+                .format(),
+            "Scanned 2 save(s), would keep 1 and delete 1, moving 1.0 KiB to the quarantine"
+        );
+    }
+}
@@ -1,47 +1,415 @@
-use crate::save_type::SaveType;
+use std::{
+    cmp::Ordering,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-#[derive(Debug, PartialEq, Clone)]
+use crate::{
+    json::{FromJson, JsonValue, ToJson},
+    save_type::SaveType,
+    timestamp::format_local,
+};
+
+/// Saves order newest first: by save number descending with unnumbered saves last, then by
+/// modified time descending with unknown times last, then by file name. The remaining fields
+/// only break ties between otherwise identical saves, so sorting is deterministic.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SaveInformation {
     pub file_name: String,
+    /// The folder name exactly as on disk, used to build the path when deleting.
+    pub folder_name: OsString,
     pub character_name: String,
+    /// The numeric id after the character name, different for every playthrough.
+    pub campaign_id: Option<String>,
     pub save_type: SaveType,
-    pub save_number: u16,
+    /// `None` when the folder name does not end in a number, such saves are ordered by mtime.
+    pub save_number: Option<u64>,
+    pub modified: Option<SystemTime>,
+    pub size_bytes: u64,
+    /// The folder has no `.lsv` file or an empty one, e.g. after a crash while saving.
+    pub corrupt: bool,
+    /// The save folder the save was found in, `None` for saves that were not scanned from disk.
+    pub root: Option<PathBuf>,
+    /// The game version that wrote the save, only known when its metadata was read.
+    pub game_version: Option<String>,
+    /// The folder a copy made by hand was made from, e.g. for `Tav-123456789__QuickSave_50 (1)`.
+    pub duplicate_of: Option<String>,
+    /// Some files of the folder are online-only, e.g. in a OneDrive folder with Files On-Demand.
+    pub cloud_only: bool,
 }
 impl SaveInformation {
     pub fn new(
         file_name: String,
         character_name: String,
         save_type: SaveType,
-        save_number: u16,
+        save_number: Option<u64>,
     ) -> Self {
         SaveInformation {
+            folder_name: OsString::from(&file_name),
             file_name,
             character_name,
+            campaign_id: None,
             save_type,
             save_number,
+            modified: None,
+            size_bytes: 0,
+            corrupt: false,
+            root: None,
+            game_version: None,
+            duplicate_of: None,
+            cloud_only: false,
         }
     }
 
+    pub fn with_folder_name(mut self, folder_name: OsString) -> Self {
+        self.folder_name = folder_name;
+        self
+    }
+
+    pub fn with_duplicate_of(mut self, duplicate_of: Option<String>) -> Self {
+        self.duplicate_of = duplicate_of;
+        self
+    }
+
+    pub fn with_campaign_id(mut self, campaign_id: Option<String>) -> Self {
+        self.campaign_id = campaign_id;
+        self
+    }
+
+    pub fn with_modified(mut self, modified: Option<SystemTime>) -> Self {
+        self.modified = modified;
+        self
+    }
+
+    pub fn with_size_bytes(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    pub fn with_corrupt(mut self, corrupt: bool) -> Self {
+        self.corrupt = corrupt;
+        self
+    }
+
+    pub fn with_cloud_only(mut self, cloud_only: bool) -> Self {
+        self.cloud_only = cloud_only;
+        self
+    }
+
+    pub fn with_root(mut self, root: &Path) -> Self {
+        self.root = Some(root.to_path_buf());
+        self
+    }
+
+    /// The folder of the save, below its own root if it has one, otherwise below `default_root`.
+    pub fn root_or<'a>(&'a self, default_root: &'a Path) -> &'a Path {
+        self.root.as_deref().unwrap_or(default_root)
+    }
+
     #[cfg(test)]
     pub fn new_random(save_type: SaveType, character_name: String) -> Self {
         use rand::Rng;
 
         let save_number = rand::thread_rng().gen_range(u16::MIN..=u16::MAX);
 
-        match save_type {
-            SaveType::Quick => SaveInformation {
-                file_name: format!("{}-123456789__QuickSave_{}", character_name, save_number),
-                character_name,
-                save_type,
-                save_number,
+        let file_name = match save_type {
+            SaveType::Quick => format!("{}-123456789__QuickSave_{}", character_name, save_number),
+            SaveType::Auto => format!("{}-123456789__AutoSave_{}", character_name, save_number),
+            SaveType::Honour => {
+                format!("{}-123456789__HonourMode_{}", character_name, save_number)
+            }
+            SaveType::Manual => format!("{}-123456789__Camp_{}", character_name, save_number),
+            SaveType::Unrecognized => format!("{}-Camp_{}", character_name, save_number),
+        };
+
+        SaveInformation::new(
+            file_name,
+            character_name,
+            save_type,
+            Some(save_number.into()),
+        )
+    }
+}
+impl Ord for SaveInformation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .save_number
+            .cmp(&self.save_number)
+            .then_with(|| other.modified.cmp(&self.modified))
+            .then_with(|| self.file_name.cmp(&other.file_name))
+            .then_with(|| self.folder_name.cmp(&other.folder_name))
+            .then_with(|| self.root.cmp(&other.root))
+            .then_with(|| self.character_name.cmp(&other.character_name))
+            .then_with(|| self.campaign_id.cmp(&other.campaign_id))
+            .then_with(|| self.save_type.cmp(&other.save_type))
+            .then_with(|| self.size_bytes.cmp(&other.size_bytes))
+            .then_with(|| self.corrupt.cmp(&other.corrupt))
+            .then_with(|| self.game_version.cmp(&other.game_version))
+            .then_with(|| self.duplicate_of.cmp(&other.duplicate_of))
+            .then_with(|| self.cloud_only.cmp(&other.cloud_only))
+    }
+}
+impl PartialOrd for SaveInformation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl ToJson for SaveInformation {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            (
+                "character_name",
+                JsonValue::String(self.character_name.clone()),
+            ),
+            ("file_name", JsonValue::String(self.file_name.clone())),
+            (
+                "campaign_id",
+                self.campaign_id
+                    .as_ref()
+                    .map_or(JsonValue::Null, |campaign_id| {
+                        JsonValue::String(campaign_id.clone())
+                    }),
+            ),
+            ("save_type", self.save_type.to_json()),
+            (
+                "save_number",
+                self.save_number.map_or(JsonValue::Null, JsonValue::Number),
+            ),
+            (
+                "modified",
+                self.modified
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map_or(JsonValue::Null, |since_epoch| {
+                        JsonValue::Number(since_epoch.as_secs())
+                    }),
+            ),
+            // For people reading reports, `modified` is what plans are read back from.
+            (
+                "modified_local",
+                self.modified.map_or(JsonValue::Null, |modified| {
+                    JsonValue::String(format_local(modified))
+                }),
+            ),
+            ("size_bytes", JsonValue::Number(self.size_bytes)),
+            ("corrupt", JsonValue::Bool(self.corrupt)),
+            (
+                "root",
+                self.root.as_ref().map_or(JsonValue::Null, |root| {
+                    JsonValue::String(root.to_string_lossy().to_string())
+                }),
+            ),
+            (
+                "game_version",
+                self.game_version
+                    .as_ref()
+                    .map_or(JsonValue::Null, |game_version| {
+                        JsonValue::String(game_version.clone())
+                    }),
+            ),
+            (
+                "duplicate_of",
+                self.duplicate_of
+                    .as_ref()
+                    .map_or(JsonValue::Null, |duplicate_of| {
+                        JsonValue::String(duplicate_of.clone())
+                    }),
+            ),
+            ("cloud_only", JsonValue::Bool(self.cloud_only)),
+        ])
+    }
+}
+impl FromJson for SaveInformation {
+    fn from_json(value: &JsonValue) -> Result<Self, String> {
+        let field = |key: &str| value.get(key).ok_or(format!("Missing field {}", key));
+        let string_field = |key: &str| {
+            field(key).and_then(|field| {
+                field
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or(format!("Field {} is not a string", key))
+            })
+        };
+        let number_field = |key: &str| {
+            field(key).and_then(|field| {
+                field
+                    .as_u64()
+                    .ok_or(format!("Field {} is not a number", key))
+            })
+        };
+
+        let file_name = string_field("file_name")?;
+
+        Ok(SaveInformation {
+            folder_name: OsString::from(&file_name),
+            file_name,
+            character_name: string_field("character_name")?,
+            // Absent in plans written before saves were grouped by campaign.
+            campaign_id: match value.get("campaign_id") {
+                None | Some(JsonValue::Null) => None,
+                Some(campaign_id) => Some(
+                    campaign_id
+                        .as_str()
+                        .ok_or("Field campaign_id is not a string".to_string())?
+                        .to_string(),
+                ),
             },
-            SaveType::Auto => SaveInformation {
-                file_name: format!("{}-123456789__AutoSave_{}", character_name, save_number),
-                character_name,
-                save_type,
-                save_number,
+            save_type: SaveType::from_json(field("save_type")?)?,
+            save_number: match field("save_number")? {
+                JsonValue::Null => None,
+                save_number => Some(
+                    save_number
+                        .as_u64()
+                        .ok_or("Field save_number is not a number".to_string())?,
+                ),
             },
-            _ => panic!("Not a randomizable save pattern"),
-        }
+            modified: match field("modified")? {
+                JsonValue::Null => None,
+                modified => Some(
+                    UNIX_EPOCH
+                        + Duration::from_secs(
+                            modified
+                                .as_u64()
+                                .ok_or("Field modified is not a number".to_string())?,
+                        ),
+                ),
+            },
+            size_bytes: number_field("size_bytes")?,
+            // Absent in plans written before corrupt saves were detected.
+            corrupt: value
+                .get("corrupt")
+                .map(|corrupt| {
+                    corrupt
+                        .as_bool()
+                        .ok_or("Field corrupt is not a boolean".to_string())
+                })
+                .transpose()?
+                .unwrap_or(false),
+            // Absent in plans written before several save folders could be cleaned at once.
+            root: match value.get("root") {
+                None | Some(JsonValue::Null) => None,
+                Some(root) => Some(PathBuf::from(
+                    root.as_str()
+                        .ok_or("Field root is not a string".to_string())?,
+                )),
+            },
+            // Absent in plans written before save metadata could be read.
+            game_version: match value.get("game_version") {
+                None | Some(JsonValue::Null) => None,
+                Some(game_version) => Some(
+                    game_version
+                        .as_str()
+                        .ok_or("Field game_version is not a string".to_string())?
+                        .to_string(),
+                ),
+            },
+            // Absent in plans written before copies were told apart.
+            duplicate_of: match value.get("duplicate_of") {
+                None | Some(JsonValue::Null) => None,
+                Some(duplicate_of) => Some(
+                    duplicate_of
+                        .as_str()
+                        .ok_or("Field duplicate_of is not a string".to_string())?
+                        .to_string(),
+                ),
+            },
+            // Absent in plans written before online-only files were detected.
+            cloud_only: value
+                .get("cloud_only")
+                .map(|cloud_only| {
+                    cloud_only
+                        .as_bool()
+                        .ok_or("Field cloud_only is not a boolean".to_string())
+                })
+                .transpose()?
+                .unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod cmp_should {
+    use std::time::{Duration, SystemTime};
+
+    use rand::seq::SliceRandom;
+
+    use crate::{save_information::SaveInformation, save_type::SaveType};
+
+    fn save(file_name: &str, number: Option<u64>, modified: Option<SystemTime>) -> SaveInformation {
+        SaveInformation::new(
+            file_name.to_string(),
+            "Tav".to_string(),
+            SaveType::Quick,
+            number,
+        )
+        .with_modified(modified)
+    }
+
+    #[test]
+    fn order_by_number_then_modified_time_then_file_name() {
+        let now = SystemTime::now();
+        let expected = vec![
+            save(
+                "Tav-1__QuickSave_9",
+                Some(9),
+                Some(now - Duration::from_secs(60)),
+            ),
+            save("Tav-2__QuickSave_4", Some(4), Some(now)),
+            save(
+                "Tav-1__QuickSave_4",
+                Some(4),
+                Some(now - Duration::from_secs(60)),
+            ),
+            save(
+                "Tav-3__QuickSave_4",
+                Some(4),
+                Some(now - Duration::from_secs(60)),
+            ),
+            save("Tav-1__QuickSave_4", Some(4), None),
+            save("Tav-1__QuickSave_new", None, Some(now)),
+            save("Tav-1__QuickSave_old", None, None),
+        ];
+
+        let mut saves = expected.clone();
+        saves.reverse();
+        saves.sort();
+
+        assert_eq!(saves, expected);
+    }
+
+    #[test]
+    fn sort_equal_keys_the_same_way_every_time() {
+        let now = SystemTime::now();
+        let saves: Vec<SaveInformation> = ["Tav-1__QuickSave_4", "Tav-2__QuickSave_4"]
+            .iter()
+            .flat_map(|file_name| {
+                [
+                    save(file_name, Some(4), Some(now)),
+                    save(file_name, Some(4), Some(now)).with_corrupt(true),
+                    save(file_name, Some(4), Some(now)).with_size_bytes(1024),
+                ]
+            })
+            .collect();
+        let mut expected = saves.clone();
+        expected.sort();
+
+        (0..20).for_each(|_| {
+            let mut shuffled = saves.clone();
+            shuffled.shuffle(&mut rand::thread_rng());
+            shuffled.sort();
+
+            assert_eq!(shuffled, expected);
+        });
+    }
+
+    #[test]
+    fn keep_equal_saves_in_place_when_sorting_stably() {
+        let save_a = save("Tav-1__QuickSave_4", Some(4), None);
+        let saves = vec![save_a.clone(), save_a.clone(), save_a.clone()];
+        let mut sorted = saves.clone();
+        sorted.sort();
+
+        assert_eq!(sorted, saves);
+        assert_eq!(save_a.cmp(&save_a.clone()), std::cmp::Ordering::Equal);
     }
 }
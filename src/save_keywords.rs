@@ -0,0 +1,174 @@
+use std::sync::RwLock;
+
+use crate::save_type::SaveType;
+
+/// How quick saves and autosaves are named in the folder names, lowercase and without spaces
+/// or dashes as those are skipped when matching. Only the English names are built in, those of
+/// other languages are given with `--quick-keyword` and `--auto-keyword`.
+const QUICK_KEYWORDS: &[&str] = &["quicksave"];
+const AUTO_KEYWORDS: &[&str] = &["autosave"];
+
+/// Keywords from `--quick-keyword` and `--auto-keyword`, in the same form as the built-in ones.
+static EXTRA_KEYWORDS: RwLock<Vec<(SaveType, String)>> = RwLock::new(Vec::new());
+
+pub fn set_extra(quick_keywords: &[String], auto_keywords: &[String]) {
+    let extra = extra_keywords(quick_keywords, auto_keywords);
+    if let Ok(mut keywords) = EXTRA_KEYWORDS.write() {
+        *keywords = extra;
+    }
+}
+
+fn extra_keywords(quick_keywords: &[String], auto_keywords: &[String]) -> Vec<(SaveType, String)> {
+    quick_keywords
+        .iter()
+        .map(|keyword| (SaveType::Quick, normalize(keyword)))
+        .chain(
+            auto_keywords
+                .iter()
+                .map(|keyword| (SaveType::Auto, normalize(keyword))),
+        )
+        .filter(|(_, keyword)| !keyword.is_empty())
+        .collect()
+}
+
+/// The save type of the first keyword in the folder name and where the keyword starts and
+/// ends. Keywords follow an underscore as in `Tav-123456789__QuickSave_3`, so the name of the
+/// character or a manual save mentioning a quick save is not taken for one.
+pub fn find(folder_name: &str) -> Option<(SaveType, usize, usize)> {
+    let extra = EXTRA_KEYWORDS
+        .read()
+        .map(|keywords| keywords.clone())
+        .unwrap_or_default();
+
+    find_with(folder_name, &extra)
+}
+
+fn find_with(folder_name: &str, extra: &[(SaveType, String)]) -> Option<(SaveType, usize, usize)> {
+    let keywords: Vec<(SaveType, &str)> = QUICK_KEYWORDS
+        .iter()
+        .map(|keyword| (SaveType::Quick, *keyword))
+        .chain(
+            AUTO_KEYWORDS
+                .iter()
+                .map(|keyword| (SaveType::Auto, *keyword)),
+        )
+        .chain(
+            extra
+                .iter()
+                .map(|(save_type, keyword)| (save_type.clone(), keyword.as_str())),
+        )
+        .collect();
+
+    folder_name
+        .char_indices()
+        .filter(|(start, _)| folder_name[..*start].ends_with('_'))
+        .find_map(|(start, _)| {
+            keywords.iter().find_map(|(save_type, keyword)| {
+                length_of_match(&folder_name[start..], keyword)
+                    .map(|length| (save_type.clone(), start, start + length))
+            })
+        })
+}
+
+/// The length in bytes of `text` that matches `keyword` from its start, ignoring case and
+/// any spaces and dashes within the keyword like in `Quick-Save`.
+fn length_of_match(text: &str, keyword: &str) -> Option<usize> {
+    let mut keyword_chars = keyword.chars().peekable();
+
+    for (index, c) in text.char_indices() {
+        if keyword_chars.peek().is_none() {
+            return Some(index);
+        }
+        if index > 0 && is_separator(c) {
+            continue;
+        }
+        if !c
+            .to_lowercase()
+            .all(|lowercase| keyword_chars.next() == Some(lowercase))
+        {
+            return None;
+        }
+    }
+
+    keyword_chars.peek().is_none().then_some(text.len())
+}
+
+/// Brings a keyword from the command line into the form of the built-in ones.
+fn normalize(keyword: &str) -> String {
+    keyword
+        .chars()
+        .filter(|c| !is_separator(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_separator(c: char) -> bool {
+    c == ' ' || c == '-'
+}
+
+#[cfg(test)]
+mod find_should {
+    use crate::{
+        save_keywords::{extra_keywords, find, find_with},
+        save_type::SaveType,
+    };
+
+    #[test]
+    fn find_english_keywords_in_any_case_and_hyphenation() {
+        assert_eq!(
+            find("Tav-123456789__QuickSave_3"),
+            Some((SaveType::Quick, 15, 24))
+        );
+        assert_eq!(
+            find("Tav-123456789__AUTO-SAVE_3"),
+            Some((SaveType::Auto, 15, 24))
+        );
+        assert_eq!(
+            find("Tav-123456789__Quick Save_3"),
+            Some((SaveType::Quick, 15, 25))
+        );
+    }
+
+    #[test]
+    fn find_keywords_given_for_other_languages() {
+        let extra = extra_keywords(
+            &["Schnellspeichern".to_string(), "快速存档".to_string()],
+            &["Автосохранение".to_string()],
+        );
+
+        assert_eq!(find("Tav-123456789__Schnellspeichern_3"), None);
+        assert_eq!(
+            find_with("Tav-123456789__Schnellspeichern_3", &extra).map(|(save_type, ..)| save_type),
+            Some(SaveType::Quick)
+        );
+        assert_eq!(
+            find_with("Тав-123456789__Автосохранение_3", &extra).map(|(save_type, ..)| save_type),
+            Some(SaveType::Auto)
+        );
+        assert_eq!(
+            find_with("塔夫-123456789__快速存档_3", &extra),
+            Some((SaveType::Quick, 18, 30))
+        );
+    }
+
+    #[test]
+    fn ignore_keywords_not_following_an_underscore() {
+        assert_eq!(find("Quicksaver-123456789__Camp_3"), None);
+        assert_eq!(find("Tav-123456789__Before my quicksave_3"), None);
+        assert_eq!(find("Autosaver Tav-123456789__Camp_3"), None);
+    }
+}
+
+#[cfg(test)]
+mod normalize_should {
+    use crate::save_keywords::{length_of_match, normalize};
+
+    #[test]
+    fn match_keywords_from_the_command_line_like_built_in_ones() {
+        let keyword = normalize("Zz-Rapid");
+
+        assert_eq!(keyword, "zzrapid");
+        assert_eq!(length_of_match("ZZRAPID_3", &keyword), Some(7));
+        assert_eq!(length_of_match("zz rapid_3", &keyword), Some(8));
+    }
+}
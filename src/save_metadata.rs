@@ -0,0 +1,280 @@
+use std::{fs, path::Path, time::SystemTime};
+
+use crate::{lsf, lspk, save_information::SaveInformation, timestamp};
+
+const META_LSF: &str = "meta.lsf";
+const META_LSX: &str = "meta.lsx";
+
+/// What a save records about itself in its `meta.lsf`, each field `None` when it is missing.
+#[derive(Debug, PartialEq, Default)]
+pub struct SaveMetadata {
+    pub character_name: Option<String>,
+    pub saved: Option<SystemTime>,
+    pub game_version: Option<String>,
+}
+impl SaveMetadata {
+    /// Reads the metadata of the save in `save_folder`. Saves keep it in their `.lsv` package,
+    /// a loose `meta.lsx` or `meta.lsf` left by unpacking the save is read instead if present.
+    pub fn read(save_folder: &Path) -> Result<Self, String> {
+        let attributes = match read_loose_file(save_folder)? {
+            Some(attributes) => attributes,
+            None => read_packaged_file(save_folder)?,
+        };
+
+        let metadata = SaveMetadata::from_attributes(&attributes);
+        if metadata == SaveMetadata::default() {
+            return Err("metadata has no character name, save time or game version".to_string());
+        }
+        Ok(metadata)
+    }
+
+    fn from_attributes(attributes: &[(String, String)]) -> Self {
+        let value = |name: &str| {
+            attributes
+                .iter()
+                .find(|(attribute, _)| attribute == name)
+                .map(|(_, value)| value.as_str())
+                .filter(|value| !value.is_empty())
+        };
+        let number = |name: &str| value(name).and_then(|value| value.parse::<u64>().ok());
+
+        let date = number("Year").zip(number("Month")).zip(number("Day"));
+        let clock = number("Hours")
+            .zip(number("Minutes"))
+            .zip(number("Seconds"));
+        let saved = date
+            .zip(clock)
+            .and_then(|(((year, month), day), ((hour, minute), second))| {
+                // Older versions count the year from 1900 to fit it in a byte.
+                let year = if year < 1900 { year + 1900 } else { year };
+                timestamp::from_local((year, month, day), (hour, minute, second))
+            });
+
+        SaveMetadata {
+            character_name: value("LeaderName").map(str::to_string),
+            saved,
+            game_version: value("GameVersion").map(str::to_string),
+        }
+    }
+
+    /// Prefers the fields read from the metadata over those parsed from the folder name.
+    pub fn apply_to(self, save_information: SaveInformation) -> SaveInformation {
+        SaveInformation {
+            character_name: self
+                .character_name
+                .unwrap_or(save_information.character_name),
+            modified: self.saved.or(save_information.modified),
+            game_version: self.game_version.or(save_information.game_version),
+            ..save_information
+        }
+    }
+}
+
+fn read_loose_file(save_folder: &Path) -> Result<Option<Vec<(String, String)>>, String> {
+    let lsx = save_folder.join(META_LSX);
+    if lsx.is_file() {
+        let text = fs::read_to_string(&lsx).map_err(|e| format!("{}: {}", lsx.display(), e))?;
+        return Ok(Some(read_lsx_attributes(&text)));
+    }
+
+    let lsf = save_folder.join(META_LSF);
+    if lsf.is_file() {
+        let bytes = fs::read(&lsf).map_err(|e| format!("{}: {}", lsf.display(), e))?;
+        return lsf::read_attributes(&bytes)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", lsf.display(), e));
+    }
+
+    Ok(None)
+}
+
+fn read_packaged_file(save_folder: &Path) -> Result<Vec<(String, String)>, String> {
+    let package = fs::read_dir(save_folder)
+        .map_err(|e| format!("{}: {}", save_folder.display(), e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("lsv"))
+        })
+        .ok_or("no .lsv package".to_string())?;
+
+    let bytes = lspk::read_file(&package, META_LSF)?.ok_or(format!(
+        "{}: no {} in package",
+        package.display(),
+        META_LSF
+    ))?;
+    lsf::read_attributes(&bytes).map_err(|e| format!("{}: {}", package.display(), e))
+}
+
+/// The `id` and `value` of every `<attribute>` element, the text form of an LSF file.
+fn read_lsx_attributes(text: &str) -> Vec<(String, String)> {
+    text.split("<attribute")
+        .skip(1)
+        .filter_map(|element| {
+            let element = &element[..element.find('>')?];
+            Some((
+                unescape(xml_attribute(element, "id")?),
+                unescape(xml_attribute(element, "value")?),
+            ))
+        })
+        .collect()
+}
+
+fn xml_attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let length = element[start..].find('"')?;
+
+    Some(&element[start..start + length])
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod read_should {
+    use std::{fs, time::SystemTime};
+
+    use crate::{
+        lsf::write_attributes, lspk::write_package, save_metadata::SaveMetadata,
+        test_utils::TempDir, timestamp,
+    };
+
+    #[test]
+    fn read_metadata_from_save_package() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(&save_path).unwrap();
+        let meta = write_attributes(&[
+            ("LeaderName", "Shadowheart"),
+            ("GameVersion", "4.1.1.5022896"),
+            ("Year", "2024"),
+            ("Month", "3"),
+            ("Day", "2"),
+            ("Hours", "21"),
+            ("Minutes", "14"),
+            ("Seconds", "5"),
+        ]);
+        fs::write(
+            save_path.join("Tav-123456789__QuickSave_1.lsv"),
+            write_package(&[("Globals.lsf", b"globals"), ("meta.lsf", &meta)]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            SaveMetadata::read(&save_path).unwrap(),
+            SaveMetadata {
+                character_name: Some("Shadowheart".to_string()),
+                saved: timestamp::from_local((2024, 3, 2), (21, 14, 5)),
+                game_version: Some("4.1.1.5022896".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn prefer_loose_lsx_file() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::write(
+            save_path.join("meta.lsx"),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<save>
+    <region id="MetaData">
+        <node id="MetaData">
+            <attribute id="LeaderName" type="LSString" value="Karlach &amp; Co" />
+            <attribute id="GameVersion" type="LSString" value="4.1.1.5022896" />
+        </node>
+    </region>
+</save>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            SaveMetadata::read(&save_path).unwrap(),
+            SaveMetadata {
+                character_name: Some("Karlach & Co".to_string()),
+                saved: None,
+                game_version: Some("4.1.1.5022896".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn fail_for_package_without_metadata() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+
+        assert!(SaveMetadata::read(&save_path).is_err());
+
+        fs::write(
+            save_path.join("test.lsv"),
+            write_package(&[("Globals.lsf", b"globals")]),
+        )
+        .unwrap();
+
+        assert!(SaveMetadata::read(&save_path)
+            .unwrap_err()
+            .ends_with("no meta.lsf in package"));
+    }
+
+    #[test]
+    fn count_byte_sized_years_from_1900() {
+        let attributes: Vec<(String, String)> = [
+            ("Year", "124"),
+            ("Month", "3"),
+            ("Day", "2"),
+            ("Hours", "21"),
+            ("Minutes", "14"),
+            ("Seconds", "5"),
+        ]
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        assert_eq!(
+            SaveMetadata::from_attributes(&attributes).saved,
+            timestamp::from_local((2024, 3, 2), (21, 14, 5))
+        );
+        assert!(SaveMetadata::from_attributes(&attributes)
+            .saved
+            .is_some_and(|saved| saved < SystemTime::now()));
+    }
+}
+
+#[cfg(test)]
+mod apply_to_should {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{
+        save_information::SaveInformation, save_metadata::SaveMetadata, save_type::SaveType,
+    };
+
+    #[test]
+    fn prefer_metadata_and_keep_folder_name_fields_it_lacks() {
+        let save_information = SaveInformation::new(
+            "Tav-123456789__QuickSave_1".to_string(),
+            "Tav".to_string(),
+            SaveType::Quick,
+            Some(1),
+        )
+        .with_modified(Some(UNIX_EPOCH + Duration::from_secs(100)));
+        let metadata = SaveMetadata {
+            character_name: Some("Shadowheart".to_string()),
+            saved: None,
+            game_version: Some("4.1.1.5022896".to_string()),
+        };
+
+        let applied = metadata.apply_to(save_information.clone());
+
+        assert_eq!(applied.character_name, "Shadowheart");
+        assert_eq!(applied.modified, save_information.modified);
+        assert_eq!(applied.game_version, Some("4.1.1.5022896".to_string()));
+        assert_eq!(applied.file_name, save_information.file_name);
+    }
+}
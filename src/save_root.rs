@@ -0,0 +1,228 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{ignore_list::IgnoreList, lspk, program_errors::ProgramError, undo, verbatim_path};
+
+/// Percentage of the folders in a directory that have to be saves for it to be cleaned without
+/// `--force-path`, unless it is named like a save folder.
+pub const DEFAULT_MIN_SAVE_PERCENT: usize = 50;
+
+/// Whether `directory` looks like a folder of Baldur's Gate 3 saves: it is named `Story` or
+/// lies below a `Savegames` folder, or at least `min_save_percent` of its folders parse as
+/// saves holding an `.lsv` package. An empty or files-only directory doesn't count, its files
+/// could otherwise be deleted as loose files.
+pub fn looks_like_save_folder(directory: &Path, min_save_percent: usize) -> bool {
+    let named_like_saves = directory.file_name().is_some_and(|name| name == "Story")
+        || directory
+            .components()
+            .any(|component| component.as_os_str() == "Savegames");
+    if named_like_saves {
+        return true;
+    }
+
+    let ignore_list = IgnoreList::default();
+    let folders: Vec<PathBuf> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name();
+            name != undo::UNDO_DIR_NAME
+                && ignore_list
+                    .matching_entry(&name.to_string_lossy())
+                    .is_none()
+        })
+        .map(|entry| entry.path())
+        .collect();
+    if folders.is_empty() {
+        return false;
+    }
+
+    let saves = folders
+        .iter()
+        .filter(|folder| {
+            folder
+                .file_name()
+                .is_some_and(|name| crate::package_details(&name.to_string_lossy()).is_ok())
+                && lspk::contains_package(folder)
+        })
+        .count();
+
+    saves * 100 >= folders.len() * min_save_percent
+}
+
+/// Checks that deleting `target` can't touch anything outside `root`: it has to end in a plain
+/// folder name and the folder holding it has to lie within `root` once `..` and symlinks are
+/// resolved. `target` itself may be a symlink, every deletion removes only the link.
+pub fn check_within(root: &Path, target: &Path) -> Result<(), ProgramError> {
+    let outside = |reason: &str| {
+        ProgramError::OutsideSaveRoot(format!(
+            "{} {} {}",
+            verbatim_path::for_display(target).display(),
+            reason,
+            root.display()
+        ))
+    };
+    if !matches!(target.components().next_back(), Some(Component::Normal(_))) {
+        return Err(outside("is not a folder in"));
+    }
+
+    let canonical_root = fs::canonicalize(root)
+        .map_err(|e| ProgramError::FailedToReadDir(format!("{}: {}", root.display(), e)))?;
+    let within = target
+        .parent()
+        .and_then(|parent| fs::canonicalize(parent).ok())
+        .is_some_and(|parent| parent.starts_with(&canonical_root));
+    match within {
+        true => Ok(()),
+        false => Err(outside("resolves to outside of")),
+    }
+}
+
+#[cfg(test)]
+mod check_within_should {
+    use std::fs;
+
+    use crate::{program_errors::ProgramError, save_root::check_within, test_utils::TempDir};
+
+    #[test]
+    fn accept_folders_in_the_root() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+
+        assert_eq!(check_within(temp_dir.path(), &save), Ok(()));
+        assert_eq!(
+            check_within(
+                temp_dir.path(),
+                &temp_dir.path().join("Tav-123456789__QuickSave_2")
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn refuse_names_leaving_the_root() {
+        let temp_dir = TempDir::new();
+        let root = temp_dir.path().join("Story");
+        fs::create_dir_all(root.join("Tav-123456789__QuickSave_1")).unwrap();
+        fs::create_dir(temp_dir.path().join("Documents")).unwrap();
+
+        [
+            "..",
+            "../Documents",
+            "Tav-123456789__QuickSave_1/../..",
+            ".",
+        ]
+        .iter()
+        .chain([temp_dir.path().join("Documents").to_str().unwrap()].iter())
+        .for_each(|name| {
+            assert!(
+                matches!(
+                    check_within(&root, &root.join(name)),
+                    Err(ProgramError::OutsideSaveRoot(_))
+                ),
+                "{}",
+                name
+            )
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuse_folders_reached_through_a_symlink_to_outside() {
+        let temp_dir = TempDir::new();
+        let root = temp_dir.path().join("Story");
+        fs::create_dir(&root).unwrap();
+        let elsewhere = temp_dir.path().join("Documents");
+        fs::create_dir_all(elsewhere.join("Tav-123456789__QuickSave_1")).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, root.join("link")).unwrap();
+
+        assert!(matches!(
+            check_within(&root, &root.join("link/Tav-123456789__QuickSave_1")),
+            Err(ProgramError::OutsideSaveRoot(_))
+        ));
+        // Deleting the link itself only removes the link.
+        assert_eq!(check_within(&root, &root.join("link")), Ok(()));
+    }
+}
+
+#[cfg(test)]
+mod looks_like_save_folder_should {
+    use std::fs;
+
+    use crate::{
+        save_root::{looks_like_save_folder, DEFAULT_MIN_SAVE_PERCENT},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn refuse_a_directory_of_other_folders() {
+        let temp_dir = TempDir::new();
+        ["Photos", "Invoices", "Projects", "Music"]
+            .iter()
+            .for_each(|name| fs::create_dir(temp_dir.path().join(name)).unwrap());
+        // Matches the folder name pattern by chance, but holds no save.
+        fs::create_dir(temp_dir.path().join("Backup-2023_QuickSave_4")).unwrap();
+
+        assert!(!looks_like_save_folder(
+            temp_dir.path(),
+            DEFAULT_MIN_SAVE_PERCENT
+        ));
+    }
+
+    #[test]
+    fn accept_mostly_saves() {
+        let temp_dir = TempDir::new();
+        temp_dir.create_save("Tav-123456789__QuickSave_1");
+        temp_dir.create_save("Tav-123456789__QuickSave_2");
+        fs::create_dir(temp_dir.path().join("Notes")).unwrap();
+
+        assert!(looks_like_save_folder(
+            temp_dir.path(),
+            DEFAULT_MIN_SAVE_PERCENT
+        ));
+        assert!(!looks_like_save_folder(temp_dir.path(), 90));
+    }
+
+    #[test]
+    fn accept_folders_named_like_save_folders() {
+        let temp_dir = TempDir::new();
+        let story = temp_dir.path().join("Savegames/Story");
+        fs::create_dir_all(story.join("Photos")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("Savegames/Photos")).unwrap();
+
+        assert!(looks_like_save_folder(&story, DEFAULT_MIN_SAVE_PERCENT));
+        assert!(looks_like_save_folder(
+            &temp_dir.path().join("Savegames"),
+            DEFAULT_MIN_SAVE_PERCENT
+        ));
+    }
+
+    #[test]
+    fn refuse_directories_without_folders() {
+        let temp_dir = TempDir::new();
+        assert!(!looks_like_save_folder(
+            temp_dir.path(),
+            DEFAULT_MIN_SAVE_PERCENT
+        ));
+
+        fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("invoice.pdf"), "").unwrap();
+        assert!(!looks_like_save_folder(
+            temp_dir.path(),
+            DEFAULT_MIN_SAVE_PERCENT
+        ));
+    }
+
+    #[test]
+    fn accept_an_empty_story_folder() {
+        let temp_dir = TempDir::new();
+        let story = temp_dir.path().join("Story");
+        fs::create_dir(&story).unwrap();
+
+        assert!(looks_like_save_folder(&story, DEFAULT_MIN_SAVE_PERCENT));
+    }
+}
@@ -1,6 +1,29 @@
-#[derive(Debug, PartialEq, Eq, Clone)]
+use crate::json::{FromJson, JsonValue, ToJson};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum SaveType {
     Quick,
     Auto,
+    /// The single rolling save of an Honour Mode run, never deleted without --include-honour.
+    Honour,
+    /// Saved by the player, never deleted without --preserve-manual.
+    Manual,
     Unrecognized,
 }
+impl ToJson for SaveType {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(format!("{:?}", self))
+    }
+}
+impl FromJson for SaveType {
+    fn from_json(value: &JsonValue) -> Result<Self, String> {
+        match value.as_str() {
+            Some("Quick") => Ok(SaveType::Quick),
+            Some("Auto") => Ok(SaveType::Auto),
+            Some("Honour") => Ok(SaveType::Honour),
+            Some("Manual") => Ok(SaveType::Manual),
+            Some("Unrecognized") => Ok(SaveType::Unrecognized),
+            _ => Err(format!("Unknown save type {}", value)),
+        }
+    }
+}
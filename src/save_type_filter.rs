@@ -0,0 +1,27 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+
+use crate::save_type::SaveType;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+pub enum SaveTypeFilter {
+    Quick,
+    Auto,
+}
+impl SaveTypeFilter {
+    pub fn allows(&self, save_type: &SaveType) -> bool {
+        matches!(
+            (self, save_type),
+            (SaveTypeFilter::Quick, SaveType::Quick) | (SaveTypeFilter::Auto, SaveType::Auto)
+        )
+    }
+}
+impl Display for SaveTypeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveTypeFilter::Quick => write!(f, "quick saves"),
+            SaveTypeFilter::Auto => write!(f, "auto saves"),
+        }
+    }
+}
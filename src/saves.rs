@@ -1,15 +1,70 @@
-use crate::save_information::SaveInformation;
+use crate::{
+    json::{JsonValue, ToJson},
+    save_information::SaveInformation,
+};
 
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct Saves {
     pub quick_saves: Vec<SaveInformation>,
     pub auto_saves: Vec<SaveInformation>,
+    pub honour_saves: Vec<SaveInformation>,
+    pub manual_saves: Vec<SaveInformation>,
+    /// Folders with a character and number that aren't of any known save type.
+    pub unrecognized_saves: Vec<SaveInformation>,
+    /// Corrupt or incomplete folders of any save type, never counted towards the preserved saves.
+    pub corrupt_saves: Vec<SaveInformation>,
+    /// Copies of save folders made by hand, never counted towards the preserved saves either.
+    pub copies: Vec<SaveInformation>,
 }
 impl Saves {
-    pub fn new() -> Self {
+    /// Every save of the character, corrupt ones and copies included.
+    pub fn count(&self) -> usize {
+        self.quick_saves.len()
+            + self.auto_saves.len()
+            + self.honour_saves.len()
+            + self.manual_saves.len()
+            + self.unrecognized_saves.len()
+            + self.corrupt_saves.len()
+            + self.copies.len()
+    }
+
+    /// Applies `f` to every save, keeping each in its list.
+    pub fn map_each(self, mut f: impl FnMut(SaveInformation) -> SaveInformation) -> Self {
+        let mut map_list = |saves: Vec<SaveInformation>| saves.into_iter().map(&mut f).collect();
+
         Saves {
-            quick_saves: Vec::new(),
-            auto_saves: Vec::new(),
+            quick_saves: map_list(self.quick_saves),
+            auto_saves: map_list(self.auto_saves),
+            honour_saves: map_list(self.honour_saves),
+            manual_saves: map_list(self.manual_saves),
+            unrecognized_saves: map_list(self.unrecognized_saves),
+            corrupt_saves: map_list(self.corrupt_saves),
+            copies: map_list(self.copies),
         }
     }
+
+    /// Every save of the character, corrupt ones and copies included.
+    pub fn all(&self) -> impl Iterator<Item = &SaveInformation> {
+        self.quick_saves
+            .iter()
+            .chain(&self.auto_saves)
+            .chain(&self.honour_saves)
+            .chain(&self.manual_saves)
+            .chain(&self.unrecognized_saves)
+            .chain(&self.corrupt_saves)
+            .chain(&self.copies)
+    }
+}
+impl ToJson for Saves {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            ("quick_saves", self.quick_saves.to_json()),
+            ("auto_saves", self.auto_saves.to_json()),
+            ("honour_saves", self.honour_saves.to_json()),
+            ("manual_saves", self.manual_saves.to_json()),
+            ("unrecognized_saves", self.unrecognized_saves.to_json()),
+            ("corrupt_saves", self.corrupt_saves.to_json()),
+            ("copies", self.copies.to_json()),
+        ])
+    }
 }
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use crate::{program_errors::ProgramError, save_information::SaveInformation};
+
+#[derive(Debug, PartialEq, Default)]
+pub struct ScanResult {
+    pub saves: Vec<SaveInformation>,
+    /// Folders that could not be parsed as a save, with the reason they were skipped.
+    pub skipped_folders: Vec<(String, ProgramError)>,
+    /// Folders left out by `--match` or `--exclude`, with the reason they were left out.
+    pub filtered_folders: Vec<(String, String)>,
+    /// Folders and files matching the ignore list, with the glob they matched.
+    pub ignored: Vec<(String, String)>,
+    /// Folders without any file below them, which aren't counted as saves.
+    pub empty_folders: Vec<PathBuf>,
+    /// Files directly in the scanned directory, never parsed as saves.
+    pub loose_files: Vec<PathBuf>,
+    /// Symlinked folders, only scanned as saves with `--follow-symlinks`.
+    pub symlinks: Vec<PathBuf>,
+}
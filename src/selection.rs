@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+
+use crate::confirmation;
+
+/// The answer given at the confirmation prompt.
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Nothing,
+    /// Zero based indices into the displayed list, sorted and without duplicates.
+    Indices(Vec<usize>),
+}
+
+/// Parses `y`, `n` and the other answers of `parse_confirmation`, a list of one based indices and ranges like `1,4-7,10`
+/// or its complement like `all except 2,5` against a list of `count` entries.
+pub fn parse_selection(input: &str, count: usize) -> Result<Selection, String> {
+    let input = input.trim();
+
+    match confirmation::parse_confirmation(input) {
+        Some(true) => return Ok(Selection::All),
+        Some(false) => return Ok(Selection::Nothing),
+        None if input.eq_ignore_ascii_case("all") => return Ok(Selection::All),
+        None if input.is_empty() => return Ok(Selection::Nothing),
+        None => (),
+    }
+
+    let lowercase_input = input.to_ascii_lowercase();
+    match lowercase_input.strip_prefix("all except") {
+        Some(excepted) => parse_indices(excepted, count).map(|excepted| {
+            Selection::Indices(
+                (0..count)
+                    .filter(|index| !excepted.contains(index))
+                    .collect(),
+            )
+        }),
+        None => parse_indices(input, count)
+            .map(|indices| Selection::Indices(indices.into_iter().collect())),
+    }
+}
+
+fn parse_indices(input: &str, count: usize) -> Result<BTreeSet<usize>, String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .map(|part| {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (parse_index(start, count)?, parse_index(end, count)?),
+                None => (parse_index(part, count)?, parse_index(part, count)?),
+            };
+
+            if start > end {
+                return Err(format!("Range {} is reversed", part));
+            }
+            Ok(start..=end)
+        })
+        .try_fold(BTreeSet::new(), |mut indices, range| {
+            indices.extend(range?);
+            Ok(indices)
+        })
+}
+
+fn parse_index(input: &str, count: usize) -> Result<usize, String> {
+    let input = input.trim();
+
+    input
+        .parse::<usize>()
+        .map_err(|_| format!("{} is not a number", input))
+        .and_then(|index| {
+            if index == 0 || index > count {
+                Err(format!("{} is not between 1 and {}", index, count))
+            } else {
+                Ok(index - 1)
+            }
+        })
+}
+
+#[cfg(test)]
+mod parse_selection_should {
+    use crate::selection::{parse_selection, Selection};
+
+    #[test]
+    fn parse_yes_and_no() {
+        assert_eq!(parse_selection("Y", 3), Ok(Selection::All));
+        assert_eq!(parse_selection("all", 3), Ok(Selection::All));
+        assert_eq!(parse_selection("yes", 3), Ok(Selection::All));
+        assert_eq!(parse_selection("n", 3), Ok(Selection::Nothing));
+        assert_eq!(parse_selection("No", 3), Ok(Selection::Nothing));
+        assert_eq!(parse_selection("", 3), Ok(Selection::Nothing));
+    }
+
+    #[test]
+    fn parse_indices_and_ranges() {
+        assert_eq!(
+            parse_selection("1,4-7, 10", 10),
+            Ok(Selection::Indices(vec![0, 3, 4, 5, 6, 9]))
+        );
+        assert_eq!(
+            parse_selection("3,1-2,2", 3),
+            Ok(Selection::Indices(vec![0, 1, 2]))
+        );
+    }
+
+    #[test]
+    fn parse_except_form() {
+        assert_eq!(
+            parse_selection("all except 2,5", 5),
+            Ok(Selection::Indices(vec![0, 2, 3]))
+        );
+        assert_eq!(
+            parse_selection("ALL EXCEPT 1-3", 3),
+            Ok(Selection::Indices(vec![]))
+        );
+    }
+
+    #[test]
+    fn reject_out_of_bounds_indices() {
+        assert_eq!(
+            parse_selection("1,11", 10),
+            Err("11 is not between 1 and 10".to_string())
+        );
+        assert_eq!(
+            parse_selection("0", 10),
+            Err("0 is not between 1 and 10".to_string())
+        );
+        assert_eq!(
+            parse_selection("all except 4", 3),
+            Err("4 is not between 1 and 3".to_string())
+        );
+    }
+
+    #[test]
+    fn reject_malformed_input() {
+        assert_eq!(
+            parse_selection("yes please", 3),
+            Err("yes please is not a number".to_string())
+        );
+        assert_eq!(
+            parse_selection("3-1", 3),
+            Err("Range 3-1 is reversed".to_string())
+        );
+        assert_eq!(
+            parse_selection("1,,2", 3),
+            Err(" is not a number".to_string())
+        );
+    }
+}
@@ -0,0 +1,77 @@
+const UNITS: [(&str, u64); 9] = [
+    ("b", 1),
+    ("kb", 1000),
+    ("mb", 1000 * 1000),
+    ("gb", 1000 * 1000 * 1000),
+    ("tb", 1000 * 1000 * 1000 * 1000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+];
+
+/// Parses sizes such as `800MB`, `1.5GB` or `512MiB` given on the command line into bytes.
+/// A number without unit is taken as bytes.
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let unit_index = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_index);
+
+    let amount = amount
+        .parse::<f64>()
+        .map_err(|e| format!("Invalid amount in {}: {}", value, e))?;
+    let unit = unit.trim().to_ascii_lowercase();
+    let bytes_per_unit = match unit.as_str() {
+        "" => 1,
+        unit => UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, bytes)| *bytes)
+            .ok_or(format!(
+                "Unknown unit {} in {}, expected one of B, KB, MB, GB, TB, KiB, MiB, GiB, TiB",
+                unit, value
+            ))?,
+    };
+
+    Ok((amount * bytes_per_unit as f64) as u64)
+}
+
+#[cfg(test)]
+mod parse_size_should {
+    use crate::size_arg::parse_size;
+
+    #[test]
+    fn parse_decimal_units() {
+        assert_eq!(parse_size("800MB").unwrap(), 800_000_000);
+        assert_eq!(parse_size("5GB").unwrap(), 5_000_000_000);
+        assert_eq!(parse_size("12kb").unwrap(), 12_000);
+    }
+
+    #[test]
+    fn parse_binary_units() {
+        assert_eq!(parse_size("1KiB").unwrap(), 1024);
+        assert_eq!(parse_size("2MiB").unwrap(), 2 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parse_fractions() {
+        assert_eq!(parse_size("1.5GB").unwrap(), 1_500_000_000);
+    }
+
+    #[test]
+    fn treat_plain_numbers_as_bytes() {
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+    }
+
+    #[test]
+    fn error_on_unknown_unit() {
+        assert!(parse_size("5XB").is_err());
+    }
+
+    #[test]
+    fn error_without_amount() {
+        assert!(parse_size("GB").is_err());
+    }
+}
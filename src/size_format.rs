@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+static EXACT: AtomicBool = AtomicBool::new(false);
+
+/// Shows every size as a byte count, set with `--bytes`.
+pub fn set_exact(exact: bool) {
+    EXACT.store(exact, Ordering::Relaxed);
+}
+
+/// Formats a byte count with the largest binary unit that keeps the value at or above one,
+/// e.g. `512 B`, `1.5 KiB` or `3.2 GiB`, or as the exact count with `--bytes`.
+pub fn format_size(bytes: u64) -> String {
+    format_size_as(bytes, EXACT.load(Ordering::Relaxed))
+}
+
+fn format_size_as(bytes: u64, exact: bool) -> String {
+    if exact || bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod format_size_should {
+    use crate::size_format::{format_size, format_size_as};
+
+    #[test]
+    fn format_bytes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+    }
+
+    #[test]
+    fn format_kibibytes() {
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn format_mebibytes() {
+        assert_eq!(format_size(1024 * 1024), "1.0 MiB");
+        assert_eq!(format_size(60 * 1024 * 1024), "60.0 MiB");
+    }
+
+    #[test]
+    fn format_gibibytes() {
+        assert_eq!(format_size(1024 * 1024 * 1024), "1.0 GiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024 / 2), "2.5 GiB");
+    }
+
+    #[test]
+    fn format_tebibytes_and_beyond() {
+        assert_eq!(format_size(1 << 40), "1.0 TiB");
+        assert_eq!(format_size(2048 << 40), "2048.0 TiB");
+    }
+
+    #[test]
+    fn format_exact_bytes_when_asked() {
+        assert_eq!(format_size_as(1023, true), "1023 B");
+        assert_eq!(format_size_as(60 * 1024 * 1024, true), "62914560 B");
+    }
+}
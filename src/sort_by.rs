@@ -0,0 +1,27 @@
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+
+use crate::save_information::SaveInformation;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, ValueEnum)]
+pub enum SortBy {
+    /// The number in the folder name, which the game reuses after slots are deleted
+    #[default]
+    Number,
+    /// The time the save folder was last modified
+    Mtime,
+}
+impl SortBy {
+    /// Orders newest first. In number mode this is the ordering of `SaveInformation`, in mtime
+    /// mode saves without a modified time sort last and equal times fall back to that ordering.
+    pub fn newest_first(self, save_a: &SaveInformation, save_b: &SaveInformation) -> Ordering {
+        match self {
+            SortBy::Number => save_a.cmp(save_b),
+            SortBy::Mtime => save_b
+                .modified
+                .cmp(&save_a.modified)
+                .then_with(|| save_a.cmp(save_b)),
+        }
+    }
+}
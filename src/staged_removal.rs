@@ -0,0 +1,283 @@
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{program_errors::ProgramError, retry, verbatim_path};
+
+/// Appended to the hidden name a folder is renamed to before it is removed, so scans leave it
+/// out and the next run can tell it apart from a save.
+pub const STAGING_SUFFIX: &str = ".deleting";
+
+/// The name `path` is renamed to before removal: `.<name>.deleting` in the same folder, so the
+/// rename never crosses a volume.
+pub fn staging_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(".");
+    name.push(path.file_name().unwrap_or(path.as_os_str()));
+    name.push(STAGING_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// Removes a folder with everything below it by renaming it to its staging name first. The
+/// rename is atomic, so a run killed halfway leaves either the untouched folder or a staged one
+/// for `finish_leftovers`, never a half removed folder under its original name.
+pub fn remove(path: &Path) -> Result<(), ProgramError> {
+    let staged = staging_path(&verbatim_path::for_removal(path));
+    // Left behind by an earlier run that stopped while removing a folder of the same name.
+    if staged.exists() {
+        remove_staged(&staged, path)?;
+    }
+
+    retry::io(|| fs::rename(verbatim_path::for_removal(path), &staged))
+        .map_err(|e| failure(path, vec![(path.to_path_buf(), e)]))?;
+    remove_staged(&staged, path)
+}
+
+/// Finishes removing the folders a previous run staged but didn't get to remove. They were
+/// already partly removed, so putting them back would bring back broken saves. Returns the
+/// removed folders and those that failed.
+pub fn finish_leftovers(directory: &Path) -> (Vec<PathBuf>, Vec<(PathBuf, ProgramError)>) {
+    leftovers(directory).into_iter().fold(
+        (Vec::new(), Vec::new()),
+        |(mut removed, mut failed), staged| {
+            match remove_staged(&verbatim_path::for_removal(&staged), &staged) {
+                Ok(()) => removed.push(staged),
+                Err(e) => failed.push((staged, e)),
+            }
+            (removed, failed)
+        },
+    )
+}
+
+fn leftovers(directory: &Path) -> Vec<PathBuf> {
+    fs::read_dir(directory)
+        .map(|children| {
+            children
+                .flatten()
+                .filter(|child| {
+                    let name = child.file_name().to_string_lossy().to_string();
+                    name.starts_with('.') && name.ends_with(STAGING_SUFFIX)
+                })
+                .map(|child| child.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Removes the staged folder, naming every file that is left in the error. `shown` is the path
+/// used in the error.
+fn remove_staged(staged: &Path, shown: &Path) -> Result<(), ProgramError> {
+    let failures = remove_tree(staged, &|file| retry::io(|| fs::remove_file(file)));
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failure(shown, failures))
+    }
+}
+
+/// Tries to remove every file below `path` instead of stopping at the first that fails, and
+/// returns those that failed. A folder is only removed once everything in it is. A symlinked
+/// save folder is only a link, the folder it links to stays.
+fn remove_tree(
+    path: &Path,
+    remove_file: &impl Fn(&Path) -> io::Result<()>,
+) -> Vec<(PathBuf, io::Error)> {
+    if path.is_symlink() {
+        // Windows removes links to folders like folders, elsewhere they are files.
+        let result = if cfg!(windows) {
+            fs::remove_dir(path)
+        } else {
+            remove_file(path)
+        };
+        return result
+            .err()
+            .map(|e| (path.to_path_buf(), e))
+            .into_iter()
+            .collect();
+    }
+
+    // A loose file is staged like a folder but has nothing below it.
+    if !path.is_dir() {
+        return remove_file(path)
+            .err()
+            .map(|e| (path.to_path_buf(), e))
+            .into_iter()
+            .collect();
+    }
+
+    let children = match fs::read_dir(path) {
+        Ok(children) => children,
+        Err(e) => return vec![(path.to_path_buf(), e)],
+    };
+    let failures: Vec<(PathBuf, io::Error)> = children
+        .flat_map(|child| match child {
+            Ok(child) if child.path().is_dir() && !child.path().is_symlink() => {
+                remove_tree(&child.path(), remove_file)
+            }
+            Ok(child) => remove_file(&child.path())
+                .err()
+                .map(|e| (child.path(), e))
+                .into_iter()
+                .collect(),
+            Err(e) => vec![(path.to_path_buf(), e)],
+        })
+        .collect();
+
+    if failures.is_empty() {
+        fs::remove_dir(path)
+            .err()
+            .map(|e| (path.to_path_buf(), e))
+            .into_iter()
+            .collect()
+    } else {
+        failures
+    }
+}
+
+fn failure(shown: &Path, failures: Vec<(PathBuf, io::Error)>) -> ProgramError {
+    ProgramError::FailedToDelete(format!(
+        "{}: {} file(s) could not be removed: {}",
+        verbatim_path::for_display(shown).display(),
+        failures.len(),
+        failures
+            .iter()
+            .map(|(path, e)| format!("{} ({})", verbatim_path::for_display(path).display(), e))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod remove_should {
+    use std::{fs, path::Path};
+
+    use crate::{
+        staged_removal::{remove, staging_path},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn remove_the_folder_and_its_staging_name() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(save.join("Mods/Data")).unwrap();
+
+        remove(&save).unwrap();
+
+        assert!(!save.exists());
+        assert!(!staging_path(&save).exists());
+    }
+
+    #[test]
+    fn replace_a_staged_folder_of_the_same_name() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let staged = staging_path(&save);
+        fs::create_dir(&staged).unwrap();
+        fs::write(staged.join("test.lsv"), b"LS").unwrap();
+
+        remove(&save).unwrap();
+
+        assert!(!save.exists());
+        assert!(!staged.exists());
+    }
+
+    #[test]
+    fn stage_next_to_the_folder() {
+        assert_eq!(
+            staging_path(Path::new("saves/Tav-123456789__QuickSave_1")),
+            Path::new("saves/.Tav-123456789__QuickSave_1.deleting")
+        );
+    }
+}
+
+#[cfg(test)]
+mod remove_tree_should {
+    use std::{fs, io, path::Path};
+
+    use crate::{staged_removal::remove_tree, test_utils::TempDir};
+
+    #[test]
+    fn try_every_file_and_report_all_that_failed() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::create_dir_all(save.join("Mods")).unwrap();
+        ["a.locked", "Mods/b.locked", "Mods/c.webp", "d.webp"]
+            .iter()
+            .for_each(|file| fs::write(save.join(file), b"").unwrap());
+        let remove_file = |file: &Path| match file.extension() {
+            Some(extension) if extension == "locked" => {
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            }
+            _ => fs::remove_file(file),
+        };
+
+        let mut failed: Vec<_> = remove_tree(&save, &remove_file)
+            .into_iter()
+            .map(|(path, e)| (path, e.kind()))
+            .collect();
+        failed.sort();
+
+        assert_eq!(
+            failed,
+            vec![
+                (save.join("Mods/b.locked"), io::ErrorKind::PermissionDenied),
+                (save.join("a.locked"), io::ErrorKind::PermissionDenied),
+            ]
+        );
+        assert!(!save.join("test.lsv").exists());
+        assert!(!save.join("Mods/c.webp").exists());
+        assert!(!save.join("d.webp").exists());
+        assert!(save.join("Mods").exists());
+    }
+
+    #[test]
+    fn report_the_folder_when_it_cannot_be_read() {
+        let temp_dir = TempDir::new();
+        let missing = temp_dir.path().join("missing");
+
+        let failed = remove_tree(&missing, &|file| fs::remove_file(file));
+
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, missing);
+    }
+}
+
+#[cfg(test)]
+mod finish_leftovers_should {
+    use std::fs;
+
+    use crate::{
+        staged_removal::{finish_leftovers, staging_path},
+        test_utils::TempDir,
+    };
+
+    #[test]
+    fn remove_folders_left_staged_by_an_interrupted_run() {
+        let temp_dir = TempDir::new();
+        let staged = staging_path(&temp_dir.path().join("Tav-123456789__QuickSave_1"));
+        fs::create_dir_all(staged.join("Mods")).unwrap();
+        fs::write(staged.join("test.lsv"), b"LS").unwrap();
+        let save = temp_dir.create_save("Tav-123456789__QuickSave_2");
+        fs::create_dir(temp_dir.path().join(".stversions")).unwrap();
+
+        let (removed, failed) = finish_leftovers(temp_dir.path());
+
+        assert_eq!(removed, vec![staged.clone()]);
+        assert!(failed.is_empty());
+        assert!(!staged.exists());
+        assert!(save.exists());
+        assert!(temp_dir.path().join(".stversions").exists());
+    }
+
+    #[test]
+    fn find_nothing_in_missing_folders() {
+        let temp_dir = TempDir::new();
+
+        let (removed, failed) = finish_leftovers(&temp_dir.path().join("missing"));
+
+        assert!(removed.is_empty());
+        assert!(failed.is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use crate::{
+    character_key::CharacterKey, save_information::SaveInformation, saves::Saves,
+    size_format::format_size,
+};
+
+/// Renders the disk usage of each character, largest first, followed by a grand total.
+pub fn format_stats(map: &HashMap<CharacterKey, Saves>, raw_bytes: bool) -> String {
+    let display_size = |bytes: u64| {
+        if raw_bytes {
+            format!("{} bytes", bytes)
+        } else {
+            format_size(bytes)
+        }
+    };
+
+    let mut characters: Vec<(&CharacterKey, u64, u64)> = map
+        .iter()
+        .map(|(name, saves)| {
+            (
+                name,
+                total_size(&saves.quick_saves),
+                total_size(&saves.auto_saves),
+            )
+        })
+        .collect();
+    characters.sort_by(|(name_a, quick_a, auto_a), (name_b, quick_b, auto_b)| {
+        (quick_b + auto_b)
+            .cmp(&(quick_a + auto_a))
+            .then_with(|| name_a.cmp(name_b))
+    });
+
+    let grand_total: u64 = characters
+        .iter()
+        .map(|(_, quick_size, auto_size)| quick_size + auto_size)
+        .sum();
+
+    characters
+        .into_iter()
+        .map(|(name, quick_size, auto_size)| {
+            format!(
+                "{}: {} (quick saves {}, auto saves {})\n",
+                name,
+                display_size(quick_size + auto_size),
+                display_size(quick_size),
+                display_size(auto_size)
+            )
+        })
+        .chain(std::iter::once(format!(
+            "Total: {}\n",
+            display_size(grand_total)
+        )))
+        .collect()
+}
+
+fn total_size(saves: &[SaveInformation]) -> u64 {
+    saves
+        .iter()
+        .map(|save_information| save_information.size_bytes)
+        .sum()
+}
+
+#[cfg(test)]
+mod format_stats_should {
+    use std::fs;
+
+    use crate::{load_saves, stats::format_stats, test_utils::TempDir};
+
+    fn save_with_size(temp_dir: &TempDir, folder_name: &str, size: usize) {
+        let save_path = temp_dir.path().join(folder_name);
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("save.lsv"), vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn sort_characters_by_size_with_total() {
+        let temp_dir = TempDir::new();
+        save_with_size(&temp_dir, "Tav-123456789__QuickSave_1", 1000);
+        save_with_size(&temp_dir, "Tav-123456789__AutoSave_1", 24);
+        save_with_size(&temp_dir, "Durge-987654321__QuickSave_1", 2048);
+        let map = load_saves(&[temp_dir.path().to_path_buf()], false).unwrap();
+
+        assert_eq!(
+            format_stats(&map, false),
+            "Durge #987654: 2.0 KiB (quick saves 2.0 KiB, auto saves 0 B)\n\
+             Tav #123456: 1.0 KiB (quick saves 1000 B, auto saves 24 B)\n\
+             Total: 3.0 KiB\n"
+        );
+    }
+
+    #[test]
+    fn print_raw_bytes() {
+        let temp_dir = TempDir::new();
+        save_with_size(&temp_dir, "Tav-123456789__QuickSave_1", 1000);
+        let map = load_saves(&[temp_dir.path().to_path_buf()], false).unwrap();
+
+        assert_eq!(
+            format_stats(&map, true),
+            "Tav #123456: 1000 bytes (quick saves 1000 bytes, auto saves 0 bytes)\nTotal: 1000 bytes\n"
+        );
+    }
+}
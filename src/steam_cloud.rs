@@ -0,0 +1,100 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::default_paths::STEAM_APP_ID;
+
+/// Why Steam Cloud likely syncs the saves in `directory`, `None` if nothing points to it. Saves
+/// in a Steam library, like in a Proton prefix, are synced when the game has cloud saves on.
+/// Elsewhere Steam lists the synced files per account in
+/// `userdata/<account>/1086940/remotecache.vdf`.
+pub fn sync_reason(directory: &Path, steam_roots: &[PathBuf]) -> Option<String> {
+    if directory
+        .components()
+        .any(|component| component == Component::Normal("steamapps".as_ref()))
+    {
+        return Some("it is inside a Steam library".to_string());
+    }
+
+    steam_roots
+        .iter()
+        .flat_map(|steam_root| remote_caches(steam_root))
+        .find(|remote_cache| fs::read_to_string(remote_cache).is_ok_and(|vdf| lists_saves(&vdf)))
+        .map(|remote_cache| format!("Steam Cloud lists the saves in {}", remote_cache.display()))
+}
+
+fn remote_caches(steam_root: &Path) -> Vec<PathBuf> {
+    fs::read_dir(steam_root.join("userdata"))
+        .map(|accounts| {
+            accounts
+                .flatten()
+                .map(|account| account.path().join(STEAM_APP_ID).join("remotecache.vdf"))
+                .filter(|remote_cache| remote_cache.is_file())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The save files are listed below `Savegames/Story`, with either separator and escaped
+/// backslashes.
+fn lists_saves(vdf: &str) -> bool {
+    vdf.replace("\\\\", "/")
+        .replace('\\', "/")
+        .contains("Savegames/Story")
+}
+
+#[cfg(test)]
+mod sync_reason_should {
+    use std::{fs, path::Path};
+
+    use crate::{steam_cloud::sync_reason, test_utils::TempDir};
+
+    fn write_remote_cache(steam_root: &Path, vdf: &str) {
+        let app_dir = steam_root.join("userdata/12345678/1086940");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("remotecache.vdf"), vdf).unwrap();
+    }
+
+    #[test]
+    fn warn_about_saves_in_a_steam_library() {
+        let saves = Path::new(
+            "/home/deck/.steam/steam/steamapps/compatdata/1086940/pfx/drive_c/users/steamuser",
+        );
+
+        assert_eq!(
+            sync_reason(saves, &[]),
+            Some("it is inside a Steam library".to_string())
+        );
+    }
+
+    #[test]
+    fn warn_when_steam_cloud_lists_saves() {
+        let steam_root = TempDir::new();
+        write_remote_cache(
+            steam_root.path(),
+            "\"1086940\"\n{\n\t\"Public\\\\Savegames\\\\Story\\\\Tav-123456789__QuickSave_1\\\\Tav.lsv\"\n\t{\n\t}\n}",
+        );
+
+        let reason = sync_reason(Path::new("saves"), &[steam_root.path().to_path_buf()]);
+
+        assert!(reason
+            .unwrap()
+            .starts_with("Steam Cloud lists the saves in "));
+    }
+
+    #[test]
+    fn stay_quiet_without_synced_saves() {
+        let steam_root = TempDir::new();
+        write_remote_cache(
+            steam_root.path(),
+            "\"1086940\"\n{\n\t\"graphicSettings.lsx\"\n}",
+        );
+
+        assert_eq!(
+            sync_reason(Path::new("saves"), &[steam_root.path().to_path_buf()]),
+            None
+        );
+        assert_eq!(sync_reason(Path::new("saves"), &[]), None);
+    }
+}
@@ -0,0 +1,426 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::{Mutex, PoisonError},
+    time::UNIX_EPOCH,
+};
+
+use crate::{program_errors::ProgramError, zip_archive, zstd};
+
+const BLOCK_SIZE: usize = 512;
+/// Two empty blocks mark the end of a tar archive.
+const END_OF_ARCHIVE: [u8; 2 * BLOCK_SIZE] = [0; 2 * BLOCK_SIZE];
+const NAME_LENGTH: usize = 100;
+const PREFIX_LENGTH: usize = 155;
+const FILE_MODE: u32 = 0o644;
+const DIRECTORY_MODE: u32 = 0o755;
+const REGULAR_FILE: u8 = b'0';
+const DIRECTORY: u8 = b'5';
+
+/// Workers delete folders in parallel, appending to the archive happens one folder at a time.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Appends `path`, a folder with every file below it or a single file, to the zstd compressed
+/// tar archive at `archive_path`, creating it if needed. Every folder is its own zstd frame and the end marker
+/// of the tar archive is a frame of its own, so the next folder replaces just that frame. The
+/// frame is synced and decompressed again before returning, so the folder can be removed once
+/// this succeeds. Extract a save again with `tar -xf <archive> <folder name>`.
+pub fn append(path: &Path, archive_path: &Path) -> Result<(), ProgramError> {
+    let _lock = APPEND_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let write_error = |e: io::Error| {
+        ProgramError::FailedToWriteFile(format!("{}: {}", archive_path.display(), e))
+    };
+
+    let mut archive = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(archive_path)
+        .map_err(write_error)?;
+    let start = end_of_entries(&mut archive, archive_path)?;
+
+    let result = archive
+        .seek(SeekFrom::Start(start))
+        .map_err(write_error)
+        .and_then(|_| write_entries(path, &mut archive, archive_path))
+        .and_then(|_| {
+            archive
+                .write_all(&end_frame())
+                .and_then(|_| archive.sync_all())
+                .map_err(write_error)
+        })
+        .and_then(|_| verify(&mut archive, start, archive_path));
+
+    if result.is_err() {
+        // Leave the archive as it was before this folder.
+        let _ = archive
+            .set_len(start)
+            .and_then(|_| archive.seek(SeekFrom::Start(start)))
+            .and_then(|_| archive.write_all(&end_frame()));
+    }
+    result
+}
+
+/// The end marker of the tar archive, compressed the same way every time.
+fn end_frame() -> Vec<u8> {
+    zstd::Encoder::new(Vec::new())
+        .and_then(|mut encoder| encoder.write_all(&END_OF_ARCHIVE).map(|_| encoder))
+        .and_then(|encoder| encoder.finish())
+        .unwrap_or_default()
+}
+
+/// Where new entries go, right before the end marker frame of an existing archive.
+fn end_of_entries(archive: &mut File, archive_path: &Path) -> Result<u64, ProgramError> {
+    let length = archive
+        .metadata()
+        .map_err(|e| ProgramError::FailedToReadFile(format!("{}: {}", archive_path.display(), e)))?
+        .len();
+    if length == 0 {
+        return Ok(0);
+    }
+
+    let not_an_archive = || {
+        ProgramError::FailedToWriteFile(format!(
+            "{} exists but is not a tar.zst archive written by bg3sc, refusing to overwrite it",
+            archive_path.display()
+        ))
+    };
+    let end_frame = end_frame();
+    let end_frame_length = end_frame.len() as u64;
+    if length < end_frame_length {
+        return Err(not_an_archive());
+    }
+
+    let mut end_marker = vec![0u8; end_frame.len()];
+    archive
+        .seek(SeekFrom::Start(length - end_frame_length))
+        .and_then(|_| archive.read_exact(&mut end_marker))
+        .map_err(|e| {
+            ProgramError::FailedToReadFile(format!("{}: {}", archive_path.display(), e))
+        })?;
+    if end_marker != end_frame {
+        return Err(not_an_archive());
+    }
+
+    Ok(length - end_frame_length)
+}
+
+/// Decompresses the frame written at `start` and checks that the end marker follows it.
+fn verify(archive: &mut File, start: u64, archive_path: &Path) -> Result<(), ProgramError> {
+    let read_error =
+        |e: String| ProgramError::FailedToReadFile(format!("{}: {}", archive_path.display(), e));
+    archive
+        .seek(SeekFrom::Start(start))
+        .map_err(|e| read_error(e.to_string()))?;
+
+    let mut reader = BufReader::new(archive);
+    zstd::decompress_frame(&mut reader, &mut io::sink()).map_err(read_error)?;
+    let mut rest = Vec::new();
+    reader
+        .read_to_end(&mut rest)
+        .map_err(|e| read_error(e.to_string()))?;
+
+    if rest != end_frame() {
+        return Err(ProgramError::FailedToWriteFile(format!(
+            "{} does not read back what was written",
+            archive_path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Compresses the header and data blocks of the folder and its files, named below the folder's
+/// name, or of a single file into one frame. Files are streamed, so large saves are never held
+/// in memory.
+fn write_entries(path: &Path, archive: &mut File, archive_path: &Path) -> Result<(), ProgramError> {
+    let root = path.parent().unwrap_or(path);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or(ProgramError::FailedToReadDir(format!(
+            "{} has no name",
+            path.display()
+        )))?;
+    let write_error = |e: io::Error| {
+        ProgramError::FailedToWriteFile(format!("{}: {}", archive_path.display(), e))
+    };
+
+    let mut encoder = zstd::Encoder::new(archive).map_err(write_error)?;
+    let files = if path.is_dir() {
+        encoder
+            .write_all(&header(
+                &format!("{}/", name),
+                0,
+                DIRECTORY_MODE,
+                DIRECTORY,
+                modified_secs(path),
+            )?)
+            .map_err(write_error)?;
+        zip_archive::collect_files(root, path)?
+    } else {
+        vec![(name, path.to_path_buf())]
+    };
+
+    for (name, path) in files {
+        let read_error =
+            |e: io::Error| ProgramError::FailedToReadFile(format!("{}: {}", path.display(), e));
+        let file = File::open(&path).map_err(read_error)?;
+        let size = file.metadata().map_err(read_error)?.len();
+
+        encoder
+            .write_all(&header(
+                &name,
+                size,
+                FILE_MODE,
+                REGULAR_FILE,
+                modified_secs(&path),
+            )?)
+            .map_err(write_error)?;
+        let copied = io::copy(&mut file.take(size), &mut encoder).map_err(|e| {
+            ProgramError::FailedToWriteFile(format!(
+                "{} into {}: {}",
+                path.display(),
+                archive_path.display(),
+                e
+            ))
+        })?;
+        if copied != size {
+            return Err(ProgramError::FailedToReadFile(format!(
+                "{} changed while it was archived",
+                path.display()
+            )));
+        }
+        let padding = (size as usize).next_multiple_of(BLOCK_SIZE) - size as usize;
+        encoder
+            .write_all(&END_OF_ARCHIVE[..padding])
+            .map_err(write_error)?;
+    }
+
+    encoder.finish().map(|_| ()).map_err(write_error)
+}
+
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since_epoch| since_epoch.as_secs())
+}
+
+/// A ustar header, names longer than 100 bytes are split into the prefix field at a `/`.
+fn header(
+    name: &str,
+    size: u64,
+    mode: u32,
+    typeflag: u8,
+    mtime: u64,
+) -> Result<[u8; BLOCK_SIZE], ProgramError> {
+    let (prefix, name) = split_name(name).ok_or(ProgramError::FailedToWriteFile(format!(
+        "{} is too long for a tar archive",
+        name
+    )))?;
+
+    let mut header = [0u8; BLOCK_SIZE];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], u64::from(mode));
+    write_octal(&mut header[108..116], 0); // uid
+    write_octal(&mut header[116..124], 0); // gid
+    write_octal(&mut header[124..136], size);
+    write_octal(&mut header[136..148], mtime);
+    header[148..156].fill(b' '); // the checksum counts itself as spaces
+    header[156] = typeflag;
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    let checksum: u32 = header.iter().map(|byte| u32::from(*byte)).sum();
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+
+    Ok(header)
+}
+
+fn split_name(name: &str) -> Option<(&str, &str)> {
+    if name.len() <= NAME_LENGTH {
+        return Some(("", name));
+    }
+
+    name.char_indices()
+        .filter(|(_, c)| *c == '/')
+        .map(|(index, _)| (&name[..index], &name[index + 1..]))
+        .find(|(prefix, rest)| {
+            prefix.len() <= PREFIX_LENGTH && rest.len() <= NAME_LENGTH && !rest.is_empty()
+        })
+}
+
+/// Zero padded octal followed by a NUL, filling the field.
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}\0", value, width = field.len() - 1);
+    field.copy_from_slice(&digits.as_bytes()[digits.len() - field.len()..]);
+}
+
+#[cfg(test)]
+mod append_should {
+    use std::{fs, io::Cursor, path::Path};
+
+    use crate::{
+        program_errors::ProgramError,
+        tar_archive::{append, BLOCK_SIZE},
+        test_utils::TempDir,
+        zstd,
+    };
+
+    fn octal(field: &[u8]) -> usize {
+        let digits: String = field
+            .iter()
+            .take_while(|byte| **byte != 0 && **byte != b' ')
+            .map(|byte| *byte as char)
+            .collect();
+        usize::from_str_radix(&digits, 8).unwrap()
+    }
+
+    /// Decompresses every frame, then reads the entries back by walking the headers up to the
+    /// end marker.
+    fn read_tar(path: &Path) -> Vec<(String, Vec<u8>)> {
+        let compressed = fs::read(path).unwrap();
+        let mut frames = Cursor::new(compressed.as_slice());
+        let mut bytes = Vec::new();
+        while (frames.position() as usize) < compressed.len() {
+            zstd::decompress_frame(&mut frames, &mut bytes).unwrap();
+        }
+        let mut entries = Vec::new();
+        let mut offset = 0;
+
+        while bytes[offset..offset + BLOCK_SIZE]
+            .iter()
+            .any(|byte| *byte != 0)
+        {
+            let header = &bytes[offset..offset + BLOCK_SIZE];
+            let field = |range: std::ops::Range<usize>| {
+                String::from_utf8(
+                    header[range]
+                        .iter()
+                        .copied()
+                        .take_while(|byte| *byte != 0)
+                        .collect(),
+                )
+                .unwrap()
+            };
+            let checksum: usize = header
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| {
+                    if (148..156).contains(&i) {
+                        32
+                    } else {
+                        *byte as usize
+                    }
+                })
+                .sum();
+            assert_eq!(octal(&header[148..156]), checksum);
+
+            let prefix = field(345..500);
+            let name = match prefix.is_empty() {
+                true => field(0..100),
+                false => format!("{}/{}", prefix, field(0..100)),
+            };
+            let size = octal(&header[124..136]);
+            let data_start = offset + BLOCK_SIZE;
+            entries.push((name, bytes[data_start..data_start + size].to_vec()));
+            offset = data_start + size.next_multiple_of(BLOCK_SIZE);
+        }
+        assert_eq!(bytes.len(), offset + 2 * BLOCK_SIZE);
+
+        entries
+    }
+
+    #[test]
+    fn append_folders_and_remember_earlier_ones() {
+        let temp_dir = TempDir::new();
+        let first = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let second = temp_dir.create_save("Tav-123456789__QuickSave_2");
+        fs::create_dir(second.join("nested")).unwrap();
+        fs::write(second.join("nested").join("extra.bin"), vec![7u8; 600]).unwrap();
+        let archive_path = temp_dir.path().join("archive.tar.zst");
+
+        append(&first, &archive_path).unwrap();
+        append(&second, &archive_path).unwrap();
+
+        assert_eq!(
+            read_tar(&archive_path),
+            vec![
+                ("Tav-123456789__QuickSave_1/".to_string(), vec![]),
+                (
+                    "Tav-123456789__QuickSave_1/test.lsv".to_string(),
+                    b"LSPK".to_vec()
+                ),
+                ("Tav-123456789__QuickSave_2/".to_string(), vec![]),
+                (
+                    "Tav-123456789__QuickSave_2/nested/extra.bin".to_string(),
+                    vec![7u8; 600]
+                ),
+                (
+                    "Tav-123456789__QuickSave_2/test.lsv".to_string(),
+                    b"LSPK".to_vec()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn compress_the_saves() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::write(save_path.join("Tav.lsv"), b"LSPK".repeat(100_000)).unwrap();
+        let archive_path = temp_dir.path().join("archive.tar.zst");
+
+        append(&save_path, &archive_path).unwrap();
+
+        assert!(fs::metadata(&archive_path).unwrap().len() < 40_000);
+        assert_eq!(read_tar(&archive_path)[1].1, b"LSPK".repeat(100_000));
+    }
+
+    #[test]
+    fn append_a_single_file() {
+        let temp_dir = TempDir::new();
+        let file = temp_dir.path().join("orphan.lsv");
+        fs::write(&file, b"LSPK").unwrap();
+        let archive_path = temp_dir.path().join("archive.tar.zst");
+
+        append(&file, &archive_path).unwrap();
+
+        assert_eq!(
+            read_tar(&archive_path),
+            vec![("orphan.lsv".to_string(), b"LSPK".to_vec())]
+        );
+    }
+
+    #[test]
+    fn split_long_names_into_prefix() {
+        let temp_dir = TempDir::new();
+        let folder_name = format!("{}-123456789__QuickSave_1", "Tav".repeat(25));
+        let save_path = temp_dir.create_save(&folder_name);
+        let archive_path = temp_dir.path().join("archive.tar.zst");
+
+        append(&save_path, &archive_path).unwrap();
+
+        assert_eq!(
+            read_tar(&archive_path)[1].0,
+            format!("{}/test.lsv", folder_name)
+        );
+    }
+
+    #[test]
+    fn refuse_to_overwrite_other_files() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        let archive_path = temp_dir.path().join("notes.txt");
+        fs::write(&archive_path, b"my notes").unwrap();
+
+        let result = append(&save_path, &archive_path);
+
+        assert!(matches!(result, Err(ProgramError::FailedToWriteFile(_))));
+        assert_eq!(fs::read(&archive_path).unwrap(), b"my notes");
+    }
+}
@@ -0,0 +1,44 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use rand::Rng;
+
+/// A uniquely named directory under the system temp dir, removed again when dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+impl TempDir {
+    pub fn new() -> Self {
+        let path = env::temp_dir().join(format!("bg3sc_test_{}", rand::thread_rng().gen::<u64>()));
+        fs::create_dir_all(&path).unwrap();
+
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Creates a save folder containing a single `.lsv` file and returns its path.
+    pub fn create_save(&self, folder_name: &str) -> PathBuf {
+        let save_path = self.path.join(folder_name);
+        fs::create_dir_all(&save_path).unwrap();
+        fs::write(save_path.join("test.lsv"), b"LSPK").unwrap();
+
+        save_path
+    }
+}
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+pub fn set_modified(path: &Path, modified: SystemTime) {
+    fs::File::open(path)
+        .and_then(|file| file.set_modified(modified))
+        .unwrap();
+}
@@ -0,0 +1,334 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Formats a timestamp as an ISO 8601 UTC date time, e.g. `2024-03-02T21:14:05Z`.
+pub fn format_utc(time: SystemTime) -> String {
+    let (date, clock) = date_and_clock(seconds_since_epoch(time));
+
+    format!("{}T{}Z", date, clock)
+}
+
+/// Formats a timestamp in the local time zone for people reading the output,
+/// e.g. `2024-03-02 22:14:05 +01:00`.
+pub fn format_local(time: SystemTime) -> String {
+    let seconds = seconds_since_epoch(time);
+
+    format_with_offset(seconds, platform::utc_offset(seconds))
+}
+
+/// Formats a timestamp as an ISO 8601 date time in the local time zone without the zone,
+/// e.g. `2024-03-02T22:14:05`, the form the freedesktop.org trash info files use.
+pub fn format_local_iso(time: SystemTime) -> String {
+    let seconds = seconds_since_epoch(time);
+
+    format_iso_with_offset(seconds, platform::utc_offset(seconds))
+}
+
+/// Like `format_local` to the minute, short enough for a column, e.g. `2024-03-02 22:14`.
+pub fn format_local_minutes(time: SystemTime) -> String {
+    let seconds = seconds_since_epoch(time);
+
+    format_minutes_with_offset(seconds, platform::utc_offset(seconds))
+}
+
+/// The modified time of a save for a column of listed saves, `unknown` padded to the same width
+/// when it could not be read so the columns after it stay aligned.
+pub fn format_modified(modified: Option<SystemTime>) -> String {
+    modified.map_or(
+        format!("{:<width$}", "unknown", width = "2024-03-02 22:14".len()),
+        format_local_minutes,
+    )
+}
+
+/// The time of a date and clock in the local time zone, `None` for dates before the epoch or
+/// out of range fields, like those of a damaged save.
+pub fn from_local(date: (u64, u64, u64), clock: (u64, u64, u64)) -> Option<SystemTime> {
+    let local_seconds = utc_seconds(date, clock)?;
+    // The offset at the local time is good enough, it only differs around a daylight saving switch.
+    let seconds = local_seconds.checked_add_signed(-platform::utc_offset(local_seconds))?;
+
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn utc_seconds(
+    (year, month, day): (u64, u64, u64),
+    (hour, minute, second): (u64, u64, u64),
+) -> Option<u64> {
+    if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second)
+}
+
+fn format_with_offset(seconds: u64, offset: i64) -> String {
+    let (date, clock) = date_and_clock(seconds.saturating_add_signed(offset));
+
+    format!(
+        "{} {} {}{:02}:{:02}",
+        date,
+        clock,
+        if offset < 0 { '-' } else { '+' },
+        offset.unsigned_abs() / 3600,
+        offset.unsigned_abs() % 3600 / 60
+    )
+}
+
+fn format_iso_with_offset(seconds: u64, offset: i64) -> String {
+    let (date, clock) = date_and_clock(seconds.saturating_add_signed(offset));
+
+    format!("{}T{}", date, clock)
+}
+
+fn format_minutes_with_offset(seconds: u64, offset: i64) -> String {
+    let (date, clock) = date_and_clock(seconds.saturating_add_signed(offset));
+
+    format!("{} {}", date, &clock[..5])
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or(0)
+}
+
+fn date_and_clock(seconds: u64) -> (String, String) {
+    let (year, month, day) = civil_from_days(seconds / SECONDS_PER_DAY);
+    let seconds_of_day = seconds % SECONDS_PER_DAY;
+
+    (
+        format!("{:04}-{:02}-{:02}", year, month, day),
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60,
+            seconds_of_day % 60
+        ),
+    )
+}
+
+/// Converts days since the unix epoch into a (year, month, day) date of the proleptic
+/// gregorian calendar. See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// The inverse of `civil_from_days`, for dates from 1970 on.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::{
+        mem::MaybeUninit,
+        os::raw::{c_char, c_int, c_long},
+    };
+
+    /// `struct tm` as laid out by glibc, musl and the BSDs including macOS.
+    #[repr(C)]
+    struct Tm {
+        tm_sec: c_int,
+        tm_min: c_int,
+        tm_hour: c_int,
+        tm_mday: c_int,
+        tm_mon: c_int,
+        tm_year: c_int,
+        tm_wday: c_int,
+        tm_yday: c_int,
+        tm_isdst: c_int,
+        tm_gmtoff: c_long,
+        tm_zone: *const c_char,
+    }
+
+    extern "C" {
+        fn tzset();
+        fn localtime_r(time: *const c_long, result: *mut Tm) -> *mut Tm;
+    }
+
+    /// Seconds the local time zone is ahead of UTC at the given time, 0 if unknown.
+    pub fn utc_offset(seconds: u64) -> i64 {
+        let Ok(time) = c_long::try_from(seconds) else {
+            return 0;
+        };
+        let mut tm = MaybeUninit::<Tm>::zeroed();
+
+        unsafe {
+            tzset();
+            if localtime_r(&time, tm.as_mut_ptr()).is_null() {
+                return 0;
+            }
+            // c_long is only 32 bits wide on some targets.
+            #[allow(clippy::useless_conversion)]
+            tm.assume_init().tm_gmtoff.into()
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    const TIME_ZONE_ID_DAYLIGHT: u32 = 2;
+
+    #[repr(C)]
+    struct TimeZoneInformation {
+        bias: i32,
+        standard_name: [u16; 32],
+        standard_date: [u16; 8],
+        standard_bias: i32,
+        daylight_name: [u16; 32],
+        daylight_date: [u16; 8],
+        daylight_bias: i32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetTimeZoneInformation(information: *mut TimeZoneInformation) -> u32;
+    }
+
+    /// Seconds the local time zone is ahead of UTC. Windows only reports whether daylight
+    /// saving applies right now, so older saves may be an hour off around the switch.
+    pub fn utc_offset(_: u64) -> i64 {
+        let mut information = TimeZoneInformation {
+            bias: 0,
+            standard_name: [0; 32],
+            standard_date: [0; 8],
+            standard_bias: 0,
+            daylight_name: [0; 32],
+            daylight_date: [0; 8],
+            daylight_bias: 0,
+        };
+
+        let bias = match unsafe { GetTimeZoneInformation(&mut information) } {
+            u32::MAX => return 0,
+            TIME_ZONE_ID_DAYLIGHT => information.bias + information.daylight_bias,
+            _ => information.bias + information.standard_bias,
+        };
+        // The bias is how many minutes UTC is ahead of local time.
+        -i64::from(bias) * 60
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    pub fn utc_offset(_: u64) -> i64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod format_utc_should {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::timestamp::format_utc;
+
+    #[test]
+    fn format_epoch() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn format_known_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_709_414_045);
+
+        assert_eq!(format_utc(time), "2024-03-02T21:14:05Z");
+    }
+
+    #[test]
+    fn format_leap_day() {
+        let time = UNIX_EPOCH + Duration::from_secs(951_782_400);
+
+        assert_eq!(format_utc(time), "2000-02-29T00:00:00Z");
+    }
+}
+
+#[cfg(test)]
+mod format_local_should {
+    use std::time::UNIX_EPOCH;
+
+    use crate::timestamp::{
+        format_iso_with_offset, format_minutes_with_offset, format_modified, format_with_offset,
+    };
+
+    #[test]
+    fn shift_by_offset_and_show_it() {
+        assert_eq!(
+            format_with_offset(1_709_414_045, 3600),
+            "2024-03-02 22:14:05 +01:00"
+        );
+        assert_eq!(
+            format_with_offset(1_709_414_045, -(5 * 3600 + 30 * 60)),
+            "2024-03-02 15:44:05 -05:30"
+        );
+    }
+
+    #[test]
+    fn shorten_to_the_minute_for_columns() {
+        assert_eq!(
+            format_minutes_with_offset(1_709_414_045, 3600),
+            "2024-03-02 22:14"
+        );
+    }
+
+    #[test]
+    fn leave_the_zone_out_for_trash_info() {
+        assert_eq!(
+            format_iso_with_offset(1_709_414_045, 3600),
+            "2024-03-02T22:14:05"
+        );
+    }
+
+    #[test]
+    fn show_unknown_modified_time_as_wide_as_a_date() {
+        assert_eq!(format_modified(None), "unknown         ");
+        assert_eq!(
+            format_modified(Some(UNIX_EPOCH)).len(),
+            format_modified(None).len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod utc_seconds_should {
+    use crate::timestamp::utc_seconds;
+
+    #[test]
+    fn convert_known_date_and_clock() {
+        assert_eq!(utc_seconds((2024, 3, 2), (21, 14, 5)), Some(1_709_414_045));
+        assert_eq!(utc_seconds((2000, 2, 29), (0, 0, 0)), Some(951_782_400));
+        assert_eq!(utc_seconds((1970, 1, 1), (0, 0, 0)), Some(0));
+    }
+
+    #[test]
+    fn reject_out_of_range_fields() {
+        assert_eq!(utc_seconds((1969, 12, 31), (0, 0, 0)), None);
+        assert_eq!(utc_seconds((2024, 13, 1), (0, 0, 0)), None);
+        assert_eq!(utc_seconds((2024, 1, 1), (24, 0, 0)), None);
+    }
+}
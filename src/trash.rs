@@ -0,0 +1,227 @@
+#[cfg(any(windows, target_os = "macos"))]
+use std::process::Command;
+#[cfg(not(any(windows, target_os = "macos")))]
+use std::{env, time::SystemTime};
+use std::{fs, path::Path};
+
+use crate::program_errors::ProgramError;
+#[cfg(not(any(windows, target_os = "macos")))]
+use crate::timestamp;
+#[cfg(windows)]
+use crate::verbatim_path;
+
+/// Moves a save folder to the Recycle Bin through the shell's own recycle support.
+#[cfg(windows)]
+pub fn move_to_trash(path: &Path) -> Result<(), ProgramError> {
+    // Canonical paths are verbatim on Windows, which the Visual Basic file system rejects.
+    let path = verbatim_path::for_display(
+        &fs::canonicalize(path).map_err(|e| ProgramError::FailedToTrash(e.to_string()))?,
+    );
+    // DeleteDirectory refuses files, loose files need DeleteFile.
+    let method = if path.is_dir() {
+        "DeleteDirectory"
+    } else {
+        "DeleteFile"
+    };
+    let script = format!(
+        "Add-Type -AssemblyName Microsoft.VisualBasic; \
+         [Microsoft.VisualBasic.FileIO.FileSystem]::{}('{}', 'OnlyErrorDialogs', 'SendToRecycleBin')",
+        method,
+        path.display().to_string().replace('\'', "''")
+    );
+
+    run_trash_command(Command::new("powershell").args(["-NoProfile", "-Command", &script]))
+}
+
+/// Moves a save folder to the Trash through Finder, which also allows putting it back.
+#[cfg(target_os = "macos")]
+pub fn move_to_trash(path: &Path) -> Result<(), ProgramError> {
+    let path = fs::canonicalize(path).map_err(|e| ProgramError::FailedToTrash(e.to_string()))?;
+    let script = format!(
+        "tell application \"Finder\" to delete POSIX file \"{}\"",
+        path.display()
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    );
+
+    run_trash_command(Command::new("osascript").args(["-e", &script]))
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn run_trash_command(command: &mut Command) -> Result<(), ProgramError> {
+    let output = command
+        .output()
+        .map_err(|e| ProgramError::FailedToTrash(e.to_string()))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(ProgramError::FailedToTrash(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Moves a save folder to the user's trash following the freedesktop.org trash specification.
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn move_to_trash(path: &Path) -> Result<(), ProgramError> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .filter(|data_home| !data_home.is_empty())
+        .map(std::path::PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+        .ok_or(ProgramError::FailedToTrash(
+            "Neither XDG_DATA_HOME nor HOME is set".to_string(),
+        ))?;
+
+    move_to_trash_in(path, &data_home.join("Trash"), SystemTime::now())
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn move_to_trash_in(path: &Path, trash_dir: &Path, now: SystemTime) -> Result<(), ProgramError> {
+    let to_trash_error = |e: std::io::Error| ProgramError::FailedToTrash(e.to_string());
+    let path = fs::canonicalize(path).map_err(to_trash_error)?;
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    fs::create_dir_all(&files_dir)
+        .and_then(|_| fs::create_dir_all(&info_dir))
+        .map_err(to_trash_error)?;
+
+    let folder_name = path
+        .file_name()
+        .ok_or(ProgramError::FailedToTrash(format!(
+            "{} has no folder name",
+            path.display()
+        )))?
+        .to_string_lossy()
+        .to_string();
+    let trash_info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode(&path.to_string_lossy()),
+        timestamp::format_local_iso(now)
+    );
+
+    // Creating the info file first reserves the name, as the specification asks.
+    let (trashed_name, info_path) = (1..)
+        .map(|n| match n {
+            1 => folder_name.clone(),
+            n => format!("{}.{}", folder_name, n),
+        })
+        .map(|name| {
+            let info_path = info_dir.join(format!("{}.trashinfo", name));
+            (name, info_path)
+        })
+        .find(|(name, info_path)| {
+            !files_dir.join(name).exists()
+                && fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(info_path)
+                    .is_ok()
+        })
+        .ok_or(ProgramError::FailedToTrash(
+            "No free name in the trash".to_string(),
+        ))?;
+
+    // A failed rename, e.g. across filesystems, is reported instead of deleting the folder.
+    fs::write(&info_path, trash_info)
+        .and_then(|_| fs::rename(&path, files_dir.join(&trashed_name)))
+        .map_err(|e| {
+            let _ = fs::remove_file(&info_path);
+            to_trash_error(e)
+        })
+}
+
+/// Percent encodes a path for a `.trashinfo` file, keeping `/` and unreserved characters.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(all(test, not(any(windows, target_os = "macos"))))]
+mod move_to_trash_in_should {
+    use std::{
+        fs,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    use crate::{
+        test_utils::TempDir,
+        timestamp,
+        trash::{move_to_trash_in, percent_encode},
+    };
+
+    #[test]
+    fn move_folder_and_write_trash_info() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Some'me-123456789__QuickSave_3");
+        let trash_dir = temp_dir.path().join("Trash");
+        let now = UNIX_EPOCH + Duration::from_secs(1_709_414_045);
+
+        move_to_trash_in(&save_path, &trash_dir, now).unwrap();
+
+        assert!(!save_path.exists());
+        assert_eq!(
+            fs::read(
+                trash_dir
+                    .join("files/Some'me-123456789__QuickSave_3")
+                    .join("test.lsv")
+            )
+            .unwrap(),
+            b"LSPK"
+        );
+        let trash_info =
+            fs::read_to_string(trash_dir.join("info/Some'me-123456789__QuickSave_3.trashinfo"))
+                .unwrap();
+        assert!(trash_info.ends_with(&format!(
+            "Some%27me-123456789__QuickSave_3\nDeletionDate={}\n",
+            timestamp::format_local_iso(now)
+        )));
+    }
+
+    #[test]
+    fn number_name_collisions() {
+        let temp_dir = TempDir::new();
+        let trash_dir = temp_dir.path().join("Trash");
+
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        move_to_trash_in(&save_path, &trash_dir, UNIX_EPOCH).unwrap();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_3");
+        move_to_trash_in(&save_path, &trash_dir, UNIX_EPOCH).unwrap();
+
+        assert!(trash_dir.join("files/Tav-123456789__QuickSave_3").is_dir());
+        assert!(trash_dir
+            .join("files/Tav-123456789__QuickSave_3.2")
+            .is_dir());
+        assert!(trash_dir
+            .join("info/Tav-123456789__QuickSave_3.2.trashinfo")
+            .is_file());
+    }
+
+    #[test]
+    fn error_on_missing_folder() {
+        let temp_dir = TempDir::new();
+
+        let result = move_to_trash_in(
+            &temp_dir.path().join("missing"),
+            &temp_dir.path().join("Trash"),
+            UNIX_EPOCH,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn percent_encode_reserved_characters() {
+        assert_eq!(
+            percent_encode("/home/me/Some'me Else-1__QuickSave_3"),
+            "/home/me/Some%27me%20Else-1__QuickSave_3"
+        );
+    }
+}
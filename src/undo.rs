@@ -0,0 +1,296 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    json::{FromJson, JsonValue, ToJson},
+    program_errors::ProgramError,
+    quarantine,
+};
+
+/// Folder inside the save directory that holds the saves of the last run until it is purged.
+pub const UNDO_DIR_NAME: &str = ".bg3sc_undo";
+const JOURNAL_FILE_NAME: &str = "journal.json";
+
+pub fn undo_dir(save_dir: &Path) -> PathBuf {
+    save_dir.join(UNDO_DIR_NAME)
+}
+
+/// Records where each save of a run was staged, written before anything is moved.
+#[derive(Debug, PartialEq)]
+pub struct Journal {
+    pub run_id: String,
+    pub entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct JournalEntry {
+    pub source: PathBuf,
+    pub staged: PathBuf,
+}
+
+impl Journal {
+    /// Starts a run by writing its journal, the staged saves go to a folder named after the run.
+    pub fn begin(
+        save_dir: &Path,
+        sources: &[PathBuf],
+        now: SystemTime,
+    ) -> Result<Self, ProgramError> {
+        let run_id = format!(
+            "{}-{}",
+            now.duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or(0),
+            std::process::id()
+        );
+        let run_dir = undo_dir(save_dir).join(&run_id);
+        fs::create_dir_all(&run_dir).map_err(|e| {
+            ProgramError::FailedToWriteFile(format!("{}: {}", run_dir.display(), e))
+        })?;
+
+        // Saves from different save folders can share a name, later ones get a numbered suffix.
+        let entries = sources.iter().fold(Vec::new(), |mut entries, source| {
+            let folder_name = source
+                .file_name()
+                .unwrap_or(source.as_os_str())
+                .to_string_lossy()
+                .to_string();
+            let staged = (0..)
+                .map(|n| match n {
+                    0 => run_dir.join(&folder_name),
+                    n => run_dir.join(format!("{} ({})", folder_name, n)),
+                })
+                .find(|staged| {
+                    !entries
+                        .iter()
+                        .any(|entry: &JournalEntry| &entry.staged == staged)
+                })
+                .unwrap_or(run_dir.join(&folder_name));
+            entries.push(JournalEntry {
+                source: source.clone(),
+                staged,
+            });
+            entries
+        });
+        let journal = Journal { entries, run_id };
+        let journal_path = undo_dir(save_dir).join(JOURNAL_FILE_NAME);
+        fs::write(&journal_path, format!("{:#}\n", journal.to_json())).map_err(|e| {
+            ProgramError::FailedToWriteFile(format!("{}: {}", journal_path.display(), e))
+        })?;
+
+        Ok(journal)
+    }
+
+    pub fn read(save_dir: &Path) -> Result<Self, ProgramError> {
+        let journal_path = undo_dir(save_dir).join(JOURNAL_FILE_NAME);
+        if !journal_path.exists() {
+            return Err(ProgramError::NothingToUndo(format!(
+                "No journal found at {}",
+                journal_path.display()
+            )));
+        }
+
+        fs::read_to_string(&journal_path)
+            .map_err(|e| {
+                ProgramError::FailedToReadFile(format!("{}: {}", journal_path.display(), e))
+            })
+            .and_then(|content| {
+                JsonValue::parse(&content)
+                    .and_then(|value| Journal::from_json(&value))
+                    .map_err(|e| {
+                        ProgramError::InvalidJournal(format!("{}: {}", journal_path.display(), e))
+                    })
+            })
+    }
+
+    /// Moves a save listed in the journal into the undo area.
+    pub fn stage(&self, source: &Path) -> Result<(), ProgramError> {
+        self.entries
+            .iter()
+            .find(|entry| entry.source == source)
+            .ok_or(ProgramError::FailedToDelete(format!(
+                "{} is not part of the undo journal",
+                source.display()
+            )))
+            .and_then(|entry| quarantine::move_dir(&entry.source, &entry.staged))
+    }
+
+    /// Moves every staged save back, returning the restored paths and the ones that failed.
+    pub fn undo(&self) -> (Vec<PathBuf>, Vec<(PathBuf, ProgramError)>) {
+        self.entries
+            .iter()
+            // Saves that were never staged, e.g. because the run stopped early, are still in place.
+            .filter(|entry| entry.staged.exists() || !entry.source.exists())
+            .fold(
+                (Vec::new(), Vec::new()),
+                |(mut restored, mut failed), entry| {
+                    let result = if entry.source.exists() {
+                        Err(ProgramError::FailedToRestore(format!(
+                            "{} already exists",
+                            entry.source.display()
+                        )))
+                    } else if !entry.staged.exists() {
+                        Err(ProgramError::FailedToRestore(format!(
+                            "{} is missing from the undo area",
+                            entry.staged.display()
+                        )))
+                    } else {
+                        quarantine::move_dir(&entry.staged, &entry.source)
+                    };
+
+                    match result {
+                        Ok(_) => restored.push(entry.source.clone()),
+                        Err(e) => failed.push((entry.source.clone(), e)),
+                    }
+                    (restored, failed)
+                },
+            )
+    }
+}
+impl ToJson for Journal {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object(vec![
+            ("run_id", JsonValue::String(self.run_id.clone())),
+            (
+                "entries",
+                JsonValue::Array(
+                    self.entries
+                        .iter()
+                        .map(|entry| {
+                            JsonValue::object(vec![
+                                (
+                                    "source",
+                                    JsonValue::String(entry.source.to_string_lossy().to_string()),
+                                ),
+                                (
+                                    "staged",
+                                    JsonValue::String(entry.staged.to_string_lossy().to_string()),
+                                ),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+impl FromJson for Journal {
+    fn from_json(value: &JsonValue) -> Result<Self, String> {
+        let string_field = |value: &JsonValue, key: &str| {
+            value
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+                .ok_or(format!("Missing or invalid field {}", key))
+        };
+
+        Ok(Journal {
+            run_id: string_field(value, "run_id")?,
+            entries: value
+                .get("entries")
+                .and_then(JsonValue::as_array)
+                .ok_or("Missing or invalid field entries".to_string())?
+                .iter()
+                .map(|entry| {
+                    Ok(JournalEntry {
+                        source: PathBuf::from(string_field(entry, "source")?),
+                        staged: PathBuf::from(string_field(entry, "staged")?),
+                    })
+                })
+                .collect::<Result<Vec<JournalEntry>, String>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod journal_should {
+    use std::{fs, time::UNIX_EPOCH};
+
+    use crate::{
+        program_errors::ProgramError,
+        test_utils::TempDir,
+        undo::{undo_dir, Journal},
+    };
+
+    #[test]
+    fn stage_and_undo_saves() {
+        let temp_dir = TempDir::new();
+        let sources = vec![
+            temp_dir.create_save("Tav-123456789__QuickSave_3"),
+            temp_dir.create_save("Tav-123456789__QuickSave_4"),
+        ];
+
+        let journal = Journal::begin(temp_dir.path(), &sources, UNIX_EPOCH).unwrap();
+        journal.stage(&sources[0]).unwrap();
+        assert!(!sources[0].exists());
+
+        let journal = Journal::read(temp_dir.path()).unwrap();
+        let (restored, failed) = journal.undo();
+
+        assert_eq!(restored, vec![sources[0].clone()]);
+        assert!(failed.is_empty());
+        assert_eq!(fs::read(sources[0].join("test.lsv")).unwrap(), b"LSPK");
+        assert!(sources[1].exists());
+    }
+
+    #[test]
+    fn stage_saves_of_the_same_name_side_by_side() {
+        let temp_dir = TempDir::new();
+        let other_dir = TempDir::new();
+        let sources = vec![
+            temp_dir.create_save("Tav-123456789__QuickSave_3"),
+            other_dir.create_save("Tav-123456789__QuickSave_3"),
+        ];
+
+        let journal = Journal::begin(temp_dir.path(), &sources, UNIX_EPOCH).unwrap();
+        sources
+            .iter()
+            .for_each(|source| journal.stage(source).unwrap());
+        assert_ne!(journal.entries[0].staged, journal.entries[1].staged);
+
+        let (restored, failed) = journal.undo();
+        assert_eq!(restored, sources);
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn report_saves_that_cannot_be_restored() {
+        let temp_dir = TempDir::new();
+        let sources = vec![temp_dir.create_save("Tav-123456789__QuickSave_3")];
+        let journal = Journal::begin(temp_dir.path(), &sources, UNIX_EPOCH).unwrap();
+        journal.stage(&sources[0]).unwrap();
+        temp_dir.create_save("Tav-123456789__QuickSave_3");
+
+        let (restored, failed) = journal.undo();
+
+        assert!(restored.is_empty());
+        assert!(matches!(
+            failed.as_slice(),
+            [(_, ProgramError::FailedToRestore(_))]
+        ));
+    }
+
+    #[test]
+    fn error_without_journal() {
+        let temp_dir = TempDir::new();
+
+        let result = Journal::read(temp_dir.path());
+        assert!(matches!(result, Err(ProgramError::NothingToUndo(_))));
+    }
+
+    #[test]
+    fn error_on_corrupt_journal() {
+        let temp_dir = TempDir::new();
+        fs::create_dir(undo_dir(temp_dir.path())).unwrap();
+        fs::write(
+            undo_dir(temp_dir.path()).join("journal.json"),
+            "{\"run_id\":",
+        )
+        .unwrap();
+
+        let result = Journal::read(temp_dir.path());
+        assert!(matches!(result, Err(ProgramError::InvalidJournal(_))));
+    }
+}
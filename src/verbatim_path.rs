@@ -0,0 +1,219 @@
+use std::{
+    env,
+    ffi::OsString,
+    path::{Component, Path, PathBuf, Prefix},
+};
+
+/// Save folders at paths longer than this are removed through a verbatim path, leaving room
+/// below the 260 characters of `MAX_PATH` for the files and mod folders inside them.
+const LONG_PATH: usize = 200;
+
+/// Windows strips trailing spaces and dots from path components unless the path is verbatim,
+/// so a folder named `Tav-123456789__QuickSave_3 ` can only be removed as
+/// `\\?\C:\...\Tav-123456789__QuickSave_3 `. Verbatim paths are also exempt from `MAX_PATH`,
+/// which long character names in a deep profile folder can exceed. Other paths and other
+/// platforms are unchanged.
+pub fn for_removal(path: &Path) -> PathBuf {
+    if cfg!(windows) && (needs_verbatim(path) || is_long(path)) {
+        verbatim(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// The path without the verbatim prefix added by `for_removal`, to show to the user.
+pub fn for_display(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(path) => match path.strip_prefix(r"\\?\UNC\") {
+            Some(unc) => PathBuf::from(format!(r"\\{}", unc)),
+            None => PathBuf::from(path.strip_prefix(r"\\?\").unwrap_or(path)),
+        },
+        None => path.to_path_buf(),
+    }
+}
+
+fn needs_verbatim(path: &Path) -> bool {
+    path.components().any(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        name != "." && name != ".." && (name.ends_with(' ') || name.ends_with('.'))
+    })
+}
+
+fn is_long(path: &Path) -> bool {
+    let length = |path: &Path| path.as_os_str().len();
+
+    match env::current_dir() {
+        _ if path.is_absolute() => length(path) > LONG_PATH,
+        Ok(current_dir) => length(&current_dir.join(path)) > LONG_PATH,
+        Err(_) => length(path) > LONG_PATH,
+    }
+}
+
+/// Verbatim paths skip all normalization, so they have to be absolute and without `.` or `..`.
+/// Done by hand as `std::path::absolute` would strip the trailing spaces this is all about.
+fn verbatim(path: &Path) -> PathBuf {
+    let absolute = match env::current_dir() {
+        _ if path.is_absolute() => path.to_path_buf(),
+        Ok(current_dir) => current_dir.join(path),
+        Err(_) => return path.to_path_buf(),
+    };
+    let normalized = absolute
+        .components()
+        .fold(PathBuf::new(), |mut normalized, component| {
+            match component {
+                Component::CurDir => (),
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                component => normalized.push(component),
+            }
+            normalized
+        });
+
+    let mut components = normalized.components();
+    let mut verbatim = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => {
+                let mut verbatim = OsString::from(r"\\?\");
+                verbatim.push(normalized.as_os_str());
+                return PathBuf::from(verbatim);
+            }
+            Prefix::UNC(server, share) => {
+                let mut verbatim = OsString::from(r"\\?\UNC\");
+                verbatim.push(server);
+                verbatim.push(r"\");
+                verbatim.push(share);
+                verbatim
+            }
+            // Already verbatim or a device path, which are never normalized either.
+            _ => return normalized,
+        },
+        _ => return normalized,
+    };
+    components
+        .filter(|component| *component != Component::RootDir)
+        .for_each(|component| {
+            verbatim.push(r"\");
+            verbatim.push(component.as_os_str());
+        });
+
+    PathBuf::from(verbatim)
+}
+
+#[cfg(test)]
+mod for_removal_should {
+    use std::path::Path;
+
+    use crate::verbatim_path::{for_removal, is_long, needs_verbatim, LONG_PATH};
+
+    #[test]
+    fn detect_trailing_spaces_and_dots() {
+        assert!(needs_verbatim(Path::new(
+            "saves/Tav-123456789__QuickSave_3 "
+        )));
+        assert!(needs_verbatim(Path::new(
+            "saves/Tav-123456789__QuickSave_3."
+        )));
+        assert!(!needs_verbatim(Path::new(
+            "./saves/../Tav-123456789__QuickSave_3"
+        )));
+    }
+
+    #[test]
+    fn detect_long_paths() {
+        let root = if cfg!(windows) { r"C:\" } else { "/" };
+        let folder_name = format!("{}-123456789__QuickSave_3", "Tav".repeat(LONG_PATH / 3));
+
+        assert!(is_long(&Path::new(root).join(&folder_name)));
+        assert!(!is_long(
+            &Path::new(root).join("Tav-123456789__QuickSave_3")
+        ));
+    }
+
+    #[test]
+    fn keep_ordinary_paths() {
+        let path = Path::new("saves/Tav-123456789__QuickSave_3");
+
+        assert_eq!(for_removal(path), path);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn keep_trailing_spaces_outside_windows() {
+        let path = Path::new("saves/Tav-123456789__QuickSave_3 ");
+
+        assert_eq!(for_removal(path), path);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn prefix_trailing_spaces_on_windows() {
+        assert_eq!(
+            for_removal(Path::new(r"C:\saves\Tav-123456789__QuickSave_3 ")),
+            Path::new(r"\\?\C:\saves\Tav-123456789__QuickSave_3 ")
+        );
+        assert_eq!(
+            for_removal(Path::new(r"\\server\share\Tav-123456789__QuickSave_3.")),
+            Path::new(r"\\?\UNC\server\share\Tav-123456789__QuickSave_3.")
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn prefix_long_paths_on_windows() {
+        let path = Path::new(r"C:\Users\Tav\AppData\Local\Larian Studios\Baldur's Gate 3\PlayerProfiles\Public\Savegames\Story")
+            .join(format!("{}-123456789__QuickSave_3", "Tav".repeat(LONG_PATH / 3)));
+
+        let verbatim = for_removal(&path);
+
+        assert!(verbatim.to_string_lossy().starts_with(r"\\?\C:\Users"));
+        assert!(verbatim.ends_with(path.file_name().unwrap()));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn remove_folders_beyond_max_path() {
+        use crate::test_utils::TempDir;
+
+        let temp_dir = TempDir::new();
+        let folder = temp_dir
+            .path()
+            .join(format!("{}-123456789__QuickSave_3", "Tav".repeat(60)))
+            .join("a".repeat(100));
+        std::fs::create_dir_all(for_removal(&folder)).unwrap();
+        std::fs::write(for_removal(&folder.join("test.lsv")), b"LSPK").unwrap();
+
+        std::fs::remove_dir_all(for_removal(folder.parent().unwrap())).unwrap();
+
+        assert!(!for_removal(folder.parent().unwrap()).exists());
+    }
+}
+
+#[cfg(test)]
+mod for_display_should {
+    use std::path::Path;
+
+    use crate::verbatim_path::for_display;
+
+    #[test]
+    fn strip_the_verbatim_prefix() {
+        assert_eq!(
+            for_display(Path::new(r"\\?\C:\saves\Tav-123456789__QuickSave_3 ")),
+            Path::new(r"C:\saves\Tav-123456789__QuickSave_3 ")
+        );
+        assert_eq!(
+            for_display(Path::new(
+                r"\\?\UNC\server\share\Tav-123456789__QuickSave_3"
+            )),
+            Path::new(r"\\server\share\Tav-123456789__QuickSave_3")
+        );
+    }
+
+    #[test]
+    fn keep_other_paths() {
+        assert_eq!(
+            for_display(Path::new("saves/Tav-123456789__QuickSave_3")),
+            Path::new("saves/Tav-123456789__QuickSave_3")
+        );
+    }
+}
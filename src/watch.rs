@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    interrupt,
+    output::info,
+    program_errors::ProgramError,
+    run_summary::{FreedSpace, RunSummary},
+};
+
+/// Saves modified more recently than this may still be written by the game and are never
+/// deleted while watching.
+pub const SETTLE_TIME: Duration = Duration::from_secs(2 * 60);
+/// Without `--interval` a clean starts once the save folder stopped changing for this long.
+const DEBOUNCE: Duration = Duration::from_secs(30);
+/// How often the save folder is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The name and modified time of every entry in the watched folders, with the newest modified
+/// time of the files directly inside it. Rewriting a quick save in place leaves the folder's own
+/// time alone, its files show the change without walking or measuring the whole tree. Comparing
+/// two of them stands in for filesystem notifications, which std has no portable way to receive.
+#[derive(Debug, PartialEq, Default)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>, Option<SystemTime>)>);
+impl Snapshot {
+    pub fn take(directories: &[PathBuf]) -> Self {
+        let mut entries: Vec<(PathBuf, Option<SystemTime>, Option<SystemTime>)> = directories
+            .iter()
+            .filter_map(|directory| fs::read_dir(directory).ok())
+            .flatten()
+            .flatten()
+            .map(|dir_entry| {
+                (
+                    dir_entry.path(),
+                    modified(&dir_entry),
+                    fs::read_dir(dir_entry.path())
+                        .ok()
+                        .and_then(|files| files.flatten().filter_map(|file| modified(&file)).max()),
+                )
+            })
+            .collect();
+        entries.sort();
+
+        Snapshot(entries)
+    }
+}
+
+fn modified(dir_entry: &fs::DirEntry) -> Option<SystemTime> {
+    dir_entry
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Tells when a change to the save folder has settled, so a save being written is not
+/// cleaned halfway through.
+pub struct ChangeDetector {
+    snapshot: Snapshot,
+    changed_at: Option<Instant>,
+}
+impl ChangeDetector {
+    pub fn new(snapshot: Snapshot) -> Self {
+        ChangeDetector {
+            snapshot,
+            changed_at: None,
+        }
+    }
+
+    /// Whether the folder changed and then stayed the same for `DEBOUNCE`.
+    pub fn observe(&mut self, snapshot: Snapshot, now: Instant) -> bool {
+        if snapshot != self.snapshot {
+            self.snapshot = snapshot;
+            self.changed_at = Some(now);
+            return false;
+        }
+
+        match self.changed_at {
+            Some(changed_at) if now.duration_since(changed_at) >= DEBOUNCE => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Everything removed since watching started.
+#[derive(Debug, PartialEq, Default)]
+pub struct WatchSession {
+    pub passes: usize,
+    pub deleted: usize,
+    pub failed: usize,
+    pub bytes_removed: u64,
+    pub freed_space: FreedSpace,
+}
+impl WatchSession {
+    pub fn record(&mut self, summary: &RunSummary) {
+        self.passes += 1;
+        self.deleted += summary.deleted;
+        self.failed += summary.failed;
+        self.bytes_removed += summary.bytes_removed;
+        self.freed_space = summary.freed_space;
+    }
+
+    pub fn format(&self) -> String {
+        format!(
+            "Stopped watching after {} clean(s), deleted {}, failed {}, {}",
+            self.passes,
+            self.deleted,
+            self.failed,
+            self.freed_space.format(self.bytes_removed, false)
+        )
+    }
+}
+
+/// Cleans right away and again every `interval`, or whenever the folders settle after a
+/// change, until Ctrl+C is pressed. Only the first clean stops watching when it fails.
+pub fn watch(
+    directories: &[PathBuf],
+    interval: Option<Duration>,
+    mut clean: impl FnMut() -> Result<RunSummary, ProgramError>,
+) -> Result<WatchSession, ProgramError> {
+    interrupt::install();
+
+    let mut session = WatchSession::default();
+    session.record(&clean()?);
+
+    let mut change_detector = ChangeDetector::new(Snapshot::take(directories));
+    let mut last_clean = Instant::now();
+    info!("Watching for new saves, press Ctrl+C to stop");
+
+    while !interrupt::sleep(POLL_INTERVAL) {
+        let now = Instant::now();
+        let due = match interval {
+            Some(interval) => now.duration_since(last_clean) >= interval,
+            None => change_detector.observe(Snapshot::take(directories), now),
+        };
+        if !due {
+            continue;
+        }
+
+        match clean() {
+            Ok(summary) => session.record(&summary),
+            Err(e) => eprintln!("Clean failed, still watching: {}", e),
+        }
+        // The clean itself changes the folder, that is not a reason to clean again.
+        change_detector = ChangeDetector::new(Snapshot::take(directories));
+        last_clean = Instant::now();
+    }
+
+    Ok(session)
+}
+
+#[cfg(test)]
+mod snapshot_should {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        test_utils::{set_modified, TempDir},
+        watch::Snapshot,
+    };
+
+    #[test]
+    fn notice_a_save_rewritten_in_place() {
+        let temp_dir = TempDir::new();
+        let save = temp_dir.path().join("Tav-123456789__QuickSave_1");
+        fs::create_dir(&save).unwrap();
+        fs::write(save.join("QuickSave_1.lsv"), "").unwrap();
+        let folder_time = SystemTime::now() - Duration::from_secs(600);
+        set_modified(&save.join("QuickSave_1.lsv"), folder_time);
+        set_modified(&save, folder_time);
+        let directories = [temp_dir.path().to_path_buf()];
+
+        let before = Snapshot::take(&directories);
+        assert_eq!(Snapshot::take(&directories), before);
+
+        set_modified(&save.join("QuickSave_1.lsv"), SystemTime::now());
+        set_modified(&save, folder_time);
+
+        assert_ne!(Snapshot::take(&directories), before);
+    }
+}
+
+#[cfg(test)]
+mod change_detector_should {
+    use std::{
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    use crate::watch::{ChangeDetector, Snapshot, DEBOUNCE};
+
+    fn snapshot(names: &[&str]) -> Snapshot {
+        Snapshot(
+            names
+                .iter()
+                .map(|name| (PathBuf::from(name), None, None))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn wait_until_changes_settle() {
+        let start = Instant::now();
+        let mut change_detector = ChangeDetector::new(snapshot(&["Save_1"]));
+
+        assert!(!change_detector.observe(snapshot(&["Save_1"]), start));
+        assert!(!change_detector.observe(snapshot(&["Save_1", "Save_2"]), start));
+        // Still being written, the debounce starts over.
+        let later = start + DEBOUNCE / 2;
+        assert!(!change_detector.observe(snapshot(&["Save_1", "Save_2", "Save_3"]), later));
+        assert!(!change_detector.observe(
+            snapshot(&["Save_1", "Save_2", "Save_3"]),
+            later + DEBOUNCE - Duration::from_secs(1)
+        ));
+        assert!(
+            change_detector.observe(snapshot(&["Save_1", "Save_2", "Save_3"]), later + DEBOUNCE)
+        );
+        // Only once per change.
+        assert!(!change_detector.observe(
+            snapshot(&["Save_1", "Save_2", "Save_3"]),
+            later + DEBOUNCE * 2
+        ));
+    }
+}
+
+#[cfg(test)]
+mod watch_session_should {
+    use crate::{
+        run_summary::{FreedSpace, RunSummary},
+        watch::WatchSession,
+    };
+
+    #[test]
+    fn add_up_every_clean() {
+        let mut session = WatchSession::default();
+        [3, 0, 2].iter().for_each(|deleted| {
+            session.record(&RunSummary {
+                scanned: 20,
+                kept: 20 - deleted,
+                deleted: *deleted,
+                failed: 0,
+                bytes_removed: *deleted as u64 * 1024,
+                dry_run: false,
+                freed_space: FreedSpace::Trashed,
+            })
+        });
+
+        assert_eq!(
+            session.format(),
+            "Stopped watching after 3 clean(s), deleted 5, failed 0, moved 5.0 KiB to the recycle bin"
+        );
+    }
+}
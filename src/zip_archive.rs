@@ -0,0 +1,227 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::program_errors::ProgramError;
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const VERSION: u16 = 20;
+// Bit 11 marks file names as UTF-8.
+const UTF8_FLAG: u16 = 0x0800;
+const STORED: u16 = 0;
+// 1980-01-01 00:00, the earliest date a zip file can hold.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Writes every file below `folder` into an uncompressed zip archive at `archive_path`.
+/// The archive is written next to its destination first, so a failed backup never leaves a
+/// truncated archive behind under the final name.
+pub fn write_zip(folder: &Path, archive_path: &Path) -> Result<(), ProgramError> {
+    let files = collect_files(folder, folder)?;
+    let mut archive = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (name, path) in &files {
+        let data = fs::read(path)
+            .map_err(|e| ProgramError::FailedToReadFile(format!("{}: {}", path.display(), e)))?;
+        let size = u32::try_from(data.len()).map_err(|_| {
+            ProgramError::FailedToWriteFile(format!("{} is too large to archive", path.display()))
+        })?;
+        let offset = u32::try_from(archive.len()).map_err(|_| {
+            ProgramError::FailedToWriteFile(format!("{} is too large to archive", folder.display()))
+        })?;
+        let crc = crc32(&data);
+
+        push_u32(&mut archive, LOCAL_FILE_HEADER_SIGNATURE);
+        push_entry_fields(&mut archive, name, crc, size);
+        push_u16(&mut archive, 0);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(&data);
+
+        push_u32(&mut central_directory, CENTRAL_DIRECTORY_SIGNATURE);
+        push_u16(&mut central_directory, VERSION);
+        push_entry_fields(&mut central_directory, name, crc, size);
+        push_u16(&mut central_directory, 0); // extra field length
+        push_u16(&mut central_directory, 0); // comment length
+        push_u16(&mut central_directory, 0); // disk number
+        push_u16(&mut central_directory, 0); // internal attributes
+        push_u32(&mut central_directory, 0); // external attributes
+        push_u32(&mut central_directory, offset);
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let entries = u16::try_from(files.len()).map_err(|_| {
+        ProgramError::FailedToWriteFile(format!("{} has too many files", folder.display()))
+    })?;
+    let central_directory_offset = archive.len() as u32;
+    let central_directory_size = central_directory.len() as u32;
+    archive.extend_from_slice(&central_directory);
+    push_u32(&mut archive, END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+    push_u16(&mut archive, 0); // disk number
+    push_u16(&mut archive, 0); // disk with the central directory
+    push_u16(&mut archive, entries);
+    push_u16(&mut archive, entries);
+    push_u32(&mut archive, central_directory_size);
+    push_u32(&mut archive, central_directory_offset);
+    push_u16(&mut archive, 0); // comment length
+
+    let mut partial_path = archive_path.as_os_str().to_owned();
+    partial_path.push(".partial");
+    fs::write(&partial_path, archive)
+        .and_then(|_| fs::rename(&partial_path, archive_path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&partial_path);
+            ProgramError::FailedToWriteFile(format!("{}: {}", archive_path.display(), e))
+        })
+}
+
+/// The fields shared by local file headers and central directory entries, up to the name length.
+fn push_entry_fields(buffer: &mut Vec<u8>, name: &str, crc: u32, size: u32) {
+    push_u16(buffer, VERSION);
+    push_u16(buffer, UTF8_FLAG);
+    push_u16(buffer, STORED);
+    push_u16(buffer, DOS_TIME);
+    push_u16(buffer, DOS_DATE);
+    push_u32(buffer, crc);
+    push_u32(buffer, size); // compressed size
+    push_u32(buffer, size); // uncompressed size
+    push_u16(buffer, name.len() as u16);
+}
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Lists the files below `folder` with their `/` separated names relative to `root`.
+pub fn collect_files(root: &Path, folder: &Path) -> Result<Vec<(String, PathBuf)>, ProgramError> {
+    let mut entries = fs::read_dir(folder)
+        .map_err(|e| ProgramError::FailedToReadDir(format!("{}: {}", folder.display(), e)))?
+        .collect::<Result<Vec<fs::DirEntry>, std::io::Error>>()
+        .map_err(|e| ProgramError::FailedToReadDir(format!("{}: {}", folder.display(), e)))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    entries
+        .into_iter()
+        .try_fold(Vec::new(), |mut files, entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                files.extend(collect_files(root, &path)?);
+            } else {
+                let name = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((name, path));
+            }
+
+            Ok(files)
+        })
+}
+
+/// CRC-32 as used by zip, bitwise since archives are only written once per deleted save.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(0xFFFF_FFFF, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod write_zip_should {
+    use std::{fs, path::Path};
+
+    use crate::{
+        test_utils::TempDir,
+        zip_archive::{crc32, write_zip, CENTRAL_DIRECTORY_SIGNATURE},
+    };
+
+    fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Reads the stored entries back by walking the local file headers.
+    fn read_zip(path: &Path) -> Vec<(String, Vec<u8>)> {
+        let bytes = fs::read(path).unwrap();
+        let mut entries = Vec::new();
+        let mut offset = 0;
+
+        while read_u32(&bytes, offset) != CENTRAL_DIRECTORY_SIGNATURE {
+            let crc = read_u32(&bytes, offset + 14);
+            let size = read_u32(&bytes, offset + 18) as usize;
+            let name_length = read_u16(&bytes, offset + 26) as usize;
+            let extra_length = read_u16(&bytes, offset + 28) as usize;
+            let name_start = offset + 30;
+            let data_start = name_start + name_length + extra_length;
+
+            let name = String::from_utf8(bytes[name_start..name_start + name_length].to_vec());
+            let data = bytes[data_start..data_start + size].to_vec();
+            assert_eq!(crc32(&data), crc);
+            entries.push((name.unwrap(), data));
+            offset = data_start + size;
+        }
+
+        entries
+    }
+
+    #[test]
+    fn round_trip_folder_contents() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+        fs::write(
+            save_path.join("Tav-123456789__QuickSave_1.webp"),
+            b"RIFF1234",
+        )
+        .unwrap();
+        fs::create_dir(save_path.join("nested")).unwrap();
+        fs::write(save_path.join("nested").join("extra.bin"), [0u8, 255, 7]).unwrap();
+        let archive_path = temp_dir.path().join("backup.zip");
+
+        write_zip(&save_path, &archive_path).unwrap();
+
+        assert_eq!(
+            read_zip(&archive_path),
+            vec![
+                (
+                    "Tav-123456789__QuickSave_1.webp".to_string(),
+                    b"RIFF1234".to_vec()
+                ),
+                ("nested/extra.bin".to_string(), vec![0u8, 255, 7]),
+                ("test.lsv".to_string(), b"LSPK".to_vec()),
+            ]
+        );
+        assert!(!temp_dir.path().join("backup.zip.partial").exists());
+    }
+
+    #[test]
+    fn fail_when_destination_is_missing() {
+        let temp_dir = TempDir::new();
+        let save_path = temp_dir.create_save("Tav-123456789__QuickSave_1");
+
+        let result = write_zip(&save_path, &temp_dir.path().join("missing").join("a.zip"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn compute_standard_crc() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
@@ -0,0 +1,704 @@
+use std::io::{self, Read, Write};
+
+/// Magic number at the start of a Zstandard frame.
+const FRAME_MAGIC: u32 = 0xFD2F_B528;
+/// No content size, a window descriptor follows and the frame ends with a content checksum.
+const FRAME_HEADER_DESCRIPTOR: u8 = 0b0000_0100;
+/// A window of 2^(10 + 7) bytes, exactly one block: matches never reach into earlier blocks.
+const WINDOW_DESCRIPTOR: u8 = 7 << 3;
+const BLOCK_SIZE: usize = 128 * 1024;
+const RAW_BLOCK: u32 = 0;
+const COMPRESSED_BLOCK: u32 = 2;
+const LAST_BLOCK: u32 = 1;
+const MIN_MATCH: usize = 4;
+const HASH_LOG: u32 = 15;
+
+/// Baseline and number of extra bits of every literal length code.
+#[rustfmt::skip]
+const LITERAL_LENGTH_CODES: [(u32, u32); 36] = [
+    (0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 0),
+    (8, 0), (9, 0), (10, 0), (11, 0), (12, 0), (13, 0), (14, 0), (15, 0),
+    (16, 1), (18, 1), (20, 1), (22, 1), (24, 2), (28, 2), (32, 3), (40, 3),
+    (48, 4), (64, 6), (128, 7), (256, 8), (512, 9), (1024, 10), (2048, 11), (4096, 12),
+    (8192, 13), (16384, 14), (32768, 15), (65536, 16),
+];
+/// Baseline and number of extra bits of every match length code.
+#[rustfmt::skip]
+const MATCH_LENGTH_CODES: [(u32, u32); 53] = [
+    (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 0), (10, 0),
+    (11, 0), (12, 0), (13, 0), (14, 0), (15, 0), (16, 0), (17, 0), (18, 0),
+    (19, 0), (20, 0), (21, 0), (22, 0), (23, 0), (24, 0), (25, 0), (26, 0),
+    (27, 0), (28, 0), (29, 0), (30, 0), (31, 0), (32, 0), (33, 0), (34, 0),
+    (35, 1), (37, 1), (39, 1), (41, 1), (43, 2), (47, 2), (51, 3), (59, 3),
+    (67, 4), (83, 4), (99, 5), (131, 7), (259, 8), (515, 9), (1027, 10), (2051, 11),
+    (4099, 12), (8195, 13), (16387, 14), (32771, 15), (65539, 16),
+];
+
+/// The predefined distributions every Zstandard decoder knows, so no tables are stored.
+const LITERAL_LENGTH_DISTRIBUTION: [i16; 36] = [
+    4, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 2, 1, 1, 1, 1, 1,
+    -1, -1, -1, -1,
+];
+const MATCH_LENGTH_DISTRIBUTION: [i16; 53] = [
+    1, 4, 3, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1, -1, -1,
+];
+const OFFSET_DISTRIBUTION: [i16; 29] = [
+    1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, -1, -1, -1, -1, -1,
+];
+const LITERAL_LENGTH_ACCURACY: u32 = 6;
+const MATCH_LENGTH_ACCURACY: u32 = 6;
+const OFFSET_ACCURACY: u32 = 5;
+
+/// Compresses everything written to it into a single Zstandard frame, one block at a time.
+/// Blocks hold raw literals and sequences coded with the predefined tables, which is plenty
+/// for save folders and keeps both directions small enough to live here.
+pub struct Encoder<W: Write> {
+    output: W,
+    block: Vec<u8>,
+    checksum: Xxh64,
+}
+impl<W: Write> Encoder<W> {
+    pub fn new(mut output: W) -> io::Result<Self> {
+        output.write_all(&FRAME_MAGIC.to_le_bytes())?;
+        output.write_all(&[FRAME_HEADER_DESCRIPTOR, WINDOW_DESCRIPTOR])?;
+        Ok(Encoder {
+            output,
+            block: Vec::with_capacity(BLOCK_SIZE),
+            checksum: Xxh64::new(),
+        })
+    }
+
+    /// Writes the last block and the content checksum, returning the output.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_block(true)?;
+        let checksum = self.checksum.finish() as u32;
+        self.output.write_all(&checksum.to_le_bytes())?;
+        Ok(self.output)
+    }
+
+    fn write_block(&mut self, last: bool) -> io::Result<()> {
+        self.checksum.update(&self.block);
+        let compressed = compress_block(&self.block);
+        let (block_type, content) = match compressed {
+            Some(ref compressed) if compressed.len() < self.block.len() => {
+                (COMPRESSED_BLOCK, compressed.as_slice())
+            }
+            _ => (RAW_BLOCK, self.block.as_slice()),
+        };
+        let header = (content.len() as u32) << 3 | block_type << 1 | (u32::from(last) * LAST_BLOCK);
+        self.output.write_all(&header.to_le_bytes()[..3])?;
+        self.output.write_all(content)?;
+        self.block.clear();
+        Ok(())
+    }
+}
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let taken = buf.len().min(BLOCK_SIZE - self.block.len());
+        self.block.extend_from_slice(&buf[..taken]);
+        if self.block.len() == BLOCK_SIZE {
+            self.write_block(false)?;
+        }
+        Ok(taken)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+struct Sequence {
+    literal_length: u32,
+    offset: u32,
+    match_length: u32,
+}
+
+/// Literals section and sequences section of a compressed block, `None` without any match.
+fn compress_block(block: &[u8]) -> Option<Vec<u8>> {
+    let (literals, sequences) = find_matches(block);
+    if sequences.is_empty() {
+        return None;
+    }
+
+    // Raw literals with the 20 bit size.
+    let mut compressed = vec![
+        0b1100 | ((literals.len() & 0xF) << 4) as u8,
+        (literals.len() >> 4) as u8,
+        (literals.len() >> 12) as u8,
+    ];
+    compressed.extend_from_slice(&literals);
+
+    match sequences.len() {
+        count if count < 128 => compressed.push(count as u8),
+        count if count < 0x7F00 => {
+            compressed.extend_from_slice(&[(count >> 8) as u8 + 0x80, count as u8])
+        }
+        count => {
+            compressed.push(0xFF);
+            compressed.extend_from_slice(&((count - 0x7F00) as u16).to_le_bytes());
+        }
+    }
+    // Every symbol uses its predefined table.
+    compressed.push(0);
+    compressed.extend_from_slice(&encode_sequences(&sequences));
+    Some(compressed)
+}
+
+/// Greedy matching against the last position with the same four bytes.
+fn find_matches(block: &[u8]) -> (Vec<u8>, Vec<Sequence>) {
+    let mut last_seen = vec![usize::MAX; 1 << HASH_LOG];
+    let mut literals = Vec::new();
+    let mut sequences = Vec::new();
+    let mut anchor = 0;
+    let mut position = 0;
+
+    while position + MIN_MATCH <= block.len() {
+        let bytes = &block[position..position + MIN_MATCH];
+        let hash = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            .wrapping_mul(2_654_435_761)
+            >> (32 - HASH_LOG);
+        let candidate = std::mem::replace(&mut last_seen[hash as usize], position);
+        if candidate == usize::MAX || block[candidate..candidate + MIN_MATCH] != *bytes {
+            position += 1;
+            continue;
+        }
+
+        let length = MIN_MATCH
+            + block[position + MIN_MATCH..]
+                .iter()
+                .zip(&block[candidate + MIN_MATCH..])
+                .take_while(|(byte, earlier)| byte == earlier)
+                .count();
+        literals.extend_from_slice(&block[anchor..position]);
+        sequences.push(Sequence {
+            literal_length: (position - anchor) as u32,
+            offset: (position - candidate) as u32,
+            match_length: length as u32,
+        });
+        position += length;
+        anchor = position;
+    }
+    literals.extend_from_slice(&block[anchor..]);
+
+    (literals, sequences)
+}
+
+/// The code of `value` and its extra bits.
+fn length_code(codes: &[(u32, u32)], value: u32) -> (u8, u32, u32) {
+    let code = codes
+        .iter()
+        .rposition(|(baseline, _)| *baseline <= value)
+        .unwrap_or(0);
+    let (baseline, bits) = codes[code];
+    (code as u8, value - baseline, bits)
+}
+
+/// Offsets are stored plus three, the values below are repeat offsets which are never used.
+fn offset_code(offset: u32) -> (u8, u32, u32) {
+    let value = offset + 3;
+    let code = 31 - value.leading_zeros();
+    (code as u8, value - (1 << code), code)
+}
+
+/// The sequences bitstream, written backwards so the decoder reads the first sequence first.
+fn encode_sequences(sequences: &[Sequence]) -> Vec<u8> {
+    let literal_length_table = FseTable::new(&LITERAL_LENGTH_DISTRIBUTION, LITERAL_LENGTH_ACCURACY);
+    let match_length_table = FseTable::new(&MATCH_LENGTH_DISTRIBUTION, MATCH_LENGTH_ACCURACY);
+    let offset_table = FseTable::new(&OFFSET_DISTRIBUTION, OFFSET_ACCURACY);
+
+    let codes: Vec<[(u8, u32, u32); 3]> = sequences
+        .iter()
+        .map(|sequence| {
+            [
+                length_code(&LITERAL_LENGTH_CODES, sequence.literal_length),
+                offset_code(sequence.offset),
+                length_code(&MATCH_LENGTH_CODES, sequence.match_length),
+            ]
+        })
+        .collect();
+    let states = |table: &FseTable, stream: usize| {
+        table.states(&codes.iter().map(|code| code[stream].0).collect::<Vec<u8>>())
+    };
+    let literal_length_states = states(&literal_length_table, 0);
+    let offset_states = states(&offset_table, 1);
+    let match_length_states = states(&match_length_table, 2);
+
+    let mut writer = BitWriter::default();
+    for (index, [literal_length, offset, match_length]) in codes.iter().enumerate().rev() {
+        if index + 1 < codes.len() {
+            writer.add_transition(&offset_table, &offset_states, index);
+            writer.add_transition(&match_length_table, &match_length_states, index);
+            writer.add_transition(&literal_length_table, &literal_length_states, index);
+        }
+        writer.add(literal_length.1, literal_length.2);
+        writer.add(match_length.1, match_length.2);
+        writer.add(offset.1, offset.2);
+    }
+    writer.add(u32::from(match_length_states[0]), MATCH_LENGTH_ACCURACY);
+    writer.add(u32::from(offset_states[0]), OFFSET_ACCURACY);
+    writer.add(u32::from(literal_length_states[0]), LITERAL_LENGTH_ACCURACY);
+    writer.finish()
+}
+
+/// Decoding table of a finite state entropy distribution, built as the format prescribes.
+struct FseTable {
+    symbols: Vec<u8>,
+    bits: Vec<u32>,
+    baselines: Vec<u32>,
+}
+impl FseTable {
+    fn new(distribution: &[i16], accuracy_log: u32) -> Self {
+        let size = 1usize << accuracy_log;
+        let mut symbols = vec![0u8; size];
+
+        // Symbols below probability one take a cell each from the end.
+        let mut high_threshold = size;
+        for (symbol, probability) in distribution.iter().enumerate() {
+            if *probability == -1 {
+                high_threshold -= 1;
+                symbols[high_threshold] = symbol as u8;
+            }
+        }
+        let step = (size >> 1) + (size >> 3) + 3;
+        let mut position = 0;
+        for (symbol, probability) in distribution.iter().enumerate() {
+            for _ in 0..(*probability).max(0) {
+                symbols[position] = symbol as u8;
+                position = (position + step) & (size - 1);
+                while position >= high_threshold {
+                    position = (position + step) & (size - 1);
+                }
+            }
+        }
+
+        let mut next: Vec<u32> = distribution
+            .iter()
+            .map(|probability| (*probability).max(1) as u32)
+            .collect();
+        let (bits, baselines) = symbols
+            .iter()
+            .map(|symbol| {
+                let state = next[*symbol as usize];
+                next[*symbol as usize] += 1;
+                let bits = accuracy_log - (31 - state.leading_zeros());
+                (bits, (state << bits) - size as u32)
+            })
+            .unzip();
+
+        FseTable {
+            symbols,
+            bits,
+            baselines,
+        }
+    }
+
+    /// The state to decode each symbol from, chosen backwards so that every state leads to the
+    /// next one.
+    fn states(&self, symbols: &[u8]) -> Vec<u16> {
+        let leads_to = |symbol: u8, next_state: usize| {
+            (0..self.symbols.len())
+                .find(|state| {
+                    let baseline = self.baselines[*state] as usize;
+                    self.symbols[*state] == symbol
+                        && (baseline..baseline + (1 << self.bits[*state])).contains(&next_state)
+                })
+                .unwrap_or(0) as u16
+        };
+
+        let mut states = vec![0u16; symbols.len()];
+        for index in (0..symbols.len()).rev() {
+            let next_state = states.get(index + 1).map_or(0, |state| *state as usize);
+            states[index] = leads_to(symbols[index], next_state);
+        }
+        states
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    container: u64,
+    count: u32,
+}
+impl BitWriter {
+    fn add(&mut self, value: u32, bits: u32) {
+        self.container |= u64::from(value) << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.container as u8);
+            self.container >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// The bits that take the decoder from the state of sequence `index` to the next one.
+    fn add_transition(&mut self, table: &FseTable, states: &[u16], index: usize) {
+        let state = states[index] as usize;
+        self.add(
+            u32::from(states[index + 1]) - table.baselines[state],
+            table.bits[state],
+        );
+    }
+
+    /// Marks where the stream ends with a set bit and pads the last byte.
+    fn finish(mut self) -> Vec<u8> {
+        self.add(1, 1);
+        if self.count > 0 {
+            self.bytes.push(self.container as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Reads a bitstream backwards from its end marker.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        let last = bytes
+            .last()
+            .filter(|last| **last != 0)
+            .ok_or("Zstandard bitstream has no end marker")?;
+        Ok(BitReader {
+            bytes,
+            position: (bytes.len() - 1) * 8 + (7 - last.leading_zeros() as usize),
+        })
+    }
+
+    fn read(&mut self, bits: u32) -> Result<u32, String> {
+        self.position = self
+            .position
+            .checked_sub(bits as usize)
+            .ok_or("Zstandard bitstream is truncated")?;
+        Ok((0..bits as usize).fold(0, |value, bit| {
+            let at = self.position + bit;
+            value | u32::from(self.bytes[at / 8] >> (at % 8) & 1) << bit
+        }))
+    }
+}
+
+/// Decompresses one frame written by `Encoder` from `input` into `output`, checking its content
+/// checksum. Frames from other encoders may use features this does not read.
+pub fn decompress_frame(input: &mut impl Read, output: &mut impl Write) -> Result<(), String> {
+    let mut header = [0u8; 6];
+    read_exact(input, &mut header)?;
+    if u32::from_le_bytes([header[0], header[1], header[2], header[3]]) != FRAME_MAGIC {
+        return Err("Not a Zstandard frame".to_string());
+    }
+    if header[4..] != [FRAME_HEADER_DESCRIPTOR, WINDOW_DESCRIPTOR] {
+        return Err("Zstandard frame was not written by bg3sc".to_string());
+    }
+
+    let mut checksum = Xxh64::new();
+    let mut block = Vec::new();
+    loop {
+        let mut block_header = [0u8; 4];
+        read_exact(input, &mut block_header[..3])?;
+        let block_header = u32::from_le_bytes(block_header);
+        let mut content = vec![0u8; (block_header >> 3) as usize];
+        if content.len() > BLOCK_SIZE {
+            return Err("Zstandard block is too large".to_string());
+        }
+        read_exact(input, &mut content)?;
+
+        block.clear();
+        match block_header >> 1 & 0b11 {
+            RAW_BLOCK => block.extend_from_slice(&content),
+            COMPRESSED_BLOCK => decompress_block(&content, &mut block)?,
+            _ => return Err("Zstandard block type is not supported".to_string()),
+        }
+        checksum.update(&block);
+        output.write_all(&block).map_err(|e| e.to_string())?;
+
+        if block_header & LAST_BLOCK != 0 {
+            break;
+        }
+    }
+
+    let mut expected = [0u8; 4];
+    read_exact(input, &mut expected)?;
+    if u32::from_le_bytes(expected) != checksum.finish() as u32 {
+        return Err("Zstandard frame does not match its checksum".to_string());
+    }
+    Ok(())
+}
+
+fn read_exact(input: &mut impl Read, buffer: &mut [u8]) -> Result<(), String> {
+    input.read_exact(buffer).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => "Zstandard frame is truncated".to_string(),
+        _ => e.to_string(),
+    })
+}
+
+fn decompress_block(content: &[u8], output: &mut Vec<u8>) -> Result<(), String> {
+    let truncated = || "Zstandard block is truncated".to_string();
+    let byte = |at: usize| {
+        content
+            .get(at)
+            .map(|byte| *byte as usize)
+            .ok_or_else(truncated)
+    };
+
+    if byte(0)? & 0b11 != 0 {
+        return Err("Zstandard literals are compressed, which is not supported".to_string());
+    }
+    let (literals_start, literals_length) = match byte(0)? >> 2 & 0b11 {
+        0b01 => (2, (byte(0)? >> 4) + (byte(1)? << 4)),
+        0b11 => (3, (byte(0)? >> 4) + (byte(1)? << 4) + (byte(2)? << 12)),
+        _ => (1, byte(0)? >> 3),
+    };
+    let literals = content
+        .get(literals_start..literals_start + literals_length)
+        .ok_or_else(truncated)?;
+
+    let mut position = literals_start + literals_length;
+    let count = match byte(position)? {
+        0 => 0,
+        count if count < 128 => count,
+        255 => byte(position + 1)? + (byte(position + 2)? << 8) + 0x7F00,
+        count => ((count - 0x80) << 8) + byte(position + 1)?,
+    };
+    position += match count {
+        0 => 1,
+        count if count < 128 => 1,
+        count if count < 0x7F00 => 2,
+        _ => 3,
+    };
+
+    let mut literals = literals.iter();
+    if count > 0 {
+        if byte(position)? != 0 {
+            return Err(
+                "Zstandard sequences use stored tables, which is not supported".to_string(),
+            );
+        }
+        decode_sequences(&content[position + 1..], count, &mut literals, output)?;
+    }
+    output.extend(literals);
+    Ok(())
+}
+
+fn decode_sequences(
+    bitstream: &[u8],
+    count: usize,
+    literals: &mut std::slice::Iter<u8>,
+    output: &mut Vec<u8>,
+) -> Result<(), String> {
+    let literal_length_table = FseTable::new(&LITERAL_LENGTH_DISTRIBUTION, LITERAL_LENGTH_ACCURACY);
+    let match_length_table = FseTable::new(&MATCH_LENGTH_DISTRIBUTION, MATCH_LENGTH_ACCURACY);
+    let offset_table = FseTable::new(&OFFSET_DISTRIBUTION, OFFSET_ACCURACY);
+
+    let mut reader = BitReader::new(bitstream)?;
+    let mut literal_length_state = reader.read(LITERAL_LENGTH_ACCURACY)? as usize;
+    let mut offset_state = reader.read(OFFSET_ACCURACY)? as usize;
+    let mut match_length_state = reader.read(MATCH_LENGTH_ACCURACY)? as usize;
+
+    for index in 0..count {
+        let offset_code = u32::from(offset_table.symbols[offset_state]);
+        let offset = (1 << offset_code) + reader.read(offset_code)?;
+        let (baseline, bits) =
+            MATCH_LENGTH_CODES[match_length_table.symbols[match_length_state] as usize];
+        let match_length = baseline + reader.read(bits)?;
+        let (baseline, bits) =
+            LITERAL_LENGTH_CODES[literal_length_table.symbols[literal_length_state] as usize];
+        let literal_length = baseline + reader.read(bits)?;
+
+        if index + 1 < count {
+            let next_state = |table: &FseTable, state: usize, reader: &mut BitReader| {
+                Ok::<usize, String>(
+                    (table.baselines[state] + reader.read(table.bits[state])?) as usize,
+                )
+            };
+            literal_length_state =
+                next_state(&literal_length_table, literal_length_state, &mut reader)?;
+            match_length_state = next_state(&match_length_table, match_length_state, &mut reader)?;
+            offset_state = next_state(&offset_table, offset_state, &mut reader)?;
+        }
+
+        if literals.len() < literal_length as usize {
+            return Err("Zstandard sequence uses more literals than the block has".to_string());
+        }
+        output.extend(literals.by_ref().take(literal_length as usize));
+        let offset = (offset as usize)
+            .checked_sub(3)
+            .filter(|offset| (1..=output.len()).contains(offset))
+            .ok_or("Zstandard sequence has an offset that is not supported")?;
+        for _ in 0..match_length {
+            output.push(output[output.len() - offset]);
+        }
+    }
+
+    if reader.position != 0 {
+        return Err("Zstandard bitstream has bits left over".to_string());
+    }
+    Ok(())
+}
+
+const PRIME_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// XXH64 with seed zero, the content checksum of Zstandard frames.
+struct Xxh64 {
+    accumulators: [u64; 4],
+    buffer: Vec<u8>,
+    length: u64,
+}
+impl Xxh64 {
+    fn new() -> Self {
+        Xxh64 {
+            accumulators: [
+                PRIME_1.wrapping_add(PRIME_2),
+                PRIME_2,
+                0,
+                0u64.wrapping_sub(PRIME_1),
+            ],
+            buffer: Vec::with_capacity(32),
+            length: 0,
+        }
+    }
+
+    fn round(accumulator: u64, lane: u64) -> u64 {
+        accumulator
+            .wrapping_add(lane.wrapping_mul(PRIME_2))
+            .rotate_left(31)
+            .wrapping_mul(PRIME_1)
+    }
+
+    fn lane(bytes: &[u8]) -> u64 {
+        u64::from_le_bytes(bytes[..8].try_into().unwrap_or_default())
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        while !data.is_empty() {
+            let taken = data.len().min(32 - self.buffer.len());
+            self.buffer.extend_from_slice(&data[..taken]);
+            data = &data[taken..];
+            if self.buffer.len() == 32 {
+                for (index, accumulator) in self.accumulators.iter_mut().enumerate() {
+                    *accumulator = Self::round(*accumulator, Self::lane(&self.buffer[index * 8..]));
+                }
+                self.buffer.clear();
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hash = if self.length >= 32 {
+            let [first, second, third, fourth] = self.accumulators;
+            let hash = first
+                .rotate_left(1)
+                .wrapping_add(second.rotate_left(7))
+                .wrapping_add(third.rotate_left(12))
+                .wrapping_add(fourth.rotate_left(18));
+            self.accumulators.iter().fold(hash, |hash, accumulator| {
+                (hash ^ Self::round(0, *accumulator))
+                    .wrapping_mul(PRIME_1)
+                    .wrapping_add(PRIME_4)
+            })
+        } else {
+            PRIME_5
+        }
+        .wrapping_add(self.length);
+
+        let mut rest = self.buffer.as_slice();
+        while rest.len() >= 8 {
+            hash = (hash ^ Self::round(0, Self::lane(rest)))
+                .rotate_left(27)
+                .wrapping_mul(PRIME_1)
+                .wrapping_add(PRIME_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            let word = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+            hash = (hash ^ u64::from(word).wrapping_mul(PRIME_1))
+                .rotate_left(23)
+                .wrapping_mul(PRIME_2)
+                .wrapping_add(PRIME_3);
+            rest = &rest[4..];
+        }
+        for byte in rest {
+            hash = (hash ^ u64::from(*byte).wrapping_mul(PRIME_5))
+                .rotate_left(11)
+                .wrapping_mul(PRIME_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(PRIME_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(PRIME_3);
+        hash ^ (hash >> 32)
+    }
+}
+
+#[cfg(test)]
+mod encoder_should {
+    use std::io::{Cursor, Write};
+
+    use crate::zstd::{decompress_frame, Encoder, Xxh64};
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn decompress(frame: &[u8]) -> Result<Vec<u8>, String> {
+        let mut output = Vec::new();
+        decompress_frame(&mut Cursor::new(frame), &mut output).map(|_| output)
+    }
+
+    #[test]
+    fn round_trip_repetitive_and_random_data() {
+        let repetitive: Vec<u8> = (0..300_000u32)
+            .flat_map(|index| format!("Tav-123456789__QuickSave_{}\n", index % 97).into_bytes())
+            .collect();
+        let random: Vec<u8> = (0..200_000)
+            .map(|_| rand::random::<u8>())
+            .chain(vec![0u8; 5000])
+            .collect();
+
+        for data in [repetitive, random, vec![], b"LSPK".to_vec()] {
+            assert_eq!(decompress(&compress(&data)).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn shrink_repetitive_data() {
+        let data = vec![0u8; 1024];
+
+        assert!(compress(&data).len() < 32);
+    }
+
+    #[test]
+    fn refuse_corrupted_frames() {
+        let mut frame = compress(b"Tav-123456789__QuickSave_1 Tav-123456789__QuickSave_2");
+        let last = frame.len() - 1;
+        frame[last] ^= 1;
+
+        assert!(decompress(&frame).is_err());
+        assert!(decompress(&frame[..10]).is_err());
+    }
+
+    #[test]
+    fn hash_like_xxh64() {
+        let hash = |data: &[u8]| {
+            let mut hasher = Xxh64::new();
+            hasher.update(data);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(b""), 0xEF46_DB37_51D8_E999);
+        assert_eq!(hash(b"a"), 0xD24E_C4F1_A98C_6E5B);
+        assert_eq!(
+            hash(b"Nobody inspects the spammish repetition"),
+            0xFBCE_A83C_8A37_8BF1
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+use rand::Rng;
+
+/// A save folder under the system temp dir, removed again when dropped.
+pub struct SaveDir {
+    pub path: PathBuf,
+}
+impl SaveDir {
+    pub fn with_quick_saves(count: u64) -> Self {
+        let path = env::temp_dir().join(format!("bg3sc_test_{}", rand::thread_rng().gen::<u64>()));
+        (1..=count).for_each(|n| {
+            let save = path.join(format!("Tav-123456789__QuickSave_{}", n));
+            fs::create_dir_all(&save).unwrap();
+            fs::write(save.join("test.lsv"), b"LSPK").unwrap();
+        });
+
+        SaveDir { path }
+    }
+}
+impl Drop for SaveDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Runs bg3sc against `save_dir` without picking up the user's config file or save folder.
+pub fn bg3sc(save_dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bg3sc"))
+        .args(args)
+        .arg("-p")
+        .arg(save_dir)
+        .env("XDG_CONFIG_HOME", save_dir.join("config"))
+        .env_remove("BG3_SAVE_DIR")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Runs refused up front exit without reading stdin, closing the pipe early.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+
+    child.wait_with_output().unwrap()
+}
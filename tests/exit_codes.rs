@@ -0,0 +1,125 @@
+mod common;
+
+use std::{fs, process::Command};
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn exit_with_zero_after_cleaning() {
+    let save_dir = SaveDir::with_quick_saves(4);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn exit_with_one_when_nothing_matched() {
+    let save_dir = SaveDir::with_quick_saves(1);
+
+    let missing = bg3sc(&save_dir.path.join("missing"), &["--dry-run"], "");
+    let unmatched = bg3sc(&save_dir.path, &["--dry-run", "-c", "Astarion"], "");
+
+    assert_eq!(missing.status.code(), Some(1));
+    assert_eq!(unmatched.status.code(), Some(1));
+}
+
+#[test]
+fn exit_with_two_when_some_folders_failed() {
+    let save_dir = SaveDir::with_quick_saves(4);
+    // A file where the quarantine directory should be makes every move fail.
+    let quarantine = save_dir.path.join("quarantine");
+    fs::write(&quarantine, "").unwrap();
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "2",
+            "--yes",
+            "--allow-while-running",
+            "--quarantine",
+            quarantine.to_str().unwrap(),
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn exit_with_three_when_a_file_cannot_be_read() {
+    let save_dir = SaveDir::with_quick_saves(1);
+    let exclude_from = save_dir.path.join("missing.txt");
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "--dry-run",
+            "--exclude-from",
+            exclude_from.to_str().unwrap(),
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn report_a_broken_config_file() {
+    let save_dir = SaveDir::with_quick_saves(1);
+    let config = save_dir.path.join("bg3sc.toml");
+    fs::write(&config, "saves_to_keep = lots\n").unwrap();
+    let missing = save_dir.path.join("missing.toml");
+
+    let broken = bg3sc(
+        &save_dir.path,
+        &["--dry-run", "--config", config.to_str().unwrap()],
+        "",
+    );
+    let unreadable = bg3sc(
+        &save_dir.path,
+        &["--dry-run", "--config", missing.to_str().unwrap()],
+        "",
+    );
+
+    assert_eq!(broken.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&broken.stderr);
+    assert!(stderr.starts_with("Encountered error:"));
+    assert!(!stderr.contains("InvalidConfig("));
+    assert_eq!(unreadable.status.code(), Some(3));
+}
+
+#[test]
+fn report_when_no_save_folder_is_found() {
+    let home = SaveDir::with_quick_saves(0);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_bg3sc"))
+        .arg("--dry-run")
+        .env_remove("BG3_SAVE_DIR")
+        .env("XDG_CONFIG_HOME", home.path.join("config"))
+        .env("XDG_DATA_HOME", home.path.join("share"))
+        .env("HOME", &home.path)
+        .env("LOCALAPPDATA", &home.path)
+        .env("USERPROFILE", &home.path)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Encountered error:"));
+    assert!(!stderr.contains("NoPath("));
+}
+
+#[test]
+fn document_exit_codes_in_help() {
+    let save_dir = SaveDir::with_quick_saves(0);
+
+    let output = bg3sc(&save_dir.path, &["--help"], "");
+
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Exit codes:"));
+}
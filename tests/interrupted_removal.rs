@@ -0,0 +1,40 @@
+mod common;
+
+use std::fs;
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn finish_removing_folders_left_by_an_interrupted_run() {
+    let save_dir = SaveDir::with_quick_saves(4);
+    let staged = save_dir.path.join(".Tav-123456789__QuickSave_0.deleting");
+    fs::create_dir_all(staged.join("Mods")).unwrap();
+    fs::write(staged.join("test.lsv"), b"LS").unwrap();
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(!staged.exists());
+    assert!(!save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_4").exists());
+}
+
+#[test]
+fn leave_staged_folders_alone_on_a_dry_run() {
+    let save_dir = SaveDir::with_quick_saves(4);
+    let staged = save_dir.path.join(".Tav-123456789__QuickSave_0.deleting");
+    fs::create_dir(&staged).unwrap();
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--dry-run", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(staged.exists());
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn refuse_to_delete_most_saves() {
+    let save_dir = SaveDir::with_quick_saves(10);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "3", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would delete 7 of 10 save(s)"));
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+}
+
+#[test]
+fn warn_about_the_refusal_on_dry_run() {
+    let save_dir = SaveDir::with_quick_saves(10);
+
+    let output = bg3sc(&save_dir.path, &["-s", "3", "--dry-run"], "");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("a real run would refuse: This would delete 7 of 10 save(s)"));
+}
+
+#[test]
+fn delete_up_to_the_share() {
+    let save_dir = SaveDir::with_quick_saves(10);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "4", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(!save_dir.path.join("Tav-123456789__QuickSave_6").exists());
+}
+
+#[test]
+fn delete_most_saves_when_allowed() {
+    let save_dir = SaveDir::with_quick_saves(10);
+
+    let allowed = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "1",
+            "--yes",
+            "--allow-while-running",
+            "--allow-mass-delete",
+        ],
+        "",
+    );
+
+    assert_eq!(allowed.status.code(), Some(0));
+    assert!(!save_dir.path.join("Tav-123456789__QuickSave_9").exists());
+}
+
+#[test]
+fn raise_the_share() {
+    let save_dir = SaveDir::with_quick_saves(10);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "3",
+            "--yes",
+            "--allow-while-running",
+            "--max-delete-percent",
+            "70",
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+}
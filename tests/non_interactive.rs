@@ -0,0 +1,60 @@
+mod common;
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn refuse_to_ask_when_stdin_is_not_a_terminal() {
+    let save_dir = SaveDir::with_quick_saves(4);
+
+    // A stray "y" on stdin must not confirm anything.
+    let output = bg3sc(&save_dir.path, &["-s", "2", "--allow-while-running"], "y\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Encountered error:"));
+    assert!(stderr.contains("--yes or --dry-run"));
+    assert!(!stderr.contains("NotInteractive("));
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+}
+
+#[test]
+fn refuse_to_ask_when_stdin_is_empty() {
+    let save_dir = SaveDir::with_quick_saves(4);
+
+    let output = bg3sc(&save_dir.path, &["-s", "2", "--allow-while-running"], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+}
+
+#[test]
+fn run_without_a_terminal_when_opted_in() {
+    let save_dir = SaveDir::with_quick_saves(4);
+
+    let dry_run = bg3sc(&save_dir.path, &["-s", "2", "--dry-run"], "");
+    assert_eq!(dry_run.status.code(), Some(0));
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+
+    let yes = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--allow-while-running"],
+        "",
+    );
+    assert_eq!(yes.status.code(), Some(0));
+    assert!(!save_dir.path.join("Tav-123456789__QuickSave_1").exists());
+}
+
+#[test]
+fn refuse_to_ask_which_folders_to_restore() {
+    let save_dir = SaveDir::with_quick_saves(0);
+    let quarantine = save_dir.path.join("quarantine");
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["restore", quarantine.to_str().unwrap()],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--all"));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::fs;
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn delete_the_other_folders_when_one_fails() {
+    let save_dir = SaveDir::with_quick_saves(5);
+    // Not a save package, so the folder is refused.
+    fs::write(
+        save_dir.path.join("Tav-123456789__QuickSave_2/test.lsv"),
+        "XXXX",
+    )
+    .unwrap();
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Could not delete 1 folder(s):"));
+    assert!(stderr.contains("  Tav-123456789__QuickSave_2: "));
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_2").exists());
+    [1, 3].iter().for_each(|n| {
+        assert!(!save_dir
+            .path
+            .join(format!("Tav-123456789__QuickSave_{}", n))
+            .exists())
+    });
+    assert!(save_dir.path.join("Tav-123456789__QuickSave_4").exists());
+}
+
+#[test]
+fn list_failed_folders_in_the_json_report() {
+    let save_dir = SaveDir::with_quick_saves(5);
+    fs::write(
+        save_dir.path.join("Tav-123456789__QuickSave_1/test.lsv"),
+        "XXXX",
+    )
+    .unwrap();
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "2",
+            "--yes",
+            "--allow-while-running",
+            "--output",
+            "json",
+        ],
+        "",
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"failed\":[{\"save\":{"));
+    assert!(stdout.contains("\"file_name\":\"Tav-123456789__QuickSave_1\""));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::{fs, process};
+
+use common::{bg3sc, SaveDir};
+
+/// Writes a plan deleting all but the two newest of four quick saves, returning where it is.
+fn write_plan(save_dir: &SaveDir, plan_dir: &SaveDir) -> String {
+    fs::create_dir_all(&plan_dir.path).unwrap();
+    let plan = plan_dir.path.join("plan.json").display().to_string();
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--write-plan", &plan, "--allow-while-running"],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    plan
+}
+
+fn remaining(save_dir: &SaveDir) -> Vec<bool> {
+    (1..=4)
+        .map(|n| {
+            save_dir
+                .path
+                .join(format!("Tav-123456789__QuickSave_{}", n))
+                .exists()
+        })
+        .collect()
+}
+
+#[test]
+fn apply_the_plan_once_to_its_own_folder() {
+    let save_dir = SaveDir::with_quick_saves(4);
+    let other_dir = SaveDir::with_quick_saves(0);
+    let plan = write_plan(&save_dir, &other_dir);
+    ["Public", "Kids"].iter().for_each(|profile| {
+        fs::create_dir_all(other_dir.path.join(profile).join("Savegames/Story")).unwrap()
+    });
+
+    let output = bg3sc(
+        &other_dir.path,
+        &[
+            "--apply-plan",
+            &plan,
+            "--yes",
+            "--allow-while-running",
+            "--all-profiles",
+        ],
+        "",
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout.matches("Applying plan").count(), 1);
+    assert_eq!(remaining(&save_dir), vec![false, false, true, true]);
+}
+
+#[test]
+fn lock_the_folder_of_the_plan() {
+    let save_dir = SaveDir::with_quick_saves(4);
+    let other_dir = SaveDir::with_quick_saves(0);
+    let plan = write_plan(&save_dir, &other_dir);
+    // Held by this test process, which is still running.
+    fs::write(save_dir.path.join(".bg3sc.lock"), process::id().to_string()).unwrap();
+
+    let output = bg3sc(
+        &other_dir.path,
+        &["--apply-plan", &plan, "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --wait"));
+    assert_eq!(remaining(&save_dir), vec![true; 4]);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use common::{bg3sc, SaveDir};
+
+fn story_dir(player_profiles: &Path, profile: &str) -> PathBuf {
+    player_profiles
+        .join(profile)
+        .join("Savegames")
+        .join("Story")
+}
+
+/// A `PlayerProfiles` folder holding the same quick saves in every profile, up to the count.
+fn with_profiles(profiles: &[(&str, u64)]) -> SaveDir {
+    let save_dir = SaveDir::with_quick_saves(0);
+    profiles.iter().for_each(|(profile, count)| {
+        (1..=*count).for_each(|n| {
+            let save =
+                story_dir(&save_dir.path, profile).join(format!("Tav-123456789__QuickSave_{}", n));
+            fs::create_dir_all(&save).unwrap();
+            fs::write(save.join("test.lsv"), b"LSPK").unwrap();
+        })
+    });
+
+    save_dir
+}
+
+fn remaining(save_dir: &SaveDir, profile: &str, count: u64) -> Vec<bool> {
+    (1..=count)
+        .map(|n| {
+            story_dir(&save_dir.path, profile)
+                .join(format!("Tav-123456789__QuickSave_{}", n))
+                .exists()
+        })
+        .collect()
+}
+
+#[test]
+fn clean_every_profile_on_its_own() {
+    let save_dir = with_profiles(&[("Public", 5), ("Kids", 3)]);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "2",
+            "--yes",
+            "--allow-while-running",
+            "--all-profiles",
+        ],
+        "",
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("== Kids =="));
+    assert!(stdout.contains("== Public =="));
+    assert_eq!(
+        remaining(&save_dir, "Public", 5),
+        vec![false, false, false, true, true]
+    );
+    assert_eq!(remaining(&save_dir, "Kids", 3), vec![false, true, true]);
+}
+
+#[test]
+fn clean_only_the_named_profile() {
+    let save_dir = with_profiles(&[("Public", 5), ("Kids", 3)]);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &[
+            "-s",
+            "2",
+            "--yes",
+            "--allow-while-running",
+            "--profile",
+            "Kids",
+        ],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(remaining(&save_dir, "Public", 5), vec![true; 5]);
+    assert_eq!(remaining(&save_dir, "Kids", 3), vec![false, true, true]);
+}
+
+#[test]
+fn ask_for_a_profile_when_several_have_saves() {
+    let save_dir = with_profiles(&[("Public", 5), ("Kids", 3)]);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pick one with --profile"));
+    assert_eq!(remaining(&save_dir, "Public", 5), vec![true; 5]);
+    assert_eq!(remaining(&save_dir, "Kids", 3), vec![true; 3]);
+}
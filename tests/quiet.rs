@@ -0,0 +1,20 @@
+mod common;
+
+use common::{bg3sc, SaveDir};
+
+#[test]
+fn print_only_the_outcome() {
+    let save_dir = SaveDir::with_quick_saves(4);
+
+    let output = bg3sc(
+        &save_dir.path,
+        &["-s", "2", "--yes", "--quiet", "--allow-while-running"],
+        "",
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Deleted 2 folder(s), failed 0\n"
+    );
+}